@@ -25,6 +25,24 @@ pub trait Address:
     fn as_u8(self) -> u8 {
         self.as_usize() as u8
     }
+
+    /// Adds `rhs`, returning `None` if the result would wrap past the end of the address space.
+    ///
+    /// The `+` operator always wraps, as the hardware does; this variant is for diagnostic buses
+    /// that want to flag a wraparound as a likely emulator bug.
+    ///
+    /// ```
+    /// use ull::{Address, Word};
+    ///
+    /// assert_eq!(Word(0xFFFE).checked_add(1), Some(Word(0xFFFF)));
+    /// assert_eq!(Word(0xFFFF).checked_add(1), None);
+    /// ```
+    #[inline]
+    fn checked_add(self, rhs: usize) -> Option<Self> {
+        let expected = self.as_usize().checked_add(rhs)?;
+        let result = self + rhs;
+        (result.as_usize() == expected).then_some(result)
+    }
 }
 
 impl Address for Byte {
@@ -130,3 +148,25 @@ impl Address for usize {
         self as u8
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Address;
+    use crate::{Byte, Nibble, Word};
+
+    #[test]
+    fn checked_add_detects_word_wraparound() {
+        assert_eq!(Word(0x1234).checked_add(0x10), Some(Word(0x1244)));
+        assert_eq!(Word(0xFFFF).checked_add(0), Some(Word(0xFFFF)));
+        assert_eq!(Word(0xFFFF).checked_add(1), None);
+        assert_eq!(Word(0x0000).checked_add(0x1_0000), None);
+    }
+
+    #[test]
+    fn checked_add_detects_narrow_wraparound() {
+        assert_eq!(Byte(0xFE).checked_add(1), Some(Byte(0xFF)));
+        assert_eq!(Byte(0xFF).checked_add(1), None);
+        assert_eq!(Nibble::from(0x0Fu8).checked_add(1), None);
+        assert_eq!(Address::checked_add(usize::MAX, 1), None);
+    }
+}
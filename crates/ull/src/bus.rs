@@ -1,6 +1,7 @@
 //! Memory and I/O bus abstraction.
 
 use crate::{Address, Byte, Word};
+use alloc::string::String;
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct DmaRequest {
@@ -16,6 +17,25 @@ pub enum DmaResult {
     Denied,
 }
 
+/// Phase within a bus cycle. Some hardware (e.g., NES DMA) cares whether we're in
+/// the read (GET) or write (PUT) half of a cycle.
+///
+/// For which of the alternating CPU cycles an access lands on, as passed to
+/// [`Bus::read_phased`], see [`CyclePhase`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Phase {
+    Read,
+    Write,
+}
+
+/// Which of the alternating CPU cycles an access lands on. Some hardware (e.g., NES DMA) treats
+/// GET and PUT cycles differently; it has nothing to do with the access's direction.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CyclePhase {
+    Get,
+    Put,
+}
+
 pub trait Bus {
     type Access: Copy;
     type Data: Copy;
@@ -29,6 +49,20 @@ pub trait Bus {
         A: Address,
         V: Into<Self::Data>;
 
+    /// Read `addr` knowing whether it falls on a GET or PUT cycle (see [`CyclePhase`]).
+    ///
+    /// CPUs call this for the accesses whose cycle they can place exactly, so hardware that
+    /// samples its inputs differently on the two phases can tell them apart; which accesses those
+    /// are, and how the phase is derived, is documented by each CPU. The default ignores `phase`
+    /// and calls [`read`](Self::read); wrapping buses should forward it.
+    fn read_phased<A>(&mut self, addr: A, access: Self::Access, phase: CyclePhase) -> Self::Data
+    where
+        A: Address,
+    {
+        let _ = phase;
+        self.read(addr, access)
+    }
+
     /// Read a contiguous block of memory starting at `start` into `dst`.
     ///
     /// Default implementation issues repeated [`read`](Self::read) calls and wraps addresses using
@@ -39,11 +73,9 @@ pub trait Bus {
         A: Address,
         Self::Data: Into<Byte>,
     {
-        let mut addr = start;
-        for byte in dst {
-            let value: Byte = self.read(addr, access).into();
+        for (offset, byte) in dst.iter_mut().enumerate() {
+            let value: Byte = self.read(start + offset, access).into();
             *byte = u8::from(value);
-            addr += 1;
         }
     }
 
@@ -57,17 +89,135 @@ pub trait Bus {
         A: Address,
         Self::Data: From<u8>,
     {
-        let mut addr = start;
-        for &byte in data {
-            self.write(addr, Self::Data::from(byte), access);
-            addr += 1;
+        for (offset, &byte) in data.iter().enumerate() {
+            self.write(start + offset, Self::Data::from(byte), access);
+        }
+    }
+
+    /// Fill `len` bytes starting at `start` with `value`.
+    ///
+    /// Default implementation issues repeated [`write`](Self::write) calls and wraps addresses using
+    /// the `Address` type's wrapping semantics. Override this when the bus can fill a region
+    /// directly (e.g. with `slice::fill`).
+    fn fill_block<A>(&mut self, start: A, len: usize, value: Byte, access: Self::Access)
+    where
+        A: Address,
+        Self::Data: From<u8>,
+    {
+        for offset in 0..len {
+            self.write(start + offset, Self::Data::from(value.0), access);
         }
     }
 
+    /// Read a little-endian 16-bit value from `addr` and the address after it.
+    ///
+    /// The second address wraps with the `Address` type, so a [`Byte`] address reads a pointer
+    /// that straddles `$FF`/`$00` the way the 6502 does in zero page. Override this if your bus
+    /// can service 16-bit reads directly.
+    fn read16<A>(&mut self, addr: A, access: Self::Access) -> Word
+    where
+        A: Address,
+        Self::Data: Into<Byte>,
+    {
+        let lo: Byte = self.read(addr, access).into();
+        let hi: Byte = self.read(addr + 1, access).into();
+        Word::from((lo, hi))
+    }
+
+    /// Write `value` to `addr` and the address after it, low byte first.
+    ///
+    /// Addresses wrap the same way as in [`read16`](Self::read16). Override this if your bus can
+    /// service 16-bit writes directly.
+    fn write16<A, V>(&mut self, addr: A, value: V, access: Self::Access)
+    where
+        A: Address,
+        V: Into<Word>,
+        Self::Data: From<u8>,
+    {
+        let value: Word = value.into();
+        self.write(addr, Self::Data::from(value.lo().0), access);
+        self.write(addr + 1, Self::Data::from(value.hi().0), access);
+    }
+
+    /// Read a zero-terminated string of at most `max` bytes starting at `start`.
+    ///
+    /// Returns the text and the number of bytes consumed, including the terminator when one was
+    /// found within `max`, so `start + consumed` is the byte after the string. With
+    /// `mask_high_bit` set, bit 7 is cleared first, for machines like the Apple I that store
+    /// ASCII with the high bit on. Other bytes above `$7F` map to the Latin-1 character of the
+    /// same value.
+    fn read_cstr(
+        &mut self,
+        start: Word,
+        max: usize,
+        mask_high_bit: bool,
+        access: Self::Access,
+    ) -> (String, usize)
+    where
+        Self::Data: Into<Byte>,
+    {
+        let mut text = String::new();
+        for offset in 0..max {
+            let mut byte = u8::from(self.read(start + offset, access).into());
+            if mask_high_bit {
+                byte &= 0x7F;
+            }
+            if byte == 0 {
+                return (text, offset + 1);
+            }
+            text.push(char::from(byte));
+        }
+        (text, max)
+    }
+
     fn on_tick(&mut self, cycles: u8) {
         let _ = cycles;
     }
 
+    /// Running total of [`read`](Self::read)/[`write`](Self::write) calls serviced so far, if
+    /// the bus keeps one.
+    ///
+    /// CPUs compare this before and after an instruction to report how many bus cycles it
+    /// performed, which should match the cycles it charged once dummy accesses are modeled.
+    /// The default returns `None` so ordinary buses pay nothing for the bookkeeping.
+    fn access_count(&self) -> Option<u64> {
+        None
+    }
+
+    /// Take the address of the first watched write since the last call, if the bus watches any.
+    ///
+    /// CPUs poll this after each instruction to stop on writes to memory of interest (see
+    /// `ull65`'s `WatchBus`). The default returns `None`; wrapping buses should forward it.
+    fn take_watch_hit(&mut self) -> Option<Word> {
+        None
+    }
+
+    /// Copy `len` bytes from `src` to `dst` the way a DMA controller would, returning the cycles
+    /// the transfer occupies the bus.
+    ///
+    /// The default alternates [`read`](Self::read) and [`write`](Self::write) one byte at a time,
+    /// so memory-mapped devices on either side see every access, and charges one cycle for each
+    /// (`2 * len`). Both addresses wrap at `$FFFF`. Reads are tagged with `read_access` and writes
+    /// with `write_access` (e.g. `DmaRead`/`DmaWrite`). Override this if the bus can move the
+    /// block more efficiently or its controller has different timing.
+    ///
+    /// This performs the copy immediately; use [`request_dma`](Self::request_dma) for transfers
+    /// the bus schedules itself.
+    fn dma_copy(
+        &mut self,
+        src: Word,
+        dst: Word,
+        len: u16,
+        read_access: Self::Access,
+        write_access: Self::Access,
+    ) -> u32 {
+        for offset in 0..len {
+            let value = self.read(src + offset, read_access);
+            self.write(dst + offset, value, write_access);
+        }
+        u32::from(len) * 2
+    }
+
     fn request_dma(&mut self, request: DmaRequest) -> DmaResult {
         let _ = request;
         DmaResult::Denied
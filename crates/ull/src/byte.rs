@@ -12,7 +12,7 @@
 //! assert_eq!(Byte(0xFF) + 1u8, Byte(0x00));
 //! ```
 
-use crate::{Address, nibble::Nibble};
+use crate::{Address, nibble::Nibble, parse::{ParseError, parse_hex}};
 use core::fmt::{Display, Formatter, LowerHex, UpperHex};
 use core::ops::{
     Add, AddAssign, BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Not, Shl,
@@ -24,6 +24,8 @@ use core::ops::{
 /// Wraps a `u8` and provides operator overloads that automatically wrap on overflow,
 /// matching 6502 hardware behavior. Use the [`byte!`](crate::byte!) macro for convenient construction.
 #[derive(Default, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
 pub struct Byte(pub u8);
 
 #[macro_export]
@@ -44,6 +46,94 @@ impl Byte {
         self.0 & 0x80 != 0
     }
 
+    /// Wrapping add that also reports whether the sum carried out of bit 7, like
+    /// [`u8::overflowing_add`].
+    #[inline]
+    #[must_use]
+    pub fn overflowing_add(self, rhs: Byte) -> (Byte, bool) {
+        let (raw, carry) = self.0.overflowing_add(rhs.0);
+        (Byte(raw), carry)
+    }
+
+    /// Wrapping subtract that also reports whether it borrowed, like [`u8::overflowing_sub`].
+    ///
+    /// The 6502 carry after a compare or subtract is the inverse of this borrow.
+    #[inline]
+    #[must_use]
+    pub fn overflowing_sub(self, rhs: Byte) -> (Byte, bool) {
+        let (raw, borrow) = self.0.overflowing_sub(rhs.0);
+        (Byte(raw), borrow)
+    }
+
+    /// Parse a hex byte such as `FF`, `$FF` or `0xff`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ParseError`] if `text` has no digits, contains a non-hex character, or is
+    /// above `$FF`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ull::{Byte, ParseError};
+    ///
+    /// assert_eq!(Byte::from_hex("$2A"), Ok(Byte(0x2A)));
+    /// assert_eq!(Byte::from_hex("0x100"), Err(ParseError::OutOfRange));
+    /// ```
+    pub fn from_hex(text: &str) -> Result<Byte, ParseError> {
+        parse_hex(text, u16::from(u8::MAX)).map(|value| Byte(value as u8))
+    }
+
+    /// Decode a packed BCD value, e.g. `$99` into 99.
+    ///
+    /// Returns `None` if either nibble isn't a decimal digit.
+    #[inline]
+    #[must_use]
+    pub fn from_bcd(value: u8) -> Option<Byte> {
+        let packed = Byte(value);
+        let tens = packed.hi().to_digit()?;
+        let ones = packed.lo().to_digit()?;
+        Some(Byte(tens * 10 + ones))
+    }
+
+    /// Encode this value as packed BCD, e.g. 99 into `$99`.
+    ///
+    /// Returns `None` for values above 99, which don't fit in two digits.
+    #[inline]
+    #[must_use]
+    pub fn to_bcd(self) -> Option<u8> {
+        let tens = Nibble::from_digit(self.0 / 10)?;
+        let ones = Nibble::from_digit(self.0 % 10)?;
+        Some(Byte::from((ones, tens)).0)
+    }
+
+    /// Returns bit `index` (0 is the least significant).
+    #[inline]
+    #[must_use]
+    pub fn bit(self, index: u8) -> bool {
+        debug_assert!(index < 8, "bit index {index} out of range");
+        self.0 & (1 << index) != 0
+    }
+
+    /// Returns a copy with bit `index` set to `value`.
+    #[inline]
+    #[must_use]
+    pub fn with_bit(self, index: u8, value: bool) -> Byte {
+        debug_assert!(index < 8, "bit index {index} out of range");
+        let mask = 1 << index;
+        if value {
+            Byte(self.0 | mask)
+        } else {
+            Byte(self.0 & !mask)
+        }
+    }
+
+    /// Sets bit `index` to `value` in place.
+    #[inline]
+    pub fn set_bit(&mut self, index: u8, value: bool) {
+        *self = self.with_bit(index, value);
+    }
+
     #[inline]
     #[must_use]
     pub fn lo(self) -> Nibble {
@@ -507,4 +597,70 @@ mod tests {
         assert_eq!(Byte(raw), Byte(0xF0));
         assert!(borrow);
     }
+
+    #[test]
+    fn byte_overflowing_add_reports_carry() {
+        assert_eq!(Byte(0xFF).overflowing_add(Byte(0x01)), (Byte(0x00), true));
+        assert_eq!(Byte(0xFE).overflowing_add(Byte(0x01)), (Byte(0xFF), false));
+        assert_eq!(Byte(0x80).overflowing_add(Byte(0x80)), (Byte(0x00), true));
+    }
+
+    #[test]
+    fn byte_overflowing_sub_reports_borrow() {
+        assert_eq!(Byte(0x00).overflowing_sub(Byte(0x01)), (Byte(0xFF), true));
+        assert_eq!(Byte(0x01).overflowing_sub(Byte(0x01)), (Byte(0x00), false));
+        assert_eq!(Byte(0x01).overflowing_sub(Byte(0x00)), (Byte(0x01), false));
+    }
+
+    #[test]
+    fn bit_helpers_cover_every_position() {
+        for index in 0..8 {
+            let only = Byte(1 << index);
+            assert!(only.bit(index));
+            assert!((0..8).filter(|&other| other != index).all(|other| !only.bit(other)));
+
+            assert_eq!(Byte::ZERO.with_bit(index, true), only);
+            assert_eq!(Byte::MAX.with_bit(index, false), Byte(!(1 << index)));
+            assert_eq!(only.with_bit(index, true), only);
+
+            let mut value = Byte(0x5A);
+            value.set_bit(index, true);
+            assert_eq!(value, Byte(0x5A | (1 << index)));
+            value.set_bit(index, false);
+            assert_eq!(value, Byte(0x5A & !(1 << index)));
+        }
+    }
+
+    #[test]
+    fn bcd_round_trips_and_rejects_invalid_digits() {
+        assert_eq!(Byte::from_bcd(0x99), Some(Byte(99)));
+        assert_eq!(Byte::from_bcd(0x42), Some(Byte(42)));
+        assert_eq!(Byte::from_bcd(0x00), Some(Byte(0)));
+        assert_eq!(Byte::from_bcd(0x0A), None);
+        assert_eq!(Byte::from_bcd(0xA0), None);
+
+        assert_eq!(Byte(99).to_bcd(), Some(0x99));
+        assert_eq!(Byte(7).to_bcd(), Some(0x07));
+        assert_eq!(Byte(100).to_bcd(), None);
+
+        for value in 0..=99 {
+            assert_eq!(Byte(value).to_bcd().and_then(Byte::from_bcd), Some(Byte(value)));
+        }
+    }
+
+    #[test]
+    fn from_hex_accepts_prefixes_and_rejects_bad_input() {
+        use crate::ParseError;
+
+        assert_eq!(Byte::from_hex("FF"), Ok(Byte(0xFF)));
+        assert_eq!(Byte::from_hex("$0a"), Ok(Byte(0x0A)));
+        assert_eq!(Byte::from_hex("0x7"), Ok(Byte(0x07)));
+        assert_eq!(Byte::from_hex("$00FF"), Ok(Byte(0xFF)));
+
+        assert_eq!(Byte::from_hex("$100"), Err(ParseError::OutOfRange));
+        assert_eq!(Byte::from_hex(""), Err(ParseError::Empty));
+        assert_eq!(Byte::from_hex("$"), Err(ParseError::Empty));
+        assert_eq!(Byte::from_hex("G1"), Err(ParseError::InvalidDigit));
+        assert_eq!(Byte::from_hex("-1"), Err(ParseError::InvalidDigit));
+    }
 }
@@ -9,5 +9,7 @@ pub mod word;
 pub use word::Word;
 pub mod address;
 pub use address::Address;
+pub mod parse;
+pub use parse::ParseError;
 pub mod bus;
-pub use bus::{Bus, DmaRequest, DmaResult};
+pub use bus::{Bus, CyclePhase, DmaRequest, DmaResult, Phase};
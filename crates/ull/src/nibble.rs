@@ -11,6 +11,8 @@ use core::fmt;
 use core::ops::{Add, AddAssign, BitAnd, BitOr, BitXor, Not, Sub, SubAssign};
 
 #[derive(Default, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
 pub struct Nibble(pub u8);
 
 #[macro_export]
@@ -24,6 +26,20 @@ impl Nibble {
     pub const ZERO: Nibble = Nibble(0);
     pub const MAX: u8 = 0x0F;
 
+    /// The nibble holding decimal digit `digit`, or `None` if it isn't `0..=9`.
+    #[inline]
+    #[must_use]
+    pub fn from_digit(digit: u8) -> Option<Nibble> {
+        (digit <= 9).then_some(Nibble(digit))
+    }
+
+    /// The decimal digit this nibble holds, or `None` for `$A-$F`.
+    #[inline]
+    #[must_use]
+    pub fn to_digit(self) -> Option<u8> {
+        (self.0 <= 9).then_some(self.0)
+    }
+
     #[inline]
     fn mask(value: u8) -> u8 {
         value & Self::MAX
@@ -228,4 +244,14 @@ mod tests {
     fn sub_wraps_to_low_four_bits() {
         assert_eq!(Nibble::from(0x01_u8) - 0x03_u8, Nibble::from(0x0E_u8));
     }
+
+    #[test]
+    fn decimal_digits_round_trip() {
+        for digit in 0..=9 {
+            assert_eq!(Nibble::from_digit(digit).and_then(Nibble::to_digit), Some(digit));
+        }
+        assert_eq!(Nibble::from_digit(10), None);
+        assert_eq!(Nibble(0x0A).to_digit(), None);
+        assert_eq!(Nibble(0x0F).to_digit(), None);
+    }
 }
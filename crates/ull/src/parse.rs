@@ -0,0 +1,50 @@
+//! Parsing of hex literals typed by users, e.g. into a monitor prompt.
+
+use core::fmt;
+
+/// Error returned by [`Byte::from_hex`](crate::Byte::from_hex) and
+/// [`Word::from_hex`](crate::Word::from_hex).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseError {
+    /// No digits after the optional `$`/`0x` prefix.
+    Empty,
+    /// A character that isn't a hex digit.
+    InvalidDigit,
+    /// The value doesn't fit in the target type.
+    OutOfRange,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ParseError::Empty => "no hex digits",
+            ParseError::InvalidDigit => "invalid hex digit",
+            ParseError::OutOfRange => "hex value out of range",
+        })
+    }
+}
+
+impl core::error::Error for ParseError {}
+
+/// Parse `text` as hex with an optional `$` or `0x`/`0X` prefix, rejecting values above `max`.
+///
+/// Leading zeros are accepted, so `$00FF` is a valid byte.
+pub(crate) fn parse_hex(text: &str, max: u16) -> Result<u16, ParseError> {
+    let digits = text
+        .strip_prefix('$')
+        .or_else(|| text.strip_prefix("0x"))
+        .or_else(|| text.strip_prefix("0X"))
+        .unwrap_or(text);
+    if digits.is_empty() {
+        return Err(ParseError::Empty);
+    }
+
+    digits.chars().try_fold(0u16, |value, c| {
+        let digit = c.to_digit(16).ok_or(ParseError::InvalidDigit)? as u16;
+        value
+            .checked_mul(16)
+            .and_then(|value| value.checked_add(digit))
+            .filter(|&value| value <= max)
+            .ok_or(ParseError::OutOfRange)
+    })
+}
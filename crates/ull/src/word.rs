@@ -13,7 +13,7 @@
 //! assert_eq!(addr.hi().0, 0x80);
 //! ```
 
-use crate::{Address, byte::Byte, nibble::Nibble};
+use crate::{Address, byte::Byte, nibble::Nibble, parse::{ParseError, parse_hex}};
 use core::fmt::{LowerHex, UpperHex};
 use core::ops::{
     Add, AddAssign, BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Not, Shl,
@@ -25,7 +25,9 @@ use core::ops::{
 /// Wraps a `u16` and provides operator overloads with wrapping arithmetic.
 /// Primarily used for memory addresses (0x0000-0xFFFF). Use the [`word!`](crate::word!) macro
 /// for convenient construction.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
 pub struct Word(pub u16);
 
 /// Convenience macro for creating [`Word`] values.
@@ -73,6 +75,46 @@ impl Word {
     pub fn lo_hi(self) -> (Byte, Byte) {
         (self.lo(), self.hi())
     }
+
+    /// Add an unsigned 8-bit index, wrapping at `$FFFF`, and report whether the high byte
+    /// changed (a page crossing).
+    ///
+    /// This is the "base + X/Y" step of the indexed addressing modes, where a crossing costs
+    /// the CPU an extra cycle.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ull::Word;
+    ///
+    /// assert_eq!(Word(0x12FF).offset(1), (Word(0x1300), true));
+    /// assert_eq!(Word(0x1200).offset(1), (Word(0x1201), false));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn offset(self, delta: u8) -> (Word, bool) {
+        let target = Word(self.0.wrapping_add(u16::from(delta)));
+        (target, target.hi() != self.hi())
+    }
+
+    /// Parse a hex address such as `8000`, `$8000` or `0xC000`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ParseError`] if `text` has no digits, contains a non-hex character, or is
+    /// above `$FFFF`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ull::{ParseError, Word};
+    ///
+    /// assert_eq!(Word::from_hex("$8000"), Ok(Word(0x8000)));
+    /// assert_eq!(Word::from_hex("$10000"), Err(ParseError::OutOfRange));
+    /// ```
+    pub fn from_hex(text: &str) -> Result<Word, ParseError> {
+        parse_hex(text, u16::MAX).map(Word)
+    }
 }
 
 impl From<(u8, u8)> for Word {
@@ -569,4 +611,29 @@ mod tests {
 
         assert_eq!(!word, Word(0xF0F0));
     }
+
+    #[test]
+    fn offset_reports_page_crossings() {
+        assert_eq!(Word(0x12FF).offset(1), (Word(0x1300), true));
+        assert_eq!(Word(0x1200).offset(1), (Word(0x1201), false));
+        assert_eq!(Word(0x1200).offset(0xFF), (Word(0x12FF), false));
+        assert_eq!(Word(0xFFFF).offset(1), (Word(0x0000), true));
+        assert_eq!(Word(0x12FF).offset(0), (Word(0x12FF), false));
+    }
+
+    #[test]
+    fn from_hex_accepts_prefixes_and_rejects_bad_input() {
+        use crate::ParseError;
+
+        assert_eq!(Word::from_hex("8000"), Ok(Word(0x8000)));
+        assert_eq!(Word::from_hex("$fffc"), Ok(Word(0xFFFC)));
+        assert_eq!(Word::from_hex("0XC000"), Ok(Word(0xC000)));
+        assert_eq!(Word::from_hex("0x0000FFFF"), Ok(Word(0xFFFF)));
+
+        assert_eq!(Word::from_hex("$10000"), Err(ParseError::OutOfRange));
+        assert_eq!(Word::from_hex("123456789ABCDEF0"), Err(ParseError::OutOfRange));
+        assert_eq!(Word::from_hex("0x"), Err(ParseError::Empty));
+        assert_eq!(Word::from_hex("$80 00"), Err(ParseError::InvalidDigit));
+        assert_eq!(Word::from_hex("$$8000"), Err(ParseError::InvalidDigit));
+    }
 }
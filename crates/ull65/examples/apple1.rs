@@ -8,7 +8,7 @@ use std::io::{self, Write};
 
 use ull::{Address, Bus, Byte, Word};
 use ull65::AccessType;
-use ull65::{Cpu, IRQ_VECTOR_LO, NMI_VECTOR_LO, RESET_VECTOR_LO};
+use ull65::{Cpu, Mos6502CompatibleBus, IRQ_VECTOR_LO, NMI_VECTOR_LO, RESET_VECTOR_LO};
 
 const MEMORY_SIZE: usize = 0x10000;
 const BASIC_START: Word = Word(0xE000);
@@ -182,6 +182,8 @@ impl Bus for Apple1Bus {
     }
 }
 
+impl Mos6502CompatibleBus for Apple1Bus {}
+
 fn pump(cpu: &mut Cpu<Apple1Bus>, bus: &mut Apple1Bus) {
     for _ in 0..PUMP_CYCLES {
         if cpu.tick(bus) == 0 {
@@ -4,6 +4,7 @@
 //! instead of invoking the IRQ vector.
 
 use ull::Word;
+use ull65::addressing_mode::AddressingModeKind;
 use ull65::bus::Mos6502CompatibleBus;
 use ull65::instruction::{mos6502::Mos6502, Instruction, InstructionSet};
 use ull65::processor::run::{RunConfig, RunPredicate};
@@ -13,12 +14,14 @@ use ull65::{Cpu, SimpleBus};
 struct Trap6502;
 
 impl InstructionSet for Trap6502 {
-    fn instruction_table<B: Mos6502CompatibleBus + 'static>()
+    fn instruction_table<B: Mos6502CompatibleBus>()
     -> ull65::instruction::InstructionTable<B> {
         // Start from the canonical MOS table and replace opcode 0x00 (BRK).
         Mos6502::base_table::<B>().with(
             0x00,
             Instruction {
+                mnemonic: "BRK",
+                mode: AddressingModeKind::Implied,
                 cycles: 7,
                 execute: trap_brk::<B>,
             },
@@ -8,7 +8,7 @@
 use std::collections::VecDeque;
 use ull::{Address, Bus, Byte, DmaRequest, DmaResult, Word};
 use ull65::instruction::mos6502::Mos6502;
-use ull65::{AccessType, Cpu};
+use ull65::{AccessType, Cpu, Mos6502CompatibleBus};
 
 const DMA_TRIGGER_ADDR: Word = Word(0xD002);
 const DMA_LENGTH: u8 = 8;
@@ -77,6 +77,8 @@ impl Bus for DemoBus {
     }
 }
 
+impl Mos6502CompatibleBus for DemoBus {}
+
 fn main() {
     let mut bus = DemoBus::default();
 
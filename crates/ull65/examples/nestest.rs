@@ -128,12 +128,15 @@ impl Bus for NesBus {
     }
 }
 
+impl Mos6502CompatibleBus for NesBus {}
+
 struct Ricoh2a03;
 
 impl InstructionSet for Ricoh2a03 {
-    fn instruction_table<B: Mos6502CompatibleBus + 'static>() -> InstructionTable<B> {
-        Mos6502::base_table()
+    fn instruction_table<B: Mos6502CompatibleBus>() -> InstructionTable<B> {
+        Mos6502::base_table_for::<Self, B>()
     }
+    const NAME: &'static str = "Ricoh 2A03";
     const SUPPORTS_DECIMAL_MODE: bool = false;
 }
 
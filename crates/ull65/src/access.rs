@@ -1,12 +1,7 @@
+use crate::processor::cpu::{IRQ_VECTOR_LO, NMI_VECTOR_LO, RESET_VECTOR_LO};
 use ull::{Bus, Byte, Word};
 
-/// Phase within a bus cycle. Some hardware (e.g., NES DMA) cares whether we're in
-/// the read (GET) or write (PUT) half of a cycle.
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
-pub enum Phase {
-    Read,
-    Write,
-}
+pub use ull::{CyclePhase, Phase};
 
 /// High-level purpose of a bus access combined with its direction (6502-specific).
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
@@ -20,7 +15,15 @@ pub enum AccessType {
     InterruptVectorRead,
     DmaRead,
     DmaWrite,
+    /// Read the CPU makes and discards, e.g. the unfixed address of a page-crossing indexed read.
     DummyRead,
+    /// Write-back of an unmodified value, e.g. by NMOS read-modify-write instructions.
+    DummyWrite,
+    /// Look at memory on behalf of a debugger or tool (disassembler, tracer, memory diff).
+    ///
+    /// The CPU never issues it, so buses should answer without side effects: no I/O register
+    /// reacting to the read, and no change to open-bus state.
+    Peek,
 }
 
 impl AccessType {
@@ -28,10 +31,15 @@ impl AccessType {
     pub const fn is_write(self) -> bool {
         matches!(
             self,
-            AccessType::DataWrite | AccessType::StackWrite | AccessType::DmaWrite
+            AccessType::DataWrite
+                | AccessType::StackWrite
+                | AccessType::DmaWrite
+                | AccessType::DummyWrite
         )
     }
 
+    /// Direction of the access. Whether it lands on a GET or PUT cycle is a separate matter;
+    /// see [`CyclePhase`].
     #[must_use]
     pub const fn phase(self) -> Phase {
         if self.is_write() {
@@ -42,8 +50,19 @@ impl AccessType {
     }
 }
 
+/// Write the 6502 hardware vectors through a bus, low byte first.
+///
+/// Each setter is a plain [`DataWrite`](AccessType::DataWrite) pair, so a bus that protects its
+/// ROM will drop them like any other store; load vectors that live in ROM along with the image.
 pub trait ResetVectorExt {
+    /// Point the reset vector ($FFFC/$FFFD) at `target`.
     fn set_reset_vector(&mut self, target: Word);
+
+    /// Point the NMI vector ($FFFA/$FFFB) at `target`.
+    fn set_nmi_vector(&mut self, target: Word);
+
+    /// Point the IRQ/BRK vector ($FFFE/$FFFF) at `target`.
+    fn set_irq_vector(&mut self, target: Word);
 }
 
 impl<B> ResetVectorExt for B
@@ -51,7 +70,14 @@ where
     B: Bus<Access = AccessType, Data = Byte>,
 {
     fn set_reset_vector(&mut self, target: Word) {
-        self.write(Word(0xFFFC), target.lo(), AccessType::DataWrite);
-        self.write(Word(0xFFFD), target.hi(), AccessType::DataWrite);
+        self.write16(RESET_VECTOR_LO, target, AccessType::DataWrite);
+    }
+
+    fn set_nmi_vector(&mut self, target: Word) {
+        self.write16(NMI_VECTOR_LO, target, AccessType::DataWrite);
+    }
+
+    fn set_irq_vector(&mut self, target: Word) {
+        self.write16(IRQ_VECTOR_LO, target, AccessType::DataWrite);
     }
 }
@@ -1,11 +1,120 @@
+pub mod banked;
+pub mod diff;
+pub mod loader;
 pub mod simple_bus;
+pub mod slice;
 pub mod testing_bus;
+pub mod trap_bus;
+pub mod watch_bus;
 
-use ull::{Bus, Byte};
+use ull::{Address, Bus, Byte, CyclePhase, Word};
 
 use crate::AccessType;
 
-/// Convenience bound for MOS 6502/WDC 65C02 compatible buses.
-pub trait Mos6502CompatibleBus: Bus<Access = AccessType, Data = Byte> {}
+/// MOS 6502/WDC 65C02 compatible bus, with hooks for memory-mapped I/O.
+///
+/// The CPU makes every access through [`cpu_read`](Self::cpu_read) and
+/// [`cpu_write`](Self::cpu_write), which offer it to [`on_read_io`](Self::on_read_io) /
+/// [`on_write_io`](Self::on_write_io) first and fall through to the bus's own `read`/`write`
+/// when the hook declines. Both hooks decline by default, so a plain memory bus opts in with an
+/// empty `impl Mos6502CompatibleBus for MyBus {}`, and a bus with devices overrides them for its
+/// registers without touching its memory dispatch. Registers with read side effects should leave
+/// [`AccessType::Peek`] reads alone, so a disassembler or memory diff doesn't disturb the device;
+/// [`AccessType::DummyRead`] is a real CPU cycle and triggers them on hardware.
+///
+/// The hooks see the 16-bit address space; 65C816 accesses above `$FFFF` go straight to memory.
+///
+/// # Examples
+///
+/// ```
+/// use ull::{Address, Bus, Byte, Word};
+/// use ull65::instruction::mos6502::Mos6502;
+/// use ull65::{AccessType, Cpu, Mos6502CompatibleBus};
+///
+/// /// 64 KB of RAM with a free-running counter readable at $D000.
+/// struct CounterBus {
+///     ram: Vec<u8>,
+///     counter: u8,
+/// }
+///
+/// impl Bus for CounterBus {
+///     type Access = AccessType;
+///     type Data = Byte;
+///
+///     fn read<A: Address>(&mut self, addr: A, _access: AccessType) -> Byte {
+///         Byte(self.ram[addr.as_usize() & 0xFFFF])
+///     }
+///
+///     fn write<A: Address, V: Into<Byte>>(&mut self, addr: A, value: V, _access: AccessType) {
+///         self.ram[addr.as_usize() & 0xFFFF] = value.into().0;
+///     }
+/// }
+///
+/// impl Mos6502CompatibleBus for CounterBus {
+///     fn on_read_io(&mut self, addr: Word, _access: AccessType) -> Option<Byte> {
+///         (addr == Word(0xD000)).then(|| {
+///             self.counter = self.counter.wrapping_add(1);
+///             Byte(self.counter)
+///         })
+///     }
+/// }
+///
+/// let mut bus = CounterBus { ram: vec![0; 0x10000], counter: 0 };
+/// // LDA $D000; LDA $D000
+/// let program = [0xAD, 0x00, 0xD0, 0xAD, 0x00, 0xD0];
+/// let mut cpu: Cpu<CounterBus> =
+///     Cpu::with_program::<Mos6502>(&mut bus, Word(0x8000), &program, Word(0x8000));
+/// cpu.step(&mut bus);
+/// cpu.step(&mut bus);
+///
+/// assert_eq!(cpu.a, Byte(2));
+/// ```
+pub trait Mos6502CompatibleBus: Bus<Access = AccessType, Data = Byte> {
+    /// Service a read of `addr`, or return `None` to read memory instead.
+    fn on_read_io(&mut self, _addr: Word, _access: AccessType) -> Option<Byte> {
+        None
+    }
 
-impl<T> Mos6502CompatibleBus for T where T: Bus<Access = AccessType, Data = Byte> {}
+    /// Service a write to `addr`, or return `false` to write memory instead.
+    fn on_write_io(&mut self, _addr: Word, _value: Byte, _access: AccessType) -> bool {
+        false
+    }
+
+    /// Read `addr` the way the CPU does: through [`on_read_io`](Self::on_read_io), then memory.
+    #[inline]
+    fn cpu_read<A: Address>(&mut self, addr: A, access: AccessType) -> Byte {
+        match io_address(addr).and_then(|io| self.on_read_io(io, access)) {
+            Some(value) => value,
+            None => self.read(addr, access),
+        }
+    }
+
+    /// [`cpu_read`](Self::cpu_read) at a known point of the clock; see [`Bus::read_phased`].
+    #[inline]
+    fn cpu_read_phased<A: Address>(
+        &mut self,
+        addr: A,
+        access: AccessType,
+        phase: CyclePhase,
+    ) -> Byte {
+        match io_address(addr).and_then(|io| self.on_read_io(io, access)) {
+            Some(value) => value,
+            None => self.read_phased(addr, access, phase),
+        }
+    }
+
+    /// Write `addr` the way the CPU does: through [`on_write_io`](Self::on_write_io), then memory.
+    #[inline]
+    fn cpu_write<A: Address, V: Into<Byte>>(&mut self, addr: A, value: V, access: AccessType) {
+        let value = value.into();
+        if !io_address(addr).is_some_and(|io| self.on_write_io(io, value, access)) {
+            self.write(addr, value, access);
+        }
+    }
+}
+
+/// The address the I/O hooks see, if `addr` lies in the 16-bit address space.
+#[inline]
+fn io_address<A: Address>(addr: A) -> Option<Word> {
+    u16::try_from(addr.as_usize()).ok().map(Word)
+}
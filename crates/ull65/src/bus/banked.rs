@@ -0,0 +1,311 @@
+//! Bus built from memory banks and MMIO handlers mapped onto address ranges.
+
+use alloc::{boxed::Box, vec, vec::Vec};
+use core::fmt;
+use core::ops::RangeInclusive;
+use ull::{Address, Bus, Byte};
+
+use crate::AccessType;
+use crate::bus::Mos6502CompatibleBus;
+
+/// A memory-mapped device serviced by the host.
+///
+/// `offset` is relative to the start of the range the handler was mapped at, so a device with
+/// four registers sees offsets `0..=3` wherever it lands in the address space. `access` is passed
+/// through from the bus, so a register that clears on read can leave [`AccessType::Peek`]s from
+/// debuggers and disassemblers alone.
+pub trait IoHandler {
+    fn read(&mut self, offset: u16, access: AccessType) -> Byte;
+    fn write(&mut self, offset: u16, value: Byte, access: AccessType);
+}
+
+/// Handle to a bank owned by a [`BankedBus`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BankId(usize);
+
+#[derive(Debug, Clone, Copy)]
+enum Target {
+    Memory {
+        bank: usize,
+        offset: usize,
+        read_only: bool,
+    },
+    Io(usize),
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Region {
+    start: u16,
+    end: u16,
+    target: Target,
+}
+
+/// Memory map assembled from RAM/ROM banks and I/O handlers.
+///
+/// Each mapped range points at a bank (with a starting offset and a read-only bit) or at an
+/// [`IoHandler`]. Later mappings take priority over earlier ones where they overlap, so a
+/// cartridge window can be mapped over a RAM-filled address space. Addresses past the end of a
+/// bank wrap back to its start, which is how mirrors are expressed: a 2 KB bank mapped at
+/// `$0000-$1FFF` appears four times. Bank switching is just mapping another bank (or another
+/// offset into the same one) over the window with [`map_bank`](Self::map_bank).
+///
+/// Writes to read-only regions are dropped, and unmapped addresses read as `$FF`.
+///
+/// # Examples
+///
+/// ```
+/// use ull::{Bus, Byte, Word};
+/// use ull65::bus::banked::BankedBus;
+/// use ull65::AccessType;
+///
+/// let mut bus = BankedBus::new();
+/// bus.map_ram(0x0000..=0x07FF);
+/// let rom = bus.map_rom(0xC000..=0xFFFF, vec![0xEA; 0x2000]);
+///
+/// bus.write(Word(0xC000), Byte(0x00), AccessType::DataWrite);
+/// assert_eq!(bus.read(Word(0xC000), AccessType::DataRead), Byte(0xEA));
+/// // The 8 KB image is mirrored across the 16 KB window.
+/// assert_eq!(bus.bank(rom).len(), 0x2000);
+/// assert_eq!(bus.read(Word(0xE000), AccessType::DataRead), Byte(0xEA));
+/// ```
+#[derive(Default)]
+pub struct BankedBus {
+    banks: Vec<Box<[u8]>>,
+    /// Handler slots; a slot is emptied once every range it was mapped at is covered by later
+    /// mappings, and reused by the next [`map_io`](Self::map_io).
+    io: Vec<Option<Box<dyn IoHandler>>>,
+    regions: Vec<Region>,
+}
+
+impl BankedBus {
+    /// Value read from addresses nothing is mapped at.
+    pub const UNMAPPED: Byte = Byte(0xFF);
+
+    /// Create a bus with nothing mapped.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Take ownership of `bytes` as a new bank without mapping it anywhere.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bytes` is empty.
+    pub fn add_bank(&mut self, bytes: impl Into<Box<[u8]>>) -> BankId {
+        let bytes = bytes.into();
+        assert!(!bytes.is_empty(), "banks must hold at least one byte");
+        self.banks.push(bytes);
+        BankId(self.banks.len() - 1)
+    }
+
+    /// Map `range` onto `bank`, starting `offset` bytes into it.
+    ///
+    /// Remapping a range that's already mapped is how bank switching works.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bank` didn't come from this bus.
+    pub fn map_bank(
+        &mut self,
+        range: RangeInclusive<u16>,
+        bank: BankId,
+        offset: usize,
+        read_only: bool,
+    ) {
+        assert!(bank.0 < self.banks.len(), "unknown bank {bank:?}");
+        self.map(
+            range,
+            Target::Memory {
+                bank: bank.0,
+                offset,
+                read_only,
+            },
+        );
+    }
+
+    /// Add a zero-filled RAM bank the size of `range` and map it there.
+    pub fn map_ram(&mut self, range: RangeInclusive<u16>) -> BankId {
+        let bank = self.add_bank(vec![0; Self::range_len(&range)]);
+        self.map_bank(range, bank, 0, false);
+        bank
+    }
+
+    /// Add `bytes` as a read-only bank and map it at `range`, mirrored if it's shorter.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bytes` is empty.
+    pub fn map_rom(&mut self, range: RangeInclusive<u16>, bytes: impl Into<Box<[u8]>>) -> BankId {
+        let bank = self.add_bank(bytes);
+        self.map_bank(range, bank, 0, true);
+        bank
+    }
+
+    /// Route every read and write in `range` to `handler`.
+    ///
+    /// A handler that ends up completely hidden behind later mappings, e.g. because another
+    /// device was mapped over the same range, is dropped.
+    pub fn map_io<H: IoHandler + 'static>(&mut self, range: RangeInclusive<u16>, handler: H) {
+        let slot = match self.io.iter().position(Option::is_none) {
+            Some(slot) => slot,
+            None => {
+                self.io.push(None);
+                self.io.len() - 1
+            }
+        };
+        self.io[slot] = Some(Box::new(handler));
+        self.map(range, Target::Io(slot));
+    }
+
+    /// Contents of `bank`, bypassing the memory map and write protection.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bank` didn't come from this bus.
+    #[must_use]
+    pub fn bank(&self, bank: BankId) -> &[u8] {
+        &self.banks[bank.0]
+    }
+
+    /// Mutable contents of `bank`, e.g. to patch a ROM image in place.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bank` didn't come from this bus.
+    pub fn bank_mut(&mut self, bank: BankId) -> &mut [u8] {
+        &mut self.banks[bank.0]
+    }
+
+    fn map(&mut self, range: RangeInclusive<u16>, target: Target) {
+        self.regions.push(Region {
+            start: *range.start(),
+            end: *range.end(),
+            target,
+        });
+
+        // Switching a bank remaps the same window over and over; drop regions that can no longer
+        // be reached instead of stacking them, along with I/O handlers left without a region.
+        let mut index = 0;
+        while index < self.regions.len() {
+            let region = self.regions[index];
+            if Self::covered(&self.regions[index + 1..], region.start, region.end) {
+                self.regions.remove(index);
+            } else {
+                index += 1;
+            }
+        }
+        for (slot, handler) in self.io.iter_mut().enumerate() {
+            let mapped = self
+                .regions
+                .iter()
+                .any(|region| matches!(region.target, Target::Io(io) if io == slot));
+            if !mapped {
+                *handler = None;
+            }
+        }
+    }
+
+    /// Whether `later` regions together cover every address in `start..=end`.
+    fn covered(later: &[Region], start: u16, end: u16) -> bool {
+        let mut addr = start;
+        loop {
+            let Some(region) = later
+                .iter()
+                .find(|region| (region.start..=region.end).contains(&addr))
+            else {
+                return false;
+            };
+            if region.end >= end {
+                return true;
+            }
+            addr = region.end + 1;
+        }
+    }
+
+    fn range_len(range: &RangeInclusive<u16>) -> usize {
+        usize::from(*range.end()).saturating_sub(usize::from(*range.start())) + 1
+    }
+
+    /// The most recently mapped region containing `addr`, and `addr`'s offset into it.
+    fn lookup(&self, addr: u16) -> Option<(Target, usize)> {
+        self.regions
+            .iter()
+            .rev()
+            .find(|region| (region.start..=region.end).contains(&addr))
+            .map(|region| (region.target, usize::from(addr - region.start)))
+    }
+
+    fn bank_index(&self, bank: usize, offset: usize, within: usize) -> usize {
+        (offset + within) % self.banks[bank].len()
+    }
+}
+
+impl fmt::Debug for BankedBus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BankedBus")
+            .field(
+                "banks",
+                &self.banks.iter().map(|bank| bank.len()).collect::<Vec<_>>(),
+            )
+            .field("io", &self.io.iter().flatten().count())
+            .field("regions", &self.regions)
+            .finish()
+    }
+}
+
+impl Bus for BankedBus {
+    type Access = AccessType;
+    type Data = Byte;
+
+    fn read<A>(&mut self, addr: A, access: Self::Access) -> Self::Data
+    where
+        A: Address,
+    {
+        match self.lookup(addr.as_u16()) {
+            Some((Target::Memory { bank, offset, .. }, within)) => {
+                let index = self.bank_index(bank, offset, within);
+                Byte(self.banks[bank][index])
+            }
+            Some((Target::Io(slot), within)) => match self.io[slot].as_deref_mut() {
+                Some(handler) => handler.read(within as u16, access),
+                None => Self::UNMAPPED,
+            },
+            None => Self::UNMAPPED,
+        }
+    }
+
+    fn write<A, V>(&mut self, addr: A, value: V, access: Self::Access)
+    where
+        A: Address,
+        V: Into<Self::Data>,
+    {
+        match self.lookup(addr.as_u16()) {
+            Some((
+                Target::Memory {
+                    bank,
+                    offset,
+                    read_only: false,
+                },
+                within,
+            )) => {
+                let index = self.bank_index(bank, offset, within);
+                self.banks[bank][index] = value.into().0;
+            }
+            Some((Target::Io(slot), within)) => {
+                if let Some(handler) = self.io[slot].as_deref_mut() {
+                    handler.write(within as u16, value.into(), access);
+                }
+            }
+            Some((
+                Target::Memory {
+                    read_only: true, ..
+                },
+                _,
+            ))
+            | None => {}
+        }
+    }
+}
+
+impl Mos6502CompatibleBus for BankedBus {}
@@ -0,0 +1,84 @@
+//! Memory snapshots and before/after comparison for debugging.
+
+use crate::AccessType;
+use crate::bus::Mos6502CompatibleBus;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::ops::RangeInclusive;
+use ull::Word;
+
+/// Copy `range` out of `bus` so it can later be compared with [`diff_memory`].
+///
+/// Memory is read with [`AccessType::Peek`], so buses that track side effects can ignore
+/// these accesses.
+pub fn snapshot<B: Mos6502CompatibleBus>(bus: &mut B, range: RangeInclusive<u16>) -> Vec<u8> {
+    let mut bytes = vec![0; range.len()];
+    bus.read_block(Word(*range.start()), &mut bytes, AccessType::Peek);
+    bytes
+}
+
+/// Addresses in `range` whose value on `bus` differs from `before`, as
+/// `(address, old, new)` in ascending address order.
+///
+/// `before[0]` is the byte that was at the start of `range`, as returned by [`snapshot`]. If
+/// `before` is shorter than `range`, only that many bytes are compared. Reads use
+/// [`AccessType::Peek`].
+///
+/// # Examples
+///
+/// ```
+/// use ull::{Bus, Byte, Word};
+/// use ull65::bus::diff::{diff_memory, snapshot};
+/// use ull65::{AccessType, SimpleBus};
+///
+/// let mut bus = SimpleBus::default();
+/// let before = snapshot(&mut bus, 0x0200..=0x02FF);
+///
+/// bus.write(Word(0x0210), Byte(0x42), AccessType::DataWrite);
+///
+/// assert_eq!(diff_memory(&before, &mut bus, 0x0200..=0x02FF), [(Word(0x0210), 0x00, 0x42)]);
+/// ```
+pub fn diff_memory<B: Mos6502CompatibleBus>(
+    before: &[u8],
+    after_bus: &mut B,
+    range: RangeInclusive<u16>,
+) -> Vec<(Word, u8, u8)> {
+    let after = snapshot(after_bus, range.clone());
+    range
+        .zip(before.iter().zip(after))
+        .filter(|&(_, (&old, new))| old != new)
+        .map(|(addr, (&old, new))| (Word(addr), old, new))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SimpleBus;
+    use ull::{Bus, Byte};
+
+    #[test]
+    fn diff_reports_exactly_the_modified_bytes() {
+        let mut bus = SimpleBus::default();
+        bus.load(Word(0x00F0), &[0x11; 0x20]);
+        let before = snapshot(&mut bus, 0x0000..=0xFFFF);
+
+        bus.write(Word(0x00F8), Byte(0x12), AccessType::DataWrite);
+        bus.write(Word(0x0101), Byte(0x00), AccessType::DataWrite);
+        bus.write(Word(0x0105), Byte(0x11), AccessType::DataWrite); // unchanged
+        bus.write(Word(0xFFFF), Byte(0xEE), AccessType::DataWrite);
+
+        assert_eq!(
+            diff_memory(&before, &mut bus, 0x0000..=0xFFFF),
+            [
+                (Word(0x00F8), 0x11, 0x12),
+                (Word(0x0101), 0x11, 0x00),
+                (Word(0xFFFF), 0x00, 0xEE),
+            ]
+        );
+        assert_eq!(
+            diff_memory(&before[0x100..], &mut bus, 0x0100..=0x01FF),
+            [(Word(0x0101), 0x11, 0x00)]
+        );
+    }
+}
@@ -0,0 +1,342 @@
+//! Loaders for the text object formats assemblers emit.
+//!
+//! Each loader writes the decoded bytes through [`Bus::write`] with
+//! [`AccessType::DataWrite`], so bus side effects (write-protected ranges, traps) apply just as
+//! they would to a program storing the same bytes.
+
+use alloc::vec::Vec;
+use ull::{Bus, Byte, Word};
+
+use crate::{AccessType, Error, Result};
+
+const ADDRESS_SPACE: usize = 0x10000;
+
+/// Load an Intel HEX image into `bus`, returning the start address if the image has one.
+///
+/// Handles data (`00`), end-of-file (`01`), extended segment/linear address (`02`/`04`) and
+/// start segment/linear address (`03`/`05`) records. Blank lines are skipped and anything
+/// after the end-of-file record is ignored.
+///
+/// # Errors
+///
+/// Returns [`Error::Parse`] for a malformed record, a checksum mismatch, an unknown record
+/// type, or a missing end-of-file record, and [`Error::OutOfBounds`] for data that falls
+/// outside the 64 KB address space. Bytes from earlier records have already been written
+/// when an error is reported.
+///
+/// # Examples
+///
+/// ```
+/// use ull::{Bus, Word};
+/// use ull65::bus::loader::load_ihex;
+/// use ull65::{AccessType, SimpleBus};
+///
+/// let mut bus = SimpleBus::default();
+/// let start = load_ihex(&mut bus, ":03800000A9428D05\n:00000001FF\n").unwrap();
+///
+/// assert_eq!(start, None);
+/// assert_eq!(bus.read(Word(0x8001), AccessType::DataRead).0, 0x42);
+/// ```
+pub fn load_ihex<B>(bus: &mut B, text: &str) -> Result<Option<Word>>
+where
+    B: Bus<Access = AccessType, Data = Byte>,
+{
+    let mut base = 0usize;
+    let mut start = None;
+
+    for (index, line) in text.lines().enumerate() {
+        let line_no = index + 1;
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        let digits = line
+            .strip_prefix(':')
+            .ok_or(parse_error(line_no, "missing ':' start code"))?;
+        let record = decode_hex(digits, line_no)?;
+        if record.len() < 5 || record.len() != usize::from(record[0]) + 5 {
+            return Err(parse_error(
+                line_no,
+                "record length doesn't match its byte count",
+            ));
+        }
+        if record.iter().fold(0u8, |sum, &byte| sum.wrapping_add(byte)) != 0 {
+            return Err(parse_error(line_no, "checksum mismatch"));
+        }
+
+        let offset = usize::from(u16::from_be_bytes([record[1], record[2]]));
+        let data = &record[4..record.len() - 1];
+        match (record[3], data) {
+            (0x00, _) => write_bytes(bus, base + offset, data)?,
+            (0x01, _) => return Ok(start),
+            (0x02, &[hi, lo]) => base = usize::from(u16::from_be_bytes([hi, lo])) << 4,
+            (0x04, &[hi, lo]) => base = usize::from(u16::from_be_bytes([hi, lo])) << 16,
+            (0x03, &[cs_hi, cs_lo, ip_hi, ip_lo]) => {
+                let segment = usize::from(u16::from_be_bytes([cs_hi, cs_lo])) << 4;
+                let ip = usize::from(u16::from_be_bytes([ip_hi, ip_lo]));
+                start = Some(entry_point(segment + ip, line_no)?);
+            }
+            (0x05, &[b3, b2, b1, b0]) => {
+                start = Some(entry_point(
+                    u32::from_be_bytes([b3, b2, b1, b0]) as usize,
+                    line_no,
+                )?);
+            }
+            (0x02..=0x05, _) => {
+                return Err(parse_error(line_no, "wrong data length for record type"));
+            }
+            _ => return Err(parse_error(line_no, "unsupported record type")),
+        }
+    }
+
+    Err(parse_error(
+        text.lines().count(),
+        "missing end-of-file record",
+    ))
+}
+
+/// Load a Motorola S-record image into `bus`, returning the start address if the image has one.
+///
+/// Data records with 16-, 24- and 32-bit addresses (`S1`/`S2`/`S3`) are written; the `S0`
+/// header and `S5`/`S6` record counts are checked and skipped. A termination record
+/// (`S7`/`S8`/`S9`) supplies the start address and ends the image; without one, loading stops
+/// at the end of `text` with no start address. Blank lines are skipped.
+///
+/// # Errors
+///
+/// Returns [`Error::Parse`] for a malformed record, a checksum mismatch, an unknown record
+/// type, or a start address past `$FFFF`, and [`Error::OutOfBounds`] for data that falls
+/// outside the 64 KB address space. Bytes from earlier records have already been written when an error is reported.
+///
+/// # Examples
+///
+/// ```
+/// use ull::{Bus, Word};
+/// use ull65::bus::loader::load_srec;
+/// use ull65::{AccessType, SimpleBus};
+///
+/// let mut bus = SimpleBus::default();
+/// let start = load_srec(&mut bus, "S1068000A9428D01\nS90380007C\n").unwrap();
+///
+/// assert_eq!(start, Some(Word(0x8000)));
+/// assert_eq!(bus.read(Word(0x8001), AccessType::DataRead).0, 0x42);
+/// ```
+pub fn load_srec<B>(bus: &mut B, text: &str) -> Result<Option<Word>>
+where
+    B: Bus<Access = AccessType, Data = Byte>,
+{
+    for (index, line) in text.lines().enumerate() {
+        let line_no = index + 1;
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        let (kind, digits) = line
+            .strip_prefix('S')
+            .and_then(|rest| rest.split_at_checked(1))
+            .ok_or(parse_error(line_no, "missing 'S' record type"))?;
+        let record = decode_hex(digits, line_no)?;
+        if record.is_empty() || record.len() != usize::from(record[0]) + 1 {
+            return Err(parse_error(
+                line_no,
+                "record length doesn't match its byte count",
+            ));
+        }
+        if record.iter().fold(0u8, |sum, &byte| sum.wrapping_add(byte)) != 0xFF {
+            return Err(parse_error(line_no, "checksum mismatch"));
+        }
+
+        let address_bytes = match kind {
+            "0" | "1" | "5" | "9" => 2,
+            "2" | "6" | "8" => 3,
+            "3" | "7" => 4,
+            _ => return Err(parse_error(line_no, "unsupported record type")),
+        };
+        // Count byte, address and checksum.
+        if record.len() < address_bytes + 2 {
+            return Err(parse_error(line_no, "record too short for its address"));
+        }
+        let address = record[1..=address_bytes]
+            .iter()
+            .fold(0usize, |addr, &byte| addr << 8 | usize::from(byte));
+        let data = &record[address_bytes + 1..record.len() - 1];
+
+        match kind {
+            "1" | "2" | "3" => write_bytes(bus, address, data)?,
+            "7" | "8" | "9" => return entry_point(address, line_no).map(Some),
+            _ => {}
+        }
+    }
+
+    Ok(None)
+}
+
+/// Decode pairs of hex digits into bytes.
+fn decode_hex(digits: &str, line: usize) -> Result<Vec<u8>> {
+    if !digits.len().is_multiple_of(2) {
+        return Err(parse_error(line, "odd number of hex digits"));
+    }
+    digits
+        .as_bytes()
+        .chunks(2)
+        .map(|pair| match pair {
+            &[hi, lo] if hi.is_ascii_hexdigit() && lo.is_ascii_hexdigit() => {
+                Ok(hex_value(hi) << 4 | hex_value(lo))
+            }
+            _ => Err(parse_error(line, "invalid hex digit")),
+        })
+        .collect()
+}
+
+fn hex_value(digit: u8) -> u8 {
+    match digit {
+        b'0'..=b'9' => digit - b'0',
+        _ => (digit | 0x20) - b'a' + 10,
+    }
+}
+
+fn write_bytes<B>(bus: &mut B, start: usize, data: &[u8]) -> Result<()>
+where
+    B: Bus<Access = AccessType, Data = Byte>,
+{
+    if start + data.len() > ADDRESS_SPACE {
+        return Err(Error::OutOfBounds {
+            start,
+            len: data.len(),
+            limit: ADDRESS_SPACE,
+        });
+    }
+    for (offset, &byte) in data.iter().enumerate() {
+        bus.write(
+            Word((start + offset) as u16),
+            Byte(byte),
+            AccessType::DataWrite,
+        );
+    }
+    Ok(())
+}
+
+fn entry_point(addr: usize, line: usize) -> Result<Word> {
+    u16::try_from(addr)
+        .map(Word)
+        .map_err(|_| parse_error(line, "start address outside the 64 KB address space"))
+}
+
+const fn parse_error(line: usize, reason: &'static str) -> Error {
+    Error::Parse { line, reason }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SimpleBus;
+
+    fn read(bus: &mut SimpleBus, addr: u16) -> u8 {
+        bus.read(Word(addr), AccessType::DataRead).0
+    }
+
+    #[test]
+    fn ihex_writes_data_and_reports_the_start_address() {
+        let hex = "\
+:10C00000A9008D0002E8D0FA4C00C0EAEAEAEAEAA8
+:02C010001234E8
+:040000050000C00037
+:00000001FF
+";
+        let mut bus = SimpleBus::default();
+
+        let start = load_ihex(&mut bus, hex).unwrap();
+
+        assert_eq!(start, Some(Word(0xC000)));
+        assert_eq!(read(&mut bus, 0xC000), 0xA9);
+        assert_eq!(read(&mut bus, 0xC00F), 0xEA);
+        assert_eq!(read(&mut bus, 0xC010), 0x12);
+        assert_eq!(read(&mut bus, 0xC011), 0x34);
+        assert_eq!(read(&mut bus, 0xC012), 0x00);
+    }
+
+    #[test]
+    fn ihex_extended_linear_address_must_stay_in_64k() {
+        let mut bus = SimpleBus::default();
+        let hex = ":020000040000FA\n:01FFFF00AA57\n:00000001FF\n";
+        assert_eq!(load_ihex(&mut bus, hex), Ok(None));
+        assert_eq!(read(&mut bus, 0xFFFF), 0xAA);
+
+        let hex = ":020000040001F9\n:0100000055AA\n:00000001FF\n";
+        assert_eq!(
+            load_ihex(&mut bus, hex),
+            Err(Error::OutOfBounds {
+                start: 0x10000,
+                len: 1,
+                limit: 0x10000
+            })
+        );
+    }
+
+    #[test]
+    fn srec_loads_16_bit_records() {
+        let srec = "S00600004844521B\nS108C000A9008D0002FF\nS5030003F9\nS90380007C\n";
+        let mut bus = SimpleBus::default();
+
+        assert_eq!(load_srec(&mut bus, srec), Ok(Some(Word(0x8000))));
+        assert_eq!(read(&mut bus, 0xC000), 0xA9);
+        assert_eq!(read(&mut bus, 0xC004), 0x02);
+        // The S0 header's bytes go nowhere.
+        assert_eq!(read(&mut bus, 0x0000), 0x00);
+    }
+
+    #[test]
+    fn srec_loads_24_bit_records() {
+        let mut bus = SimpleBus::default();
+        assert_eq!(
+            load_srec(&mut bus, "S20600C010EAEA55\nS80400C0003B\n"),
+            Ok(Some(Word(0xC000)))
+        );
+        assert_eq!(read(&mut bus, 0xC010), 0xEA);
+        assert_eq!(read(&mut bus, 0xC011), 0xEA);
+
+        assert_eq!(
+            load_srec(&mut bus, "S205010000EA0F\n"),
+            Err(Error::OutOfBounds {
+                start: 0x10000,
+                len: 1,
+                limit: 0x10000
+            })
+        );
+        assert_eq!(
+            load_srec(&mut bus, "S20600C010EAEA54\n"),
+            Err(Error::Parse {
+                line: 1,
+                reason: "checksum mismatch"
+            })
+        );
+    }
+
+    #[test]
+    fn ihex_rejects_bad_checksums_and_missing_eof() {
+        let mut bus = SimpleBus::default();
+        assert_eq!(
+            load_ihex(&mut bus, ":0100000055AB\n:00000001FF\n"),
+            Err(Error::Parse {
+                line: 1,
+                reason: "checksum mismatch"
+            })
+        );
+        assert_eq!(
+            load_ihex(&mut bus, ":0100000055AA\n"),
+            Err(Error::Parse {
+                line: 1,
+                reason: "missing end-of-file record"
+            })
+        );
+        assert_eq!(
+            load_ihex(&mut bus, ":01000000G5AA\n"),
+            Err(Error::Parse {
+                line: 1,
+                reason: "invalid hex digit"
+            })
+        );
+    }
+}
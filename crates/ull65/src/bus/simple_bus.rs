@@ -1,14 +1,62 @@
 //! Basic flat memory implementation for 8-bit 65xx CPUs.
 
-use alloc::{boxed::Box, vec};
+use alloc::{boxed::Box, vec, vec::Vec};
+use core::ops::RangeInclusive;
 use ull::{Address, Byte, Word};
 use ull::Bus;
-use crate::AccessType;
+use crate::bus::Mos6502CompatibleBus;
+use crate::{AccessType, Error, Result};
+
+/// A write that [`SimpleBus`] dropped because it landed in a read-only range, recorded while
+/// [`set_trap_rom_writes`](SimpleBus::set_trap_rom_writes) is on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RomWrite {
+    pub addr: Word,
+    pub value: Byte,
+}
+
+/// Address ranges plus a bitmap of the pages they touch, so the common case of an address in a
+/// page with no ranges is answered without scanning them.
+#[derive(Debug, Default)]
+struct RangeSet {
+    pages: [u64; 4],
+    ranges: Vec<RangeInclusive<u16>>,
+}
+
+impl RangeSet {
+    fn insert(&mut self, range: RangeInclusive<u16>) {
+        if range.is_empty() {
+            return;
+        }
+        for page in (range.start() >> 8)..=(range.end() >> 8) {
+            self.pages[usize::from(page >> 6)] |= 1 << (page & 63);
+        }
+        self.ranges.push(range);
+    }
+
+    fn contains(&self, addr: u16) -> bool {
+        let page = addr >> 8;
+        self.pages[usize::from(page >> 6)] & (1 << (page & 63)) != 0
+            && self.ranges.iter().any(|range| range.contains(&addr))
+    }
+}
 
 /// Simple contiguous memory without mirroring or bank logic.
+///
+/// Everything is RAM unless a range is write-protected with
+/// [`mark_readonly`](Self::mark_readonly) or left unconnected with
+/// [`mark_unmapped`](Self::mark_unmapped). Only the low 16 bits of an address are decoded, so the
+/// 65C816's 24-bit addresses see the same 64 KB mirrored into every bank.
 #[derive(Debug)]
 pub struct SimpleBus {
     mem: Box<[u8]>,
+    readonly: RangeSet,
+    unmapped: RangeSet,
+    ignored_writes: u64,
+    trap_rom_writes: bool,
+    rom_write: Option<RomWrite>,
+    open_bus: bool,
+    last_value: Byte,
 }
 
 impl SimpleBus {
@@ -28,12 +76,167 @@ impl SimpleBus {
             idx = (idx + 1) & Self::ADDR_MASK;
         }
     }
+
+    /// Like [`load`](Self::load), but refuses images that would wrap past $FFFF.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::OutOfBounds`] without writing anything if `bytes` doesn't fit.
+    pub fn try_load(&mut self, start: Word, bytes: &[u8]) -> Result<()> {
+        let begin = start.as_usize();
+        if begin + bytes.len() > Self::MEM_SIZE {
+            return Err(Error::OutOfBounds {
+                start: begin,
+                len: bytes.len(),
+                limit: Self::MEM_SIZE,
+            });
+        }
+        self.mem[begin..begin + bytes.len()].copy_from_slice(bytes);
+        Ok(())
+    }
+
+    /// Treat `range` as ROM: bus writes that land in it are dropped and counted in
+    /// [`ignored_writes`](Self::ignored_writes).
+    ///
+    /// Reads are unaffected, and [`load`](Self::load)/[`try_load`](Self::try_load) still write
+    /// the region, so load the image before or after protecting it.
+    pub fn mark_readonly(&mut self, range: RangeInclusive<u16>) {
+        self.readonly.insert(range);
+    }
+
+    /// Disconnect `range`: writes are dropped (and counted in
+    /// [`ignored_writes`](Self::ignored_writes)) and reads return `$00`, or the last value on
+    /// the bus once [`set_open_bus`](Self::set_open_bus) is enabled.
+    pub fn mark_unmapped(&mut self, range: RangeInclusive<u16>) {
+        self.unmapped.insert(range);
+    }
+
+    /// Make reads from unmapped ranges return the last byte read or written (open bus) instead of
+    /// `$00`.
+    ///
+    /// On real systems nothing drives the data lines for such an address, so the CPU sees
+    /// whatever the previous bus cycle left there; often that's the last operand byte fetched.
+    /// [`Peek`](AccessType::Peek) reads aren't bus cycles, so they leave that value alone.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ull::{Bus, Byte, Word};
+    /// use ull65::{AccessType, SimpleBus};
+    ///
+    /// let mut bus = SimpleBus::default();
+    /// bus.mark_unmapped(0x4000..=0x7FFF);
+    /// bus.set_open_bus(true);
+    ///
+    /// bus.write(Word(0x0200), Byte(0xAB), AccessType::DataWrite);
+    /// assert_eq!(bus.read(Word(0x5000), AccessType::DataRead), Byte(0xAB));
+    /// ```
+    pub fn set_open_bus(&mut self, enabled: bool) {
+        self.open_bus = enabled;
+    }
+
+    /// Record writes into [`mark_readonly`](Self::mark_readonly) ranges so they can be reported
+    /// with [`take_rom_write`](Self::take_rom_write) instead of vanishing silently.
+    ///
+    /// Handy when porting code that might scribble over its own ROM. The write is still dropped
+    /// and counted; writes to unmapped ranges aren't recorded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ull::{Byte, Word};
+    /// use ull65::bus::simple_bus::RomWrite;
+    /// use ull65::instruction::mos6502::Mos6502;
+    /// use ull65::{Cpu, SimpleBus};
+    ///
+    /// let mut bus = SimpleBus::default();
+    /// // LDA #$EA; STA $8000
+    /// let program = [0xA9, 0xEA, 0x8D, 0x00, 0x80];
+    /// let mut cpu: Cpu<SimpleBus> =
+    ///     Cpu::with_program::<Mos6502>(&mut bus, Word(0x8000), &program, Word(0x8000));
+    /// bus.mark_readonly(0x8000..=0xFFFF);
+    /// bus.set_trap_rom_writes(true);
+    ///
+    /// cpu.step(&mut bus);
+    /// cpu.step(&mut bus);
+    ///
+    /// let violation = RomWrite { addr: Word(0x8000), value: Byte(0xEA) };
+    /// assert_eq!(bus.take_rom_write(), Some(violation));
+    /// assert_eq!(bus.take_rom_write(), None);
+    /// ```
+    pub fn set_trap_rom_writes(&mut self, enabled: bool) {
+        self.trap_rom_writes = enabled;
+    }
+
+    /// Take the first write into a read-only range recorded since the last call, if any.
+    ///
+    /// Only the first is kept, since that's usually the one that went wrong; later ones still
+    /// show up in [`ignored_writes`](Self::ignored_writes). An NMOS read-modify-write's
+    /// [`DummyWrite`](AccessType::DummyWrite) isn't recorded, so an `INC` on ROM reports the
+    /// value it tried to store.
+    pub fn take_rom_write(&mut self) -> Option<RomWrite> {
+        self.rom_write.take()
+    }
+
+    /// Number of writes dropped because they targeted a read-only or unmapped range.
+    #[must_use]
+    pub fn ignored_writes(&self) -> u64 {
+        self.ignored_writes
+    }
+
+    fn is_unmapped(&self, idx: usize) -> bool {
+        self.unmapped.contains(idx as u16)
+    }
+
+    /// Value seen when reading `idx`, tracking it as the last value on the bus unless it's a
+    /// [`Peek`](AccessType::Peek).
+    fn load_byte(&mut self, idx: usize, access: AccessType) -> u8 {
+        let value = if !self.is_unmapped(idx) {
+            self.mem[idx]
+        } else if self.open_bus {
+            self.last_value.0
+        } else {
+            0
+        };
+        if access != AccessType::Peek {
+            self.last_value = Byte(value);
+        }
+        value
+    }
+
+    /// Store `value` at `idx` unless it's protected or unmapped.
+    fn store(&mut self, idx: usize, value: u8, access: AccessType) {
+        self.last_value = Byte(value);
+        if self.is_unmapped(idx) {
+            self.ignored_writes += 1;
+            return;
+        }
+        if self.readonly.contains(idx as u16) {
+            self.ignored_writes += 1;
+            let dummy = access == AccessType::DummyWrite;
+            if !dummy && self.trap_rom_writes && self.rom_write.is_none() {
+                self.rom_write = Some(RomWrite {
+                    addr: Word(idx as u16),
+                    value: Byte(value),
+                });
+            }
+            return;
+        }
+        self.mem[idx] = value;
+    }
 }
 
 impl Default for SimpleBus {
     fn default() -> Self {
         Self {
             mem: vec![0; Self::MEM_SIZE].into_boxed_slice(),
+            readonly: RangeSet::default(),
+            unmapped: RangeSet::default(),
+            ignored_writes: 0,
+            trap_rom_writes: false,
+            rom_write: None,
+            open_bus: false,
+            last_value: Byte(0),
         }
     }
 }
@@ -42,41 +245,54 @@ impl Bus for SimpleBus {
     type Access = AccessType;
     type Data = Byte;
 
-    fn read<A>(&mut self, addr: A, _access: Self::Access) -> Self::Data
+    fn read<A>(&mut self, addr: A, access: Self::Access) -> Self::Data
     where
         A: Address,
     {
-        Byte(self.mem[addr.as_usize()])
+        Byte(self.load_byte(addr.as_usize() & Self::ADDR_MASK, access))
     }
 
-    fn write<A, V>(&mut self, addr: A, value: V, _access: Self::Access)
+    fn write<A, V>(&mut self, addr: A, value: V, access: Self::Access)
     where
         A: Address,
         V: Into<Self::Data>,
     {
         let byte: Byte = value.into();
-        self.mem[addr.as_usize()] = byte.0;
+        self.store(addr.as_usize() & Self::ADDR_MASK, byte.0, access);
     }
 
-    fn read_block<A>(&mut self, start: A, dst: &mut [u8], _access: Self::Access)
+    fn read_block<A>(&mut self, start: A, dst: &mut [u8], access: Self::Access)
     where
         A: Address,
     {
         let mut idx = start.as_usize() & Self::ADDR_MASK;
         for byte in dst {
-            *byte = self.mem[idx];
+            *byte = self.load_byte(idx, access);
             idx = (idx + 1) & Self::ADDR_MASK;
         }
     }
 
-    fn write_block<A>(&mut self, start: A, data: &[u8], _access: Self::Access)
+    fn write_block<A>(&mut self, start: A, data: &[u8], access: Self::Access)
     where
         A: Address,
     {
         let mut idx = start.as_usize() & Self::ADDR_MASK;
         for &byte in data {
-            self.mem[idx] = byte;
+            self.store(idx, byte, access);
+            idx = (idx + 1) & Self::ADDR_MASK;
+        }
+    }
+
+    fn fill_block<A>(&mut self, start: A, len: usize, value: Byte, access: Self::Access)
+    where
+        A: Address,
+    {
+        let mut idx = start.as_usize() & Self::ADDR_MASK;
+        for _ in 0..len {
+            self.store(idx, value.0, access);
             idx = (idx + 1) & Self::ADDR_MASK;
         }
     }
 }
+
+impl Mos6502CompatibleBus for SimpleBus {}
@@ -0,0 +1,63 @@
+//! Bus adapter over a borrowed byte slice.
+
+use crate::AccessType;
+use crate::bus::Mos6502CompatibleBus;
+use ull::{Address, Bus, Byte};
+
+/// Uses a plain `&mut [u8]` as memory, for quick experiments and tests.
+///
+/// Addresses wrap modulo the slice length, so a 16 KB slice is mirrored four times across the
+/// 64 KB address space, much like partially decoded RAM; a 64 KB slice maps one to one. Accessing
+/// an empty slice panics.
+///
+/// # Examples
+///
+/// ```
+/// use ull::{Byte, Word};
+/// use ull65::bus::slice::SliceBus;
+/// use ull65::instruction::mos6502::Mos6502;
+/// use ull65::Cpu;
+///
+/// let mut mem = [0u8; 0x10000];
+/// mem[0x8000..0x8002].copy_from_slice(&[0xA9, 0x42]); // LDA #$42
+/// mem[0xFFFC..0xFFFE].copy_from_slice(&[0x00, 0x80]);
+///
+/// let mut bus = SliceBus(&mut mem);
+/// let mut cpu: Cpu<SliceBus> = Cpu::with_instruction_set::<Mos6502>();
+/// cpu.reset(&mut bus);
+/// cpu.step(&mut bus);
+///
+/// assert_eq!(cpu.a, Byte(0x42));
+/// assert_eq!(cpu.pc, Word(0x8002));
+/// ```
+#[derive(Debug)]
+pub struct SliceBus<'a>(pub &'a mut [u8]);
+
+impl SliceBus<'_> {
+    fn index(&self, addr: usize) -> usize {
+        addr % self.0.len()
+    }
+}
+
+impl Bus for SliceBus<'_> {
+    type Access = AccessType;
+    type Data = Byte;
+
+    fn read<A>(&mut self, addr: A, _access: Self::Access) -> Self::Data
+    where
+        A: Address,
+    {
+        Byte(self.0[self.index(addr.as_usize())])
+    }
+
+    fn write<A, V>(&mut self, addr: A, value: V, _access: Self::Access)
+    where
+        A: Address,
+        V: Into<Self::Data>,
+    {
+        let idx = self.index(addr.as_usize());
+        self.0[idx] = value.into().0;
+    }
+}
+
+impl Mos6502CompatibleBus for SliceBus<'_> {}
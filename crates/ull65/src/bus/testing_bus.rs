@@ -1,15 +1,25 @@
 //! Utility bus for deterministic unit testing.
 
-use alloc::{boxed::Box, collections::VecDeque, vec};
-use ull::{Address, Byte};
+use alloc::{boxed::Box, collections::VecDeque, vec, vec::Vec};
+use ull::{Address, Byte, Word};
 use ull::{Bus, DmaRequest, DmaResult};
-use crate::AccessType;
+use crate::bus::Mos6502CompatibleBus;
+use crate::{AccessType, Phase};
+
+/// One bus access recorded by [`TestingBus`]: address, purpose, the byte transferred, and
+/// whether it was a read or a write.
+pub type AccessLogEntry = (Word, AccessType, Byte, Phase);
 
 pub struct TestingBus {
     mem: Box<[u8]>,
     pub ticks: u64,
+    /// Cycle count passed to each [`Bus::on_tick`] call, in call order.
+    pub tick_log: Vec<u8>,
     pub dma_ticks: u64,
+    /// Number of `read`/`write` calls serviced, reported through [`Bus::access_count`].
+    pub accesses: u64,
     dma_queue: VecDeque<u8>,
+    log: Option<Vec<AccessLogEntry>>,
 }
 
 impl TestingBus {
@@ -17,6 +27,31 @@ impl TestingBus {
     pub fn queue_dma(&mut self, cycles: u8) {
         self.dma_queue.push_back(cycles);
     }
+
+    /// Start recording every `read`/`write` in order; see [`take_log`](Self::take_log).
+    pub fn enable_logging(&mut self) {
+        self.log.get_or_insert_with(Vec::new);
+    }
+
+    /// Return the accesses recorded so far and start a fresh log.
+    ///
+    /// Empty unless [`enable_logging`](Self::enable_logging) was called.
+    pub fn take_log(&mut self) -> Vec<AccessLogEntry> {
+        self.log.as_mut().map(core::mem::take).unwrap_or_default()
+    }
+
+    /// Discard the accesses recorded so far, leaving logging enabled if it was.
+    pub fn clear_log(&mut self) {
+        if let Some(log) = self.log.as_mut() {
+            log.clear();
+        }
+    }
+
+    fn record(&mut self, addr: Word, access: AccessType, value: Byte, phase: Phase) {
+        if let Some(log) = self.log.as_mut() {
+            log.push((addr, access, value, phase));
+        }
+    }
 }
 
 impl Default for TestingBus {
@@ -24,8 +59,11 @@ impl Default for TestingBus {
         Self {
             mem: vec![0; 0x10000].into_boxed_slice(),
             ticks: 0,
+            tick_log: Vec::new(),
             dma_ticks: 0,
+            accesses: 0,
             dma_queue: VecDeque::new(),
+            log: None,
         }
     }
 }
@@ -34,24 +72,34 @@ impl Bus for TestingBus {
     type Access = AccessType;
     type Data = Byte;
 
-    fn read<A>(&mut self, addr: A, _access: Self::Access) -> Self::Data
+    fn read<A>(&mut self, addr: A, access: Self::Access) -> Self::Data
     where
         A: Address,
     {
-        Byte(self.mem[addr.as_usize()])
+        self.accesses += 1;
+        let value = Byte(self.mem[addr.as_usize()]);
+        self.record(Word(addr.as_u16()), access, value, Phase::Read);
+        value
     }
 
-    fn write<A, V>(&mut self, addr: A, value: V, _access: Self::Access)
+    fn write<A, V>(&mut self, addr: A, value: V, access: Self::Access)
     where
         A: Address,
         V: Into<Self::Data>,
     {
+        self.accesses += 1;
         let byte: Byte = value.into();
         self.mem[addr.as_usize()] = byte.0;
+        self.record(Word(addr.as_u16()), access, byte, Phase::Write);
     }
 
     fn on_tick(&mut self, cycles: u8) {
         self.ticks += u64::from(cycles);
+        self.tick_log.push(cycles);
+    }
+
+    fn access_count(&self) -> Option<u64> {
+        Some(self.accesses)
     }
 
     fn request_dma(&mut self, request: DmaRequest) -> DmaResult {
@@ -68,3 +116,5 @@ impl Bus for TestingBus {
         }
     }
 }
+
+impl Mos6502CompatibleBus for TestingBus {}
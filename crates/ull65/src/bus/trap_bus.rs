@@ -0,0 +1,183 @@
+//! Bus wrapper that routes magic addresses to host callbacks.
+
+use alloc::{boxed::Box, collections::BTreeMap};
+use core::fmt;
+use ull::{Address, Byte, Word};
+use ull::{Bus, CyclePhase, DmaRequest, DmaResult};
+use crate::AccessType;
+use crate::bus::Mos6502CompatibleBus;
+
+/// Host callback servicing a read from a trapped address.
+pub type ReadTrap<B> = Box<dyn FnMut(&mut B) -> Byte>;
+/// Host callback servicing a write to a trapped address.
+pub type WriteTrap<B> = Box<dyn FnMut(&mut B, Byte)>;
+
+/// Wraps another bus and intercepts data accesses to registered addresses.
+///
+/// Test harnesses and ports such as EhBASIC implement character I/O by reading or writing a
+/// "magic" address and expecting the host to service it. Register those addresses with
+/// [`on_read_trap`](Self::on_read_trap) / [`on_write_trap`](Self::on_write_trap); every other
+/// access is forwarded to the inner bus unchanged. Only [`AccessType::DataRead`] and
+/// [`AccessType::DataWrite`] are trapped, so opcode fetches, stack traffic, vector reads and DMA
+/// still see the underlying memory.
+///
+/// # Examples
+///
+/// ```
+/// use ull::{Bus, Byte, Word};
+/// use ull65::bus::trap_bus::TrapBus;
+/// use ull65::instruction::mos6502::Mos6502;
+/// use ull65::{AccessType, Cpu, SimpleBus};
+///
+/// let mut bus = TrapBus::new(SimpleBus::default());
+/// bus.on_write_trap(Word(0xF001), |_inner, byte| print!("{}", char::from(byte.0)));
+///
+/// // LDA #'!'; STA $F001
+/// let program = [0xA9, b'!', 0x8D, 0x01, 0xF0];
+/// let mut cpu: Cpu<TrapBus<SimpleBus>> =
+///     Cpu::with_program::<Mos6502>(&mut bus, Word(0x8000), &program, Word(0x8000));
+/// cpu.step(&mut bus);
+/// cpu.step(&mut bus);
+///
+/// // The trapped write never reached memory.
+/// assert_eq!(bus.inner_mut().read(Word(0xF001), AccessType::DataRead), Byte(0));
+/// ```
+pub struct TrapBus<B> {
+    inner: B,
+    read_traps: BTreeMap<u16, ReadTrap<B>>,
+    write_traps: BTreeMap<u16, WriteTrap<B>>,
+}
+
+impl<B> TrapBus<B> {
+    /// Wrap `inner` with an empty trap registry.
+    #[must_use]
+    pub fn new(inner: B) -> Self {
+        Self {
+            inner,
+            read_traps: BTreeMap::new(),
+            write_traps: BTreeMap::new(),
+        }
+    }
+
+    /// Service data reads from `addr` with `handler` instead of the inner bus.
+    ///
+    /// Returns the previously registered handler for the address, if any.
+    pub fn on_read_trap<F>(&mut self, addr: Word, handler: F) -> Option<ReadTrap<B>>
+    where
+        F: FnMut(&mut B) -> Byte + 'static,
+    {
+        self.read_traps.insert(addr.0, Box::new(handler))
+    }
+
+    /// Service data writes to `addr` with `handler` instead of the inner bus.
+    ///
+    /// Returns the previously registered handler for the address, if any.
+    pub fn on_write_trap<F>(&mut self, addr: Word, handler: F) -> Option<WriteTrap<B>>
+    where
+        F: FnMut(&mut B, Byte) + 'static,
+    {
+        self.write_traps.insert(addr.0, Box::new(handler))
+    }
+
+    /// Stop trapping reads from `addr`.
+    pub fn remove_read_trap(&mut self, addr: Word) -> Option<ReadTrap<B>> {
+        self.read_traps.remove(&addr.0)
+    }
+
+    /// Stop trapping writes to `addr`.
+    pub fn remove_write_trap(&mut self, addr: Word) -> Option<WriteTrap<B>> {
+        self.write_traps.remove(&addr.0)
+    }
+
+    #[must_use]
+    pub fn inner(&self) -> &B {
+        &self.inner
+    }
+
+    pub fn inner_mut(&mut self) -> &mut B {
+        &mut self.inner
+    }
+
+    #[must_use]
+    pub fn into_inner(self) -> B {
+        self.inner
+    }
+}
+
+impl<B: fmt::Debug> fmt::Debug for TrapBus<B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TrapBus")
+            .field("inner", &self.inner)
+            .field("read_traps", &self.read_traps.keys())
+            .field("write_traps", &self.write_traps.keys())
+            .finish()
+    }
+}
+
+impl<B> Bus for TrapBus<B>
+where
+    B: Mos6502CompatibleBus,
+{
+    type Access = AccessType;
+    type Data = Byte;
+
+    fn read<A>(&mut self, addr: A, access: Self::Access) -> Self::Data
+    where
+        A: Address,
+    {
+        if access == AccessType::DataRead
+            && let Some(handler) = self.read_traps.get_mut(&addr.as_u16())
+        {
+            return handler(&mut self.inner);
+        }
+        self.inner.cpu_read(addr, access)
+    }
+
+    fn read_phased<A>(&mut self, addr: A, access: Self::Access, phase: CyclePhase) -> Self::Data
+    where
+        A: Address,
+    {
+        if access == AccessType::DataRead
+            && let Some(handler) = self.read_traps.get_mut(&addr.as_u16())
+        {
+            return handler(&mut self.inner);
+        }
+        self.inner.cpu_read_phased(addr, access, phase)
+    }
+
+    fn write<A, V>(&mut self, addr: A, value: V, access: Self::Access)
+    where
+        A: Address,
+        V: Into<Self::Data>,
+    {
+        if access == AccessType::DataWrite
+            && let Some(handler) = self.write_traps.get_mut(&addr.as_u16())
+        {
+            handler(&mut self.inner, value.into());
+            return;
+        }
+        self.inner.cpu_write(addr, value, access);
+    }
+
+    fn on_tick(&mut self, cycles: u8) {
+        self.inner.on_tick(cycles);
+    }
+
+    fn access_count(&self) -> Option<u64> {
+        self.inner.access_count()
+    }
+
+    fn take_watch_hit(&mut self) -> Option<Word> {
+        self.inner.take_watch_hit()
+    }
+
+    fn request_dma(&mut self, request: DmaRequest) -> DmaResult {
+        self.inner.request_dma(request)
+    }
+
+    fn poll_dma_cycle(&mut self) -> Option<u8> {
+        self.inner.poll_dma_cycle()
+    }
+}
+
+impl<B: Mos6502CompatibleBus> Mos6502CompatibleBus for TrapBus<B> {}
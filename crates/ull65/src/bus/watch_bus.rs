@@ -0,0 +1,140 @@
+//! Bus wrapper that flags writes to watched addresses.
+
+use alloc::collections::BTreeSet;
+use ull::{Address, Byte, Word};
+use ull::{Bus, CyclePhase, DmaRequest, DmaResult};
+use crate::AccessType;
+use crate::bus::Mos6502CompatibleBus;
+
+/// Wraps another bus and remembers when a write lands on a watched address.
+///
+/// Watchpoints are per byte: watching `$0200` doesn't catch a write to `$0201`, so watch each
+/// byte of a multi-byte value you care about. Every write is checked, whatever its
+/// [`AccessType`] (stack pushes and DMA included), and the write still reaches the inner bus.
+/// [`Cpu::run_until`](crate::Cpu::run_until) polls [`Bus::take_watch_hit`] after each instruction
+/// and stops with [`RunOutcome::HitWatchpoint`](crate::RunOutcome::HitWatchpoint), so the
+/// instruction that wrote has completed when the run returns.
+///
+/// # Examples
+///
+/// ```
+/// use ull::Word;
+/// use ull65::bus::watch_bus::WatchBus;
+/// use ull65::instruction::mos6502::Mos6502;
+/// use ull65::{Cpu, RunConfig, RunOutcome, SimpleBus};
+///
+/// let mut bus = WatchBus::new(SimpleBus::default());
+/// bus.watch(Word(0x0210));
+///
+/// // loop: INX; STX $0200,X; JMP loop
+/// let program = [0xE8, 0x9D, 0x00, 0x02, 0x4C, 0x00, 0x80];
+/// let mut cpu: Cpu<WatchBus<SimpleBus>> =
+///     Cpu::with_program::<Mos6502>(&mut bus, Word(0x8000), &program, Word(0x8000));
+///
+/// let summary = cpu.run_until(&mut bus, RunConfig::default());
+/// assert_eq!(summary.outcome, RunOutcome::HitWatchpoint(Word(0x0210)));
+/// assert_eq!(cpu.x.0, 0x10);
+/// ```
+#[derive(Debug)]
+pub struct WatchBus<B> {
+    inner: B,
+    watched: BTreeSet<u16>,
+    hit: Option<Word>,
+}
+
+impl<B> WatchBus<B> {
+    /// Wrap `inner` with no addresses watched.
+    #[must_use]
+    pub fn new(inner: B) -> Self {
+        Self {
+            inner,
+            watched: BTreeSet::new(),
+            hit: None,
+        }
+    }
+
+    /// Flag writes to `addr`. Returns `false` if it was already watched.
+    pub fn watch(&mut self, addr: Word) -> bool {
+        self.watched.insert(addr.0)
+    }
+
+    /// Stop watching `addr`. Returns `false` if it wasn't watched.
+    pub fn unwatch(&mut self, addr: Word) -> bool {
+        self.watched.remove(&addr.0)
+    }
+
+    #[must_use]
+    pub fn is_watched(&self, addr: Word) -> bool {
+        self.watched.contains(&addr.0)
+    }
+
+    #[must_use]
+    pub fn inner(&self) -> &B {
+        &self.inner
+    }
+
+    pub fn inner_mut(&mut self) -> &mut B {
+        &mut self.inner
+    }
+
+    #[must_use]
+    pub fn into_inner(self) -> B {
+        self.inner
+    }
+}
+
+impl<B> Bus for WatchBus<B>
+where
+    B: Mos6502CompatibleBus,
+{
+    type Access = AccessType;
+    type Data = Byte;
+
+    fn read<A>(&mut self, addr: A, access: Self::Access) -> Self::Data
+    where
+        A: Address,
+    {
+        self.inner.cpu_read(addr, access)
+    }
+
+    fn read_phased<A>(&mut self, addr: A, access: Self::Access, phase: CyclePhase) -> Self::Data
+    where
+        A: Address,
+    {
+        self.inner.cpu_read_phased(addr, access, phase)
+    }
+
+    fn write<A, V>(&mut self, addr: A, value: V, access: Self::Access)
+    where
+        A: Address,
+        V: Into<Self::Data>,
+    {
+        let addr = addr.as_u16();
+        if self.hit.is_none() && self.watched.contains(&addr) {
+            self.hit = Some(Word(addr));
+        }
+        self.inner.cpu_write(Word(addr), value, access);
+    }
+
+    fn on_tick(&mut self, cycles: u8) {
+        self.inner.on_tick(cycles);
+    }
+
+    fn access_count(&self) -> Option<u64> {
+        self.inner.access_count()
+    }
+
+    fn take_watch_hit(&mut self) -> Option<Word> {
+        self.hit.take().or_else(|| self.inner.take_watch_hit())
+    }
+
+    fn request_dma(&mut self, request: DmaRequest) -> DmaResult {
+        self.inner.request_dma(request)
+    }
+
+    fn poll_dma_cycle(&mut self) -> Option<u8> {
+        self.inner.poll_dma_cycle()
+    }
+}
+
+impl<B: Mos6502CompatibleBus> Mos6502CompatibleBus for WatchBus<B> {}
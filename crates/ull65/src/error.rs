@@ -0,0 +1,39 @@
+//! Crate-wide error type for the fallible parts of the API.
+//!
+//! Execution itself never fails (the 6502 has no faults), so this only covers host-side
+//! operations such as loading images, parsing text formats, or running with strict checks.
+
+use core::fmt;
+use ull::Word;
+
+/// Errors reported by fallible `ull65` operations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Error {
+    /// A block of `len` bytes starting at `start` doesn't fit below `limit`.
+    OutOfBounds { start: usize, len: usize, limit: usize },
+    /// Text input (hex dumps, object files, assembly) couldn't be parsed.
+    Parse { line: usize, reason: &'static str },
+    /// An opcode the active configuration refuses to execute was fetched at `pc`.
+    UnsupportedOpcode { opcode: u8, pc: Word },
+}
+
+/// Convenience alias for results carrying [`Error`].
+pub type Result<T> = core::result::Result<T, Error>;
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::OutOfBounds { start, len, limit } => write!(
+                f,
+                "{len} bytes at ${start:04X} exceed the ${limit:04X}-byte address space"
+            ),
+            Error::Parse { line, reason } => write!(f, "parse error on line {line}: {reason}"),
+            Error::UnsupportedOpcode { opcode, pc } => {
+                write!(f, "unsupported opcode ${opcode:02X} at ${pc:04X}")
+            }
+        }
+    }
+}
+
+impl core::error::Error for Error {}
@@ -3,28 +3,217 @@
 //! Implement [`InstructionSet`] to define CPU variants or patch existing tables
 
 use crate::bus::Mos6502CompatibleBus;
+use crate::processor::addressing_mode::AddressingModeKind;
+use crate::processor::trace::is_documented;
 use crate::Cpu;
-use core::ops::Index;
+use alloc::boxed::Box;
+use alloc::rc::Rc;
+use core::fmt;
+use core::ops::{Deref, Index};
 
+pub mod csg65ce02;
 pub mod mos6502;
 pub mod wdc65c02s;
+#[cfg(feature = "wdc65c816")]
+pub mod wdc65c816;
 
-/// A single instruction with cycle count and execution function.
-#[derive(Debug, Copy, Clone)]
+/// A single instruction with its metadata, cycle count and execution function.
 pub struct Instruction<B: Mos6502CompatibleBus> {
+    /// Assembler mnemonic, e.g. `"LDA"`.
+    pub mnemonic: &'static str,
+    /// How the operand bytes following the opcode are interpreted.
+    pub mode: AddressingModeKind,
     pub cycles: u8,
     pub execute: fn(&mut Cpu<B>, &mut B),
 }
 
+// Implemented by hand so copying an entry doesn't require `B: Copy`; the fields are always `Copy`.
+impl<B: Mos6502CompatibleBus> Clone for Instruction<B> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<B: Mos6502CompatibleBus> Copy for Instruction<B> {}
+
+impl<B: Mos6502CompatibleBus> fmt::Debug for Instruction<B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Instruction")
+            .field("mnemonic", &self.mnemonic)
+            .field("mode", &self.mode)
+            .field("cycles", &self.cycles)
+            .field("execute", &self.execute)
+            .finish()
+    }
+}
+
+impl<B: Mos6502CompatibleBus> Instruction<B> {
+    /// Whether this instruction calls a subroutine, returns from one, or neither.
+    #[must_use]
+    pub fn flow(&self) -> FlowKind {
+        match self.mnemonic {
+            "JSR" | "BSR" => FlowKind::Call,
+            "RTS" | "RTI" | "RTN" => FlowKind::Return,
+            _ => FlowKind::Other,
+        }
+    }
+}
+
+/// How an instruction moves between subroutines, for debugger stepping.
+///
+/// Calls cover every `JSR` form (absolute, and the 65CE02's `($1234)` and `($1234,X)`) and the
+/// 65CE02's `BSR`; returns cover `RTS`, `RTI` and the 65CE02's `RTN #`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FlowKind {
+    /// Pushes a return address and jumps to a subroutine.
+    Call,
+    /// Pulls a return address pushed by a call or interrupt.
+    Return,
+    /// Anything else, including plain jumps, branches and `BRK`.
+    Other,
+}
+
 /// 256-entry instruction table mapping opcodes to instructions.
+///
+/// Build a table once and put it in an [`Rc`] to share it between any number of CPUs via
+/// [`Cpu::with_shared_table`]:
+///
+/// ```
+/// use std::rc::Rc;
+/// use ull65::instruction::{mos6502::Mos6502, wdc65c02s::Wdc65c02s};
+/// use ull65::{Cpu, SimpleBus};
+///
+/// let mos6502 = Rc::new(Mos6502::base_table());
+/// let wdc65c02 = Rc::new(Wdc65c02s::base_table());
+///
+/// let nmos: Vec<Cpu<SimpleBus>> =
+///     (0..4).map(|_| Cpu::with_shared_table(Rc::clone(&mos6502))).collect();
+/// let cmos: Cpu<SimpleBus> = Cpu::with_shared_table(wdc65c02);
+/// ```
 pub struct InstructionTable<B: Mos6502CompatibleBus>([Instruction<B>; 256]);
 
+impl<B: Mos6502CompatibleBus> Clone for InstructionTable<B> {
+    fn clone(&self) -> Self {
+        Self(self.0)
+    }
+}
+
 impl<B: Mos6502CompatibleBus> InstructionTable<B> {
     #[must_use]
     pub const fn with(mut self, opcode: u8, instruction: Instruction<B>) -> Self {
         self.0[opcode as usize] = instruction;
         self
     }
+
+    /// Build a table from `(opcode, instruction)` pairs, filling every other slot with `default`.
+    ///
+    /// Later entries win when an opcode is listed twice.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ull65::addressing_mode::AddressingModeKind;
+    /// use ull65::instruction::{mos6502, Instruction, InstructionTable};
+    /// use ull65::SimpleBus;
+    ///
+    /// // A two-instruction ISA: NOP and LDA #imm, everything else halts.
+    /// let table: InstructionTable<SimpleBus> = InstructionTable::from_entries(
+    ///     InstructionTable::<SimpleBus>::all_jam()[0],
+    ///     &[
+    ///         (
+    ///             0xEA,
+    ///             Instruction {
+    ///                 mnemonic: "NOP",
+    ///                 mode: AddressingModeKind::Implied,
+    ///                 cycles: 2,
+    ///                 execute: mos6502::nop::<SimpleBus>,
+    ///             },
+    ///         ),
+    ///         (
+    ///             0xA9,
+    ///             Instruction {
+    ///                 mnemonic: "LDA",
+    ///                 mode: AddressingModeKind::Immediate,
+    ///                 cycles: 2,
+    ///                 execute: mos6502::lda::<ull65::addressing_mode::Immediate, SimpleBus>,
+    ///             },
+    ///         ),
+    ///     ],
+    /// );
+    /// assert_eq!(table[0xEA].cycles, 2);
+    /// assert_eq!(table[0x00].cycles, 0);
+    /// ```
+    #[must_use]
+    pub const fn from_entries(default: Instruction<B>, entries: &[(u8, Instruction<B>)]) -> Self {
+        let mut table = Self([default; 256]);
+        let mut i = 0;
+        while i < entries.len() {
+            let (opcode, instruction) = entries[i];
+            table.0[opcode as usize] = instruction;
+            i += 1;
+        }
+        table
+    }
+
+    /// Iterate over every slot as `(opcode, instruction)`, in opcode order.
+    pub fn iter(&self) -> impl Iterator<Item = (u8, &Instruction<B>)> {
+        (0..=u8::MAX).zip(self.0.iter())
+    }
+
+    /// Look up the opcode encoding `mnemonic` with addressing mode `mode`.
+    ///
+    /// The NMOS table has undocumented duplicates of a few encodings (the implied `NOP`s, `SBC
+    /// #imm` at `$EB`). A documented NMOS opcode is preferred when there is one, and otherwise
+    /// the lowest match wins; filter [`iter`](Self::iter) when you need a specific one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ull65::addressing_mode::AddressingModeKind;
+    /// use ull65::instruction::mos6502::Mos6502;
+    /// use ull65::SimpleBus;
+    ///
+    /// let table = Mos6502::base_table::<SimpleBus>();
+    /// assert_eq!(table.find("STA", AddressingModeKind::AbsoluteY), Some(0x99));
+    /// assert_eq!(table.find("STA", AddressingModeKind::Immediate), None);
+    /// ```
+    #[must_use]
+    pub fn find(&self, mnemonic: &str, mode: AddressingModeKind) -> Option<u8> {
+        let mut matches = self
+            .iter()
+            .filter(|(_, instruction)| instruction.mnemonic == mnemonic && instruction.mode == mode)
+            .map(|(opcode, _)| opcode);
+        let first = matches.next()?;
+        if is_documented(first) {
+            return Some(first);
+        }
+        Some(matches.find(|&opcode| is_documented(opcode)).unwrap_or(first))
+    }
+
+    /// A table where every opcode is `JAM` and halts the CPU.
+    ///
+    /// Useful as a blank slate for fully custom cores, combined with [`with`](Self::with).
+    #[must_use]
+    pub const fn all_jam() -> Self {
+        Self(
+            [Instruction {
+                mnemonic: "JAM",
+                mode: AddressingModeKind::Implied,
+                cycles: 0,
+                execute: mos6502::jam::<B>,
+            }; 256],
+        )
+    }
+}
+
+/// Collects `(opcode, instruction)` pairs into a table, leaving unlisted opcodes as `JAM`.
+impl<B: Mos6502CompatibleBus> FromIterator<(u8, Instruction<B>)> for InstructionTable<B> {
+    fn from_iter<I: IntoIterator<Item = (u8, Instruction<B>)>>(iter: I) -> Self {
+        iter.into_iter()
+            .fold(Self::all_jam(), |table, (opcode, instruction)| {
+                table.with(opcode, instruction)
+            })
+    }
 }
 
 impl<B: Mos6502CompatibleBus> Index<usize> for InstructionTable<B> {
@@ -35,6 +224,68 @@ impl<B: Mos6502CompatibleBus> Index<usize> for InstructionTable<B> {
     }
 }
 
+/// Where a [`Cpu`] keeps its dispatch table.
+///
+/// A full table is 256 entries of function pointer plus cycle count (4KB on 64-bit targets), so
+/// rather than storing it inline the CPU either owns a boxed copy or holds a reference-counted
+/// table shared by every CPU built from it. Either way the CPU itself stays small and cheap to
+/// move.
+/// Derefs to [`InstructionTable`], so `cpu.table[opcode]` works for both.
+pub enum TableStorage<B: Mos6502CompatibleBus> {
+    /// Table owned by this CPU (what [`Cpu::with_instruction_set`] produces).
+    Owned(Box<InstructionTable<B>>),
+    /// Table shared with other CPUs (see [`Cpu::with_shared_table`]).
+    Shared(Rc<InstructionTable<B>>),
+}
+
+impl<B: Mos6502CompatibleBus> Deref for TableStorage<B> {
+    type Target = InstructionTable<B>;
+
+    fn deref(&self) -> &Self::Target {
+        match self {
+            TableStorage::Owned(table) => table,
+            TableStorage::Shared(table) => table,
+        }
+    }
+}
+
+impl<B: Mos6502CompatibleBus> Clone for TableStorage<B> {
+    /// Owned tables are copied; shared tables stay shared.
+    fn clone(&self) -> Self {
+        match self {
+            TableStorage::Owned(table) => TableStorage::Owned(table.clone()),
+            TableStorage::Shared(table) => TableStorage::Shared(Rc::clone(table)),
+        }
+    }
+}
+
+impl<B: Mos6502CompatibleBus> From<InstructionTable<B>> for TableStorage<B> {
+    fn from(table: InstructionTable<B>) -> Self {
+        TableStorage::Owned(Box::new(table))
+    }
+}
+
+impl<B: Mos6502CompatibleBus> From<Rc<InstructionTable<B>>> for TableStorage<B> {
+    fn from(table: Rc<InstructionTable<B>>) -> Self {
+        TableStorage::Shared(table)
+    }
+}
+
+/// What an NMOS table built for an [`InstructionSet`] does with its undocumented NOP opcodes.
+///
+/// `JAM` opcodes always halt, as they do on hardware, and the 65C02's NOPs are documented
+/// instructions, so neither is affected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum IllegalPolicy {
+    /// Skip the opcode and its operand bytes like a NOP, as NMOS parts (and the 2A03) do.
+    #[default]
+    Skip,
+    /// Stop with [`RunState::Halted`](crate::RunState::Halted), leaving PC on the opcode.
+    Halt,
+    /// Call the handler installed with [`Cpu::set_illegal_trap`], or halt if there isn't one.
+    Trap,
+}
+
 /// Trait for defining CPU instruction sets.
 ///
 /// Implement this to create custom or variant instruction sets (e.g., 65C02, custom extensions).
@@ -49,10 +300,12 @@ impl<B: Mos6502CompatibleBus> Index<usize> for InstructionTable<B> {
 /// struct Custom6502;
 ///
 /// impl InstructionSet for Custom6502 {
-///     fn instruction_table<B: Mos6502CompatibleBus + 'static>() -> InstructionTable<B> {
-///         // Start with MOS 6502 table and customize
+///     fn instruction_table<B: Mos6502CompatibleBus>() -> InstructionTable<B> {
+///         // Start with the MOS 6502 table and customize. `base_table_for::<Self, B>`
+///         // builds it with this set's constants, such as `ON_ILLEGAL`; `base_table::<B>()`
+///         // would use `Mos6502`'s instead.
 ///         use ull65::instruction::mos6502::Mos6502;
-///         Mos6502::base_table::<B>()
+///         Mos6502::base_table_for::<Self, B>()
 ///         // .with(opcode, custom_instruction)...
 ///     }
 /// }
@@ -60,6 +313,209 @@ impl<B: Mos6502CompatibleBus> Index<usize> for InstructionTable<B> {
 // Necessary because traits don't support `const fn` yet and we can't inline the generated table.
 pub trait InstructionSet {
     /// Generate the 256-entry instruction table for this CPU variant.
-    fn instruction_table<B: Mos6502CompatibleBus + 'static>() -> InstructionTable<B>;
+    fn instruction_table<B: Mos6502CompatibleBus>() -> InstructionTable<B>;
+    /// Human-readable name of the part, e.g. `"MOS 6502"`, for logs and trace headers.
+    const NAME: &'static str = "6502 compatible";
     const SUPPORTS_DECIMAL_MODE: bool = true;
+    /// Whether N and Z reflect the BCD-corrected result of a decimal-mode `ADC`/`SBC`, as on the
+    /// 65C02. NMOS parts leave them describing the uncorrected binary arithmetic instead. Parts that
+    /// correct the flags also take one extra cycle for those instructions.
+    const DECIMAL_FLAGS_VALID: bool = false;
+    /// Constant ORed into A by the undocumented NMOS `XAA #imm` (`$8B`) and `LAX #imm` (`$AB`),
+    /// which compute `A = (A | XAA_MAGIC) & X & imm` and `A = X = (A | XAA_MAGIC) & imm`.
+    ///
+    /// The real value depends on the chip, its temperature and even the supply voltage, with
+    /// `$EE` and `$FF` the most commonly observed, so software that relies on XAA is unreliable on
+    /// hardware. `$EE` matches the widely used single-instruction test suites.
+    const XAA_MAGIC: u8 = 0xEE;
+    /// How the undocumented NOP opcodes behave; see [`IllegalPolicy`].
+    ///
+    /// Only tables built with
+    /// [`Mos6502::base_table_for::<Self, _>`](mos6502::Mos6502::base_table_for) consult it.
+    /// [`Mos6502::base_table`](mos6502::Mos6502::base_table) is built for `Mos6502` itself, so a
+    /// table derived from it always skips, whatever this constant says.
+    const ON_ILLEGAL: IllegalPolicy = IllegalPolicy::Skip;
+    /// Whether read-modify-write instructions (`INC`, `ASL`, `ROR`, ...) write the unmodified
+    /// value back before the result, as NMOS parts do. The 65C02 re-reads the address instead.
+    const RMW_DUMMY_WRITE: bool = true;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::processor::addressing_mode::Immediate;
+    use crate::{AccessType, RunState, SimpleBus};
+    use ull::{Bus, Byte, Word};
+
+    struct Tiny;
+
+    impl InstructionSet for Tiny {
+        fn instruction_table<B: Mos6502CompatibleBus>() -> InstructionTable<B> {
+            InstructionTable::from_entries(
+                InstructionTable::<B>::all_jam()[0x00],
+                &[
+                    (
+                        0xA9,
+                        Instruction {
+                            mnemonic: "LDA",
+                            mode: AddressingModeKind::Immediate,
+                            cycles: 2,
+                            execute: mos6502::lda::<Immediate, B>,
+                        },
+                    ),
+                    (
+                        0xEA,
+                        Instruction {
+                            mnemonic: "NOP",
+                            mode: AddressingModeKind::Implied,
+                            cycles: 2,
+                            execute: mos6502::nop::<B>,
+                        },
+                    ),
+                ],
+            )
+        }
+    }
+
+    #[test]
+    fn from_entries_builds_sparse_custom_isa() {
+        let mut bus = SimpleBus::default();
+        let program = [0xA9, 0x42, 0xEA, 0xE8];
+        let mut cpu: Cpu<SimpleBus> =
+            Cpu::with_program::<Tiny>(&mut bus, Word(0x8000), &program, Word(0x8000));
+
+        assert_eq!(cpu.step(&mut bus), 2);
+        assert_eq!(cpu.a, Byte(0x42));
+        assert_eq!(cpu.step(&mut bus), 2);
+        assert_eq!(cpu.run_state, RunState::Running);
+
+        // INX isn't part of the tiny ISA, so it falls through to the JAM default.
+        cpu.step(&mut bus);
+        assert_eq!(cpu.run_state, RunState::Halted);
+        assert_eq!(cpu.x, Byte(0x00));
+        assert_eq!(bus.read(Word(0x8003), AccessType::DataRead), Byte(0xE8));
+    }
+
+    #[test]
+    fn instruction_sets_report_their_names() {
+        assert_eq!(mos6502::Mos6502::NAME, "MOS 6502");
+        assert_eq!(wdc65c02s::Wdc65c02s::NAME, "WDC 65C02S");
+        assert_eq!(csg65ce02::Csg65ce02::NAME, "CSG 65CE02");
+        assert_eq!(Tiny::NAME, "6502 compatible");
+
+        let cpu: Cpu<SimpleBus> = Cpu::with_instruction_set::<mos6502::Mos6502>();
+        assert_eq!(cpu.instruction_set_name(), Some("MOS 6502"));
+        let table = Rc::new(mos6502::Mos6502::base_table());
+        assert_eq!(Cpu::<SimpleBus>::with_shared_table(table).instruction_set_name(), None);
+    }
+
+    #[test]
+    fn from_entries_later_duplicates_win() {
+        let jam = InstructionTable::<SimpleBus>::all_jam()[0x00];
+        let nop = Instruction {
+            mnemonic: "NOP",
+            mode: AddressingModeKind::Implied,
+            cycles: 3,
+            execute: mos6502::nop::<SimpleBus>,
+        };
+        let table = InstructionTable::from_entries(jam, &[(0x10, jam), (0x10, nop)]);
+
+        assert_eq!(table[0x10].cycles, 3);
+        assert_eq!(table[0x11].cycles, 0);
+    }
+
+    #[test]
+    fn with_overrides_metadata_alongside_handler() {
+        let table = mos6502::Mos6502::base_table::<SimpleBus>().with(
+            0x02,
+            Instruction {
+                mnemonic: "HCF",
+                mode: AddressingModeKind::Immediate,
+                cycles: 2,
+                execute: mos6502::nop::<SimpleBus>,
+            },
+        );
+
+        assert_eq!(table[0x02].mnemonic, "HCF");
+        assert_eq!(table[0x02].mode, AddressingModeKind::Immediate);
+        assert_eq!(table[0xEA].mnemonic, "NOP");
+    }
+
+    #[test]
+    fn flow_classifies_calls_and_returns_across_instruction_sets() {
+        let table = csg65ce02::Csg65ce02::base_table::<SimpleBus>();
+
+        // JSR $1234, JSR ($1234), JSR ($1234,X), BSR
+        for opcode in [0x20, 0x22, 0x23, 0x63] {
+            assert_eq!(table[opcode].flow(), FlowKind::Call, "{opcode:02X}");
+        }
+        // RTI, RTS, RTN #
+        for opcode in [0x40, 0x60, 0x62] {
+            assert_eq!(table[opcode].flow(), FlowKind::Return, "{opcode:02X}");
+        }
+        // JMP $1234, BRA, BRK
+        for opcode in [0x4C, 0x80, 0x00] {
+            assert_eq!(table[opcode].flow(), FlowKind::Other, "{opcode:02X}");
+        }
+    }
+
+    #[test]
+    fn find_maps_mnemonic_and_mode_back_to_the_opcode() {
+        let table = mos6502::Mos6502::base_table::<SimpleBus>();
+
+        assert_eq!(table.find("LDA", AddressingModeKind::Immediate), Some(0xA9));
+        assert_eq!(table.find("JMP", AddressingModeKind::AbsoluteIndirect), Some(0x6C));
+        assert_eq!(table.find("SBC", AddressingModeKind::Immediate), Some(0xE9));
+        // $1A is the lowest implied NOP, but undocumented.
+        assert_eq!(table.find("NOP", AddressingModeKind::Implied), Some(0xEA));
+        assert_eq!(table.find("LDA", AddressingModeKind::Implied), None);
+        assert_eq!(table.find("lda", AddressingModeKind::Immediate), None);
+
+        assert_eq!(table.iter().count(), 256);
+        assert!(table
+            .iter()
+            .all(|(opcode, instruction)| core::ptr::eq(instruction, &table[usize::from(opcode)])));
+    }
+
+    #[test]
+    fn cloning_shared_storage_keeps_sharing() {
+        let table = Rc::new(mos6502::Mos6502::base_table::<SimpleBus>());
+
+        let shared = TableStorage::from(Rc::clone(&table));
+        let copy = shared.clone();
+        assert!(core::ptr::eq(&*copy, &*table));
+
+        let owned = TableStorage::from((*table).clone());
+        let owned_copy = owned.clone();
+        assert!(!core::ptr::eq(&*owned, &*owned_copy));
+        assert_eq!(owned_copy[0xEA].cycles, table[0xEA].cycles);
+    }
+
+    #[test]
+    fn shared_table_is_shared_by_many_cpus() {
+        let table = Rc::new(mos6502::Mos6502::instruction_table::<SimpleBus>());
+        let cpus: alloc::vec::Vec<Cpu<SimpleBus>> =
+            (0..1000).map(|_| Cpu::with_shared_table(Rc::clone(&table))).collect();
+
+        assert!(cpus.iter().all(|cpu| core::ptr::eq(&*cpu.table, &*table)));
+        assert_eq!(Rc::strong_count(&table), 1001);
+    }
+
+    #[test]
+    fn collect_fills_unlisted_opcodes_with_jam() {
+        let table: InstructionTable<SimpleBus> = [(
+            0xEA,
+            Instruction {
+                mnemonic: "NOP",
+                mode: AddressingModeKind::Implied,
+                cycles: 2,
+                execute: mos6502::nop::<SimpleBus>,
+            },
+        )]
+        .into_iter()
+        .collect();
+
+        assert_eq!(table[0xEA].cycles, 2);
+        assert_eq!(table[0x00].cycles, 0);
+    }
 }
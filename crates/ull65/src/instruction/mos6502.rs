@@ -1,1524 +1,2088 @@
 //! The original MOS 6502 instruction set implementation.
 
 use crate::bus::Mos6502CompatibleBus;
-use crate::instruction::{Instruction, InstructionSet, InstructionTable};
+use crate::instruction::{IllegalPolicy, Instruction, InstructionSet, InstructionTable};
 use crate::processor::addressing_mode::{
-    Absolute, AbsoluteIndirect, AbsoluteX, AbsoluteY, AddressingMode, Immediate, ZeroPage,
-    ZeroPageIndirectY, ZeroPageX, ZeroPageXIndirect, ZeroPageY,
+    AddressingModeKind,
+    Absolute, AbsoluteIndirect, AbsoluteX, AbsoluteY, Accumulator, AddressingMode, Immediate,
+    Implied, Relative, ZeroPage, ZeroPageIndirectY, ZeroPageX, ZeroPageXIndirect, ZeroPageY,
 };
 use crate::processor::flags::Flags;
 use crate::AccessType;
-use crate::{Cpu, RunState, IRQ_VECTOR_HI, IRQ_VECTOR_LO};
+use crate::{Cpu, RunState, IRQ_VECTOR_HI, IRQ_VECTOR_LO, NMI_VECTOR_HI, NMI_VECTOR_LO};
 use ull::{byte, word};
-use ull::{Address, Byte, Nibble};
+use ull::{Address, Byte, Word};
 
 pub struct Mos6502;
 
 impl Mos6502 {
+    /// Builds the canonical NMOS 6502 instruction table.
     #[must_use]
-    pub const fn base_table<B: Mos6502CompatibleBus + 'static>() -> InstructionTable<B> {
+    pub const fn base_table<B: Mos6502CompatibleBus>() -> InstructionTable<B> {
+        Self::base_table_for::<Self, B>()
+    }
+
+    /// Builds the NMOS table with `ADC`, `SBC`, `RRA` and `ISC` taking their decimal-mode
+    /// behavior from `S`.
+    ///
+    /// Use this for derivatives that only change [`InstructionSet`] constants, such as the
+    /// Ricoh 2A03 without BCD; [`base_table`](Self::base_table) always behaves like a stock
+    /// NMOS part.
+    #[must_use]
+    pub const fn base_table_for<S: InstructionSet, B: Mos6502CompatibleBus>(
+    ) -> InstructionTable<B> {
         InstructionTable([
             // 0x00
             Instruction {
+                mnemonic: "BRK",
+                mode: AddressingModeKind::Implied,
                 cycles: 7,
                 execute: brk::<B>,
             },
             // 0x01
             Instruction {
+                mnemonic: "ORA",
+                mode: AddressingModeKind::ZeroPageXIndirect,
                 cycles: 6,
                 execute: ora::<ZeroPageXIndirect, B>,
             },
             // 0x02
             Instruction {
+                mnemonic: "JAM",
+                mode: AddressingModeKind::Implied,
                 cycles: 0,
                 execute: jam::<B>,
             },
             // 0x03
             Instruction {
+                mnemonic: "SLO",
+                mode: AddressingModeKind::ZeroPageXIndirect,
                 cycles: 8,
-                execute: slo::<ZeroPageXIndirect, B>,
+                execute: slo::<S, ZeroPageXIndirect, B>,
             },
             // 0x04
             Instruction {
+                mnemonic: "NOP",
+                mode: AddressingModeKind::ZeroPage,
                 cycles: 3,
-                execute: illegal::<ZeroPage, B>,
+                execute: illegal_nop::<S, ZeroPage, B>,
             },
             // 0x05
             Instruction {
+                mnemonic: "ORA",
+                mode: AddressingModeKind::ZeroPage,
                 cycles: 3,
                 execute: ora::<ZeroPage, B>,
             },
             // 0x06
             Instruction {
+                mnemonic: "ASL",
+                mode: AddressingModeKind::ZeroPage,
                 cycles: 5,
-                execute: asl::<ZeroPage, B>,
+                execute: asl::<S, ZeroPage, B>,
             },
             // 0x07
             Instruction {
+                mnemonic: "SLO",
+                mode: AddressingModeKind::ZeroPage,
                 cycles: 5,
-                execute: slo::<ZeroPage, B>,
+                execute: slo::<S, ZeroPage, B>,
             },
             // 0x08
             Instruction {
+                mnemonic: "PHP",
+                mode: AddressingModeKind::Implied,
                 cycles: 3,
                 execute: php::<B>,
             },
             // 0x09
             Instruction {
+                mnemonic: "ORA",
+                mode: AddressingModeKind::Immediate,
                 cycles: 2,
                 execute: ora::<Immediate, B>,
             },
             // 0x0A
             Instruction {
+                mnemonic: "ASL",
+                mode: AddressingModeKind::Accumulator,
                 cycles: 2,
                 execute: asl_a::<B>,
             },
             // 0x0B
             Instruction {
+                mnemonic: "ANC",
+                mode: AddressingModeKind::Immediate,
                 cycles: 2,
                 execute: anc::<Immediate, B>,
             },
             // 0x0C
             Instruction {
+                mnemonic: "NOP",
+                mode: AddressingModeKind::Absolute,
                 cycles: 4,
-                execute: illegal::<Absolute, B>,
+                execute: illegal_nop::<S, Absolute, B>,
             },
             // 0x0D
             Instruction {
+                mnemonic: "ORA",
+                mode: AddressingModeKind::Absolute,
                 cycles: 4,
                 execute: ora::<Absolute, B>,
             },
             // 0x0E
             Instruction {
+                mnemonic: "ASL",
+                mode: AddressingModeKind::Absolute,
                 cycles: 6,
-                execute: asl::<Absolute, B>,
+                execute: asl::<S, Absolute, B>,
             },
             // 0x0F
             Instruction {
+                mnemonic: "SLO",
+                mode: AddressingModeKind::Absolute,
                 cycles: 6,
-                execute: slo::<Absolute, B>,
+                execute: slo::<S, Absolute, B>,
             },
             // 0x10
             Instruction {
+                mnemonic: "BPL",
+                mode: AddressingModeKind::Relative,
                 cycles: 2,
                 execute: bpl::<B>,
             },
             // 0x11
             Instruction {
+                mnemonic: "ORA",
+                mode: AddressingModeKind::ZeroPageIndirectY,
                 cycles: 5,
                 execute: ora::<ZeroPageIndirectY, B>,
             },
             // 0x12
             Instruction {
+                mnemonic: "JAM",
+                mode: AddressingModeKind::Implied,
                 cycles: 0,
                 execute: jam::<B>,
             },
             // 0x13
             Instruction {
+                mnemonic: "SLO",
+                mode: AddressingModeKind::ZeroPageIndirectY,
                 cycles: 8,
-                execute: slo::<ZeroPageIndirectY, B>,
+                execute: slo::<S, ZeroPageIndirectY, B>,
             },
             // 0x14
             Instruction {
+                mnemonic: "NOP",
+                mode: AddressingModeKind::ZeroPageX,
                 cycles: 4,
-                execute: illegal::<ZeroPageX, B>,
+                execute: illegal_nop::<S, ZeroPageX, B>,
             },
             // 0x15
             Instruction {
+                mnemonic: "ORA",
+                mode: AddressingModeKind::ZeroPageX,
                 cycles: 4,
                 execute: ora::<ZeroPageX, B>,
             },
             // 0x16
             Instruction {
+                mnemonic: "ASL",
+                mode: AddressingModeKind::ZeroPageX,
                 cycles: 6,
-                execute: asl::<ZeroPageX, B>,
+                execute: asl::<S, ZeroPageX, B>,
             },
             // 0x17
             Instruction {
+                mnemonic: "SLO",
+                mode: AddressingModeKind::ZeroPageX,
                 cycles: 6,
-                execute: slo::<ZeroPageX, B>,
+                execute: slo::<S, ZeroPageX, B>,
             },
             // 0x18
             Instruction {
+                mnemonic: "CLC",
+                mode: AddressingModeKind::Implied,
                 cycles: 2,
                 execute: clc::<B>,
             },
             // 0x19
             Instruction {
+                mnemonic: "ORA",
+                mode: AddressingModeKind::AbsoluteY,
                 cycles: 4,
                 execute: ora::<AbsoluteY, B>,
             },
             // 0x1A
             Instruction {
+                mnemonic: "NOP",
+                mode: AddressingModeKind::Implied,
                 cycles: 2,
-                execute: illegal_a::<B>,
+                execute: illegal_nop::<S, Implied, B>,
             },
             // 0x1B
             Instruction {
+                mnemonic: "SLO",
+                mode: AddressingModeKind::AbsoluteY,
                 cycles: 7,
-                execute: slo::<AbsoluteY, B>,
+                execute: slo::<S, AbsoluteY, B>,
             },
             // 0x1C
             Instruction {
+                mnemonic: "NOP",
+                mode: AddressingModeKind::AbsoluteX,
                 cycles: 4,
-                execute: illegal::<AbsoluteX, B>,
+                execute: illegal_nop::<S, AbsoluteX, B>,
             },
             // 0x1D
             Instruction {
+                mnemonic: "ORA",
+                mode: AddressingModeKind::AbsoluteX,
                 cycles: 4,
                 execute: ora::<AbsoluteX, B>,
             },
             // 0x1E
             Instruction {
+                mnemonic: "ASL",
+                mode: AddressingModeKind::AbsoluteX,
                 cycles: 7,
-                execute: asl::<AbsoluteX, B>,
+                execute: asl::<S, AbsoluteX, B>,
             },
             // 0x1F
             Instruction {
+                mnemonic: "SLO",
+                mode: AddressingModeKind::AbsoluteX,
                 cycles: 7,
-                execute: slo::<AbsoluteX, B>,
+                execute: slo::<S, AbsoluteX, B>,
             },
             // 0x20
             Instruction {
+                mnemonic: "JSR",
+                mode: AddressingModeKind::Absolute,
                 cycles: 6,
                 execute: jsr::<Absolute, B>,
             },
             // 0x21
             Instruction {
+                mnemonic: "AND",
+                mode: AddressingModeKind::ZeroPageXIndirect,
                 cycles: 6,
                 execute: and::<ZeroPageXIndirect, B>,
             },
             // 0x22
             Instruction {
+                mnemonic: "JAM",
+                mode: AddressingModeKind::Implied,
                 cycles: 0,
                 execute: jam::<B>,
             },
             // 0x23
             Instruction {
+                mnemonic: "RLA",
+                mode: AddressingModeKind::ZeroPageXIndirect,
                 cycles: 8,
-                execute: rla::<ZeroPageXIndirect, B>,
+                execute: rla::<S, ZeroPageXIndirect, B>,
             },
             // 0x24
             Instruction {
+                mnemonic: "BIT",
+                mode: AddressingModeKind::ZeroPage,
                 cycles: 3,
                 execute: bit::<ZeroPage, B>,
             },
             // 0x25
             Instruction {
+                mnemonic: "AND",
+                mode: AddressingModeKind::ZeroPage,
                 cycles: 3,
                 execute: and::<ZeroPage, B>,
             },
             // 0x26
             Instruction {
+                mnemonic: "ROL",
+                mode: AddressingModeKind::ZeroPage,
                 cycles: 5,
-                execute: rol::<ZeroPage, B>,
+                execute: rol::<S, ZeroPage, B>,
             },
             // 0x27
             Instruction {
+                mnemonic: "RLA",
+                mode: AddressingModeKind::ZeroPage,
                 cycles: 5,
-                execute: rla::<ZeroPage, B>,
+                execute: rla::<S, ZeroPage, B>,
             },
             // 0x28
             Instruction {
+                mnemonic: "PLP",
+                mode: AddressingModeKind::Implied,
                 cycles: 4,
                 execute: plp::<B>,
             },
             // 0x29
             Instruction {
+                mnemonic: "AND",
+                mode: AddressingModeKind::Immediate,
                 cycles: 2,
                 execute: and::<Immediate, B>,
             },
             // 0x2A
             Instruction {
+                mnemonic: "ROL",
+                mode: AddressingModeKind::Accumulator,
                 cycles: 2,
                 execute: rol_a::<B>,
             },
             // 0x2B
             Instruction {
+                mnemonic: "ANC",
+                mode: AddressingModeKind::Immediate,
                 cycles: 2,
                 execute: anc::<Immediate, B>,
             },
             // 0x2C
             Instruction {
+                mnemonic: "BIT",
+                mode: AddressingModeKind::Absolute,
                 cycles: 4,
                 execute: bit::<Absolute, B>,
             },
             // 0x2D
             Instruction {
+                mnemonic: "AND",
+                mode: AddressingModeKind::Absolute,
                 cycles: 4,
                 execute: and::<Absolute, B>,
             },
             // 0x2E
             Instruction {
+                mnemonic: "ROL",
+                mode: AddressingModeKind::Absolute,
                 cycles: 6,
-                execute: rol::<Absolute, B>,
+                execute: rol::<S, Absolute, B>,
             },
             // 0x2F
             Instruction {
+                mnemonic: "RLA",
+                mode: AddressingModeKind::Absolute,
                 cycles: 6,
-                execute: rla::<Absolute, B>,
+                execute: rla::<S, Absolute, B>,
             },
             // 0x30
             Instruction {
+                mnemonic: "BMI",
+                mode: AddressingModeKind::Relative,
                 cycles: 2,
                 execute: bmi::<B>,
             },
             // 0x31
             Instruction {
+                mnemonic: "AND",
+                mode: AddressingModeKind::ZeroPageIndirectY,
                 cycles: 5,
                 execute: and::<ZeroPageIndirectY, B>,
             },
             // 0x32
             Instruction {
+                mnemonic: "JAM",
+                mode: AddressingModeKind::Implied,
                 cycles: 0,
                 execute: jam::<B>,
             },
             // 0x33
             Instruction {
+                mnemonic: "RLA",
+                mode: AddressingModeKind::ZeroPageIndirectY,
                 cycles: 8,
-                execute: rla::<ZeroPageIndirectY, B>,
+                execute: rla::<S, ZeroPageIndirectY, B>,
             },
             // 0x34
             Instruction {
+                mnemonic: "NOP",
+                mode: AddressingModeKind::ZeroPageX,
                 cycles: 4,
-                execute: illegal::<ZeroPageX, B>,
+                execute: illegal_nop::<S, ZeroPageX, B>,
             },
             // 0x35
             Instruction {
+                mnemonic: "AND",
+                mode: AddressingModeKind::ZeroPageX,
                 cycles: 4,
                 execute: and::<ZeroPageX, B>,
             },
             // 0x36
             Instruction {
+                mnemonic: "ROL",
+                mode: AddressingModeKind::ZeroPageX,
                 cycles: 6,
-                execute: rol::<ZeroPageX, B>,
+                execute: rol::<S, ZeroPageX, B>,
             },
             // 0x37
             Instruction {
+                mnemonic: "RLA",
+                mode: AddressingModeKind::ZeroPageX,
                 cycles: 6,
-                execute: rla::<ZeroPageX, B>,
+                execute: rla::<S, ZeroPageX, B>,
             },
             // 0x38
             Instruction {
+                mnemonic: "SEC",
+                mode: AddressingModeKind::Implied,
                 cycles: 2,
                 execute: sec::<B>,
             },
             // 0x39
             Instruction {
+                mnemonic: "AND",
+                mode: AddressingModeKind::AbsoluteY,
                 cycles: 4,
                 execute: and::<AbsoluteY, B>,
             },
             // 0x3A
             Instruction {
+                mnemonic: "NOP",
+                mode: AddressingModeKind::Implied,
                 cycles: 2,
-                execute: illegal_a::<B>,
+                execute: illegal_nop::<S, Implied, B>,
             },
             // 0x3B
             Instruction {
+                mnemonic: "RLA",
+                mode: AddressingModeKind::AbsoluteY,
                 cycles: 7,
-                execute: rla::<AbsoluteY, B>,
+                execute: rla::<S, AbsoluteY, B>,
             },
             // 0x3C
             Instruction {
+                mnemonic: "NOP",
+                mode: AddressingModeKind::AbsoluteX,
                 cycles: 4,
-                execute: illegal::<AbsoluteX, B>,
+                execute: illegal_nop::<S, AbsoluteX, B>,
             },
             // 0x3D
             Instruction {
+                mnemonic: "AND",
+                mode: AddressingModeKind::AbsoluteX,
                 cycles: 4,
                 execute: and::<AbsoluteX, B>,
             },
             // 0x3E
             Instruction {
+                mnemonic: "ROL",
+                mode: AddressingModeKind::AbsoluteX,
                 cycles: 7,
-                execute: rol::<AbsoluteX, B>,
+                execute: rol::<S, AbsoluteX, B>,
             },
             // 0x3F
             Instruction {
+                mnemonic: "RLA",
+                mode: AddressingModeKind::AbsoluteX,
                 cycles: 7,
-                execute: rla::<AbsoluteX, B>,
+                execute: rla::<S, AbsoluteX, B>,
             },
             // 0x40
             Instruction {
+                mnemonic: "RTI",
+                mode: AddressingModeKind::Implied,
                 cycles: 6,
                 execute: rti::<B>,
             },
             // 0x41
             Instruction {
+                mnemonic: "EOR",
+                mode: AddressingModeKind::ZeroPageXIndirect,
                 cycles: 6,
                 execute: eor::<ZeroPageXIndirect, B>,
             },
             // 0x42
             Instruction {
+                mnemonic: "JAM",
+                mode: AddressingModeKind::Implied,
                 cycles: 0,
                 execute: jam::<B>,
             },
             // 0x43
             Instruction {
+                mnemonic: "SRE",
+                mode: AddressingModeKind::ZeroPageXIndirect,
                 cycles: 8,
-                execute: sre::<ZeroPageXIndirect, B>,
+                execute: sre::<S, ZeroPageXIndirect, B>,
             },
             // 0x44
             Instruction {
+                mnemonic: "NOP",
+                mode: AddressingModeKind::ZeroPage,
                 cycles: 3,
-                execute: illegal::<ZeroPage, B>,
+                execute: illegal_nop::<S, ZeroPage, B>,
             },
             // 0x45
             Instruction {
+                mnemonic: "EOR",
+                mode: AddressingModeKind::ZeroPage,
                 cycles: 3,
                 execute: eor::<ZeroPage, B>,
             },
             // 0x46
             Instruction {
+                mnemonic: "LSR",
+                mode: AddressingModeKind::ZeroPage,
                 cycles: 5,
-                execute: lsr::<ZeroPage, B>,
+                execute: lsr::<S, ZeroPage, B>,
             },
             // 0x47
             Instruction {
+                mnemonic: "SRE",
+                mode: AddressingModeKind::ZeroPage,
                 cycles: 5,
-                execute: sre::<ZeroPage, B>,
+                execute: sre::<S, ZeroPage, B>,
             },
             // 0x48
             Instruction {
+                mnemonic: "PHA",
+                mode: AddressingModeKind::Implied,
                 cycles: 3,
                 execute: pha::<B>,
             },
             // 0x49
             Instruction {
+                mnemonic: "EOR",
+                mode: AddressingModeKind::Immediate,
                 cycles: 2,
                 execute: eor::<Immediate, B>,
             },
             // 0x4A
             Instruction {
+                mnemonic: "LSR",
+                mode: AddressingModeKind::Accumulator,
                 cycles: 2,
                 execute: lsr_a::<B>,
             },
             // 0x4B
             Instruction {
+                mnemonic: "ASR",
+                mode: AddressingModeKind::Immediate,
                 cycles: 2,
                 execute: asr::<Immediate, B>,
             },
             // 0x4C
             Instruction {
+                mnemonic: "JMP",
+                mode: AddressingModeKind::Absolute,
                 cycles: 3,
                 execute: jmp::<Absolute, B>,
             },
             // 0x4D
             Instruction {
+                mnemonic: "EOR",
+                mode: AddressingModeKind::Absolute,
                 cycles: 4,
                 execute: eor::<Absolute, B>,
             },
             // 0x4E
             Instruction {
+                mnemonic: "LSR",
+                mode: AddressingModeKind::Absolute,
                 cycles: 6,
-                execute: lsr::<Absolute, B>,
+                execute: lsr::<S, Absolute, B>,
             },
             // 0x4F
             Instruction {
+                mnemonic: "SRE",
+                mode: AddressingModeKind::Absolute,
                 cycles: 6,
-                execute: sre::<Absolute, B>,
+                execute: sre::<S, Absolute, B>,
             },
             // 0x50
             Instruction {
+                mnemonic: "BVC",
+                mode: AddressingModeKind::Relative,
                 cycles: 2,
                 execute: bvc::<B>,
             },
             // 0x51
             Instruction {
+                mnemonic: "EOR",
+                mode: AddressingModeKind::ZeroPageIndirectY,
                 cycles: 5,
                 execute: eor::<ZeroPageIndirectY, B>,
             },
             // 0x52
             Instruction {
+                mnemonic: "JAM",
+                mode: AddressingModeKind::Implied,
                 cycles: 0,
                 execute: jam::<B>,
             },
             // 0x53
             Instruction {
+                mnemonic: "SRE",
+                mode: AddressingModeKind::ZeroPageIndirectY,
                 cycles: 8,
-                execute: sre::<ZeroPageIndirectY, B>,
+                execute: sre::<S, ZeroPageIndirectY, B>,
             },
             // 0x54
             Instruction {
+                mnemonic: "NOP",
+                mode: AddressingModeKind::ZeroPageX,
                 cycles: 4,
-                execute: illegal::<ZeroPageX, B>,
+                execute: illegal_nop::<S, ZeroPageX, B>,
             },
             // 0x55
             Instruction {
+                mnemonic: "EOR",
+                mode: AddressingModeKind::ZeroPageX,
                 cycles: 4,
                 execute: eor::<ZeroPageX, B>,
             },
             // 0x56
             Instruction {
+                mnemonic: "LSR",
+                mode: AddressingModeKind::ZeroPageX,
                 cycles: 6,
-                execute: lsr::<ZeroPageX, B>,
+                execute: lsr::<S, ZeroPageX, B>,
             },
             // 0x57
             Instruction {
+                mnemonic: "SRE",
+                mode: AddressingModeKind::ZeroPageX,
                 cycles: 6,
-                execute: sre::<ZeroPageX, B>,
+                execute: sre::<S, ZeroPageX, B>,
             },
             // 0x58
             Instruction {
+                mnemonic: "CLI",
+                mode: AddressingModeKind::Implied,
                 cycles: 2,
                 execute: cli::<B>,
             },
             // 0x59
             Instruction {
+                mnemonic: "EOR",
+                mode: AddressingModeKind::AbsoluteY,
                 cycles: 4,
                 execute: eor::<AbsoluteY, B>,
             },
             // 0x5A
             Instruction {
+                mnemonic: "NOP",
+                mode: AddressingModeKind::Implied,
                 cycles: 2,
-                execute: illegal_a::<B>,
+                execute: illegal_nop::<S, Implied, B>,
             },
             // 0x5B
             Instruction {
+                mnemonic: "SRE",
+                mode: AddressingModeKind::AbsoluteY,
                 cycles: 7,
-                execute: sre::<AbsoluteY, B>,
+                execute: sre::<S, AbsoluteY, B>,
             },
             // 0x5C
             Instruction {
+                mnemonic: "NOP",
+                mode: AddressingModeKind::AbsoluteX,
                 cycles: 4,
-                execute: illegal::<AbsoluteX, B>,
+                execute: illegal_nop::<S, AbsoluteX, B>,
             },
             // 0x5D
             Instruction {
+                mnemonic: "EOR",
+                mode: AddressingModeKind::AbsoluteX,
                 cycles: 4,
                 execute: eor::<AbsoluteX, B>,
             },
             // 0x5E
             Instruction {
+                mnemonic: "LSR",
+                mode: AddressingModeKind::AbsoluteX,
                 cycles: 7,
-                execute: lsr::<AbsoluteX, B>,
+                execute: lsr::<S, AbsoluteX, B>,
             },
             // 0x5F
             Instruction {
+                mnemonic: "SRE",
+                mode: AddressingModeKind::AbsoluteX,
                 cycles: 7,
-                execute: sre::<AbsoluteX, B>,
+                execute: sre::<S, AbsoluteX, B>,
             },
             // 0x60
             Instruction {
+                mnemonic: "RTS",
+                mode: AddressingModeKind::Implied,
                 cycles: 6,
                 execute: rts::<B>,
             },
             // 0x61
             Instruction {
+                mnemonic: "ADC",
+                mode: AddressingModeKind::ZeroPageXIndirect,
                 cycles: 6,
-                execute: adc::<Mos6502, ZeroPageXIndirect, B>,
+                execute: adc::<S, ZeroPageXIndirect, B>,
             },
             // 0x62
             Instruction {
+                mnemonic: "JAM",
+                mode: AddressingModeKind::Implied,
                 cycles: 0,
                 execute: jam::<B>,
             },
             // 0x63
             Instruction {
+                mnemonic: "RRA",
+                mode: AddressingModeKind::ZeroPageXIndirect,
                 cycles: 8,
-                execute: rra::<Mos6502, ZeroPageXIndirect, B>,
+                execute: rra::<S, ZeroPageXIndirect, B>,
             },
             // 0x64
             Instruction {
+                mnemonic: "NOP",
+                mode: AddressingModeKind::ZeroPage,
                 cycles: 3,
-                execute: illegal::<ZeroPage, B>,
+                execute: illegal_nop::<S, ZeroPage, B>,
             },
             // 0x65
             Instruction {
+                mnemonic: "ADC",
+                mode: AddressingModeKind::ZeroPage,
                 cycles: 3,
-                execute: adc::<Mos6502, ZeroPage, B>,
+                execute: adc::<S, ZeroPage, B>,
             },
             // 0x66
             Instruction {
+                mnemonic: "ROR",
+                mode: AddressingModeKind::ZeroPage,
                 cycles: 5,
-                execute: ror::<ZeroPage, B>,
+                execute: ror::<S, ZeroPage, B>,
             },
             // 0x67
             Instruction {
+                mnemonic: "RRA",
+                mode: AddressingModeKind::ZeroPage,
                 cycles: 5,
-                execute: rra::<Mos6502, ZeroPage, B>,
+                execute: rra::<S, ZeroPage, B>,
             },
             // 0x68
             Instruction {
+                mnemonic: "PLA",
+                mode: AddressingModeKind::Implied,
                 cycles: 4,
                 execute: pla::<B>,
             },
             // 0x69
             Instruction {
+                mnemonic: "ADC",
+                mode: AddressingModeKind::Immediate,
                 cycles: 2,
-                execute: adc::<Mos6502, Immediate, B>,
+                execute: adc::<S, Immediate, B>,
             },
             // 0x6A
             Instruction {
+                mnemonic: "ROR",
+                mode: AddressingModeKind::Accumulator,
                 cycles: 2,
                 execute: ror_a::<B>,
             },
             // 0x6B
             Instruction {
+                mnemonic: "ARR",
+                mode: AddressingModeKind::Immediate,
                 cycles: 2,
                 execute: arr::<Immediate, B>,
             },
             // 0x6C
             Instruction {
+                mnemonic: "JMP",
+                mode: AddressingModeKind::AbsoluteIndirect,
                 cycles: 5,
                 execute: jmp::<AbsoluteIndirect, B>,
             },
             // 0x6D
             Instruction {
+                mnemonic: "ADC",
+                mode: AddressingModeKind::Absolute,
                 cycles: 4,
-                execute: adc::<Mos6502, Absolute, B>,
+                execute: adc::<S, Absolute, B>,
             },
             // 0x6E
             Instruction {
+                mnemonic: "ROR",
+                mode: AddressingModeKind::Absolute,
                 cycles: 6,
-                execute: ror::<Absolute, B>,
+                execute: ror::<S, Absolute, B>,
             },
             // 0x6F
             Instruction {
+                mnemonic: "RRA",
+                mode: AddressingModeKind::Absolute,
                 cycles: 6,
-                execute: rra::<Mos6502, Absolute, B>,
+                execute: rra::<S, Absolute, B>,
             },
             // 0x70
             Instruction {
+                mnemonic: "BVS",
+                mode: AddressingModeKind::Relative,
                 cycles: 2,
                 execute: bvs::<B>,
             },
             // 0x71
             Instruction {
+                mnemonic: "ADC",
+                mode: AddressingModeKind::ZeroPageIndirectY,
                 cycles: 5,
-                execute: adc::<Mos6502, ZeroPageIndirectY, B>,
+                execute: adc::<S, ZeroPageIndirectY, B>,
             },
             // 0x72
             Instruction {
+                mnemonic: "JAM",
+                mode: AddressingModeKind::Implied,
                 cycles: 0,
                 execute: jam::<B>,
             },
             // 0x73
             Instruction {
+                mnemonic: "RRA",
+                mode: AddressingModeKind::ZeroPageIndirectY,
                 cycles: 8,
-                execute: rra::<Mos6502, ZeroPageIndirectY, B>,
+                execute: rra::<S, ZeroPageIndirectY, B>,
             },
             // 0x74
             Instruction {
+                mnemonic: "NOP",
+                mode: AddressingModeKind::ZeroPageX,
                 cycles: 4,
-                execute: illegal::<ZeroPageX, B>,
+                execute: illegal_nop::<S, ZeroPageX, B>,
             },
             // 0x75
             Instruction {
+                mnemonic: "ADC",
+                mode: AddressingModeKind::ZeroPageX,
                 cycles: 4,
-                execute: adc::<Mos6502, ZeroPageX, B>,
+                execute: adc::<S, ZeroPageX, B>,
             },
             // 0x76
             Instruction {
+                mnemonic: "ROR",
+                mode: AddressingModeKind::ZeroPageX,
                 cycles: 6,
-                execute: ror::<ZeroPageX, B>,
+                execute: ror::<S, ZeroPageX, B>,
             },
             // 0x77
             Instruction {
+                mnemonic: "RRA",
+                mode: AddressingModeKind::ZeroPageX,
                 cycles: 6,
-                execute: rra::<Mos6502, ZeroPageX, B>,
+                execute: rra::<S, ZeroPageX, B>,
             },
             // 0x78
             Instruction {
+                mnemonic: "SEI",
+                mode: AddressingModeKind::Implied,
                 cycles: 2,
                 execute: sei::<B>,
             },
             // 0x79
             Instruction {
+                mnemonic: "ADC",
+                mode: AddressingModeKind::AbsoluteY,
                 cycles: 4,
-                execute: adc::<Mos6502, AbsoluteY, B>,
+                execute: adc::<S, AbsoluteY, B>,
             },
             // 0x7A
             Instruction {
+                mnemonic: "NOP",
+                mode: AddressingModeKind::Implied,
                 cycles: 2,
-                execute: illegal_a::<B>,
+                execute: illegal_nop::<S, Implied, B>,
             },
             // 0x7B
             Instruction {
+                mnemonic: "RRA",
+                mode: AddressingModeKind::AbsoluteY,
                 cycles: 7,
-                execute: rra::<Mos6502, AbsoluteY, B>,
+                execute: rra::<S, AbsoluteY, B>,
             },
             // 0x7C
             Instruction {
+                mnemonic: "NOP",
+                mode: AddressingModeKind::AbsoluteX,
                 cycles: 4,
-                execute: illegal::<AbsoluteX, B>,
+                execute: illegal_nop::<S, AbsoluteX, B>,
             },
             // 0x7D
             Instruction {
+                mnemonic: "ADC",
+                mode: AddressingModeKind::AbsoluteX,
                 cycles: 4,
-                execute: adc::<Mos6502, AbsoluteX, B>,
+                execute: adc::<S, AbsoluteX, B>,
             },
             // 0x7E
             Instruction {
+                mnemonic: "ROR",
+                mode: AddressingModeKind::AbsoluteX,
                 cycles: 7,
-                execute: ror::<AbsoluteX, B>,
+                execute: ror::<S, AbsoluteX, B>,
             },
             // 0x7F
             Instruction {
+                mnemonic: "RRA",
+                mode: AddressingModeKind::AbsoluteX,
                 cycles: 7,
-                execute: rra::<Mos6502, AbsoluteX, B>,
+                execute: rra::<S, AbsoluteX, B>,
             },
             // 0x80
             Instruction {
+                mnemonic: "NOP",
+                mode: AddressingModeKind::Immediate,
                 cycles: 2,
-                execute: illegal::<Immediate, B>,
+                execute: illegal_nop::<S, Immediate, B>,
             },
             // 0x81
             Instruction {
+                mnemonic: "STA",
+                mode: AddressingModeKind::ZeroPageXIndirect,
                 cycles: 6,
                 execute: sta::<ZeroPageXIndirect, B>,
             },
             // 0x82
             Instruction {
+                mnemonic: "NOP",
+                mode: AddressingModeKind::Immediate,
                 cycles: 2,
-                execute: illegal::<Immediate, B>,
+                execute: illegal_nop::<S, Immediate, B>,
             },
             // 0x83
             Instruction {
+                mnemonic: "SAX",
+                mode: AddressingModeKind::ZeroPageXIndirect,
                 cycles: 6,
                 execute: sax::<ZeroPageXIndirect, B>,
             },
             // 0x84
             Instruction {
+                mnemonic: "STY",
+                mode: AddressingModeKind::ZeroPage,
                 cycles: 3,
                 execute: sty::<ZeroPage, B>,
             },
             // 0x85
             Instruction {
+                mnemonic: "STA",
+                mode: AddressingModeKind::ZeroPage,
                 cycles: 3,
                 execute: sta::<ZeroPage, B>,
             },
             // 0x86
             Instruction {
+                mnemonic: "STX",
+                mode: AddressingModeKind::ZeroPage,
                 cycles: 3,
                 execute: stx::<ZeroPage, B>,
             },
             // 0x87
             Instruction {
+                mnemonic: "SAX",
+                mode: AddressingModeKind::ZeroPage,
                 cycles: 3,
                 execute: sax::<ZeroPage, B>,
             },
             // 0x88
             Instruction {
+                mnemonic: "DEY",
+                mode: AddressingModeKind::Implied,
                 cycles: 2,
                 execute: dey::<B>,
             },
             // 0x89
             Instruction {
+                mnemonic: "NOP",
+                mode: AddressingModeKind::Immediate,
                 cycles: 2,
-                execute: illegal::<Immediate, B>,
+                execute: illegal_nop::<S, Immediate, B>,
             },
             // 0x8A
             Instruction {
+                mnemonic: "TXA",
+                mode: AddressingModeKind::Implied,
                 cycles: 2,
                 execute: txa::<B>,
             },
             // 0x8B
             Instruction {
+                mnemonic: "XAA",
+                mode: AddressingModeKind::Immediate,
                 cycles: 2,
-                execute: xaa::<Immediate, B>,
+                execute: xaa::<S, Immediate, B>,
             },
             // 0x8C
             Instruction {
+                mnemonic: "STY",
+                mode: AddressingModeKind::Absolute,
                 cycles: 4,
                 execute: sty::<Absolute, B>,
             },
             // 0x8D
             Instruction {
+                mnemonic: "STA",
+                mode: AddressingModeKind::Absolute,
                 cycles: 4,
                 execute: sta::<Absolute, B>,
             },
             // 0x8E
             Instruction {
+                mnemonic: "STX",
+                mode: AddressingModeKind::Absolute,
                 cycles: 4,
                 execute: stx::<Absolute, B>,
             },
             // 0x8F
             Instruction {
+                mnemonic: "SAX",
+                mode: AddressingModeKind::Absolute,
                 cycles: 4,
                 execute: sax::<Absolute, B>,
             },
             // 0x90
             Instruction {
+                mnemonic: "BCC",
+                mode: AddressingModeKind::Relative,
                 cycles: 2,
                 execute: bcc::<B>,
             },
             // 0x91
             Instruction {
+                mnemonic: "STA",
+                mode: AddressingModeKind::ZeroPageIndirectY,
                 cycles: 6,
                 execute: sta::<ZeroPageIndirectY, B>,
             },
             // 0x92
             Instruction {
+                mnemonic: "JAM",
+                mode: AddressingModeKind::Implied,
                 cycles: 0,
                 execute: jam::<B>,
             },
             // 0x93
             Instruction {
+                mnemonic: "SHA",
+                mode: AddressingModeKind::ZeroPageIndirectY,
                 cycles: 6,
                 execute: sha::<ZeroPageIndirectY, B>,
             },
             // 0x94
             Instruction {
+                mnemonic: "STY",
+                mode: AddressingModeKind::ZeroPageX,
                 cycles: 4,
                 execute: sty::<ZeroPageX, B>,
             },
             // 0x95
             Instruction {
+                mnemonic: "STA",
+                mode: AddressingModeKind::ZeroPageX,
                 cycles: 4,
                 execute: sta::<ZeroPageX, B>,
             },
             // 0x96
             Instruction {
+                mnemonic: "STX",
+                mode: AddressingModeKind::ZeroPageY,
                 cycles: 4,
                 execute: stx::<ZeroPageY, B>,
             },
             // 0x97
             Instruction {
+                mnemonic: "SAX",
+                mode: AddressingModeKind::ZeroPageY,
                 cycles: 4,
                 execute: sax::<ZeroPageY, B>,
             },
             // 0x98
             Instruction {
+                mnemonic: "TYA",
+                mode: AddressingModeKind::Implied,
                 cycles: 2,
                 execute: tya::<B>,
             },
             // 0x99
             Instruction {
+                mnemonic: "STA",
+                mode: AddressingModeKind::AbsoluteY,
                 cycles: 5,
                 execute: sta::<AbsoluteY, B>,
             },
             // 0x9A
             Instruction {
+                mnemonic: "TXS",
+                mode: AddressingModeKind::Implied,
                 cycles: 2,
                 execute: txs::<B>,
             },
             // 0x9B
             Instruction {
+                mnemonic: "SHS",
+                mode: AddressingModeKind::AbsoluteY,
                 cycles: 5,
                 execute: shs::<AbsoluteY, B>,
             },
             // 0x9C
             Instruction {
+                mnemonic: "SHY",
+                mode: AddressingModeKind::AbsoluteX,
                 cycles: 5,
                 execute: shy::<AbsoluteX, B>,
             },
             // 0x9D
             Instruction {
+                mnemonic: "STA",
+                mode: AddressingModeKind::AbsoluteX,
                 cycles: 5,
                 execute: sta::<AbsoluteX, B>,
             },
             // 0x9E
             Instruction {
+                mnemonic: "SHX",
+                mode: AddressingModeKind::AbsoluteY,
                 cycles: 5,
                 execute: shx::<AbsoluteY, B>,
             },
             // 0x9F
             Instruction {
+                mnemonic: "SHA",
+                mode: AddressingModeKind::AbsoluteY,
                 cycles: 5,
                 execute: sha::<AbsoluteY, B>,
             },
             // 0xA0
             Instruction {
+                mnemonic: "LDY",
+                mode: AddressingModeKind::Immediate,
                 cycles: 2,
                 execute: ldy::<Immediate, B>,
             },
             // 0xA1
             Instruction {
+                mnemonic: "LDA",
+                mode: AddressingModeKind::ZeroPageXIndirect,
                 cycles: 6,
                 execute: lda::<ZeroPageXIndirect, B>,
             },
             // 0xA2
             Instruction {
+                mnemonic: "LDX",
+                mode: AddressingModeKind::Immediate,
                 cycles: 2,
                 execute: ldx::<Immediate, B>,
             },
             // 0xA3
             Instruction {
+                mnemonic: "LAX",
+                mode: AddressingModeKind::ZeroPageXIndirect,
                 cycles: 6,
                 execute: lax::<ZeroPageXIndirect, B>,
             },
             // 0xA4
             Instruction {
+                mnemonic: "LDY",
+                mode: AddressingModeKind::ZeroPage,
                 cycles: 3,
                 execute: ldy::<ZeroPage, B>,
             },
             // 0xA5
             Instruction {
+                mnemonic: "LDA",
+                mode: AddressingModeKind::ZeroPage,
                 cycles: 3,
                 execute: lda::<ZeroPage, B>,
             },
             // 0xA6
             Instruction {
+                mnemonic: "LDX",
+                mode: AddressingModeKind::ZeroPage,
                 cycles: 3,
                 execute: ldx::<ZeroPage, B>,
             },
             // 0xA7
             Instruction {
+                mnemonic: "LAX",
+                mode: AddressingModeKind::ZeroPage,
                 cycles: 3,
                 execute: lax::<ZeroPage, B>,
             },
             // 0xA8
             Instruction {
+                mnemonic: "TAY",
+                mode: AddressingModeKind::Implied,
                 cycles: 2,
                 execute: tay::<B>,
             },
             // 0xA9
             Instruction {
+                mnemonic: "LDA",
+                mode: AddressingModeKind::Immediate,
                 cycles: 2,
                 execute: lda::<Immediate, B>,
             },
             // 0xAA
             Instruction {
+                mnemonic: "TAX",
+                mode: AddressingModeKind::Implied,
                 cycles: 2,
                 execute: tax::<B>,
             },
             // 0xAB
             Instruction {
+                mnemonic: "LAX",
+                mode: AddressingModeKind::Immediate,
                 cycles: 2,
-                execute: illegal::<Immediate, B>,
+                execute: lxa::<S, Immediate, B>,
             },
             // 0xAC
             Instruction {
+                mnemonic: "LDY",
+                mode: AddressingModeKind::Absolute,
                 cycles: 4,
                 execute: ldy::<Absolute, B>,
             },
             // 0xAD
             Instruction {
+                mnemonic: "LDA",
+                mode: AddressingModeKind::Absolute,
                 cycles: 4,
                 execute: lda::<Absolute, B>,
             },
             // 0xAE
             Instruction {
+                mnemonic: "LDX",
+                mode: AddressingModeKind::Absolute,
                 cycles: 4,
                 execute: ldx::<Absolute, B>,
             },
             // 0xAF
             Instruction {
+                mnemonic: "LAX",
+                mode: AddressingModeKind::Absolute,
                 cycles: 4,
                 execute: lax::<Absolute, B>,
             },
             // 0xB0
             Instruction {
+                mnemonic: "BCS",
+                mode: AddressingModeKind::Relative,
                 cycles: 2,
                 execute: bcs::<B>,
             },
             // 0xB1
             Instruction {
+                mnemonic: "LDA",
+                mode: AddressingModeKind::ZeroPageIndirectY,
                 cycles: 5,
                 execute: lda::<ZeroPageIndirectY, B>,
             },
             // 0xB2
             Instruction {
+                mnemonic: "JAM",
+                mode: AddressingModeKind::Implied,
                 cycles: 0,
                 execute: jam::<B>,
             },
             // 0xB3
             Instruction {
+                mnemonic: "LAX",
+                mode: AddressingModeKind::ZeroPageIndirectY,
                 cycles: 5,
                 execute: lax::<ZeroPageIndirectY, B>,
             },
             // 0xB4
             Instruction {
+                mnemonic: "LDY",
+                mode: AddressingModeKind::ZeroPageX,
                 cycles: 4,
                 execute: ldy::<ZeroPageX, B>,
             },
             // 0xB5
             Instruction {
+                mnemonic: "LDA",
+                mode: AddressingModeKind::ZeroPageX,
                 cycles: 4,
                 execute: lda::<ZeroPageX, B>,
             },
             // 0xB6
             Instruction {
+                mnemonic: "LDX",
+                mode: AddressingModeKind::ZeroPageY,
                 cycles: 4,
                 execute: ldx::<ZeroPageY, B>,
             },
             // 0xB7
             Instruction {
+                mnemonic: "LAX",
+                mode: AddressingModeKind::ZeroPageY,
                 cycles: 4,
                 execute: lax::<ZeroPageY, B>,
             },
             // 0xB8
             Instruction {
+                mnemonic: "CLV",
+                mode: AddressingModeKind::Implied,
                 cycles: 2,
                 execute: clv::<B>,
             },
             // 0xB9
             Instruction {
+                mnemonic: "LDA",
+                mode: AddressingModeKind::AbsoluteY,
                 cycles: 4,
                 execute: lda::<AbsoluteY, B>,
             },
             // 0xBA
             Instruction {
+                mnemonic: "TSX",
+                mode: AddressingModeKind::Implied,
                 cycles: 2,
                 execute: tsx::<B>,
             },
             // 0xBB
             Instruction {
+                mnemonic: "LAS",
+                mode: AddressingModeKind::AbsoluteY,
                 cycles: 4,
                 execute: las::<AbsoluteY, B>,
             },
             // 0xBC
             Instruction {
+                mnemonic: "LDY",
+                mode: AddressingModeKind::AbsoluteX,
                 cycles: 4,
                 execute: ldy::<AbsoluteX, B>,
             },
             // 0xBD
             Instruction {
+                mnemonic: "LDA",
+                mode: AddressingModeKind::AbsoluteX,
                 cycles: 4,
                 execute: lda::<AbsoluteX, B>,
             },
             // 0xBE
             Instruction {
+                mnemonic: "LDX",
+                mode: AddressingModeKind::AbsoluteY,
                 cycles: 4,
                 execute: ldx::<AbsoluteY, B>,
             },
             // 0xBF
             Instruction {
+                mnemonic: "LAX",
+                mode: AddressingModeKind::AbsoluteY,
                 cycles: 4,
                 execute: lax::<AbsoluteY, B>,
             },
             // 0xC0
             Instruction {
+                mnemonic: "CPY",
+                mode: AddressingModeKind::Immediate,
                 cycles: 2,
                 execute: cpy::<Immediate, B>,
             },
             // 0xC1
             Instruction {
+                mnemonic: "CMP",
+                mode: AddressingModeKind::ZeroPageXIndirect,
                 cycles: 6,
                 execute: cmp::<ZeroPageXIndirect, B>,
             },
             // 0xC2
             Instruction {
+                mnemonic: "NOP",
+                mode: AddressingModeKind::Immediate,
                 cycles: 2,
-                execute: illegal::<Immediate, B>,
+                execute: illegal_nop::<S, Immediate, B>,
             },
             // 0xC3
             Instruction {
+                mnemonic: "DCP",
+                mode: AddressingModeKind::ZeroPageXIndirect,
                 cycles: 8,
-                execute: dcp::<ZeroPageXIndirect, B>,
+                execute: dcp::<S, ZeroPageXIndirect, B>,
             },
             // 0xC4
             Instruction {
+                mnemonic: "CPY",
+                mode: AddressingModeKind::ZeroPage,
                 cycles: 3,
                 execute: cpy::<ZeroPage, B>,
             },
             // 0xC5
             Instruction {
+                mnemonic: "CMP",
+                mode: AddressingModeKind::ZeroPage,
                 cycles: 3,
                 execute: cmp::<ZeroPage, B>,
             },
             // 0xC6
             Instruction {
+                mnemonic: "DEC",
+                mode: AddressingModeKind::ZeroPage,
                 cycles: 5,
-                execute: dec::<ZeroPage, B>,
+                execute: dec::<S, ZeroPage, B>,
             },
             // 0xC7
             Instruction {
+                mnemonic: "DCP",
+                mode: AddressingModeKind::ZeroPage,
                 cycles: 5,
-                execute: dcp::<ZeroPage, B>,
+                execute: dcp::<S, ZeroPage, B>,
             },
             // 0xC8
             Instruction {
+                mnemonic: "INY",
+                mode: AddressingModeKind::Implied,
                 cycles: 2,
                 execute: iny::<B>,
             },
             // 0xC9
             Instruction {
+                mnemonic: "CMP",
+                mode: AddressingModeKind::Immediate,
                 cycles: 2,
                 execute: cmp::<Immediate, B>,
             },
             // 0xCA
             Instruction {
+                mnemonic: "DEX",
+                mode: AddressingModeKind::Implied,
                 cycles: 2,
                 execute: dex::<B>,
             },
             // 0xCB
             Instruction {
+                mnemonic: "SBX",
+                mode: AddressingModeKind::Immediate,
                 cycles: 2,
-                execute: sbx::<Mos6502, Immediate, B>,
+                execute: sbx::<S, Immediate, B>,
             },
             // 0xCC
             Instruction {
+                mnemonic: "CPY",
+                mode: AddressingModeKind::Absolute,
                 cycles: 4,
                 execute: cpy::<Absolute, B>,
             },
             // 0xCD
             Instruction {
+                mnemonic: "CMP",
+                mode: AddressingModeKind::Absolute,
                 cycles: 4,
                 execute: cmp::<Absolute, B>,
             },
             // 0xCE
             Instruction {
+                mnemonic: "DEC",
+                mode: AddressingModeKind::Absolute,
                 cycles: 6,
-                execute: dec::<Absolute, B>,
+                execute: dec::<S, Absolute, B>,
             },
             // 0xCF
             Instruction {
+                mnemonic: "DCP",
+                mode: AddressingModeKind::Absolute,
                 cycles: 6,
-                execute: dcp::<Absolute, B>,
+                execute: dcp::<S, Absolute, B>,
             },
             // 0xD0
             Instruction {
+                mnemonic: "BNE",
+                mode: AddressingModeKind::Relative,
                 cycles: 2,
                 execute: bne::<B>,
             },
             // 0xD1
             Instruction {
+                mnemonic: "CMP",
+                mode: AddressingModeKind::ZeroPageIndirectY,
                 cycles: 5,
                 execute: cmp::<ZeroPageIndirectY, B>,
             },
             // 0xD2
             Instruction {
+                mnemonic: "JAM",
+                mode: AddressingModeKind::Implied,
                 cycles: 0,
                 execute: jam::<B>,
             },
             // 0xD3
             Instruction {
+                mnemonic: "DCP",
+                mode: AddressingModeKind::ZeroPageIndirectY,
                 cycles: 8,
-                execute: dcp::<ZeroPageIndirectY, B>,
+                execute: dcp::<S, ZeroPageIndirectY, B>,
             },
             // 0xD4
             Instruction {
+                mnemonic: "NOP",
+                mode: AddressingModeKind::ZeroPageX,
                 cycles: 4,
-                execute: illegal::<ZeroPageX, B>,
+                execute: illegal_nop::<S, ZeroPageX, B>,
             },
             // 0xD5
             Instruction {
+                mnemonic: "CMP",
+                mode: AddressingModeKind::ZeroPageX,
                 cycles: 4,
                 execute: cmp::<ZeroPageX, B>,
             },
             // 0xD6
             Instruction {
+                mnemonic: "DEC",
+                mode: AddressingModeKind::ZeroPageX,
                 cycles: 6,
-                execute: dec::<ZeroPageX, B>,
+                execute: dec::<S, ZeroPageX, B>,
             },
             // 0xD7
             Instruction {
+                mnemonic: "DCP",
+                mode: AddressingModeKind::ZeroPageX,
                 cycles: 6,
-                execute: dcp::<ZeroPageX, B>,
+                execute: dcp::<S, ZeroPageX, B>,
             },
             // 0xD8
             Instruction {
+                mnemonic: "CLD",
+                mode: AddressingModeKind::Implied,
                 cycles: 2,
                 execute: cld::<B>,
             },
             // 0xD9
             Instruction {
+                mnemonic: "CMP",
+                mode: AddressingModeKind::AbsoluteY,
                 cycles: 4,
                 execute: cmp::<AbsoluteY, B>,
             },
             // 0xDA
             Instruction {
+                mnemonic: "NOP",
+                mode: AddressingModeKind::Implied,
                 cycles: 2,
-                execute: illegal_a::<B>,
+                execute: illegal_nop::<S, Implied, B>,
             },
             // 0xDB
             Instruction {
+                mnemonic: "DCP",
+                mode: AddressingModeKind::AbsoluteY,
                 cycles: 7,
-                execute: dcp::<AbsoluteY, B>,
+                execute: dcp::<S, AbsoluteY, B>,
             },
             // 0xDC
             Instruction {
+                mnemonic: "NOP",
+                mode: AddressingModeKind::AbsoluteX,
                 cycles: 4,
-                execute: illegal::<AbsoluteX, B>,
+                execute: illegal_nop::<S, AbsoluteX, B>,
             },
             // 0xDD
             Instruction {
+                mnemonic: "CMP",
+                mode: AddressingModeKind::AbsoluteX,
                 cycles: 4,
                 execute: cmp::<AbsoluteX, B>,
             },
             // 0xDE
             Instruction {
+                mnemonic: "DEC",
+                mode: AddressingModeKind::AbsoluteX,
                 cycles: 7,
-                execute: dec::<AbsoluteX, B>,
+                execute: dec::<S, AbsoluteX, B>,
             },
             // 0xDF
             Instruction {
+                mnemonic: "DCP",
+                mode: AddressingModeKind::AbsoluteX,
                 cycles: 7,
-                execute: dcp::<AbsoluteX, B>,
+                execute: dcp::<S, AbsoluteX, B>,
             },
             // 0xE0
             Instruction {
+                mnemonic: "CPX",
+                mode: AddressingModeKind::Immediate,
                 cycles: 2,
                 execute: cpx::<Immediate, B>,
             },
             // 0xE1
             Instruction {
+                mnemonic: "SBC",
+                mode: AddressingModeKind::ZeroPageXIndirect,
                 cycles: 6,
-                execute: sbc::<Mos6502, ZeroPageXIndirect, B>,
+                execute: sbc::<S, ZeroPageXIndirect, B>,
             },
             // 0xE2
             Instruction {
+                mnemonic: "NOP",
+                mode: AddressingModeKind::Immediate,
                 cycles: 2,
-                execute: illegal::<Immediate, B>,
+                execute: illegal_nop::<S, Immediate, B>,
             },
             // 0xE3
             Instruction {
+                mnemonic: "ISC",
+                mode: AddressingModeKind::ZeroPageXIndirect,
                 cycles: 8,
-                execute: isc::<Mos6502, ZeroPageXIndirect, B>,
+                execute: isc::<S, ZeroPageXIndirect, B>,
             },
             // 0xE4
             Instruction {
+                mnemonic: "CPX",
+                mode: AddressingModeKind::ZeroPage,
                 cycles: 3,
                 execute: cpx::<ZeroPage, B>,
             },
             // 0xE5
             Instruction {
+                mnemonic: "SBC",
+                mode: AddressingModeKind::ZeroPage,
                 cycles: 3,
-                execute: sbc::<Mos6502, ZeroPage, B>,
+                execute: sbc::<S, ZeroPage, B>,
             },
             // 0xE6
             Instruction {
+                mnemonic: "INC",
+                mode: AddressingModeKind::ZeroPage,
                 cycles: 5,
-                execute: inc::<ZeroPage, B>,
+                execute: inc::<S, ZeroPage, B>,
             },
             // 0xE7
             Instruction {
+                mnemonic: "ISC",
+                mode: AddressingModeKind::ZeroPage,
                 cycles: 5,
-                execute: isc::<Mos6502, ZeroPage, B>,
+                execute: isc::<S, ZeroPage, B>,
             },
             // 0xE8
             Instruction {
+                mnemonic: "INX",
+                mode: AddressingModeKind::Implied,
                 cycles: 2,
                 execute: inx::<B>,
             },
             // 0xE9
             Instruction {
+                mnemonic: "SBC",
+                mode: AddressingModeKind::Immediate,
                 cycles: 2,
-                execute: sbc::<Mos6502, Immediate, B>,
+                execute: sbc::<S, Immediate, B>,
             },
             // 0xEA
             Instruction {
+                mnemonic: "NOP",
+                mode: AddressingModeKind::Implied,
                 cycles: 2,
                 execute: nop::<B>,
             },
             // 0xEB
             Instruction {
+                mnemonic: "SBC",
+                mode: AddressingModeKind::Immediate,
                 cycles: 2,
-                execute: sbc::<Mos6502, Immediate, B>,
+                execute: sbc::<S, Immediate, B>,
             },
             // 0xEC
             Instruction {
+                mnemonic: "CPX",
+                mode: AddressingModeKind::Absolute,
                 cycles: 4,
                 execute: cpx::<Absolute, B>,
             },
             // 0xED
             Instruction {
+                mnemonic: "SBC",
+                mode: AddressingModeKind::Absolute,
                 cycles: 4,
-                execute: sbc::<Mos6502, Absolute, B>,
+                execute: sbc::<S, Absolute, B>,
             },
             // 0xEE
             Instruction {
+                mnemonic: "INC",
+                mode: AddressingModeKind::Absolute,
                 cycles: 6,
-                execute: inc::<Absolute, B>,
+                execute: inc::<S, Absolute, B>,
             },
             // 0xEF
             Instruction {
+                mnemonic: "ISC",
+                mode: AddressingModeKind::Absolute,
                 cycles: 6,
-                execute: isc::<Mos6502, Absolute, B>,
+                execute: isc::<S, Absolute, B>,
             },
             // 0xF0
             Instruction {
+                mnemonic: "BEQ",
+                mode: AddressingModeKind::Relative,
                 cycles: 2,
                 execute: beq::<B>,
             },
             // 0xF1
             Instruction {
+                mnemonic: "SBC",
+                mode: AddressingModeKind::ZeroPageIndirectY,
                 cycles: 5,
-                execute: sbc::<Mos6502, ZeroPageIndirectY, B>,
+                execute: sbc::<S, ZeroPageIndirectY, B>,
             },
             // 0xF2
             Instruction {
+                mnemonic: "JAM",
+                mode: AddressingModeKind::Implied,
                 cycles: 0,
                 execute: jam::<B>,
             },
             // 0xF3
             Instruction {
+                mnemonic: "ISC",
+                mode: AddressingModeKind::ZeroPageIndirectY,
                 cycles: 8,
-                execute: isc::<Mos6502, ZeroPageIndirectY, B>,
+                execute: isc::<S, ZeroPageIndirectY, B>,
             },
             // 0xF4
             Instruction {
+                mnemonic: "NOP",
+                mode: AddressingModeKind::ZeroPageX,
                 cycles: 4,
-                execute: illegal::<ZeroPageX, B>,
+                execute: illegal_nop::<S, ZeroPageX, B>,
             },
             // 0xF5
             Instruction {
+                mnemonic: "SBC",
+                mode: AddressingModeKind::ZeroPageX,
                 cycles: 4,
-                execute: sbc::<Mos6502, ZeroPageX, B>,
+                execute: sbc::<S, ZeroPageX, B>,
             },
             // 0xF6
             Instruction {
+                mnemonic: "INC",
+                mode: AddressingModeKind::ZeroPageX,
                 cycles: 6,
-                execute: inc::<ZeroPageX, B>,
+                execute: inc::<S, ZeroPageX, B>,
             },
             // 0xF7
             Instruction {
+                mnemonic: "ISC",
+                mode: AddressingModeKind::ZeroPageX,
                 cycles: 6,
-                execute: isc::<Mos6502, ZeroPageX, B>,
+                execute: isc::<S, ZeroPageX, B>,
             },
             // 0xF8
             Instruction {
+                mnemonic: "SED",
+                mode: AddressingModeKind::Implied,
                 cycles: 2,
                 execute: sed::<B>,
             },
             // 0xF9
             Instruction {
+                mnemonic: "SBC",
+                mode: AddressingModeKind::AbsoluteY,
                 cycles: 4,
-                execute: sbc::<Mos6502, AbsoluteY, B>,
+                execute: sbc::<S, AbsoluteY, B>,
             },
             // 0xFA
             Instruction {
+                mnemonic: "NOP",
+                mode: AddressingModeKind::Implied,
                 cycles: 2,
-                execute: illegal_a::<B>,
+                execute: illegal_nop::<S, Implied, B>,
             },
             // 0xFB
             Instruction {
+                mnemonic: "ISC",
+                mode: AddressingModeKind::AbsoluteY,
                 cycles: 7,
-                execute: isc::<Mos6502, AbsoluteY, B>,
+                execute: isc::<S, AbsoluteY, B>,
             },
             // 0xFC
             Instruction {
+                mnemonic: "NOP",
+                mode: AddressingModeKind::AbsoluteX,
                 cycles: 4,
-                execute: illegal::<AbsoluteX, B>,
+                execute: illegal_nop::<S, AbsoluteX, B>,
             },
             // 0xFD
             Instruction {
+                mnemonic: "SBC",
+                mode: AddressingModeKind::AbsoluteX,
                 cycles: 4,
-                execute: sbc::<Mos6502, AbsoluteX, B>,
+                execute: sbc::<S, AbsoluteX, B>,
             },
             // 0xFE
             Instruction {
+                mnemonic: "INC",
+                mode: AddressingModeKind::AbsoluteX,
                 cycles: 7,
-                execute: inc::<AbsoluteX, B>,
+                execute: inc::<S, AbsoluteX, B>,
             },
             // 0xFF
             Instruction {
+                mnemonic: "ISC",
+                mode: AddressingModeKind::AbsoluteX,
                 cycles: 7,
-                execute: isc::<Mos6502, AbsoluteX, B>,
+                execute: isc::<S, AbsoluteX, B>,
             },
         ])
     }
 }
 
 impl InstructionSet for Mos6502 {
-    fn instruction_table<B: Mos6502CompatibleBus + 'static>() -> InstructionTable<B> {
+    fn instruction_table<B: Mos6502CompatibleBus>() -> InstructionTable<B> {
         Self::base_table()
     }
+    const NAME: &'static str = "MOS 6502";
 }
 
-pub fn lda<AM: AddressingMode, B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, bus: &mut B) {
-    let addr = AM::fetch_address(cpu, bus);
-    let val = bus.read(addr, AccessType::DataRead);
+pub fn lda<AM: AddressingMode, B: Mos6502CompatibleBus>(cpu: &mut Cpu<B>, bus: &mut B) {
+    let (addr, penalty) = AM::fetch_address_penalized(cpu, bus);
+    cpu.cycles += u64::from(penalty);
+    let val = cpu.read_data(bus, addr);
     cpu.a = val;
-    cpu.p.set_zero(val == 0);
-    cpu.p.set_signed(val.is_signed());
+    cpu.p.update_nz(val);
     cpu.pc += AM::BYTES;
 }
 
-pub fn ldx<AM: AddressingMode, B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, bus: &mut B) {
-    let addr = AM::fetch_address(cpu, bus);
-    let val = bus.read(addr, AccessType::DataRead);
+pub fn ldx<AM: AddressingMode, B: Mos6502CompatibleBus>(cpu: &mut Cpu<B>, bus: &mut B) {
+    let (addr, penalty) = AM::fetch_address_penalized(cpu, bus);
+    cpu.cycles += u64::from(penalty);
+    let val = cpu.read_data(bus, addr);
     cpu.x = val;
-    cpu.p.set_zero(val == 0);
-    cpu.p.set_signed(val.is_signed());
+    cpu.p.update_nz(val);
     cpu.pc += AM::BYTES;
 }
 
-pub fn ldy<AM: AddressingMode, B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, bus: &mut B) {
-    let addr = AM::fetch_address(cpu, bus);
-    let val = bus.read(addr, AccessType::DataRead);
+pub fn ldy<AM: AddressingMode, B: Mos6502CompatibleBus>(cpu: &mut Cpu<B>, bus: &mut B) {
+    let (addr, penalty) = AM::fetch_address_penalized(cpu, bus);
+    cpu.cycles += u64::from(penalty);
+    let val = cpu.read_data(bus, addr);
     cpu.y = val;
-    cpu.p.set_zero(val == 0);
-    cpu.p.set_signed(val.is_signed());
+    cpu.p.update_nz(val);
     cpu.pc += AM::BYTES;
 }
 
-pub fn sta<AM: AddressingMode, B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, bus: &mut B) {
+pub fn sta<AM: AddressingMode, B: Mos6502CompatibleBus>(cpu: &mut Cpu<B>, bus: &mut B) {
     let addr = AM::fetch_address(cpu, bus);
-    bus.write(addr, cpu.a, AccessType::DataWrite);
+    bus.cpu_write(addr, cpu.a, AccessType::DataWrite);
     cpu.pc += AM::BYTES;
 }
 
-pub fn stx<AM: AddressingMode, B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, bus: &mut B) {
+pub fn stx<AM: AddressingMode, B: Mos6502CompatibleBus>(cpu: &mut Cpu<B>, bus: &mut B) {
     let addr = AM::fetch_address(cpu, bus);
-    bus.write(addr, cpu.x, AccessType::DataWrite);
+    bus.cpu_write(addr, cpu.x, AccessType::DataWrite);
     cpu.pc += AM::BYTES;
 }
 
-pub fn sty<AM: AddressingMode, B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, bus: &mut B) {
+pub fn sty<AM: AddressingMode, B: Mos6502CompatibleBus>(cpu: &mut Cpu<B>, bus: &mut B) {
     let addr = AM::fetch_address(cpu, bus);
-    bus.write(addr, cpu.y, AccessType::DataWrite);
+    bus.cpu_write(addr, cpu.y, AccessType::DataWrite);
     cpu.pc += AM::BYTES;
 }
 
-pub fn tax<B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, _bus: &mut B) {
+pub fn tax<B: Mos6502CompatibleBus>(cpu: &mut Cpu<B>, _bus: &mut B) {
     cpu.x = cpu.a;
-    cpu.p.set_zero(cpu.x == 0);
-    cpu.p.set_signed(cpu.x.is_signed());
-    cpu.pc += 1;
+    cpu.p.update_nz(cpu.x);
+    cpu.pc += Implied::BYTES;
 }
 
-pub fn tay<B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, _bus: &mut B) {
+pub fn tay<B: Mos6502CompatibleBus>(cpu: &mut Cpu<B>, _bus: &mut B) {
     cpu.y = cpu.a;
-    cpu.p.set_zero(cpu.y == 0);
-    cpu.p.set_signed(cpu.y.is_signed());
-    cpu.pc += 1;
+    cpu.p.update_nz(cpu.y);
+    cpu.pc += Implied::BYTES;
 }
 
-pub fn tsx<B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, _bus: &mut B) {
+pub fn tsx<B: Mos6502CompatibleBus>(cpu: &mut Cpu<B>, _bus: &mut B) {
     cpu.x = cpu.sp;
-    cpu.p.set_zero(cpu.x == 0);
-    cpu.p.set_signed(cpu.x.is_signed());
-    cpu.pc += 1;
+    cpu.p.update_nz(cpu.x);
+    cpu.pc += Implied::BYTES;
 }
 
-pub fn txa<B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, _bus: &mut B) {
+pub fn txa<B: Mos6502CompatibleBus>(cpu: &mut Cpu<B>, _bus: &mut B) {
     cpu.a = cpu.x;
-    cpu.p.set_zero(cpu.a == 0);
-    cpu.p.set_signed(cpu.a.is_signed());
-    cpu.pc += 1;
+    cpu.p.update_nz(cpu.a);
+    cpu.pc += Implied::BYTES;
 }
 
-pub fn txs<B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, _bus: &mut B) {
+pub fn txs<B: Mos6502CompatibleBus>(cpu: &mut Cpu<B>, _bus: &mut B) {
     cpu.sp = cpu.x;
-    cpu.pc += 1;
+    cpu.pc += Implied::BYTES;
 }
 
-pub fn tya<B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, _bus: &mut B) {
+pub fn tya<B: Mos6502CompatibleBus>(cpu: &mut Cpu<B>, _bus: &mut B) {
     cpu.a = cpu.y;
-    cpu.p.set_zero(cpu.a == 0);
-    cpu.p.set_signed(cpu.a.is_signed());
-    cpu.pc += 1;
+    cpu.p.update_nz(cpu.a);
+    cpu.pc += Implied::BYTES;
 }
 
-pub fn pha<B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, bus: &mut B) {
+pub fn pha<B: Mos6502CompatibleBus>(cpu: &mut Cpu<B>, bus: &mut B) {
     cpu.push(bus, cpu.a);
-    cpu.pc += 1;
+    cpu.pc += Implied::BYTES;
 }
 
-pub fn php<B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, bus: &mut B) {
+pub fn php<B: Mos6502CompatibleBus>(cpu: &mut Cpu<B>, bus: &mut B) {
     let bits = cpu.p | Flags::Break | Flags::Expansion;
     cpu.push(bus, byte!(bits));
-    cpu.pc += 1;
+    cpu.pc += Implied::BYTES;
 }
 
-pub fn pla<B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, bus: &mut B) {
+pub fn pla<B: Mos6502CompatibleBus>(cpu: &mut Cpu<B>, bus: &mut B) {
     let val = cpu.pop(bus);
     cpu.a = val;
-    cpu.p.set_zero(val == 0);
-    cpu.p.set_signed(val.is_signed());
-    cpu.pc += 1;
+    cpu.p.update_nz(val);
+    cpu.pc += Implied::BYTES;
 }
 
-pub fn plp<B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, bus: &mut B) {
+pub fn plp<B: Mos6502CompatibleBus>(cpu: &mut Cpu<B>, bus: &mut B) {
     let val = cpu.pop(bus) | Flags::Expansion;
     let masked = val & !Flags::Break;
     cpu.p = Flags::from_bits_truncate(masked.as_u8());
-    cpu.pc += 1;
+    cpu.pc += Implied::BYTES;
+}
+
+/// Read the operand of a read-modify-write instruction at `addr`.
+///
+/// Real parts spend an extra cycle on the address between the read and the final write: NMOS
+/// chips write the unmodified value back ([`AccessType::DummyWrite`]) while the 65C02 reads it
+/// again ([`AccessType::DummyRead`]), as selected by [`InstructionSet::RMW_DUMMY_WRITE`]. Either
+/// way the stored value doesn't change, but memory-mapped registers see the same access pattern
+/// as on hardware.
+pub(crate) fn read_for_modify<S: InstructionSet, B: Mos6502CompatibleBus>(
+    cpu: &Cpu<B>,
+    bus: &mut B,
+    addr: Word,
+) -> Byte {
+    let val = cpu.read_data(bus, addr);
+    if S::RMW_DUMMY_WRITE {
+        bus.cpu_write(addr, val, AccessType::DummyWrite);
+    } else {
+        bus.cpu_read(addr, AccessType::DummyRead);
+    }
+    val
 }
 
-pub fn asl<AM: AddressingMode, B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, bus: &mut B) {
+pub fn asl<S: InstructionSet, AM: AddressingMode, B: Mos6502CompatibleBus>(
+    cpu: &mut Cpu<B>,
+    bus: &mut B,
+) {
     let addr = AM::fetch_address(cpu, bus);
-    let mut val = bus.read(addr, AccessType::DataRead);
+    let mut val = read_for_modify::<S, _>(cpu, bus, addr);
 
     cpu.p.set_carry(val & Flags::Sign != 0);
     val <<= 1;
-    bus.write(addr, val, AccessType::DataWrite);
-    cpu.p.set_zero(val == 0);
-    cpu.p.set_signed(val.is_signed());
+    bus.cpu_write(addr, val, AccessType::DataWrite);
+    cpu.p.update_nz(val);
     cpu.pc += AM::BYTES;
 }
 
-pub fn asl_a<B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, _bus: &mut B) {
+pub fn asl_a<B: Mos6502CompatibleBus>(cpu: &mut Cpu<B>, _bus: &mut B) {
     cpu.p.set_carry(cpu.a & Flags::Sign != 0);
     cpu.a <<= 1;
-    cpu.p.set_zero(cpu.a == 0);
-    cpu.p.set_signed(cpu.a.is_signed());
-    cpu.pc += 1;
+    cpu.p.update_nz(cpu.a);
+    cpu.pc += Accumulator::BYTES;
 }
 
-pub fn lsr<AM: AddressingMode, B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, bus: &mut B) {
+pub fn lsr<S: InstructionSet, AM: AddressingMode, B: Mos6502CompatibleBus>(
+    cpu: &mut Cpu<B>,
+    bus: &mut B,
+) {
     let addr = AM::fetch_address(cpu, bus);
-    let mut val = bus.read(addr, AccessType::DataRead);
+    let mut val = read_for_modify::<S, _>(cpu, bus, addr);
 
     cpu.p.set_carry(val & Flags::Carry != 0);
     val >>= 1;
-    bus.write(addr, val, AccessType::DataWrite);
-    cpu.p.set_zero(val == 0);
-    cpu.p.set_signed(val.is_signed());
+    bus.cpu_write(addr, val, AccessType::DataWrite);
+    cpu.p.update_nz(val);
     cpu.pc += AM::BYTES;
 }
 
-pub fn lsr_a<B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, _bus: &mut B) {
+pub fn lsr_a<B: Mos6502CompatibleBus>(cpu: &mut Cpu<B>, _bus: &mut B) {
     cpu.p.set_carry(cpu.a & Flags::Carry != 0);
     cpu.a >>= 1;
-    cpu.p.set_zero(cpu.a == 0);
-    cpu.p.set_signed(cpu.a.is_signed());
-    cpu.pc += 1;
+    cpu.p.update_nz(cpu.a);
+    cpu.pc += Accumulator::BYTES;
 }
 
-pub fn rol<AM: AddressingMode, B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, bus: &mut B) {
+pub fn rol<S: InstructionSet, AM: AddressingMode, B: Mos6502CompatibleBus>(
+    cpu: &mut Cpu<B>,
+    bus: &mut B,
+) {
     let addr = AM::fetch_address(cpu, bus);
-    let val = bus.read(addr, AccessType::DataRead);
+    let val = read_for_modify::<S, _>(cpu, bus, addr);
     let rotated = rotate_left(cpu, val);
-    bus.write(addr, rotated, AccessType::DataWrite);
+    bus.cpu_write(addr, rotated, AccessType::DataWrite);
     cpu.pc += AM::BYTES;
 }
 
-pub fn rol_a<B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, _bus: &mut B) {
+pub fn rol_a<B: Mos6502CompatibleBus>(cpu: &mut Cpu<B>, _bus: &mut B) {
     cpu.a = rotate_left(cpu, cpu.a);
-    cpu.pc += 1;
+    cpu.pc += Accumulator::BYTES;
 }
 
-pub fn ror<AM: AddressingMode, B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, bus: &mut B) {
+pub fn ror<S: InstructionSet, AM: AddressingMode, B: Mos6502CompatibleBus>(
+    cpu: &mut Cpu<B>,
+    bus: &mut B,
+) {
     let addr = AM::fetch_address(cpu, bus);
-    let val = bus.read(addr, AccessType::DataRead);
+    let val = read_for_modify::<S, _>(cpu, bus, addr);
     let rotated = rotate_right(cpu, val);
-    bus.write(addr, rotated, AccessType::DataWrite);
+    bus.cpu_write(addr, rotated, AccessType::DataWrite);
     cpu.pc += AM::BYTES;
 }
 
-pub fn ror_a<B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, _bus: &mut B) {
+pub fn ror_a<B: Mos6502CompatibleBus>(cpu: &mut Cpu<B>, _bus: &mut B) {
     cpu.a = rotate_right(cpu, cpu.a);
-    cpu.pc += 1;
+    cpu.pc += Accumulator::BYTES;
 }
 
-fn rotate_left<B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, mut val: Byte) -> Byte {
-    let old_carry = cpu.p.bit(Flags::Carry);
-    cpu.p.set_carry(val & Flags::Sign != 0);
-    val <<= 1;
-    val |= old_carry;
-    cpu.p.set_zero(val == Byte(0));
-    cpu.p.set_signed(val.is_signed());
-    val
+/// `ROL` on a bare value: shift `value` left with `carry_in` entering bit 0.
+///
+/// Returns the result and the carry out (the old bit 7). Setting N and Z from the result is up
+/// to the caller, which makes this usable from custom instructions that don't own a [`Cpu`].
+#[must_use]
+pub fn rol_byte(value: Byte, carry_in: bool) -> (Byte, bool) {
+    let carry_out = value & Flags::Sign != 0;
+    ((value << 1) | Byte(u8::from(carry_in)), carry_out)
 }
 
-fn rotate_right<B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, mut val: Byte) -> Byte {
-    let old_carry = (cpu.p.bit(Flags::Carry)) << 7;
-    cpu.p.set_carry(val & Flags::Carry != 0);
-    val >>= 1;
-    val |= old_carry;
-    cpu.p.set_zero(val == Byte(0));
-    cpu.p.set_signed(val.is_signed());
-    val
+/// `ROR` on a bare value: shift `value` right with `carry_in` entering bit 7.
+///
+/// Returns the result and the carry out (the old bit 0); see [`rol_byte`].
+#[must_use]
+pub fn ror_byte(value: Byte, carry_in: bool) -> (Byte, bool) {
+    let carry_out = value & Flags::Carry != 0;
+    ((value >> 1) | Byte(u8::from(carry_in) << 7), carry_out)
 }
 
-pub fn and<AM: AddressingMode, B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, bus: &mut B) {
-    let addr = AM::fetch_address(cpu, bus);
-    let val = bus.read(addr, AccessType::DataRead);
+fn rotate_left<B: Mos6502CompatibleBus>(cpu: &mut Cpu<B>, val: Byte) -> Byte {
+    let (result, carry) = rol_byte(val, cpu.p.contains(Flags::Carry));
+    cpu.p.set_carry(carry);
+    cpu.p.update_nz(result);
+    result
+}
+
+fn rotate_right<B: Mos6502CompatibleBus>(cpu: &mut Cpu<B>, val: Byte) -> Byte {
+    let (result, carry) = ror_byte(val, cpu.p.contains(Flags::Carry));
+    cpu.p.set_carry(carry);
+    cpu.p.update_nz(result);
+    result
+}
+
+pub fn and<AM: AddressingMode, B: Mos6502CompatibleBus>(cpu: &mut Cpu<B>, bus: &mut B) {
+    let (addr, penalty) = AM::fetch_address_penalized(cpu, bus);
+    cpu.cycles += u64::from(penalty);
+    let val = cpu.read_data(bus, addr);
 
     cpu.a &= val;
-    cpu.p.set_zero(cpu.a == 0);
-    cpu.p.set_signed(cpu.a.is_signed());
+    cpu.p.update_nz(cpu.a);
 
     cpu.pc += AM::BYTES;
 }
 
-pub fn bit<AM: AddressingMode, B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, bus: &mut B) {
-    let addr = AM::fetch_address(cpu, bus);
-    let val = bus.read(addr, AccessType::DataRead);
+pub fn bit<AM: AddressingMode, B: Mos6502CompatibleBus>(cpu: &mut Cpu<B>, bus: &mut B) {
+    let (addr, penalty) = AM::fetch_address_penalized(cpu, bus);
+    cpu.cycles += u64::from(penalty);
+    let val = cpu.read_data(bus, addr);
     let test = val & cpu.a;
 
     cpu.p.set_zero(test == 0);
@@ -1528,88 +2092,103 @@ pub fn bit<AM: AddressingMode, B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<
     cpu.pc += AM::BYTES;
 }
 
-pub fn eor<AM: AddressingMode, B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, bus: &mut B) {
-    let addr = AM::fetch_address(cpu, bus);
-    let val = bus.read(addr, AccessType::DataRead);
+pub fn eor<AM: AddressingMode, B: Mos6502CompatibleBus>(cpu: &mut Cpu<B>, bus: &mut B) {
+    let (addr, penalty) = AM::fetch_address_penalized(cpu, bus);
+    cpu.cycles += u64::from(penalty);
+    let val = cpu.read_data(bus, addr);
 
     cpu.a ^= val;
-    cpu.p.set_zero(cpu.a == 0);
-    cpu.p.set_signed(cpu.a.is_signed());
+    cpu.p.update_nz(cpu.a);
 
     cpu.pc += AM::BYTES;
 }
 
-pub fn ora<AM: AddressingMode, B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, bus: &mut B) {
-    let addr = AM::fetch_address(cpu, bus);
-    let val = bus.read(addr, AccessType::DataRead);
+pub fn ora<AM: AddressingMode, B: Mos6502CompatibleBus>(cpu: &mut Cpu<B>, bus: &mut B) {
+    let (addr, penalty) = AM::fetch_address_penalized(cpu, bus);
+    cpu.cycles += u64::from(penalty);
+    let val = cpu.read_data(bus, addr);
 
     cpu.a |= val;
-    cpu.p.set_zero(cpu.a == 0);
-    cpu.p.set_signed(cpu.a.is_signed());
+    cpu.p.update_nz(cpu.a);
 
     cpu.pc += AM::BYTES;
 }
 
-pub fn adc<S: InstructionSet, AM: AddressingMode, B: Mos6502CompatibleBus + 'static>(
+pub fn adc<S: InstructionSet, AM: AddressingMode, B: Mos6502CompatibleBus>(
     cpu: &mut Cpu<B>,
     bus: &mut B,
 ) {
-    let addr = AM::fetch_address(cpu, bus);
-    let value = bus.read(addr, AccessType::DataRead);
+    let (addr, penalty) = AM::fetch_address_penalized(cpu, bus);
+    cpu.cycles += u64::from(penalty);
+    let value = cpu.read_data(bus, addr);
+    charge_decimal_cycle::<S, _>(cpu);
     add_with_carry::<S, _>(cpu, value);
     cpu.pc += AM::BYTES;
 }
 
-pub fn cmp<AM: AddressingMode, B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, bus: &mut B) {
-    let addr = AM::fetch_address(cpu, bus);
-    let val = bus.read(addr, AccessType::DataRead);
-    let (raw, overflow) = cpu.a.as_u8().overflowing_sub(val.as_u8());
-    let result = byte!(raw);
+pub fn cmp<AM: AddressingMode, B: Mos6502CompatibleBus>(cpu: &mut Cpu<B>, bus: &mut B) {
+    let (addr, penalty) = AM::fetch_address_penalized(cpu, bus);
+    cpu.cycles += u64::from(penalty);
+    let val = cpu.read_data(bus, addr);
+    let (result, borrow) = cpu.a.overflowing_sub(val);
 
-    cpu.p.set_zero(result == Byte(0));
-    cpu.p.set_signed(result.is_signed());
-    cpu.p.set_carry(!overflow);
+    cpu.p.update_nz(result);
+    cpu.p.set_carry(!borrow);
 
     cpu.pc += AM::BYTES;
 }
 
-pub fn cpx<AM: AddressingMode, B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, bus: &mut B) {
-    let addr = AM::fetch_address(cpu, bus);
-    let val = bus.read(addr, AccessType::DataRead);
-    let (raw, overflow) = cpu.x.as_u8().overflowing_sub(val.as_u8());
-    let result = byte!(raw);
+pub fn cpx<AM: AddressingMode, B: Mos6502CompatibleBus>(cpu: &mut Cpu<B>, bus: &mut B) {
+    let (addr, penalty) = AM::fetch_address_penalized(cpu, bus);
+    cpu.cycles += u64::from(penalty);
+    let val = cpu.read_data(bus, addr);
+    let (result, borrow) = cpu.x.overflowing_sub(val);
 
-    cpu.p.set_zero(result == Byte(0));
-    cpu.p.set_signed(result.is_signed());
-    cpu.p.set_carry(!overflow);
+    cpu.p.update_nz(result);
+    cpu.p.set_carry(!borrow);
 
     cpu.pc += AM::BYTES;
 }
 
-pub fn cpy<AM: AddressingMode, B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, bus: &mut B) {
-    let addr = AM::fetch_address(cpu, bus);
-    let val = bus.read(addr, AccessType::DataRead);
-    let (raw, overflow) = cpu.y.as_u8().overflowing_sub(val.as_u8());
-    let result = byte!(raw);
+pub fn cpy<AM: AddressingMode, B: Mos6502CompatibleBus>(cpu: &mut Cpu<B>, bus: &mut B) {
+    let (addr, penalty) = AM::fetch_address_penalized(cpu, bus);
+    cpu.cycles += u64::from(penalty);
+    let val = cpu.read_data(bus, addr);
+    let (result, borrow) = cpu.y.overflowing_sub(val);
 
-    cpu.p.set_zero(result == Byte(0));
-    cpu.p.set_signed(result.is_signed());
-    cpu.p.set_carry(!overflow);
+    cpu.p.update_nz(result);
+    cpu.p.set_carry(!borrow);
 
     cpu.pc += AM::BYTES;
 }
 
-pub fn sbc<S: InstructionSet, AM: AddressingMode, B: Mos6502CompatibleBus + 'static>(
+pub fn sbc<S: InstructionSet, AM: AddressingMode, B: Mos6502CompatibleBus>(
     cpu: &mut Cpu<B>,
     bus: &mut B,
 ) {
-    let addr = AM::fetch_address(cpu, bus);
-    let value = bus.read(addr, AccessType::DataRead);
+    let (addr, penalty) = AM::fetch_address_penalized(cpu, bus);
+    cpu.cycles += u64::from(penalty);
+    let value = cpu.read_data(bus, addr);
+    charge_decimal_cycle::<S, _>(cpu);
     sub_with_borrow::<S, _>(cpu, value);
     cpu.pc += AM::BYTES;
 }
 
-pub(crate) fn add_with_carry<S: InstructionSet, B: Mos6502CompatibleBus + 'static>(
+/// CMOS parts spend an extra cycle correcting the flags of a decimal-mode `ADC`/`SBC`.
+fn charge_decimal_cycle<S: InstructionSet, B: Mos6502CompatibleBus>(cpu: &mut Cpu<B>) {
+    if S::SUPPORTS_DECIMAL_MODE && S::DECIMAL_FLAGS_VALID && cpu.p.contains(Flags::DecimalMode) {
+        cpu.cycles += 1;
+    }
+}
+
+/// Whether `a + operand` (plus any carry) overflowed as signed arithmetic into `result`: the
+/// operands had the same sign and the result's differs. Subtraction passes the complemented
+/// operand, since that's what the ALU adds.
+pub(crate) fn signed_overflow(a: Byte, operand: Byte, result: Byte) -> bool {
+    ((a ^ result) & (operand ^ result) & Byte(0x80)) != Byte(0)
+}
+
+pub(crate) fn add_with_carry<S: InstructionSet, B: Mos6502CompatibleBus>(
     cpu: &mut Cpu<B>,
     value: Byte,
 ) {
@@ -1619,40 +2198,45 @@ pub(crate) fn add_with_carry<S: InstructionSet, B: Mos6502CompatibleBus + 'stati
     let sum = cpu.a.as_u16() + value.as_u16() + carry_in;
     let mut result = byte!((sum & 0x00FF) as u8);
 
-    cpu.p
-        .set_overflow(((cpu.a ^ result) & (value ^ result) & Byte(0x80)) != Byte(0)); // overflow when operands had same sign but result differs
+    cpu.p.set_overflow(signed_overflow(cpu.a, value, result));
 
     if decimal {
-        // In BCD mode each nibble represents a base-10 digit. Add the ones place
-        // and manually correct if it overflowed past 9, then fold the carry into
-        // the tens place and apply the same correction. Pack the resulting digits
-        // back into a byte instead of relying on binary addition overflow.
-        let mut lo = i16::from(cpu.a.lo()) + i16::from(value.lo()) + i16::from(carry_in as u8); // add ones digit
-        let mut carry_10 = 0i16;
-        if lo > 9 {
-            lo -= 10;
-            carry_10 = 1;
+        // The chip's BCD adjust, as laid out in Bruce Clark's "Decimal Mode" appendix: fix up
+        // the ones digit, fold its carry into the tens, then fix up the tens. It works on raw
+        // nibbles, so non-BCD operands come out the way the hardware has them too.
+        let (a, b) = (cpu.a.as_u16(), value.as_u16());
+        let mut ones = (a & 0x0F) + (b & 0x0F) + carry_in;
+        if ones > 9 {
+            ones = ((ones + 6) & 0x0F) + 0x10;
         }
-
-        let mut hi = i16::from(cpu.a.hi()) + i16::from(value.hi()) + carry_10; // add tens digit
-        let mut carry_out = false;
-        if hi > 9 {
-            hi -= 10;
-            carry_out = true;
+        let partial = (a & 0xF0) + (b & 0xF0) + ones;
+        let adjusted = if partial >= 0xA0 { partial + 0x60 } else { partial };
+
+        result = byte!((adjusted & 0x00FF) as u8);
+        cpu.p.set_carry(adjusted > 0xFF);
+
+        // Both NMOS and CMOS parts take V from the sum after the ones digit is adjusted but
+        // before the tens digit is, treating the tens digits as signed.
+        let signed = i16::from((a & 0xF0) as u8 as i8) + i16::from((b & 0xF0) as u8 as i8)
+            + ones as i16;
+        cpu.p.set_overflow(!(-128..=127).contains(&signed));
+
+        if !S::DECIMAL_FLAGS_VALID {
+            // NMOS: Z follows the plain binary sum and N the partially adjusted one.
+            cpu.p.set_zero(sum & 0x00FF == 0);
+            cpu.p.set_signed(partial & 0x80 != 0);
+            cpu.a = result;
+            return;
         }
-
-        result = Byte::from((Nibble::from(lo as u8), Nibble::from(hi as u8)));
-        cpu.p.set_carry(carry_out);
     } else {
         cpu.p.set_carry(sum > 0xFF);
     }
 
-    cpu.p.set_zero(result == Byte(0));
-    cpu.p.set_signed(result.is_signed());
+    cpu.p.update_nz(result);
     cpu.a = result;
 }
 
-pub(crate) fn sub_with_borrow<S: InstructionSet, B: Mos6502CompatibleBus + 'static>(
+pub(crate) fn sub_with_borrow<S: InstructionSet, B: Mos6502CompatibleBus>(
     cpu: &mut Cpu<B>,
     value: Byte,
 ) {
@@ -1660,98 +2244,103 @@ pub(crate) fn sub_with_borrow<S: InstructionSet, B: Mos6502CompatibleBus + 'stat
     let decimal = S::SUPPORTS_DECIMAL_MODE && cpu.p.contains(Flags::DecimalMode);
 
     // subtract via addition of the complement
-    let sum = cpu.a.as_u16() + (value.as_u16() ^ 0x00FF) + carry_in;
+    let complement = value ^ Byte(0xFF);
+    let sum = cpu.a.as_u16() + complement.as_u16() + carry_in;
     let mut result = byte!((sum & 0x00FF) as u8);
 
-    cpu.p
-        .set_overflow(((cpu.a ^ result) & ((value ^ Byte(0xFF)) ^ result) & Byte(0x80)) != Byte(0)); // same-sign check adjusted for subtraction form
+    cpu.p.set_overflow(signed_overflow(cpu.a, complement, result));
+    // C is the binary borrow in decimal mode too.
+    cpu.p.set_carry(sum > 0xFF);
 
     if decimal {
-        // Decimal subtraction is performed per digit. Subtract the low nibble,
-        // borrowing from the high nibble when the result would go negative. Then
-        // subtract the high nibble with the propagated borrow and re-pack the result.
-        let borrow_lo = i16::from(carry_in != 1); // carry==1 means no borrow
-        let a_lo = i16::from(cpu.a.lo());
-        let b_lo = i16::from(value.lo());
-
-        let (lo, borrow_hi) = if a_lo >= b_lo + borrow_lo {
-            (a_lo - (b_lo + borrow_lo), 0i16)
-        } else {
-            (a_lo + 10 - (b_lo + borrow_lo), 1i16) // borrow 10 from tens digit
-        };
-
-        let a_hi = i16::from(cpu.a.hi());
-        let b_hi = i16::from(value.hi());
-
-        let (hi, carry_out) = if a_hi >= b_hi + borrow_hi {
-            (a_hi - (b_hi + borrow_hi), true)
+        // Decimal subtraction borrows per digit. The NMOS part adjusts the ones digit and then
+        // the tens (Clark's sequence 3); the 65C02 adjusts the whole binary difference at once
+        // (sequence 4), which only differs for non-BCD operands.
+        let (a, b) = (i16::from(cpu.a.0), i16::from(value.0));
+        let borrow = 1 - carry_in as i16;
+        let ones = (a & 0x0F) - (b & 0x0F) - borrow;
+
+        let adjusted = if S::DECIMAL_FLAGS_VALID {
+            let mut difference = a - b - borrow;
+            if difference < 0 {
+                difference -= 0x60;
+            }
+            if ones < 0 {
+                difference -= 0x06;
+            }
+            difference
         } else {
-            (a_hi + 10 - (b_hi + borrow_hi), false) // final borrow clears carry flag
+            let ones = if ones < 0 { ((ones - 6) & 0x0F) - 0x10 } else { ones };
+            let difference = (a & 0xF0) - (b & 0xF0) + ones;
+            if difference < 0 { difference - 0x60 } else { difference }
         };
-
-        result = Byte::from((Nibble::from(lo as u8), Nibble::from(hi as u8)));
-        cpu.p.set_carry(carry_out);
-    } else {
-        cpu.p.set_carry(sum > 0xFF);
+        let binary = result;
+        result = byte!((adjusted & 0x00FF) as u8);
+
+        if !S::DECIMAL_FLAGS_VALID {
+            // NMOS: N and Z follow the binary difference, like V above.
+            cpu.p.update_nz(binary);
+            cpu.a = result;
+            return;
+        }
     }
 
-    cpu.p.set_zero(result == Byte(0));
-    cpu.p.set_signed(result.is_signed());
+    cpu.p.update_nz(result);
     cpu.a = result;
 }
 
-pub fn dec<AM: AddressingMode, B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, bus: &mut B) {
+pub fn dec<S: InstructionSet, AM: AddressingMode, B: Mos6502CompatibleBus>(
+    cpu: &mut Cpu<B>,
+    bus: &mut B,
+) {
     let addr = AM::fetch_address(cpu, bus);
-    let val: Byte = bus.read(addr, AccessType::DataRead) - 1;
-    bus.write(addr, val, AccessType::DataWrite);
-    cpu.p.set_zero(val == 0);
-    cpu.p.set_signed(val.is_signed());
+    let val: Byte = read_for_modify::<S, _>(cpu, bus, addr) - 1;
+    bus.cpu_write(addr, val, AccessType::DataWrite);
+    cpu.p.update_nz(val);
 
     cpu.pc += AM::BYTES;
 }
 
-pub fn dex<B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, _bus: &mut B) {
+pub fn dex<B: Mos6502CompatibleBus>(cpu: &mut Cpu<B>, _bus: &mut B) {
     cpu.x -= 1;
-    cpu.p.set_zero(cpu.x == 0);
-    cpu.p.set_signed(cpu.x.is_signed());
+    cpu.p.update_nz(cpu.x);
 
-    cpu.pc += 1;
+    cpu.pc += Implied::BYTES;
 }
 
-pub fn dey<B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, _bus: &mut B) {
+pub fn dey<B: Mos6502CompatibleBus>(cpu: &mut Cpu<B>, _bus: &mut B) {
     cpu.y -= 1;
-    cpu.p.set_zero(cpu.y == 0);
-    cpu.p.set_signed(cpu.y.is_signed());
-    cpu.pc += 1;
+    cpu.p.update_nz(cpu.y);
+    cpu.pc += Implied::BYTES;
 }
 
-pub fn inc<AM: AddressingMode, B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, bus: &mut B) {
+pub fn inc<S: InstructionSet, AM: AddressingMode, B: Mos6502CompatibleBus>(
+    cpu: &mut Cpu<B>,
+    bus: &mut B,
+) {
     let addr = AM::fetch_address(cpu, bus);
-    let val: Byte = bus.read(addr, AccessType::DataRead) + 1;
-    bus.write(addr, val, AccessType::DataWrite);
-    cpu.p.set_zero(val == 0);
-    cpu.p.set_signed(val.is_signed());
+    let val: Byte = read_for_modify::<S, _>(cpu, bus, addr) + 1;
+    bus.cpu_write(addr, val, AccessType::DataWrite);
+    cpu.p.update_nz(val);
 
     cpu.pc += AM::BYTES;
 }
 
-pub fn inx<B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, _bus: &mut B) {
+pub fn inx<B: Mos6502CompatibleBus>(cpu: &mut Cpu<B>, _bus: &mut B) {
     cpu.x += 1;
-    cpu.p.set_zero(cpu.x == 0);
-    cpu.p.set_signed(cpu.x.is_signed());
+    cpu.p.update_nz(cpu.x);
 
-    cpu.pc += 1;
+    cpu.pc += Implied::BYTES;
 }
 
-pub fn iny<B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, _bus: &mut B) {
+pub fn iny<B: Mos6502CompatibleBus>(cpu: &mut Cpu<B>, _bus: &mut B) {
     cpu.y += 1;
-    cpu.p.set_zero(cpu.y == 0);
-    cpu.p.set_signed(cpu.y.is_signed());
+    cpu.p.update_nz(cpu.y);
 
-    cpu.pc += 1;
+    cpu.pc += Implied::BYTES;
 }
 
-pub fn brk<B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, bus: &mut B) {
+pub fn brk<B: Mos6502CompatibleBus>(cpu: &mut Cpu<B>, bus: &mut B) {
     let return_pc = word!(cpu.pc + 2);
 
     cpu.push(bus, return_pc.hi());
@@ -1762,16 +2351,24 @@ pub fn brk<B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, bus: &mut B) {
 
     cpu.p.set_interrupt_disabled(true);
 
-    let lo = bus.read(IRQ_VECTOR_LO, AccessType::InterruptVectorRead);
-    let hi = bus.read(IRQ_VECTOR_HI, AccessType::InterruptVectorRead);
+    // An NMI that shows up before the vector fetch hijacks the break: the NMOS part jumps
+    // through the NMI vector but has already pushed P with B set, and the BRK itself is lost.
+    let (vector_lo, vector_hi) = if cpu.take_pending_nmi() {
+        (NMI_VECTOR_LO, NMI_VECTOR_HI)
+    } else {
+        (IRQ_VECTOR_LO, IRQ_VECTOR_HI)
+    };
+
+    let lo = bus.cpu_read(vector_lo, AccessType::InterruptVectorRead);
+    let hi = bus.cpu_read(vector_hi, AccessType::InterruptVectorRead);
     cpu.pc = word!((lo, hi));
 }
 
-pub fn jmp<AM: AddressingMode, B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, bus: &mut B) {
+pub fn jmp<AM: AddressingMode, B: Mos6502CompatibleBus>(cpu: &mut Cpu<B>, bus: &mut B) {
     cpu.pc = AM::fetch_address(cpu, bus);
 }
 
-pub fn jsr<AM: AddressingMode, B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, bus: &mut B) {
+pub fn jsr<AM: AddressingMode, B: Mos6502CompatibleBus>(cpu: &mut Cpu<B>, bus: &mut B) {
     let addr = AM::fetch_address(cpu, bus);
     let return_addr = cpu.pc + (AM::BYTES - 1);
 
@@ -1781,7 +2378,7 @@ pub fn jsr<AM: AddressingMode, B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<
     cpu.pc = addr;
 }
 
-pub fn rti<B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, bus: &mut B) {
+pub fn rti<B: Mos6502CompatibleBus>(cpu: &mut Cpu<B>, bus: &mut B) {
     let mut p = cpu.pop(bus);
     p |= Flags::Expansion; // force E to 1
     p &= !Flags::Break; // clear B
@@ -1792,272 +2389,215 @@ pub fn rti<B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, bus: &mut B) {
     cpu.pc = word!((lo, hi));
 }
 
-pub fn rts<B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, bus: &mut B) {
+pub fn rts<B: Mos6502CompatibleBus>(cpu: &mut Cpu<B>, bus: &mut B) {
     let lo = cpu.pop(bus);
     let hi = cpu.pop(bus);
     cpu.pc = word!((lo, hi)) + 1;
 }
 
-pub fn bcc<B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, bus: &mut B) {
-    let base = cpu.pc + 2;
-
-    if cpu.p.contains(Flags::Carry) {
-        cpu.pc = base;
+/// Shared tail of the conditional branches: fall through, or take the branch for one extra
+/// cycle plus another when the target is on a different page.
+#[inline]
+pub(crate) fn branch<AM: AddressingMode, B: Mos6502CompatibleBus>(
+    cpu: &mut Cpu<B>,
+    bus: &mut B,
+    taken: bool,
+) {
+    if !taken {
+        cpu.pc += AM::BYTES;
         return;
     }
 
-    let offset = i8::from(bus.read(cpu.pc + 1, AccessType::DataRead));
-    let target = base + offset;
-    cpu.cycles += 1;
-    if cpu.crosses_page(base, target) {
-        cpu.cycles += 1;
-    }
+    let (target, penalty) = AM::fetch_address_penalized(cpu, bus);
+    cpu.cycles += 1 + u64::from(penalty);
     cpu.pc = target;
 }
 
-pub fn bcs<B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, bus: &mut B) {
-    let base = cpu.pc + 2;
-
-    if !cpu.p.contains(Flags::Carry) {
-        cpu.pc = base;
-        return;
-    }
-
-    let offset = i8::from(bus.read(cpu.pc + 1, AccessType::DataRead));
-    let target = base + offset;
-    cpu.cycles += 1;
-    if cpu.crosses_page(base, target) {
-        cpu.cycles += 1;
-    }
-    cpu.pc = target;
+pub fn bcc<B: Mos6502CompatibleBus>(cpu: &mut Cpu<B>, bus: &mut B) {
+    let taken = !cpu.p.contains(Flags::Carry);
+    branch::<Relative, B>(cpu, bus, taken);
 }
 
-pub fn beq<B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, bus: &mut B) {
-    let base = cpu.pc + 2;
-
-    if !cpu.p.contains(Flags::Zero) {
-        cpu.pc = base;
-        return;
-    }
-
-    let offset = i8::from(bus.read(cpu.pc + 1, AccessType::DataRead));
-    let target = base + offset;
-    cpu.cycles += 1;
-    if cpu.crosses_page(base, target) {
-        cpu.cycles += 1;
-    }
-    cpu.pc = target;
+pub fn bcs<B: Mos6502CompatibleBus>(cpu: &mut Cpu<B>, bus: &mut B) {
+    let taken = cpu.p.contains(Flags::Carry);
+    branch::<Relative, B>(cpu, bus, taken);
 }
 
-pub fn bmi<B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, bus: &mut B) {
-    let base = cpu.pc + 2;
-
-    if !cpu.p.contains(Flags::Sign) {
-        cpu.pc = base;
-        return;
-    }
-
-    let offset = i8::from(bus.read(cpu.pc + 1, AccessType::DataRead));
-    let target = base + offset;
-    cpu.cycles += 1;
-    if cpu.crosses_page(base, target) {
-        cpu.cycles += 1;
-    }
-    cpu.pc = target;
+pub fn beq<B: Mos6502CompatibleBus>(cpu: &mut Cpu<B>, bus: &mut B) {
+    let taken = cpu.p.contains(Flags::Zero);
+    branch::<Relative, B>(cpu, bus, taken);
 }
 
-pub fn bne<B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, bus: &mut B) {
-    let base = cpu.pc + 2;
-
-    if cpu.p.contains(Flags::Zero) {
-        cpu.pc = base;
-        return;
-    }
-
-    let offset = i8::from(bus.read(cpu.pc + 1, AccessType::DataRead));
-    let target = base + offset;
-    cpu.cycles += 1;
-    if cpu.crosses_page(base, target) {
-        cpu.cycles += 1;
-    }
-    cpu.pc = target;
+pub fn bmi<B: Mos6502CompatibleBus>(cpu: &mut Cpu<B>, bus: &mut B) {
+    let taken = cpu.p.contains(Flags::Sign);
+    branch::<Relative, B>(cpu, bus, taken);
 }
 
-pub fn bpl<B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, bus: &mut B) {
-    let base = cpu.pc + 2;
-
-    if cpu.p.contains(Flags::Sign) {
-        cpu.pc = base;
-        return;
-    }
-
-    let offset = i8::from(bus.read(cpu.pc + 1, AccessType::DataRead));
-    let target = base + offset;
-    cpu.cycles += 1;
-    if cpu.crosses_page(base, target) {
-        cpu.cycles += 1;
-    }
-    cpu.pc = target;
+pub fn bne<B: Mos6502CompatibleBus>(cpu: &mut Cpu<B>, bus: &mut B) {
+    let taken = !cpu.p.contains(Flags::Zero);
+    branch::<Relative, B>(cpu, bus, taken);
 }
 
-pub fn bvc<B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, bus: &mut B) {
-    let base = cpu.pc + 2;
-
-    if cpu.p.contains(Flags::Overflow) {
-        cpu.pc = base;
-        return;
-    }
-
-    let offset = i8::from(bus.read(cpu.pc + 1, AccessType::DataRead));
-    let target = base + offset;
-    cpu.cycles += 1;
-    if cpu.crosses_page(base, target) {
-        cpu.cycles += 1;
-    }
-    cpu.pc = target;
+pub fn bpl<B: Mos6502CompatibleBus>(cpu: &mut Cpu<B>, bus: &mut B) {
+    let taken = !cpu.p.contains(Flags::Sign);
+    branch::<Relative, B>(cpu, bus, taken);
 }
 
-pub fn bvs<B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, bus: &mut B) {
-    let base = cpu.pc + 2;
-
-    if !cpu.p.contains(Flags::Overflow) {
-        cpu.pc = base;
-        return;
-    }
+pub fn bvc<B: Mos6502CompatibleBus>(cpu: &mut Cpu<B>, bus: &mut B) {
+    let taken = !cpu.p.contains(Flags::Overflow);
+    branch::<Relative, B>(cpu, bus, taken);
+}
 
-    let offset = i8::from(bus.read(cpu.pc + 1, AccessType::DataRead));
-    let target = base + offset;
-    cpu.cycles += 1;
-    if cpu.crosses_page(base, target) {
-        cpu.cycles += 1;
-    }
-    cpu.pc = target;
+pub fn bvs<B: Mos6502CompatibleBus>(cpu: &mut Cpu<B>, bus: &mut B) {
+    let taken = cpu.p.contains(Flags::Overflow);
+    branch::<Relative, B>(cpu, bus, taken);
 }
 
-pub fn clc<B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, _bus: &mut B) {
+pub fn clc<B: Mos6502CompatibleBus>(cpu: &mut Cpu<B>, _bus: &mut B) {
     cpu.p.set_carry(false);
-    cpu.pc += 1;
+    cpu.pc += Implied::BYTES;
 }
 
-pub fn cld<B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, _bus: &mut B) {
+pub fn cld<B: Mos6502CompatibleBus>(cpu: &mut Cpu<B>, _bus: &mut B) {
     cpu.p.set_decimal_mode(false);
-    cpu.pc += 1;
+    cpu.pc += Implied::BYTES;
 }
 
-pub fn cli<B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, _bus: &mut B) {
+pub fn cli<B: Mos6502CompatibleBus>(cpu: &mut Cpu<B>, _bus: &mut B) {
     cpu.p.set_interrupt_disabled(false);
-    cpu.pc += 1;
+    cpu.pc += Implied::BYTES;
 }
 
-pub fn clv<B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, _bus: &mut B) {
+pub fn clv<B: Mos6502CompatibleBus>(cpu: &mut Cpu<B>, _bus: &mut B) {
     cpu.p.set_overflow(false);
-    cpu.pc += 1;
+    cpu.pc += Implied::BYTES;
 }
 
-pub fn sec<B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, _bus: &mut B) {
+pub fn sec<B: Mos6502CompatibleBus>(cpu: &mut Cpu<B>, _bus: &mut B) {
     cpu.p.set_carry(true);
-    cpu.pc += 1;
+    cpu.pc += Implied::BYTES;
 }
 
-pub fn sed<B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, _bus: &mut B) {
+pub fn sed<B: Mos6502CompatibleBus>(cpu: &mut Cpu<B>, _bus: &mut B) {
     cpu.p.set_decimal_mode(true);
-    cpu.pc += 1;
+    cpu.pc += Implied::BYTES;
 }
 
-pub fn sei<B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, _bus: &mut B) {
+pub fn sei<B: Mos6502CompatibleBus>(cpu: &mut Cpu<B>, _bus: &mut B) {
     cpu.p.set_interrupt_disabled(true);
-    cpu.pc += 1;
+    cpu.pc += Implied::BYTES;
 }
 
-pub fn nop<B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, _bus: &mut B) {
-    cpu.pc += 1;
+pub fn nop<B: Mos6502CompatibleBus>(cpu: &mut Cpu<B>, _bus: &mut B) {
+    cpu.pc += Implied::BYTES;
 }
 
 // Undocumented instructions
-pub fn las<AM: AddressingMode, B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, bus: &mut B) {
-    let addr = AM::fetch_address(cpu, bus);
-    let val = bus.read(addr, AccessType::DataRead);
+pub fn las<AM: AddressingMode, B: Mos6502CompatibleBus>(cpu: &mut Cpu<B>, bus: &mut B) {
+    let (addr, penalty) = AM::fetch_address_penalized(cpu, bus);
+    cpu.cycles += u64::from(penalty);
+    let val = cpu.read_data(bus, addr);
     let result = val & cpu.sp;
 
     cpu.a = result;
     cpu.x = result;
     cpu.sp = result;
-    cpu.p.set_zero(result == Byte(0));
-    cpu.p.set_signed(result.is_signed());
+    cpu.p.update_nz(result);
     cpu.pc += AM::BYTES;
 }
 
-pub fn lax<AM: AddressingMode, B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, bus: &mut B) {
-    let addr = AM::fetch_address(cpu, bus);
-    let val = bus.read(addr, AccessType::DataRead);
+pub fn lax<AM: AddressingMode, B: Mos6502CompatibleBus>(cpu: &mut Cpu<B>, bus: &mut B) {
+    let (addr, penalty) = AM::fetch_address_penalized(cpu, bus);
+    cpu.cycles += u64::from(penalty);
+    let val = cpu.read_data(bus, addr);
 
     cpu.a = val;
     cpu.x = val;
 
-    cpu.p.set_zero(val == Byte(0));
-    cpu.p.set_signed(val.is_signed());
+    cpu.p.update_nz(val);
     cpu.pc += AM::BYTES;
 }
 
-pub fn sax<AM: AddressingMode, B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, bus: &mut B) {
+/// Unstable NMOS `LAX #imm` (also called `LXA`/`ATX`): `A = X = (A | S::XAA_MAGIC) & imm`.
+///
+/// Shares [`XAA`](xaa)'s magic constant, since both come from the same bus contention on A.
+pub fn lxa<S: InstructionSet, AM: AddressingMode, B: Mos6502CompatibleBus>(
+    cpu: &mut Cpu<B>,
+    bus: &mut B,
+) {
     let addr = AM::fetch_address(cpu, bus);
-    bus.write(addr, cpu.a & cpu.x, AccessType::DataWrite);
+    let val = cpu.read_data(bus, addr);
+    let result = (cpu.a | Byte(S::XAA_MAGIC)) & val;
+
+    cpu.a = result;
+    cpu.x = result;
+    cpu.p.update_nz(result);
 
     cpu.pc += AM::BYTES;
 }
 
-pub fn sha<AM: AddressingMode, B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, bus: &mut B) {
+pub fn sax<AM: AddressingMode, B: Mos6502CompatibleBus>(cpu: &mut Cpu<B>, bus: &mut B) {
     let addr = AM::fetch_address(cpu, bus);
-    let val = addr.hi() + 1;
-
-    bus.write(addr, val & (cpu.x & cpu.a), AccessType::DataWrite);
+    bus.cpu_write(addr, cpu.a & cpu.x, AccessType::DataWrite);
 
     cpu.pc += AM::BYTES;
 }
 
-pub fn shx<AM: AddressingMode, B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, bus: &mut B) {
-    let addr = AM::fetch_address(cpu, bus);
-    let val = addr.hi() + 1;
+/// Store `value & (base_hi + 1)` for the SHA/SHX/SHY/SHS family.
+///
+/// When the index carries into the high byte, the NMOS part drives the ANDed value onto the
+/// address bus as well, so the store lands at `(value << 8) | lo` instead of the indexed address.
+fn unstable_store<AM: AddressingMode, B: Mos6502CompatibleBus>(
+    cpu: &mut Cpu<B>,
+    bus: &mut B,
+    value: Byte,
+) {
+    let (addr, crossed) = AM::fetch_address_penalized(cpu, bus);
+    // A page cross has already added the one the high byte would have been incremented by.
+    let (addr, value) = if crossed != 0 {
+        let value = value & addr.hi();
+        (Word::from((addr.lo(), value)), value)
+    } else {
+        (addr, value & (addr.hi() + 1))
+    };
 
-    bus.write(addr, val & cpu.x, AccessType::DataWrite);
+    bus.cpu_write(addr, value, AccessType::DataWrite);
 
     cpu.pc += AM::BYTES;
 }
 
-pub fn shy<AM: AddressingMode, B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, bus: &mut B) {
-    let addr = AM::fetch_address(cpu, bus);
-    let val = addr.hi() + 1;
-
-    bus.write(addr, val & cpu.y, AccessType::DataWrite);
+pub fn sha<AM: AddressingMode, B: Mos6502CompatibleBus>(cpu: &mut Cpu<B>, bus: &mut B) {
+    unstable_store::<AM, B>(cpu, bus, cpu.x & cpu.a);
+}
 
-    cpu.pc += AM::BYTES;
+pub fn shx<AM: AddressingMode, B: Mos6502CompatibleBus>(cpu: &mut Cpu<B>, bus: &mut B) {
+    unstable_store::<AM, B>(cpu, bus, cpu.x);
 }
 
-pub fn shs<AM: AddressingMode, B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, bus: &mut B) {
-    let addr = AM::fetch_address(cpu, bus);
+pub fn shy<AM: AddressingMode, B: Mos6502CompatibleBus>(cpu: &mut Cpu<B>, bus: &mut B) {
+    unstable_store::<AM, B>(cpu, bus, cpu.y);
+}
 
+pub fn shs<AM: AddressingMode, B: Mos6502CompatibleBus>(cpu: &mut Cpu<B>, bus: &mut B) {
     cpu.sp = cpu.x & cpu.a;
-    bus.write(addr, cpu.sp & (addr.hi() + 1), AccessType::DataWrite);
-
-    cpu.pc += AM::BYTES;
+    unstable_store::<AM, B>(cpu, bus, cpu.sp);
 }
 
-pub fn anc<AM: AddressingMode, B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, bus: &mut B) {
+pub fn anc<AM: AddressingMode, B: Mos6502CompatibleBus>(cpu: &mut Cpu<B>, bus: &mut B) {
     let addr = AM::fetch_address(cpu, bus);
-    let val = bus.read(addr, AccessType::DataRead);
+    let val = cpu.read_data(bus, addr);
     let result = val & cpu.a;
 
     cpu.a = result;
-    cpu.p.set_zero(result == 0);
-    cpu.p.set_signed(result.is_signed());
+    cpu.p.update_nz(result);
     cpu.p.set_carry(result.is_signed());
 
     cpu.pc += AM::BYTES;
 }
 
-pub fn arr<AM: AddressingMode, B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, bus: &mut B) {
+pub fn arr<AM: AddressingMode, B: Mos6502CompatibleBus>(cpu: &mut Cpu<B>, bus: &mut B) {
     let addr = AM::fetch_address(cpu, bus);
-    let val = bus.read(addr, AccessType::DataRead);
+    let val = cpu.read_data(bus, addr);
     let anded = cpu.a & val;
 
     let carry_in = cpu.p.bit(Flags::Carry);
@@ -2084,15 +2624,14 @@ pub fn arr<AM: AddressingMode, B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<
     }
 
     cpu.a = result;
-    cpu.p.set_zero(result == 0);
-    cpu.p.set_signed(result.is_signed());
+    cpu.p.update_nz(result);
 
     cpu.pc += AM::BYTES;
 }
 
-pub fn asr<AM: AddressingMode, B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, bus: &mut B) {
+pub fn asr<AM: AddressingMode, B: Mos6502CompatibleBus>(cpu: &mut Cpu<B>, bus: &mut B) {
     let addr = AM::fetch_address(cpu, bus);
-    let val = bus.read(addr, AccessType::DataRead);
+    let val = cpu.read_data(bus, addr);
     let result = cpu.a & val;
 
     cpu.p.set_carry(result & 0x01 != 0);
@@ -2103,116 +2642,146 @@ pub fn asr<AM: AddressingMode, B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<
     cpu.pc += AM::BYTES;
 }
 
-pub fn dcp<AM: AddressingMode, B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, bus: &mut B) {
+pub fn dcp<S: InstructionSet, AM: AddressingMode, B: Mos6502CompatibleBus>(
+    cpu: &mut Cpu<B>,
+    bus: &mut B,
+) {
     let addr = AM::fetch_address(cpu, bus);
-    let val = bus.read(addr, AccessType::DataRead);
+    let val = read_for_modify::<S, _>(cpu, bus, addr);
     let decremented = val - 1;
-    bus.write(addr, decremented, AccessType::DataWrite);
+    bus.cpu_write(addr, decremented, AccessType::DataWrite);
 
     let result: Byte = cpu.a - decremented;
 
-    cpu.p.set_zero(result == 0);
-    cpu.p.set_signed(result.is_signed());
+    cpu.p.update_nz(result);
     cpu.p.set_carry(cpu.a >= decremented);
     cpu.pc += AM::BYTES;
 }
 
-pub fn isc<S: InstructionSet, AM: AddressingMode, B: Mos6502CompatibleBus + 'static>(
+pub fn isc<S: InstructionSet, AM: AddressingMode, B: Mos6502CompatibleBus>(
     cpu: &mut Cpu<B>,
     bus: &mut B,
 ) {
     let addr = AM::fetch_address(cpu, bus);
-    let value = bus.read(addr, AccessType::DataRead) + 1;
-    bus.write(addr, value, AccessType::DataWrite);
+    let value = read_for_modify::<S, _>(cpu, bus, addr) + 1;
+    bus.cpu_write(addr, value, AccessType::DataWrite);
 
     sub_with_borrow::<S, _>(cpu, value);
 
     cpu.pc += AM::BYTES;
 }
 
-pub fn rla<AM: AddressingMode, B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, bus: &mut B) {
+pub fn rla<S: InstructionSet, AM: AddressingMode, B: Mos6502CompatibleBus>(
+    cpu: &mut Cpu<B>,
+    bus: &mut B,
+) {
     let addr = AM::fetch_address(cpu, bus);
-    let val = bus.read(addr, AccessType::DataRead);
+    let val = read_for_modify::<S, _>(cpu, bus, addr);
     let rotated = rotate_left(cpu, val);
-    bus.write(addr, rotated, AccessType::DataWrite);
+    bus.cpu_write(addr, rotated, AccessType::DataWrite);
 
     cpu.a &= rotated;
-    cpu.p.set_zero(cpu.a == 0);
-    cpu.p.set_signed(cpu.a.is_signed());
+    cpu.p.update_nz(cpu.a);
 
     cpu.pc += AM::BYTES;
 }
-pub fn rra<S: InstructionSet, AM: AddressingMode, B: Mos6502CompatibleBus + 'static>(
+pub fn rra<S: InstructionSet, AM: AddressingMode, B: Mos6502CompatibleBus>(
     cpu: &mut Cpu<B>,
     bus: &mut B,
 ) {
     let addr = AM::fetch_address(cpu, bus);
-    let val = bus.read(addr, AccessType::DataRead);
+    let val = read_for_modify::<S, _>(cpu, bus, addr);
     let rotated = rotate_right(cpu, val);
-    bus.write(addr, rotated, AccessType::DataWrite);
+    bus.cpu_write(addr, rotated, AccessType::DataWrite);
     add_with_carry::<S, _>(cpu, rotated);
 
     cpu.pc += AM::BYTES;
 }
 
-pub fn sbx<S: InstructionSet, AM: AddressingMode, B: Mos6502CompatibleBus + 'static>(
+pub fn sbx<S: InstructionSet, AM: AddressingMode, B: Mos6502CompatibleBus>(
     cpu: &mut Cpu<B>,
     bus: &mut B,
 ) {
     let addr = AM::fetch_address(cpu, bus);
-    let val = bus.read(addr, AccessType::DataRead);
+    let val = cpu.read_data(bus, addr);
     let masked = cpu.a & cpu.x;
     let result = masked - val;
     cpu.x = result;
 
     cpu.p.set_carry(masked >= val);
-    cpu.p.set_zero(cpu.x == 0);
-    cpu.p.set_signed(cpu.x.is_signed());
+    cpu.p.update_nz(cpu.x);
 
     cpu.pc += AM::BYTES;
 }
 
-pub fn slo<AM: AddressingMode, B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, bus: &mut B) {
+pub fn slo<S: InstructionSet, AM: AddressingMode, B: Mos6502CompatibleBus>(
+    cpu: &mut Cpu<B>,
+    bus: &mut B,
+) {
     let addr = AM::fetch_address(cpu, bus);
-    let val = bus.read(addr, AccessType::DataRead);
+    let val = read_for_modify::<S, _>(cpu, bus, addr);
     let result = rotate_left(cpu, val);
-    bus.write(addr, result, AccessType::DataWrite);
+    bus.cpu_write(addr, result, AccessType::DataWrite);
 
     cpu.a |= result;
-    cpu.p.set_zero(cpu.a == 0);
-    cpu.p.set_signed(cpu.a.is_signed());
+    cpu.p.update_nz(cpu.a);
     cpu.pc += AM::BYTES;
 }
 
-pub fn sre<AM: AddressingMode, B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, bus: &mut B) {
+pub fn sre<S: InstructionSet, AM: AddressingMode, B: Mos6502CompatibleBus>(
+    cpu: &mut Cpu<B>,
+    bus: &mut B,
+) {
     let addr = AM::fetch_address(cpu, bus);
-    let val = bus.read(addr, AccessType::DataRead);
+    let val = read_for_modify::<S, _>(cpu, bus, addr);
     let result = rotate_right(cpu, val);
-    bus.write(addr, result, AccessType::DataWrite);
+    bus.cpu_write(addr, result, AccessType::DataWrite);
 
     cpu.a ^= result;
-    cpu.p.set_zero(cpu.a == 0);
-    cpu.p.set_signed(cpu.a.is_signed());
+    cpu.p.update_nz(cpu.a);
     cpu.pc += AM::BYTES;
 }
 
-pub fn xaa<AM: AddressingMode, B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, bus: &mut B) {
-    illegal_a(cpu, bus);
+/// Unstable NMOS `ANE`/`XAA`: `A = (A | S::XAA_MAGIC) & X & imm`.
+pub fn xaa<S: InstructionSet, AM: AddressingMode, B: Mos6502CompatibleBus>(
+    cpu: &mut Cpu<B>,
+    bus: &mut B,
+) {
+    let addr = AM::fetch_address(cpu, bus);
+    let val = cpu.read_data(bus, addr);
+    let result = (cpu.a | Byte(S::XAA_MAGIC)) & cpu.x & val;
+
+    cpu.a = result;
+    cpu.p.update_nz(result);
+
+    cpu.pc += AM::BYTES;
 }
 
-pub fn jam<B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, _bus: &mut B) {
+pub fn jam<B: Mos6502CompatibleBus>(cpu: &mut Cpu<B>, _bus: &mut B) {
     cpu.run_state = RunState::Halted;
 }
 
-pub fn illegal<AM: AddressingMode, B: Mos6502CompatibleBus + 'static>(
+pub fn illegal<AM: AddressingMode, B: Mos6502CompatibleBus>(
     cpu: &mut Cpu<B>,
     _bus: &mut B,
 ) {
     cpu.pc += AM::BYTES;
 }
 
-pub fn illegal_a<B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, _bus: &mut B) {
-    cpu.pc += 1;
+pub fn illegal_a<B: Mos6502CompatibleBus>(cpu: &mut Cpu<B>, _bus: &mut B) {
+    cpu.pc += Implied::BYTES;
+}
+
+/// Undocumented NMOS NOP, handled according to [`S::ON_ILLEGAL`](InstructionSet::ON_ILLEGAL).
+pub fn illegal_nop<S: InstructionSet, AM: AddressingMode, B: Mos6502CompatibleBus>(
+    cpu: &mut Cpu<B>,
+    bus: &mut B,
+) {
+    match S::ON_ILLEGAL {
+        IllegalPolicy::Skip => cpu.pc += AM::BYTES,
+        IllegalPolicy::Trap if cpu.run_illegal_trap(bus) => {}
+        IllegalPolicy::Halt | IllegalPolicy::Trap => cpu.run_state = RunState::Halted,
+    }
 }
 
 #[cfg(test)]
@@ -2223,9 +2792,52 @@ mod tests {
         ZeroPageX, ZeroPageY,
     };
     use crate::SimpleBus;
+    use crate::instruction::wdc65c02s::Wdc65c02s;
     use ull::{Bus, Word};
     type TestBus = SimpleBus;
 
+    fn rmw_data_accesses<S: InstructionSet + 'static>() -> alloc::vec::Vec<(AccessType, u8)> {
+        use crate::TestingBus;
+
+        // INC $0300
+        let mut bus = TestingBus::default();
+        bus.write(Word(0x0300), Byte(0x41), AccessType::DataWrite);
+        let mut cpu: Cpu<TestingBus> =
+            Cpu::with_program::<S>(&mut bus, Word(0x8000), &[0xEE, 0x00, 0x03], Word(0x8000));
+        bus.enable_logging();
+        cpu.step(&mut bus);
+
+        bus.take_log()
+            .into_iter()
+            .filter(|&(addr, ..)| addr == Word(0x0300))
+            .map(|(_, access, value, _)| (access, value.0))
+            .collect()
+    }
+
+    #[test]
+    fn test_rmw_writes_back_unmodified_value_on_nmos() {
+        assert_eq!(
+            rmw_data_accesses::<Mos6502>(),
+            [
+                (AccessType::DataRead, 0x41),
+                (AccessType::DummyWrite, 0x41),
+                (AccessType::DataWrite, 0x42),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_rmw_rereads_operand_on_cmos() {
+        assert_eq!(
+            rmw_data_accesses::<Wdc65c02s>(),
+            [
+                (AccessType::DataRead, 0x41),
+                (AccessType::DummyRead, 0x41),
+                (AccessType::DataWrite, 0x42),
+            ]
+        );
+    }
+
     #[test]
     fn test_lda_immediate() {
         let mut bus = TestBus::default();
@@ -2866,6 +3478,22 @@ mod tests {
         assert_eq!(cpu.pc, initial_pc + 1);
     }
 
+    #[test]
+    fn test_accumulator_and_implied_modes_are_one_byte() {
+        assert_eq!(Accumulator::BYTES, 1);
+        assert_eq!(Implied::BYTES, 1);
+
+        let mut bus = TestBus::default();
+        let mut cpu = Cpu::<TestBus>::default();
+        cpu.pc = Word(0x80FF);
+
+        asl_a(&mut cpu, &mut bus);
+        assert_eq!(cpu.pc, Word(0x8100));
+
+        tax(&mut cpu, &mut bus);
+        assert_eq!(cpu.pc, Word(0x8101));
+    }
+
     #[test]
     fn test_lsr_a() {
         let mut bus = TestBus::default();
@@ -2984,6 +3612,25 @@ mod tests {
         assert_eq!(cpu.pc, initial_pc + 1);
     }
 
+    #[test]
+    fn test_rol_and_ror_byte_match_the_accumulator_forms() {
+        assert_eq!(rol_byte(Byte(0b0101_0010), true), (Byte(0b1010_0101), false));
+        assert_eq!(rol_byte(Byte(0b1101_0010), false), (Byte(0b1010_0100), true));
+        assert_eq!(ror_byte(Byte(0b0101_0010), true), (Byte(0b1010_1001), false));
+        assert_eq!(ror_byte(Byte(0b0101_0011), false), (Byte(0b0010_1001), true));
+        assert_eq!(rol_byte(Byte(0x80), false), (Byte(0x00), true));
+        assert_eq!(ror_byte(Byte(0x01), false), (Byte(0x00), true));
+
+        for (value, carry) in [(0x00, false), (0x80, true), (0x5A, true), (0xFF, false)] {
+            let mut cpu = Cpu::<TestBus>::default();
+            let mut bus = TestBus::default();
+            cpu.a = Byte(value);
+            cpu.p.set_carry(carry);
+            rol_a(&mut cpu, &mut bus);
+            assert_eq!((cpu.a, cpu.p.contains(Flags::Carry)), rol_byte(Byte(value), carry));
+        }
+    }
+
     #[test]
     fn test_asl_zero_page() {
         let mut bus = TestBus::default();
@@ -2996,7 +3643,7 @@ mod tests {
         bus.write(cpu.pc + 1, ADDRESS, AccessType::DataWrite);
         bus.write(ADDRESS, INPUT, AccessType::DataWrite);
 
-        asl::<ZeroPage, _>(&mut cpu, &mut bus);
+        asl::<Mos6502, ZeroPage, _>(&mut cpu, &mut bus);
 
         assert_eq!(bus.read(ADDRESS, AccessType::DataRead), EXPECTED);
         assert!(!cpu.p.contains(Flags::Carry));
@@ -3017,7 +3664,7 @@ mod tests {
         bus.write(cpu.pc + 2, hi, AccessType::DataWrite);
         bus.write(TARGET_ADDRESS, INPUT, AccessType::DataWrite);
 
-        lsr::<Absolute, _>(&mut cpu, &mut bus);
+        lsr::<Mos6502, Absolute, _>(&mut cpu, &mut bus);
 
         assert_eq!(bus.read(TARGET_ADDRESS, AccessType::DataRead), EXPECTED);
         assert!(!cpu.p.contains(Flags::Carry));
@@ -3041,7 +3688,7 @@ mod tests {
         bus.write(cpu.pc + 1, BASE_ADDRESS, AccessType::DataWrite);
         bus.write(target_address, INPUT, AccessType::DataWrite);
 
-        rol::<ZeroPageX, _>(&mut cpu, &mut bus);
+        rol::<Mos6502, ZeroPageX, _>(&mut cpu, &mut bus);
 
         assert_eq!(bus.read(target_address, AccessType::DataRead), EXPECTED);
         assert!(!cpu.p.contains(Flags::Carry));
@@ -3067,7 +3714,7 @@ mod tests {
         bus.write(cpu.pc + 2, hi, AccessType::DataWrite);
         bus.write(TARGET_ADDRESS, INPUT, AccessType::DataWrite);
 
-        ror::<AbsoluteX, _>(&mut cpu, &mut bus);
+        ror::<Mos6502, AbsoluteX, _>(&mut cpu, &mut bus);
 
         assert_eq!(bus.read(TARGET_ADDRESS, AccessType::DataRead), EXPECTED);
         assert!(!cpu.p.contains(Flags::Carry));
@@ -3178,6 +3825,197 @@ mod tests {
         assert_eq!(cpu.pc, Immediate::BYTES.into());
     }
 
+    fn decimal_adc<S: InstructionSet>(a: u8, operand: u8) -> Cpu<TestBus> {
+        let mut bus = TestBus::default();
+        let mut cpu = Cpu::<TestBus>::default();
+
+        cpu.a = Byte(a);
+        cpu.p.set_decimal_mode(true);
+        bus.write(cpu.pc + 1, Byte(operand), AccessType::DataWrite);
+
+        adc::<S, Immediate, _>(&mut cpu, &mut bus);
+        cpu
+    }
+
+    type DecimalAdcCase = (u8, bool, u8, u8, bool, Option<bool>);
+
+    /// Decimal-mode `ADC` results measured on hardware, from the examples in Bruce Clark's
+    /// "Decimal Mode" tutorial (6502.org): A, carry in, operand, then A, C and V afterwards, with
+    /// V left out where the example doesn't give it. NMOS and CMOS parts agree on all three.
+    /// Valid BCD in general is covered by Klaus Dormann's functional test in
+    /// `tests/functional.rs`.
+    const DECIMAL_ADC_CASES: &[DecimalAdcCase] = &[
+        (0x12, false, 0x34, 0x46, false, None),
+        (0x15, false, 0x26, 0x41, false, None),
+        (0x81, false, 0x92, 0x73, true, None),
+        (0x58, true, 0x46, 0x05, true, None),
+        (0x24, false, 0x56, 0x80, false, Some(true)),
+        (0x93, false, 0x82, 0x75, true, Some(true)),
+        (0x89, false, 0x76, 0x65, true, Some(false)),
+        (0x80, false, 0xF0, 0xD0, true, Some(true)),
+        (0x80, false, 0xFA, 0xE0, true, Some(false)),
+        (0x2F, false, 0x4F, 0x74, false, Some(false)),
+        (0x6F, true, 0x00, 0x76, false, Some(false)),
+    ];
+
+    /// Decimal-mode `SBC` results from the same tutorial: A, carry in, operand, then A and C.
+    const DECIMAL_SBC_CASES: &[(u8, bool, u8, u8, bool)] = &[
+        (0x46, true, 0x12, 0x34, true),
+        (0x40, true, 0x13, 0x27, true),
+        (0x32, false, 0x02, 0x29, true),
+        (0x12, true, 0x21, 0x91, false),
+        (0x21, true, 0x34, 0x87, false),
+    ];
+
+    fn decimal_run<S: InstructionSet>(
+        subtract: bool,
+        a: u8,
+        carry: bool,
+        operand: u8,
+    ) -> Cpu<TestBus> {
+        let mut bus = TestBus::default();
+        let mut cpu = Cpu::<TestBus>::default();
+        cpu.a = Byte(a);
+        cpu.p = Flags::DecimalMode;
+        cpu.p.set_carry(carry);
+        bus.write(cpu.pc + 1, Byte(operand), AccessType::DataWrite);
+
+        if subtract {
+            sbc::<S, Immediate, _>(&mut cpu, &mut bus);
+        } else {
+            adc::<S, Immediate, _>(&mut cpu, &mut bus);
+        }
+        cpu
+    }
+
+    fn check_decimal_against_hardware<S: InstructionSet>() {
+        for &(a, carry, operand, result, carry_out, overflow) in DECIMAL_ADC_CASES {
+            let cpu = decimal_run::<S>(false, a, carry, operand);
+            let v = overflow.map(|_| cpu.p.contains(Flags::Overflow));
+            assert_eq!(
+                (cpu.a.0, cpu.p.contains(Flags::Carry), v),
+                (result, carry_out, overflow),
+                "${a:02X} ADC #${operand:02X}, C={carry}"
+            );
+        }
+        for &(a, carry, operand, result, carry_out) in DECIMAL_SBC_CASES {
+            let cpu = decimal_run::<S>(true, a, carry, operand);
+            assert_eq!(
+                (cpu.a.0, cpu.p.contains(Flags::Carry)),
+                (result, carry_out),
+                "${a:02X} SBC #${operand:02X}, C={carry}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_decimal_arithmetic_matches_hardware_on_nmos() {
+        check_decimal_against_hardware::<Mos6502>();
+    }
+
+    #[test]
+    fn test_signed_overflow_classic_cases() {
+        // $7F + $01 = $80: two positives make a negative.
+        assert!(signed_overflow(Byte(0x7F), Byte(0x01), Byte(0x80)));
+        // $80 - $01 = $7F, added as $80 + $FE + C: two negatives make a positive.
+        assert!(signed_overflow(Byte(0x80), Byte(0x01) ^ Byte(0xFF), Byte(0x7F)));
+        // Mixed signs can't overflow, and neither can in-range results.
+        assert!(!signed_overflow(Byte(0x7F), Byte(0x80), Byte(0xFF)));
+        assert!(!signed_overflow(Byte(0x01), Byte(0x01), Byte(0x02)));
+        assert!(!signed_overflow(Byte(0xFF), Byte(0xFF), Byte(0xFE)));
+    }
+
+    #[test]
+    fn test_adc_and_sbc_set_overflow_through_the_shared_check() {
+        let mut cpu = Cpu::<TestBus>::default();
+        let mut bus = TestBus::default();
+        cpu.a = Byte(0x7F);
+        bus.write(cpu.pc + 1, Byte(0x01), AccessType::DataWrite);
+        adc::<Mos6502, Immediate, _>(&mut cpu, &mut bus);
+        assert_eq!(cpu.a, Byte(0x80));
+        assert!(cpu.p.contains(Flags::Overflow));
+
+        let mut cpu = Cpu::<TestBus>::default();
+        cpu.a = Byte(0x80);
+        cpu.p.insert(Flags::Carry);
+        sbc::<Mos6502, Immediate, _>(&mut cpu, &mut bus);
+        assert_eq!(cpu.a, Byte(0x7F));
+        assert!(cpu.p.contains(Flags::Overflow));
+    }
+
+    #[test]
+    fn test_decimal_arithmetic_matches_hardware_on_cmos() {
+        check_decimal_against_hardware::<Wdc65c02s>();
+    }
+
+    #[test]
+    fn test_decimal_arithmetic_accepts_non_bcd_operands() {
+        // Digits above 9 used to trip the nibble conversion. $0F + $0F: the ones digit sum $1E
+        // is adjusted to 4 with a carry into the tens.
+        let cpu = decimal_adc::<Mos6502>(0x0F, 0x0F);
+        assert_eq!(cpu.a, Byte(0x14));
+        assert!(!cpu.p.contains(Flags::Carry));
+
+        // $FF + $FF + C: ones $1F becomes $15, and $1F5 gets the tens adjust too.
+        let mut cpu = Cpu::<TestBus>::default();
+        let mut bus = TestBus::default();
+        cpu.a = Byte(0xFF);
+        cpu.p.insert(Flags::DecimalMode | Flags::Carry);
+        bus.write(cpu.pc + 1, Byte(0xFF), AccessType::DataWrite);
+        adc::<Mos6502, Immediate, _>(&mut cpu, &mut bus);
+        assert_eq!(cpu.a, Byte(0x55));
+        assert!(cpu.p.contains(Flags::Carry));
+    }
+
+    #[test]
+    fn test_decimal_adc_flags_differ_between_nmos_and_cmos() {
+        // $99 + $01 = $00 with carry. The binary sum is $9A and the partially adjusted one $A0.
+        let nmos = decimal_adc::<Mos6502>(0x99, 0x01);
+        let cmos = decimal_adc::<Wdc65c02s>(0x99, 0x01);
+
+        for cpu in [&nmos, &cmos] {
+            assert_eq!(cpu.a, Byte(0x00));
+            assert!(cpu.p.contains(Flags::Carry));
+            assert!(!cpu.p.contains(Flags::Overflow));
+        }
+        assert!(!nmos.p.contains(Flags::Zero));
+        assert!(nmos.p.contains(Flags::Sign));
+        assert!(cmos.p.contains(Flags::Zero));
+        assert!(!cmos.p.contains(Flags::Sign));
+    }
+
+    #[test]
+    fn test_decimal_adc_overflow_uses_partial_sum() {
+        // $79 + $01: the ones digit carries into $80, so V is set on both parts.
+        for cpu in [decimal_adc::<Mos6502>(0x79, 0x01), decimal_adc::<Wdc65c02s>(0x79, 0x01)] {
+            assert_eq!(cpu.a, Byte(0x80));
+            assert!(cpu.p.contains(Flags::Overflow));
+            assert!(cpu.p.contains(Flags::Sign));
+        }
+    }
+
+    #[test]
+    fn test_decimal_sbc_flags_differ_between_nmos_and_cmos() {
+        fn decimal_sbc<S: InstructionSet>() -> Cpu<TestBus> {
+            let mut bus = TestBus::default();
+            let mut cpu = Cpu::<TestBus>::default();
+
+            // $00 - $21 = $79 with borrow; the binary difference is $DF.
+            cpu.p.insert(Flags::Carry | Flags::DecimalMode);
+            bus.write(cpu.pc + 1, Byte(0x21), AccessType::DataWrite);
+            sbc::<S, Immediate, _>(&mut cpu, &mut bus);
+            cpu
+        }
+
+        let nmos = decimal_sbc::<Mos6502>();
+        let cmos = decimal_sbc::<Wdc65c02s>();
+
+        assert_eq!((nmos.a, cmos.a), (Byte(0x79), Byte(0x79)));
+        assert!(!nmos.p.contains(Flags::Carry) && !cmos.p.contains(Flags::Carry));
+        assert!(nmos.p.contains(Flags::Sign));
+        assert!(!cmos.p.contains(Flags::Sign));
+    }
+
     #[test]
     fn test_inc_zero_page() {
         let mut bus = TestBus::default();
@@ -3190,7 +4028,7 @@ mod tests {
         bus.write(cpu.pc + 1, ADDRESS, AccessType::DataWrite);
         bus.write(Word(0x0030), INITIAL_VALUE, AccessType::DataWrite);
 
-        inc::<ZeroPage, _>(&mut cpu, &mut bus);
+        inc::<Mos6502, ZeroPage, _>(&mut cpu, &mut bus);
 
         assert_eq!(bus.read(Word(0x0030), AccessType::DataRead), EXPECTED_VALUE);
         assert_eq!(cpu.pc, ZeroPage::BYTES.into());
@@ -3208,7 +4046,7 @@ mod tests {
         bus.write(cpu.pc + 1, ADDRESS, AccessType::DataWrite);
         bus.write(Word(0x0031), INITIAL_VALUE, AccessType::DataWrite);
 
-        dec::<ZeroPage, _>(&mut cpu, &mut bus);
+        dec::<Mos6502, ZeroPage, _>(&mut cpu, &mut bus);
 
         assert_eq!(bus.read(Word(0x0031), AccessType::DataRead), EXPECTED_VALUE);
         assert_eq!(cpu.pc, ZeroPage::BYTES.into());
@@ -3458,6 +4296,67 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_branch_offset_at_page_edge() {
+        // (branch at, offset, target, extra cycles); the base is the byte after the operand.
+        let cases = [
+            (Word(0x80FD), 0x00, Word(0x80FF), 1),
+            (Word(0x80FD), 0x01, Word(0x8100), 2),
+            (Word(0x8100), 0xFD, Word(0x80FF), 2),
+            (Word(0x8100), 0xFE, Word(0x8100), 1),
+            (Word(0x807E), 0x7F, Word(0x80FF), 1),
+            (Word(0x80FE), 0x80, Word(0x8080), 2),
+        ];
+
+        for (pc, offset, target, extra) in cases {
+            let mut bus = TestBus::default();
+            let mut cpu = Cpu::<TestBus>::default();
+            cpu.pc = pc;
+            bus.write(pc + 1u16, Byte(offset), AccessType::DataWrite);
+            cpu.p.insert(Flags::Zero);
+
+            beq(&mut cpu, &mut bus);
+
+            assert_eq!(cpu.pc, target, "offset {offset:#04X} from {pc:?}");
+            assert_eq!(cpu.cycles, extra, "offset {offset:#04X} from {pc:?}");
+        }
+    }
+
+    #[test]
+    fn test_branch_cycle_totals() {
+        // (opcode, flag, branches when the flag is set)
+        let branches = [
+            (0x10, Flags::Sign, false),
+            (0x30, Flags::Sign, true),
+            (0x50, Flags::Overflow, false),
+            (0x70, Flags::Overflow, true),
+            (0x90, Flags::Carry, false),
+            (0xB0, Flags::Carry, true),
+            (0xD0, Flags::Zero, false),
+            (0xF0, Flags::Zero, true),
+        ];
+        // (branch at, offset, taken?, expected cycles, expected pc)
+        let cases = [
+            (Word(0x8000), 0x10, false, 2, Word(0x8002)),
+            (Word(0x8000), 0x10, true, 3, Word(0x8012)),
+            (Word(0x80F0), 0x10, true, 4, Word(0x8102)),
+            (Word(0x8000), 0xF0, true, 4, Word(0x7FF2)),
+            (Word(0x80F0), 0x10, false, 2, Word(0x80F2)),
+        ];
+
+        for (opcode, flag, when_set) in branches {
+            for (pc, offset, taken, cycles, target) in cases {
+                let mut bus = TestBus::default();
+                let mut cpu: Cpu<TestBus> =
+                    Cpu::with_program::<Mos6502>(&mut bus, pc, &[opcode, offset], pc);
+                cpu.p.set(flag, taken == when_set);
+
+                assert_eq!(cpu.step(&mut bus), cycles, "{opcode:#04X} at {pc:?}, taken: {taken}");
+                assert_eq!(cpu.pc, target, "{opcode:#04X} at {pc:?}, taken: {taken}");
+            }
+        }
+    }
+
     #[test]
     fn test_jmp_absolute() {
         let mut bus = TestBus::default();
@@ -3918,4 +4817,392 @@ mod tests {
         cpu.p.set_signed(cpu.a.is_signed());
         assert!(!cpu.p.contains(Flags::Sign));
     }
+
+    fn run_sbx_immediate(a: u8, x: u8, operand: u8, flags: Flags) -> Cpu<TestBus> {
+        let mut bus = TestBus::default();
+        let mut cpu = Cpu::<TestBus>::default();
+        cpu.pc = Word(0x0200);
+        cpu.a = Byte(a);
+        cpu.x = Byte(x);
+        cpu.p = flags;
+        bus.write(Word(0x0201), Byte(operand), AccessType::DataWrite);
+
+        sbx::<Mos6502, Immediate, _>(&mut cpu, &mut bus);
+
+        assert_eq!(cpu.a, Byte(a), "SBX must leave A untouched");
+        assert_eq!(cpu.pc, Word(0x0202));
+        cpu
+    }
+
+    #[test]
+    fn test_sbx_subtracts_from_a_and_x_without_borrow_in() {
+        // Carry clear going in must not act as a borrow: ($F0 & $3F) - $10 = $20.
+        let cpu = run_sbx_immediate(0xF0, 0x3F, 0x10, Flags::Expansion);
+
+        assert_eq!(cpu.x, Byte(0x20));
+        assert!(cpu.p.contains(Flags::Carry));
+        assert!(!cpu.p.contains(Flags::Zero));
+        assert!(!cpu.p.contains(Flags::Sign));
+    }
+
+    #[test]
+    fn test_sbx_equal_operands_set_zero_and_carry() {
+        let cpu = run_sbx_immediate(0xFF, 0x30, 0x30, Flags::Expansion);
+
+        assert_eq!(cpu.x, Byte(0x00));
+        assert!(cpu.p.contains(Flags::Carry));
+        assert!(cpu.p.contains(Flags::Zero));
+        assert!(!cpu.p.contains(Flags::Sign));
+    }
+
+    #[test]
+    fn test_sbx_borrow_clears_carry_and_sets_sign() {
+        let cpu = run_sbx_immediate(0x1F, 0xF0, 0x20, Flags::Expansion | Flags::Carry);
+
+        assert_eq!(cpu.x, Byte(0xF0));
+        assert!(!cpu.p.contains(Flags::Carry));
+        assert!(!cpu.p.contains(Flags::Zero));
+        assert!(cpu.p.contains(Flags::Sign));
+    }
+
+    #[test]
+    fn test_sbx_leaves_overflow_untouched() {
+        // $80 - $01 overflows as a signed subtract, but SBX behaves like CMP and ignores V.
+        let cpu = run_sbx_immediate(0x80, 0xFF, 0x01, Flags::Expansion);
+        assert_eq!(cpu.x, Byte(0x7F));
+        assert!(!cpu.p.contains(Flags::Overflow));
+
+        let cpu = run_sbx_immediate(0x00, 0x00, 0x00, Flags::Expansion | Flags::Overflow);
+        assert!(cpu.p.contains(Flags::Overflow));
+    }
+
+    #[test]
+    fn test_sbx_ignores_decimal_mode() {
+        // A BCD subtract would give $19; SBX is always binary.
+        let cpu = run_sbx_immediate(0x20, 0xFF, 0x01, Flags::Expansion | Flags::DecimalMode);
+
+        assert_eq!(cpu.x, Byte(0x1F));
+        assert!(cpu.p.contains(Flags::Carry));
+        assert!(cpu.p.contains(Flags::DecimalMode));
+    }
+
+    #[test]
+    fn test_sbx_dispatches_from_opcode_cb() {
+        let mut bus = TestBus::default();
+        let mut cpu = Cpu::<TestBus>::default();
+        cpu.pc = Word(0x0200);
+        cpu.a = Byte(0x0F);
+        cpu.x = Byte(0x07);
+        bus.write_block(Word(0x0200), &[0xCB, 0x02], AccessType::DataWrite);
+
+        let cycles = cpu.step(&mut bus);
+
+        assert_eq!(cycles, 2);
+        assert_eq!(cpu.x, Byte(0x05));
+        assert!(cpu.p.contains(Flags::Carry));
+        assert_eq!(cpu.pc, Word(0x0202));
+    }
+
+    fn run_shx_absolute_y(base: u16, x: u8, y: u8) -> (Cpu<TestBus>, TestBus) {
+        let mut bus = TestBus::default();
+        let mut cpu = Cpu::<TestBus>::default();
+        cpu.pc = Word(0x0200);
+        cpu.x = Byte(x);
+        cpu.y = Byte(y);
+        let (lo, hi) = Word(base).lo_hi();
+        bus.write_block(Word(0x0200), &[0x9E, lo.0, hi.0], AccessType::DataWrite);
+
+        assert_eq!(cpu.step(&mut bus), 5);
+        assert_eq!(cpu.pc, Word(0x0203));
+        (cpu, bus)
+    }
+
+    #[test]
+    fn test_shx_without_page_cross_ands_with_high_byte_plus_one() {
+        // SHX $7E10,Y: X & ($7E + 1) is stored at the indexed address.
+        let (_, mut bus) = run_shx_absolute_y(0x7E10, 0xFF, 0x20);
+        assert_eq!(bus.read(Word(0x7E30), AccessType::DataRead), Byte(0x7F));
+
+        let (_, mut bus) = run_shx_absolute_y(0xFE10, 0xF3, 0x20);
+        assert_eq!(bus.read(Word(0xFE30), AccessType::DataRead), Byte(0xF3));
+    }
+
+    #[test]
+    fn test_shx_page_cross_replaces_high_byte_with_value() {
+        // SHX $FEF0,Y with Y=$20 would hit $FF10; instead X & $FF becomes the high byte.
+        let (_, mut bus) = run_shx_absolute_y(0xFEF0, 0x12, 0x20);
+
+        assert_eq!(bus.read(Word(0x1210), AccessType::DataRead), Byte(0x12));
+        assert_eq!(bus.read(Word(0xFF10), AccessType::DataRead), Byte(0x00));
+
+        // $7EF0,Y crosses into $7F: X=$FF is ANDed down to $7F, which is also the high byte.
+        let (_, mut bus) = run_shx_absolute_y(0x7EF0, 0xFF, 0x20);
+        assert_eq!(bus.read(Word(0x7F10), AccessType::DataRead), Byte(0x7F));
+    }
+
+    #[test]
+    fn test_xaa_uses_default_magic_constant() {
+        let mut bus = TestBus::default();
+        let mut cpu = Cpu::<TestBus>::default();
+        cpu.pc = Word(0x0200);
+        cpu.a = Byte(0x01);
+        cpu.x = Byte(0xFF);
+        bus.write_block(Word(0x0200), &[0x8B, 0xF3], AccessType::DataWrite);
+
+        assert_eq!(cpu.step(&mut bus), 2);
+
+        // ($01 | $EE) & $FF & $F3 = $E3
+        assert_eq!(cpu.a, Byte(0xE3));
+        assert!(cpu.p.contains(Flags::Sign));
+        assert!(!cpu.p.contains(Flags::Zero));
+        assert_eq!(cpu.pc, Word(0x0202));
+
+        cpu.pc = Word(0x0200);
+        cpu.x = Byte(0x10);
+        cpu.step(&mut bus);
+        assert_eq!(cpu.a, Byte(0x00));
+        assert!(cpu.p.contains(Flags::Zero));
+    }
+
+    #[test]
+    fn test_lax_immediate_uses_default_magic_constant() {
+        // (A, X, imm) -> A = X = (A | $EE) & imm; X going in doesn't matter.
+        let cases = [
+            (0x00, 0x00, 0xFF, 0xEE),
+            (0x11, 0x42, 0xFF, 0xFF),
+            (0x01, 0xFF, 0x0F, 0x0F),
+            (0x00, 0x12, 0x11, 0x00),
+        ];
+
+        for (a, x, imm, expected) in cases {
+            let mut bus = TestBus::default();
+            let mut cpu = Cpu::<TestBus>::default();
+            cpu.pc = Word(0x0200);
+            cpu.a = Byte(a);
+            cpu.x = Byte(x);
+            bus.write_block(Word(0x0200), &[0xAB, imm], AccessType::DataWrite);
+
+            assert_eq!(cpu.step(&mut bus), 2);
+
+            assert_eq!((cpu.a, cpu.x), (Byte(expected), Byte(expected)), "A={a:02X} imm={imm:02X}");
+            assert_eq!(cpu.p.contains(Flags::Zero), expected == 0);
+            assert_eq!(cpu.p.contains(Flags::Sign), expected & 0x80 != 0);
+            assert_eq!(cpu.pc, Word(0x0202));
+        }
+    }
+
+    fn run_illegal_nop<S: InstructionSet>(cpu: &mut Cpu<TestBus>, bus: &mut TestBus) -> u8 {
+        // NOP $10,X (undocumented $34)
+        *cpu = Cpu::with_program::<S>(bus, Word(0x0200), &[0x34, 0x10], Word(0x0200));
+        cpu.step(bus)
+    }
+
+    #[test]
+    fn test_illegal_policy_skip_and_halt() {
+        struct Strict;
+        impl InstructionSet for Strict {
+            fn instruction_table<B: Mos6502CompatibleBus>() -> InstructionTable<B> {
+                Mos6502::base_table_for::<Self, B>()
+            }
+            const ON_ILLEGAL: IllegalPolicy = IllegalPolicy::Halt;
+        }
+
+        let mut bus = TestBus::default();
+        let mut cpu = Cpu::<TestBus>::default();
+
+        assert_eq!(run_illegal_nop::<Mos6502>(&mut cpu, &mut bus), 4);
+        assert_eq!(cpu.pc, Word(0x0202));
+        assert_eq!(cpu.run_state, RunState::Running);
+
+        run_illegal_nop::<Strict>(&mut cpu, &mut bus);
+        assert_eq!(cpu.pc, Word(0x0200));
+        assert_eq!(cpu.run_state, RunState::Halted);
+        assert_eq!(cpu.last_opcode, Byte(0x34));
+    }
+
+    #[test]
+    fn test_illegal_policy_trap_calls_the_handler_or_halts() {
+        struct Trapping;
+        impl InstructionSet for Trapping {
+            fn instruction_table<B: Mos6502CompatibleBus>() -> InstructionTable<B> {
+                Mos6502::base_table_for::<Self, B>()
+            }
+            const ON_ILLEGAL: IllegalPolicy = IllegalPolicy::Trap;
+        }
+
+        let mut bus = TestBus::default();
+        let mut cpu = Cpu::<TestBus>::default();
+
+        run_illegal_nop::<Trapping>(&mut cpu, &mut bus);
+        assert_eq!(cpu.run_state, RunState::Halted);
+
+        cpu = Cpu::with_program::<Trapping>(&mut bus, Word(0x0200), &[0x34, 0x10], Word(0x0200));
+        cpu.set_illegal_trap(|cpu, _bus| {
+            cpu.a = cpu.last_opcode;
+            cpu.pc += 2;
+        });
+        cpu.step(&mut bus);
+        assert_eq!(cpu.a, Byte(0x34));
+        assert_eq!(cpu.pc, Word(0x0202));
+        assert_eq!(cpu.run_state, RunState::Running);
+    }
+
+    #[test]
+    fn test_operand_fetch_wraps_from_fffe_to_zero() {
+        let mut bus = TestBus::default();
+        let mut cpu = Cpu::<TestBus>::default();
+        cpu.pc = Word(0xFFFE);
+
+        // LDA $1234 with the high operand byte wrapped around to $0000.
+        bus.write_block(Word(0xFFFE), &[0xAD, 0x34], AccessType::DataWrite);
+        bus.write(Word(0x0000), Byte(0x12), AccessType::DataWrite);
+        bus.write(Word(0x1234), Byte(0x99), AccessType::DataWrite);
+
+        cpu.step(&mut bus);
+
+        assert_eq!(cpu.a, Byte(0x99));
+        assert_eq!(cpu.pc, Word(0x0001));
+    }
+
+    #[test]
+    fn test_opcode_at_ffff_reads_operands_from_zero_page() {
+        let mut bus = TestBus::default();
+        let mut cpu = Cpu::<TestBus>::default();
+        cpu.pc = Word(0xFFFF);
+
+        bus.write(Word(0xFFFF), Byte(0xAD), AccessType::DataWrite); // LDA abs
+        bus.write_block(Word(0x0000), &[0x78, 0x56], AccessType::DataWrite);
+        bus.write(Word(0x5678), Byte(0x42), AccessType::DataWrite);
+
+        assert_eq!(Absolute::fetch_address(&cpu, &mut bus), Word(0x5678));
+
+        cpu.step(&mut bus);
+
+        assert_eq!(cpu.a, Byte(0x42));
+        assert_eq!(cpu.pc, Word(0x0002));
+    }
+
+    #[test]
+    fn test_single_byte_instruction_at_ffff_wraps_pc() {
+        let mut bus = TestBus::default();
+        let mut cpu = Cpu::<TestBus>::default();
+        cpu.pc = Word(0xFFFF);
+        bus.write(Word(0xFFFF), Byte(0xE8), AccessType::DataWrite); // INX
+        bus.write_block(Word(0x0000), &[0xA9, 0x07], AccessType::DataWrite); // LDA #$07
+
+        cpu.step(&mut bus);
+        assert_eq!(cpu.pc, Word(0x0000));
+        assert_eq!(cpu.x, Byte(0x01));
+
+        cpu.step(&mut bus);
+        assert_eq!(cpu.a, Byte(0x07));
+        assert_eq!(cpu.pc, Word(0x0002));
+    }
+
+    fn step_cycles(program: &[u8], x: u8, y: u8, setup: impl FnOnce(&mut TestBus)) -> u8 {
+        let mut bus = TestBus::default();
+        let mut cpu = Cpu::<TestBus>::default();
+        cpu.pc = Word(0x0200);
+        cpu.x = Byte(x);
+        cpu.y = Byte(y);
+        bus.write_block(Word(0x0200), program, AccessType::DataWrite);
+        setup(&mut bus);
+        cpu.step(&mut bus)
+    }
+
+    #[test]
+    fn test_lda_absolute_x_page_cross_costs_extra_cycle() {
+        // LDA $12FF,X
+        let program = [0xBD, 0xFF, 0x12];
+        assert_eq!(step_cycles(&program, 0x00, 0, |_| {}), 4);
+        assert_eq!(step_cycles(&program, 0x01, 0, |_| {}), 5);
+
+        let mut bus = TestBus::default();
+        let mut cpu = Cpu::<TestBus>::default();
+        cpu.pc = Word(0x0200);
+        cpu.x = Byte(0x01);
+        bus.write_block(Word(0x0200), &program, AccessType::DataWrite);
+        bus.write(Word(0x1300), Byte(0x5A), AccessType::DataWrite);
+        cpu.step(&mut bus);
+        assert_eq!(cpu.a, Byte(0x5A));
+        assert_eq!(cpu.cycles, 5);
+    }
+
+    #[test]
+    fn test_indexed_read_penalties_for_y_modes() {
+        // ORA $12F0,Y
+        assert_eq!(step_cycles(&[0x19, 0xF0, 0x12], 0, 0x0F, |_| {}), 4);
+        assert_eq!(step_cycles(&[0x19, 0xF0, 0x12], 0, 0x10, |_| {}), 5);
+
+        // CMP ($10),Y with the pointer at $10 holding $20F0
+        let pointer = |bus: &mut TestBus| {
+            bus.write_block(Word(0x0010), &[0xF0, 0x20], AccessType::DataWrite);
+        };
+        assert_eq!(step_cycles(&[0xD1, 0x10], 0, 0x0F, pointer), 5);
+        assert_eq!(step_cycles(&[0xD1, 0x10], 0, 0x10, pointer), 6);
+    }
+
+    #[test]
+    fn test_indexed_writes_and_rmw_never_add_penalty() {
+        // STA $12FF,X
+        assert_eq!(step_cycles(&[0x9D, 0xFF, 0x12], 0x00, 0, |_| {}), 5);
+        assert_eq!(step_cycles(&[0x9D, 0xFF, 0x12], 0x01, 0, |_| {}), 5);
+        // INC $12FF,X
+        assert_eq!(step_cycles(&[0xFE, 0xFF, 0x12], 0x01, 0, |_| {}), 7);
+        // STA ($10),Y
+        let pointer = |bus: &mut TestBus| {
+            bus.write_block(Word(0x0010), &[0xF0, 0x20], AccessType::DataWrite);
+        };
+        assert_eq!(step_cycles(&[0x91, 0x10], 0, 0x10, pointer), 6);
+    }
+
+    #[test]
+    fn table_metadata_names_known_opcodes() {
+        let table = Mos6502::base_table::<TestBus>();
+        let expected = [
+            (0x00, "BRK", AddressingModeKind::Implied),
+            (0x0A, "ASL", AddressingModeKind::Accumulator),
+            (0x20, "JSR", AddressingModeKind::Absolute),
+            (0x6C, "JMP", AddressingModeKind::AbsoluteIndirect),
+            (0x91, "STA", AddressingModeKind::ZeroPageIndirectY),
+            (0xA1, "LDA", AddressingModeKind::ZeroPageXIndirect),
+            (0xA9, "LDA", AddressingModeKind::Immediate),
+            (0xB6, "LDX", AddressingModeKind::ZeroPageY),
+            (0xBE, "LDX", AddressingModeKind::AbsoluteY),
+            (0xD0, "BNE", AddressingModeKind::Relative),
+            (0xA7, "LAX", AddressingModeKind::ZeroPage),
+            (0x1C, "NOP", AddressingModeKind::AbsoluteX),
+            (0x02, "JAM", AddressingModeKind::Implied),
+        ];
+        for (opcode, mnemonic, mode) in expected {
+            assert_eq!(table[opcode].mnemonic, mnemonic, "opcode {opcode:02X}");
+            assert_eq!(table[opcode].mode, mode, "opcode {opcode:02X}");
+        }
+    }
+
+    #[test]
+    fn table_metadata_lengths_match_pc_advance() {
+        let table = Mos6502::base_table::<TestBus>();
+        for opcode in 0..=255u8 {
+            let instruction = table[opcode as usize];
+            if matches!(
+                instruction.mnemonic,
+                "BRK" | "JMP" | "JSR" | "RTI" | "RTS" | "JAM"
+            ) || instruction.mode == AddressingModeKind::Relative
+            {
+                continue;
+            }
+
+            let mut bus = TestBus::default();
+            let mut cpu: Cpu<TestBus> =
+                Cpu::with_program::<Mos6502>(&mut bus, Word(0x8000), &[opcode], Word(0x8000));
+            cpu.step(&mut bus);
+            assert_eq!(
+                cpu.pc,
+                Word(0x8001 + u16::from(instruction.mode.operand_bytes())),
+                "opcode {opcode:02X} ({})",
+                instruction.mnemonic
+            );
+        }
+    }
 }
@@ -1,28 +1,32 @@
 //! The WDC 65C02 instruction set implementation.
 
 use crate::bus::Mos6502CompatibleBus;
-use crate::instruction::mos6502::{illegal, illegal_a, Mos6502};
+use crate::instruction::mos6502::{branch, illegal, illegal_a, read_for_modify, Mos6502};
 use crate::instruction::{Instruction, InstructionSet, InstructionTable};
 use crate::processor::addressing_mode::{
-    Absolute, AbsoluteIndirectCorrect, AbsoluteIndirectX, AbsoluteX, Immediate, ZeroPage,
-    ZeroPageIndirect, ZeroPageX,
+    AddressingModeKind,
+    Absolute, AbsoluteIndirectCorrect, AbsoluteIndirectX, AbsoluteX, AbsoluteXCmos,
+    AbsoluteYCmos, Accumulator, Immediate, Implied, Relative, ZeroPage, ZeroPageIndirect,
+    ZeroPageIndirectYCmos, ZeroPageX, ZeroPageXIndirect,
 };
 use crate::processor::flags::Flags;
 use crate::AccessType;
 use crate::{AddressingMode, Cpu, RunState, IRQ_VECTOR_HI, IRQ_VECTOR_LO};
-use ull::{word, Address, Byte, Word};
+use ull::{word, Byte};
 
 pub struct Wdc65c02s;
 
 impl Wdc65c02s {
     /// Builds the canonical WDC 65C02S instruction table.
     #[must_use]
-    pub const fn base_table<B: Mos6502CompatibleBus + 'static>() -> InstructionTable<B> {
-        Mos6502::base_table()
+    pub const fn base_table<B: Mos6502CompatibleBus>() -> InstructionTable<B> {
+        Mos6502::base_table_for::<Self, B>()
             // BRK with decimal clear
             .with(
                 0x00,
                 Instruction {
+                    mnemonic: "BRK",
+                    mode: AddressingModeKind::Implied,
                     cycles: 7,
                     execute: brk::<B>,
                 },
@@ -31,14 +35,18 @@ impl Wdc65c02s {
             .with(
                 0x80,
                 Instruction {
+                    mnemonic: "BRA",
+                    mode: AddressingModeKind::Relative,
                     cycles: 2,
-                    execute: bra::<Immediate, B>,
+                    execute: bra::<Relative, B>,
                 },
             )
             // STZ variants
             .with(
                 0x64,
                 Instruction {
+                    mnemonic: "STZ",
+                    mode: AddressingModeKind::ZeroPage,
                     cycles: 3,
                     execute: stz::<ZeroPage, B>,
                 },
@@ -46,6 +54,8 @@ impl Wdc65c02s {
             .with(
                 0x74,
                 Instruction {
+                    mnemonic: "STZ",
+                    mode: AddressingModeKind::ZeroPageX,
                     cycles: 4,
                     execute: stz::<ZeroPageX, B>,
                 },
@@ -53,6 +63,8 @@ impl Wdc65c02s {
             .with(
                 0x9C,
                 Instruction {
+                    mnemonic: "STZ",
+                    mode: AddressingModeKind::Absolute,
                     cycles: 4,
                     execute: stz::<Absolute, B>,
                 },
@@ -60,6 +72,8 @@ impl Wdc65c02s {
             .with(
                 0x9E,
                 Instruction {
+                    mnemonic: "STZ",
+                    mode: AddressingModeKind::AbsoluteX,
                     cycles: 5,
                     execute: stz::<AbsoluteX, B>,
                 },
@@ -68,6 +82,8 @@ impl Wdc65c02s {
             .with(
                 0x04,
                 Instruction {
+                    mnemonic: "TSB",
+                    mode: AddressingModeKind::ZeroPage,
                     cycles: 5,
                     execute: tsb::<ZeroPage, B>,
                 },
@@ -75,6 +91,8 @@ impl Wdc65c02s {
             .with(
                 0x0C,
                 Instruction {
+                    mnemonic: "TSB",
+                    mode: AddressingModeKind::Absolute,
                     cycles: 6,
                     execute: tsb::<Absolute, B>,
                 },
@@ -82,6 +100,8 @@ impl Wdc65c02s {
             .with(
                 0x14,
                 Instruction {
+                    mnemonic: "TRB",
+                    mode: AddressingModeKind::ZeroPage,
                     cycles: 5,
                     execute: trb::<ZeroPage, B>,
                 },
@@ -89,6 +109,8 @@ impl Wdc65c02s {
             .with(
                 0x1C,
                 Instruction {
+                    mnemonic: "TRB",
+                    mode: AddressingModeKind::Absolute,
                     cycles: 6,
                     execute: trb::<Absolute, B>,
                 },
@@ -97,6 +119,8 @@ impl Wdc65c02s {
             .with(
                 0x1A,
                 Instruction {
+                    mnemonic: "INC",
+                    mode: AddressingModeKind::Accumulator,
                     cycles: 2,
                     execute: inc_a::<B>,
                 },
@@ -104,6 +128,8 @@ impl Wdc65c02s {
             .with(
                 0x3A,
                 Instruction {
+                    mnemonic: "DEC",
+                    mode: AddressingModeKind::Accumulator,
                     cycles: 2,
                     execute: dec_a::<B>,
                 },
@@ -112,6 +138,8 @@ impl Wdc65c02s {
             .with(
                 0x5A,
                 Instruction {
+                    mnemonic: "PHY",
+                    mode: AddressingModeKind::Implied,
                     cycles: 3,
                     execute: phy::<B>,
                 },
@@ -119,6 +147,8 @@ impl Wdc65c02s {
             .with(
                 0x7A,
                 Instruction {
+                    mnemonic: "PLY",
+                    mode: AddressingModeKind::Implied,
                     cycles: 4,
                     execute: ply::<B>,
                 },
@@ -126,6 +156,8 @@ impl Wdc65c02s {
             .with(
                 0xDA,
                 Instruction {
+                    mnemonic: "PHX",
+                    mode: AddressingModeKind::Implied,
                     cycles: 3,
                     execute: phx::<B>,
                 },
@@ -133,6 +165,8 @@ impl Wdc65c02s {
             .with(
                 0xFA,
                 Instruction {
+                    mnemonic: "PLX",
+                    mode: AddressingModeKind::Implied,
                     cycles: 4,
                     execute: plx::<B>,
                 },
@@ -141,6 +175,8 @@ impl Wdc65c02s {
             .with(
                 0x89,
                 Instruction {
+                    mnemonic: "BIT",
+                    mode: AddressingModeKind::Immediate,
                     cycles: 2,
                     execute: bit::<B>,
                 },
@@ -149,6 +185,8 @@ impl Wdc65c02s {
             .with(
                 0x34,
                 Instruction {
+                    mnemonic: "BIT",
+                    mode: AddressingModeKind::ZeroPageX,
                     cycles: 4,
                     execute: super::mos6502::bit::<ZeroPageX, B>,
                 },
@@ -156,15 +194,19 @@ impl Wdc65c02s {
             .with(
                 0x3C,
                 Instruction {
+                    mnemonic: "BIT",
+                    mode: AddressingModeKind::AbsoluteX,
                     cycles: 4,
-                    execute: super::mos6502::bit::<AbsoluteX, B>,
+                    execute: super::mos6502::bit::<AbsoluteXCmos, B>,
                 },
             )
-            // JMP absolute indirect fixed
+            // JMP absolute indirect fixed; reading the pointer without the page-wrap bug costs a cycle
             .with(
                 0x6C,
                 Instruction {
-                    cycles: 5,
+                    mnemonic: "JMP",
+                    mode: AddressingModeKind::AbsoluteIndirectCorrect,
+                    cycles: 6,
                     execute: super::mos6502::jmp::<AbsoluteIndirectCorrect, B>,
                 },
             )
@@ -172,7 +214,9 @@ impl Wdc65c02s {
             .with(
                 0x7C,
                 Instruction {
-                    cycles: 5,
+                    mnemonic: "JMP",
+                    mode: AddressingModeKind::AbsoluteIndirectX,
+                    cycles: 6,
                     execute: super::mos6502::jmp::<AbsoluteIndirectX, B>,
                 },
             )
@@ -180,6 +224,8 @@ impl Wdc65c02s {
             .with(
                 0x02,
                 Instruction {
+                    mnemonic: "NOP",
+                    mode: AddressingModeKind::Immediate,
                     cycles: 2,
                     execute: illegal::<Immediate, B>,
                 },
@@ -188,6 +234,8 @@ impl Wdc65c02s {
             .with(
                 0x12,
                 Instruction {
+                    mnemonic: "ORA",
+                    mode: AddressingModeKind::ZeroPageIndirect,
                     cycles: 5,
                     execute: super::mos6502::ora::<ZeroPageIndirect, B>,
                 },
@@ -195,6 +243,8 @@ impl Wdc65c02s {
             .with(
                 0x32,
                 Instruction {
+                    mnemonic: "AND",
+                    mode: AddressingModeKind::ZeroPageIndirect,
                     cycles: 5,
                     execute: super::mos6502::and::<ZeroPageIndirect, B>,
                 },
@@ -202,6 +252,8 @@ impl Wdc65c02s {
             .with(
                 0x52,
                 Instruction {
+                    mnemonic: "EOR",
+                    mode: AddressingModeKind::ZeroPageIndirect,
                     cycles: 5,
                     execute: super::mos6502::eor::<ZeroPageIndirect, B>,
                 },
@@ -209,13 +261,17 @@ impl Wdc65c02s {
             .with(
                 0x72,
                 Instruction {
+                    mnemonic: "ADC",
+                    mode: AddressingModeKind::ZeroPageIndirect,
                     cycles: 5,
-                    execute: super::mos6502::adc::<Mos6502, ZeroPageIndirect, B>,
+                    execute: super::mos6502::adc::<Wdc65c02s, ZeroPageIndirect, B>,
                 },
             )
             .with(
                 0x92,
                 Instruction {
+                    mnemonic: "STA",
+                    mode: AddressingModeKind::ZeroPageIndirect,
                     cycles: 6,
                     execute: super::mos6502::sta::<ZeroPageIndirect, B>,
                 },
@@ -223,6 +279,8 @@ impl Wdc65c02s {
             .with(
                 0xB2,
                 Instruction {
+                    mnemonic: "LDA",
+                    mode: AddressingModeKind::ZeroPageIndirect,
                     cycles: 5,
                     execute: super::mos6502::lda::<ZeroPageIndirect, B>,
                 },
@@ -230,6 +288,8 @@ impl Wdc65c02s {
             .with(
                 0xD2,
                 Instruction {
+                    mnemonic: "CMP",
+                    mode: AddressingModeKind::ZeroPageIndirect,
                     cycles: 5,
                     execute: super::mos6502::cmp::<ZeroPageIndirect, B>,
                 },
@@ -237,13 +297,162 @@ impl Wdc65c02s {
             .with(
                 0xF2,
                 Instruction {
+                    mnemonic: "SBC",
+                    mode: AddressingModeKind::ZeroPageIndirect,
+                    cycles: 5,
+                    execute: super::mos6502::sbc::<Wdc65c02s, ZeroPageIndirect, B>,
+                },
+            )
+            // ADC/SBC with the 65C02's decimal-mode flags
+            .with(
+                0x61,
+                Instruction {
+                    mnemonic: "ADC",
+                    mode: AddressingModeKind::ZeroPageXIndirect,
+                    cycles: 6,
+                    execute: super::mos6502::adc::<Wdc65c02s, ZeroPageXIndirect, B>,
+                },
+            )
+            .with(
+                0x65,
+                Instruction {
+                    mnemonic: "ADC",
+                    mode: AddressingModeKind::ZeroPage,
+                    cycles: 3,
+                    execute: super::mos6502::adc::<Wdc65c02s, ZeroPage, B>,
+                },
+            )
+            .with(
+                0x69,
+                Instruction {
+                    mnemonic: "ADC",
+                    mode: AddressingModeKind::Immediate,
+                    cycles: 2,
+                    execute: super::mos6502::adc::<Wdc65c02s, Immediate, B>,
+                },
+            )
+            .with(
+                0x6D,
+                Instruction {
+                    mnemonic: "ADC",
+                    mode: AddressingModeKind::Absolute,
+                    cycles: 4,
+                    execute: super::mos6502::adc::<Wdc65c02s, Absolute, B>,
+                },
+            )
+            .with(
+                0x71,
+                Instruction {
+                    mnemonic: "ADC",
+                    mode: AddressingModeKind::ZeroPageIndirectY,
+                    cycles: 5,
+                    execute: super::mos6502::adc::<Wdc65c02s, ZeroPageIndirectYCmos, B>,
+                },
+            )
+            .with(
+                0x75,
+                Instruction {
+                    mnemonic: "ADC",
+                    mode: AddressingModeKind::ZeroPageX,
+                    cycles: 4,
+                    execute: super::mos6502::adc::<Wdc65c02s, ZeroPageX, B>,
+                },
+            )
+            .with(
+                0x79,
+                Instruction {
+                    mnemonic: "ADC",
+                    mode: AddressingModeKind::AbsoluteY,
+                    cycles: 4,
+                    execute: super::mos6502::adc::<Wdc65c02s, AbsoluteYCmos, B>,
+                },
+            )
+            .with(
+                0x7D,
+                Instruction {
+                    mnemonic: "ADC",
+                    mode: AddressingModeKind::AbsoluteX,
+                    cycles: 4,
+                    execute: super::mos6502::adc::<Wdc65c02s, AbsoluteXCmos, B>,
+                },
+            )
+            .with(
+                0xE1,
+                Instruction {
+                    mnemonic: "SBC",
+                    mode: AddressingModeKind::ZeroPageXIndirect,
+                    cycles: 6,
+                    execute: super::mos6502::sbc::<Wdc65c02s, ZeroPageXIndirect, B>,
+                },
+            )
+            .with(
+                0xE5,
+                Instruction {
+                    mnemonic: "SBC",
+                    mode: AddressingModeKind::ZeroPage,
+                    cycles: 3,
+                    execute: super::mos6502::sbc::<Wdc65c02s, ZeroPage, B>,
+                },
+            )
+            .with(
+                0xE9,
+                Instruction {
+                    mnemonic: "SBC",
+                    mode: AddressingModeKind::Immediate,
+                    cycles: 2,
+                    execute: super::mos6502::sbc::<Wdc65c02s, Immediate, B>,
+                },
+            )
+            .with(
+                0xED,
+                Instruction {
+                    mnemonic: "SBC",
+                    mode: AddressingModeKind::Absolute,
+                    cycles: 4,
+                    execute: super::mos6502::sbc::<Wdc65c02s, Absolute, B>,
+                },
+            )
+            .with(
+                0xF1,
+                Instruction {
+                    mnemonic: "SBC",
+                    mode: AddressingModeKind::ZeroPageIndirectY,
                     cycles: 5,
-                    execute: super::mos6502::sbc::<Mos6502, ZeroPageIndirect, B>,
+                    execute: super::mos6502::sbc::<Wdc65c02s, ZeroPageIndirectYCmos, B>,
+                },
+            )
+            .with(
+                0xF5,
+                Instruction {
+                    mnemonic: "SBC",
+                    mode: AddressingModeKind::ZeroPageX,
+                    cycles: 4,
+                    execute: super::mos6502::sbc::<Wdc65c02s, ZeroPageX, B>,
+                },
+            )
+            .with(
+                0xF9,
+                Instruction {
+                    mnemonic: "SBC",
+                    mode: AddressingModeKind::AbsoluteY,
+                    cycles: 4,
+                    execute: super::mos6502::sbc::<Wdc65c02s, AbsoluteYCmos, B>,
+                },
+            )
+            .with(
+                0xFD,
+                Instruction {
+                    mnemonic: "SBC",
+                    mode: AddressingModeKind::AbsoluteX,
+                    cycles: 4,
+                    execute: super::mos6502::sbc::<Wdc65c02s, AbsoluteXCmos, B>,
                 },
             )
             .with(
                 0x22,
                 Instruction {
+                    mnemonic: "NOP",
+                    mode: AddressingModeKind::Immediate,
                     cycles: 2,
                     execute: illegal::<Immediate, B>,
                 },
@@ -251,6 +460,8 @@ impl Wdc65c02s {
             .with(
                 0x42,
                 Instruction {
+                    mnemonic: "NOP",
+                    mode: AddressingModeKind::Immediate,
                     cycles: 2,
                     execute: illegal::<Immediate, B>,
                 },
@@ -258,6 +469,8 @@ impl Wdc65c02s {
             .with(
                 0x62,
                 Instruction {
+                    mnemonic: "NOP",
+                    mode: AddressingModeKind::Immediate,
                     cycles: 2,
                     execute: illegal::<Immediate, B>,
                 },
@@ -265,6 +478,8 @@ impl Wdc65c02s {
             .with(
                 0x82,
                 Instruction {
+                    mnemonic: "NOP",
+                    mode: AddressingModeKind::Immediate,
                     cycles: 2,
                     execute: illegal::<Immediate, B>,
                 },
@@ -272,6 +487,8 @@ impl Wdc65c02s {
             .with(
                 0xC2,
                 Instruction {
+                    mnemonic: "NOP",
+                    mode: AddressingModeKind::Immediate,
                     cycles: 2,
                     execute: illegal::<Immediate, B>,
                 },
@@ -279,6 +496,8 @@ impl Wdc65c02s {
             .with(
                 0xE2,
                 Instruction {
+                    mnemonic: "NOP",
+                    mode: AddressingModeKind::Immediate,
                     cycles: 2,
                     execute: illegal::<Immediate, B>,
                 },
@@ -286,6 +505,8 @@ impl Wdc65c02s {
             .with(
                 0x44,
                 Instruction {
+                    mnemonic: "NOP",
+                    mode: AddressingModeKind::ZeroPage,
                     cycles: 3,
                     execute: illegal::<ZeroPage, B>,
                 },
@@ -293,6 +514,8 @@ impl Wdc65c02s {
             .with(
                 0x54,
                 Instruction {
+                    mnemonic: "NOP",
+                    mode: AddressingModeKind::ZeroPageX,
                     cycles: 4,
                     execute: illegal::<ZeroPageX, B>,
                 },
@@ -300,6 +523,8 @@ impl Wdc65c02s {
             .with(
                 0xD4,
                 Instruction {
+                    mnemonic: "NOP",
+                    mode: AddressingModeKind::ZeroPageX,
                     cycles: 4,
                     execute: illegal::<ZeroPageX, B>,
                 },
@@ -307,6 +532,8 @@ impl Wdc65c02s {
             .with(
                 0xF4,
                 Instruction {
+                    mnemonic: "NOP",
+                    mode: AddressingModeKind::ZeroPageX,
                     cycles: 4,
                     execute: illegal::<ZeroPageX, B>,
                 },
@@ -314,6 +541,8 @@ impl Wdc65c02s {
             .with(
                 0x5C,
                 Instruction {
+                    mnemonic: "NOP",
+                    mode: AddressingModeKind::Absolute,
                     cycles: 8,
                     execute: illegal::<Absolute, B>,
                 },
@@ -321,6 +550,8 @@ impl Wdc65c02s {
             .with(
                 0xDC,
                 Instruction {
+                    mnemonic: "NOP",
+                    mode: AddressingModeKind::AbsoluteX,
                     cycles: 4,
                     execute: illegal::<AbsoluteX, B>,
                 },
@@ -328,6 +559,8 @@ impl Wdc65c02s {
             .with(
                 0xFC,
                 Instruction {
+                    mnemonic: "NOP",
+                    mode: AddressingModeKind::AbsoluteX,
                     cycles: 4,
                     execute: illegal::<AbsoluteX, B>,
                 },
@@ -335,6 +568,8 @@ impl Wdc65c02s {
             .with(
                 0x03,
                 Instruction {
+                    mnemonic: "NOP",
+                    mode: AddressingModeKind::Implied,
                     cycles: 1,
                     execute: illegal_a::<B>,
                 },
@@ -342,6 +577,8 @@ impl Wdc65c02s {
             .with(
                 0x0B,
                 Instruction {
+                    mnemonic: "NOP",
+                    mode: AddressingModeKind::Implied,
                     cycles: 1,
                     execute: illegal_a::<B>,
                 },
@@ -349,6 +586,8 @@ impl Wdc65c02s {
             .with(
                 0x13,
                 Instruction {
+                    mnemonic: "NOP",
+                    mode: AddressingModeKind::Implied,
                     cycles: 1,
                     execute: illegal_a::<B>,
                 },
@@ -356,6 +595,8 @@ impl Wdc65c02s {
             .with(
                 0x1B,
                 Instruction {
+                    mnemonic: "NOP",
+                    mode: AddressingModeKind::Implied,
                     cycles: 1,
                     execute: illegal_a::<B>,
                 },
@@ -363,6 +604,8 @@ impl Wdc65c02s {
             .with(
                 0x23,
                 Instruction {
+                    mnemonic: "NOP",
+                    mode: AddressingModeKind::Implied,
                     cycles: 1,
                     execute: illegal_a::<B>,
                 },
@@ -370,6 +613,8 @@ impl Wdc65c02s {
             .with(
                 0x2B,
                 Instruction {
+                    mnemonic: "NOP",
+                    mode: AddressingModeKind::Implied,
                     cycles: 1,
                     execute: illegal_a::<B>,
                 },
@@ -377,6 +622,8 @@ impl Wdc65c02s {
             .with(
                 0x33,
                 Instruction {
+                    mnemonic: "NOP",
+                    mode: AddressingModeKind::Implied,
                     cycles: 1,
                     execute: illegal_a::<B>,
                 },
@@ -384,6 +631,8 @@ impl Wdc65c02s {
             .with(
                 0x3B,
                 Instruction {
+                    mnemonic: "NOP",
+                    mode: AddressingModeKind::Implied,
                     cycles: 1,
                     execute: illegal_a::<B>,
                 },
@@ -391,6 +640,8 @@ impl Wdc65c02s {
             .with(
                 0x43,
                 Instruction {
+                    mnemonic: "NOP",
+                    mode: AddressingModeKind::Implied,
                     cycles: 1,
                     execute: illegal_a::<B>,
                 },
@@ -398,6 +649,8 @@ impl Wdc65c02s {
             .with(
                 0x4B,
                 Instruction {
+                    mnemonic: "NOP",
+                    mode: AddressingModeKind::Implied,
                     cycles: 1,
                     execute: illegal_a::<B>,
                 },
@@ -405,6 +658,8 @@ impl Wdc65c02s {
             .with(
                 0x53,
                 Instruction {
+                    mnemonic: "NOP",
+                    mode: AddressingModeKind::Implied,
                     cycles: 1,
                     execute: illegal_a::<B>,
                 },
@@ -412,6 +667,8 @@ impl Wdc65c02s {
             .with(
                 0x5B,
                 Instruction {
+                    mnemonic: "NOP",
+                    mode: AddressingModeKind::Implied,
                     cycles: 1,
                     execute: illegal_a::<B>,
                 },
@@ -419,6 +676,8 @@ impl Wdc65c02s {
             .with(
                 0x63,
                 Instruction {
+                    mnemonic: "NOP",
+                    mode: AddressingModeKind::Implied,
                     cycles: 1,
                     execute: illegal_a::<B>,
                 },
@@ -426,6 +685,8 @@ impl Wdc65c02s {
             .with(
                 0x6B,
                 Instruction {
+                    mnemonic: "NOP",
+                    mode: AddressingModeKind::Implied,
                     cycles: 1,
                     execute: illegal_a::<B>,
                 },
@@ -433,6 +694,8 @@ impl Wdc65c02s {
             .with(
                 0x73,
                 Instruction {
+                    mnemonic: "NOP",
+                    mode: AddressingModeKind::Implied,
                     cycles: 1,
                     execute: illegal_a::<B>,
                 },
@@ -440,6 +703,8 @@ impl Wdc65c02s {
             .with(
                 0x7B,
                 Instruction {
+                    mnemonic: "NOP",
+                    mode: AddressingModeKind::Implied,
                     cycles: 1,
                     execute: illegal_a::<B>,
                 },
@@ -447,6 +712,8 @@ impl Wdc65c02s {
             .with(
                 0x83,
                 Instruction {
+                    mnemonic: "NOP",
+                    mode: AddressingModeKind::Implied,
                     cycles: 1,
                     execute: illegal_a::<B>,
                 },
@@ -454,6 +721,8 @@ impl Wdc65c02s {
             .with(
                 0x8B,
                 Instruction {
+                    mnemonic: "NOP",
+                    mode: AddressingModeKind::Implied,
                     cycles: 1,
                     execute: illegal_a::<B>,
                 },
@@ -461,6 +730,8 @@ impl Wdc65c02s {
             .with(
                 0x93,
                 Instruction {
+                    mnemonic: "NOP",
+                    mode: AddressingModeKind::Implied,
                     cycles: 1,
                     execute: illegal_a::<B>,
                 },
@@ -468,6 +739,8 @@ impl Wdc65c02s {
             .with(
                 0x9B,
                 Instruction {
+                    mnemonic: "NOP",
+                    mode: AddressingModeKind::Implied,
                     cycles: 1,
                     execute: illegal_a::<B>,
                 },
@@ -475,6 +748,8 @@ impl Wdc65c02s {
             .with(
                 0xA3,
                 Instruction {
+                    mnemonic: "NOP",
+                    mode: AddressingModeKind::Implied,
                     cycles: 1,
                     execute: illegal_a::<B>,
                 },
@@ -482,6 +757,8 @@ impl Wdc65c02s {
             .with(
                 0xAB,
                 Instruction {
+                    mnemonic: "NOP",
+                    mode: AddressingModeKind::Implied,
                     cycles: 1,
                     execute: illegal_a::<B>,
                 },
@@ -489,6 +766,8 @@ impl Wdc65c02s {
             .with(
                 0xB3,
                 Instruction {
+                    mnemonic: "NOP",
+                    mode: AddressingModeKind::Implied,
                     cycles: 1,
                     execute: illegal_a::<B>,
                 },
@@ -496,6 +775,8 @@ impl Wdc65c02s {
             .with(
                 0xBB,
                 Instruction {
+                    mnemonic: "NOP",
+                    mode: AddressingModeKind::Implied,
                     cycles: 1,
                     execute: illegal_a::<B>,
                 },
@@ -503,6 +784,8 @@ impl Wdc65c02s {
             .with(
                 0xC3,
                 Instruction {
+                    mnemonic: "NOP",
+                    mode: AddressingModeKind::Implied,
                     cycles: 1,
                     execute: illegal_a::<B>,
                 },
@@ -510,6 +793,8 @@ impl Wdc65c02s {
             .with(
                 0xD3,
                 Instruction {
+                    mnemonic: "NOP",
+                    mode: AddressingModeKind::Implied,
                     cycles: 1,
                     execute: illegal_a::<B>,
                 },
@@ -517,6 +802,8 @@ impl Wdc65c02s {
             .with(
                 0xE3,
                 Instruction {
+                    mnemonic: "NOP",
+                    mode: AddressingModeKind::Implied,
                     cycles: 1,
                     execute: illegal_a::<B>,
                 },
@@ -524,6 +811,8 @@ impl Wdc65c02s {
             .with(
                 0xEB,
                 Instruction {
+                    mnemonic: "NOP",
+                    mode: AddressingModeKind::Implied,
                     cycles: 1,
                     execute: illegal_a::<B>,
                 },
@@ -531,6 +820,8 @@ impl Wdc65c02s {
             .with(
                 0xF3,
                 Instruction {
+                    mnemonic: "NOP",
+                    mode: AddressingModeKind::Implied,
                     cycles: 1,
                     execute: illegal_a::<B>,
                 },
@@ -538,6 +829,8 @@ impl Wdc65c02s {
             .with(
                 0xFB,
                 Instruction {
+                    mnemonic: "NOP",
+                    mode: AddressingModeKind::Implied,
                     cycles: 1,
                     execute: illegal_a::<B>,
                 },
@@ -546,6 +839,8 @@ impl Wdc65c02s {
             .with(
                 0x07,
                 Instruction {
+                    mnemonic: "RMB0",
+                    mode: AddressingModeKind::ZeroPage,
                     cycles: 5,
                     execute: rmb0::<B>,
                 },
@@ -553,6 +848,8 @@ impl Wdc65c02s {
             .with(
                 0x17,
                 Instruction {
+                    mnemonic: "RMB1",
+                    mode: AddressingModeKind::ZeroPage,
                     cycles: 5,
                     execute: rmb1::<B>,
                 },
@@ -560,6 +857,8 @@ impl Wdc65c02s {
             .with(
                 0x27,
                 Instruction {
+                    mnemonic: "RMB2",
+                    mode: AddressingModeKind::ZeroPage,
                     cycles: 5,
                     execute: rmb2::<B>,
                 },
@@ -567,6 +866,8 @@ impl Wdc65c02s {
             .with(
                 0x37,
                 Instruction {
+                    mnemonic: "RMB3",
+                    mode: AddressingModeKind::ZeroPage,
                     cycles: 5,
                     execute: rmb3::<B>,
                 },
@@ -574,6 +875,8 @@ impl Wdc65c02s {
             .with(
                 0x47,
                 Instruction {
+                    mnemonic: "RMB4",
+                    mode: AddressingModeKind::ZeroPage,
                     cycles: 5,
                     execute: rmb4::<B>,
                 },
@@ -581,6 +884,8 @@ impl Wdc65c02s {
             .with(
                 0x57,
                 Instruction {
+                    mnemonic: "RMB5",
+                    mode: AddressingModeKind::ZeroPage,
                     cycles: 5,
                     execute: rmb5::<B>,
                 },
@@ -588,6 +893,8 @@ impl Wdc65c02s {
             .with(
                 0x67,
                 Instruction {
+                    mnemonic: "RMB6",
+                    mode: AddressingModeKind::ZeroPage,
                     cycles: 5,
                     execute: rmb6::<B>,
                 },
@@ -595,6 +902,8 @@ impl Wdc65c02s {
             .with(
                 0x77,
                 Instruction {
+                    mnemonic: "RMB7",
+                    mode: AddressingModeKind::ZeroPage,
                     cycles: 5,
                     execute: rmb7::<B>,
                 },
@@ -602,6 +911,8 @@ impl Wdc65c02s {
             .with(
                 0x87,
                 Instruction {
+                    mnemonic: "SMB0",
+                    mode: AddressingModeKind::ZeroPage,
                     cycles: 5,
                     execute: smb0::<B>,
                 },
@@ -609,6 +920,8 @@ impl Wdc65c02s {
             .with(
                 0x97,
                 Instruction {
+                    mnemonic: "SMB1",
+                    mode: AddressingModeKind::ZeroPage,
                     cycles: 5,
                     execute: smb1::<B>,
                 },
@@ -616,6 +929,8 @@ impl Wdc65c02s {
             .with(
                 0xA7,
                 Instruction {
+                    mnemonic: "SMB2",
+                    mode: AddressingModeKind::ZeroPage,
                     cycles: 5,
                     execute: smb2::<B>,
                 },
@@ -623,6 +938,8 @@ impl Wdc65c02s {
             .with(
                 0xB7,
                 Instruction {
+                    mnemonic: "SMB3",
+                    mode: AddressingModeKind::ZeroPage,
                     cycles: 5,
                     execute: smb3::<B>,
                 },
@@ -630,6 +947,8 @@ impl Wdc65c02s {
             .with(
                 0xC7,
                 Instruction {
+                    mnemonic: "SMB4",
+                    mode: AddressingModeKind::ZeroPage,
                     cycles: 5,
                     execute: smb4::<B>,
                 },
@@ -637,6 +956,8 @@ impl Wdc65c02s {
             .with(
                 0xD7,
                 Instruction {
+                    mnemonic: "SMB5",
+                    mode: AddressingModeKind::ZeroPage,
                     cycles: 5,
                     execute: smb5::<B>,
                 },
@@ -644,6 +965,8 @@ impl Wdc65c02s {
             .with(
                 0xE7,
                 Instruction {
+                    mnemonic: "SMB6",
+                    mode: AddressingModeKind::ZeroPage,
                     cycles: 5,
                     execute: smb6::<B>,
                 },
@@ -651,6 +974,8 @@ impl Wdc65c02s {
             .with(
                 0xF7,
                 Instruction {
+                    mnemonic: "SMB7",
+                    mode: AddressingModeKind::ZeroPage,
                     cycles: 5,
                     execute: smb7::<B>,
                 },
@@ -659,6 +984,8 @@ impl Wdc65c02s {
             .with(
                 0x0F,
                 Instruction {
+                    mnemonic: "BBR0",
+                    mode: AddressingModeKind::ZeroPageRelative,
                     cycles: 5,
                     execute: bbr0::<B>,
                 },
@@ -666,6 +993,8 @@ impl Wdc65c02s {
             .with(
                 0x1F,
                 Instruction {
+                    mnemonic: "BBR1",
+                    mode: AddressingModeKind::ZeroPageRelative,
                     cycles: 5,
                     execute: bbr1::<B>,
                 },
@@ -673,6 +1002,8 @@ impl Wdc65c02s {
             .with(
                 0x2F,
                 Instruction {
+                    mnemonic: "BBR2",
+                    mode: AddressingModeKind::ZeroPageRelative,
                     cycles: 5,
                     execute: bbr2::<B>,
                 },
@@ -680,6 +1011,8 @@ impl Wdc65c02s {
             .with(
                 0x3F,
                 Instruction {
+                    mnemonic: "BBR3",
+                    mode: AddressingModeKind::ZeroPageRelative,
                     cycles: 5,
                     execute: bbr3::<B>,
                 },
@@ -687,6 +1020,8 @@ impl Wdc65c02s {
             .with(
                 0x4F,
                 Instruction {
+                    mnemonic: "BBR4",
+                    mode: AddressingModeKind::ZeroPageRelative,
                     cycles: 5,
                     execute: bbr4::<B>,
                 },
@@ -694,6 +1029,8 @@ impl Wdc65c02s {
             .with(
                 0x5F,
                 Instruction {
+                    mnemonic: "BBR5",
+                    mode: AddressingModeKind::ZeroPageRelative,
                     cycles: 5,
                     execute: bbr5::<B>,
                 },
@@ -701,6 +1038,8 @@ impl Wdc65c02s {
             .with(
                 0x6F,
                 Instruction {
+                    mnemonic: "BBR6",
+                    mode: AddressingModeKind::ZeroPageRelative,
                     cycles: 5,
                     execute: bbr6::<B>,
                 },
@@ -708,6 +1047,8 @@ impl Wdc65c02s {
             .with(
                 0x7F,
                 Instruction {
+                    mnemonic: "BBR7",
+                    mode: AddressingModeKind::ZeroPageRelative,
                     cycles: 5,
                     execute: bbr7::<B>,
                 },
@@ -715,6 +1056,8 @@ impl Wdc65c02s {
             .with(
                 0x8F,
                 Instruction {
+                    mnemonic: "BBS0",
+                    mode: AddressingModeKind::ZeroPageRelative,
                     cycles: 5,
                     execute: bbs0::<B>,
                 },
@@ -722,6 +1065,8 @@ impl Wdc65c02s {
             .with(
                 0x9F,
                 Instruction {
+                    mnemonic: "BBS1",
+                    mode: AddressingModeKind::ZeroPageRelative,
                     cycles: 5,
                     execute: bbs1::<B>,
                 },
@@ -729,6 +1074,8 @@ impl Wdc65c02s {
             .with(
                 0xAF,
                 Instruction {
+                    mnemonic: "BBS2",
+                    mode: AddressingModeKind::ZeroPageRelative,
                     cycles: 5,
                     execute: bbs2::<B>,
                 },
@@ -736,6 +1083,8 @@ impl Wdc65c02s {
             .with(
                 0xBF,
                 Instruction {
+                    mnemonic: "BBS3",
+                    mode: AddressingModeKind::ZeroPageRelative,
                     cycles: 5,
                     execute: bbs3::<B>,
                 },
@@ -743,6 +1092,8 @@ impl Wdc65c02s {
             .with(
                 0xCF,
                 Instruction {
+                    mnemonic: "BBS4",
+                    mode: AddressingModeKind::ZeroPageRelative,
                     cycles: 5,
                     execute: bbs4::<B>,
                 },
@@ -750,6 +1101,8 @@ impl Wdc65c02s {
             .with(
                 0xDF,
                 Instruction {
+                    mnemonic: "BBS5",
+                    mode: AddressingModeKind::ZeroPageRelative,
                     cycles: 5,
                     execute: bbs5::<B>,
                 },
@@ -757,6 +1110,8 @@ impl Wdc65c02s {
             .with(
                 0xEF,
                 Instruction {
+                    mnemonic: "BBS6",
+                    mode: AddressingModeKind::ZeroPageRelative,
                     cycles: 5,
                     execute: bbs6::<B>,
                 },
@@ -764,14 +1119,173 @@ impl Wdc65c02s {
             .with(
                 0xFF,
                 Instruction {
+                    mnemonic: "BBS7",
+                    mode: AddressingModeKind::ZeroPageRelative,
                     cycles: 5,
                     execute: bbs7::<B>,
                 },
             )
+            // Indexed reads that cross a page re-read the last operand byte instead of the
+            // un-fixed-up address (ADC, SBC and BIT abs,X are above)
+            .with(
+                0x11,
+                Instruction {
+                    mnemonic: "ORA",
+                    mode: AddressingModeKind::ZeroPageIndirectY,
+                    cycles: 5,
+                    execute: super::mos6502::ora::<ZeroPageIndirectYCmos, B>,
+                },
+            )
+            .with(
+                0x19,
+                Instruction {
+                    mnemonic: "ORA",
+                    mode: AddressingModeKind::AbsoluteY,
+                    cycles: 4,
+                    execute: super::mos6502::ora::<AbsoluteYCmos, B>,
+                },
+            )
+            .with(
+                0x1D,
+                Instruction {
+                    mnemonic: "ORA",
+                    mode: AddressingModeKind::AbsoluteX,
+                    cycles: 4,
+                    execute: super::mos6502::ora::<AbsoluteXCmos, B>,
+                },
+            )
+            .with(
+                0x31,
+                Instruction {
+                    mnemonic: "AND",
+                    mode: AddressingModeKind::ZeroPageIndirectY,
+                    cycles: 5,
+                    execute: super::mos6502::and::<ZeroPageIndirectYCmos, B>,
+                },
+            )
+            .with(
+                0x39,
+                Instruction {
+                    mnemonic: "AND",
+                    mode: AddressingModeKind::AbsoluteY,
+                    cycles: 4,
+                    execute: super::mos6502::and::<AbsoluteYCmos, B>,
+                },
+            )
+            .with(
+                0x3D,
+                Instruction {
+                    mnemonic: "AND",
+                    mode: AddressingModeKind::AbsoluteX,
+                    cycles: 4,
+                    execute: super::mos6502::and::<AbsoluteXCmos, B>,
+                },
+            )
+            .with(
+                0x51,
+                Instruction {
+                    mnemonic: "EOR",
+                    mode: AddressingModeKind::ZeroPageIndirectY,
+                    cycles: 5,
+                    execute: super::mos6502::eor::<ZeroPageIndirectYCmos, B>,
+                },
+            )
+            .with(
+                0x59,
+                Instruction {
+                    mnemonic: "EOR",
+                    mode: AddressingModeKind::AbsoluteY,
+                    cycles: 4,
+                    execute: super::mos6502::eor::<AbsoluteYCmos, B>,
+                },
+            )
+            .with(
+                0x5D,
+                Instruction {
+                    mnemonic: "EOR",
+                    mode: AddressingModeKind::AbsoluteX,
+                    cycles: 4,
+                    execute: super::mos6502::eor::<AbsoluteXCmos, B>,
+                },
+            )
+            .with(
+                0xB1,
+                Instruction {
+                    mnemonic: "LDA",
+                    mode: AddressingModeKind::ZeroPageIndirectY,
+                    cycles: 5,
+                    execute: super::mos6502::lda::<ZeroPageIndirectYCmos, B>,
+                },
+            )
+            .with(
+                0xB9,
+                Instruction {
+                    mnemonic: "LDA",
+                    mode: AddressingModeKind::AbsoluteY,
+                    cycles: 4,
+                    execute: super::mos6502::lda::<AbsoluteYCmos, B>,
+                },
+            )
+            .with(
+                0xBC,
+                Instruction {
+                    mnemonic: "LDY",
+                    mode: AddressingModeKind::AbsoluteX,
+                    cycles: 4,
+                    execute: super::mos6502::ldy::<AbsoluteXCmos, B>,
+                },
+            )
+            .with(
+                0xBD,
+                Instruction {
+                    mnemonic: "LDA",
+                    mode: AddressingModeKind::AbsoluteX,
+                    cycles: 4,
+                    execute: super::mos6502::lda::<AbsoluteXCmos, B>,
+                },
+            )
+            .with(
+                0xBE,
+                Instruction {
+                    mnemonic: "LDX",
+                    mode: AddressingModeKind::AbsoluteY,
+                    cycles: 4,
+                    execute: super::mos6502::ldx::<AbsoluteYCmos, B>,
+                },
+            )
+            .with(
+                0xD1,
+                Instruction {
+                    mnemonic: "CMP",
+                    mode: AddressingModeKind::ZeroPageIndirectY,
+                    cycles: 5,
+                    execute: super::mos6502::cmp::<ZeroPageIndirectYCmos, B>,
+                },
+            )
+            .with(
+                0xD9,
+                Instruction {
+                    mnemonic: "CMP",
+                    mode: AddressingModeKind::AbsoluteY,
+                    cycles: 4,
+                    execute: super::mos6502::cmp::<AbsoluteYCmos, B>,
+                },
+            )
+            .with(
+                0xDD,
+                Instruction {
+                    mnemonic: "CMP",
+                    mode: AddressingModeKind::AbsoluteX,
+                    cycles: 4,
+                    execute: super::mos6502::cmp::<AbsoluteXCmos, B>,
+                },
+            )
             // STP/WAI
             .with(
                 0xCB,
                 Instruction {
+                    mnemonic: "WAI",
+                    mode: AddressingModeKind::Implied,
                     cycles: 3,
                     execute: wai::<B>,
                 },
@@ -779,6 +1293,8 @@ impl Wdc65c02s {
             .with(
                 0xDB,
                 Instruction {
+                    mnemonic: "STP",
+                    mode: AddressingModeKind::Implied,
                     cycles: 3,
                     execute: stp::<B>,
                 },
@@ -787,9 +1303,12 @@ impl Wdc65c02s {
 }
 
 impl InstructionSet for Wdc65c02s {
-    fn instruction_table<B: Mos6502CompatibleBus + 'static>() -> InstructionTable<B> {
+    fn instruction_table<B: Mos6502CompatibleBus>() -> InstructionTable<B> {
         Self::base_table()
     }
+    const NAME: &'static str = "WDC 65C02S";
+    const DECIMAL_FLAGS_VALID: bool = true;
+    const RMW_DUMMY_WRITE: bool = false;
 }
 
 // Here for clarity on the bit operations since passing in `true` or `false` is a bit ambiguous,
@@ -797,199 +1316,187 @@ impl InstructionSet for Wdc65c02s {
 const RESET: bool = false;
 const SET: bool = true;
 
-pub fn bra<AM: AddressingMode, B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, bus: &mut B) {
-    let base = cpu.pc + AM::BYTES;
-    let offset = i8::from(bus.read(cpu.pc + 1, AccessType::DataRead));
-    let target = base + offset;
-
-    cpu.cycles += 1;
-    if cpu.crosses_page(base, target) {
-        cpu.cycles += 1;
-    }
-    cpu.pc = target;
+pub fn bra<AM: AddressingMode, B: Mos6502CompatibleBus>(cpu: &mut Cpu<B>, bus: &mut B) {
+    branch::<AM, B>(cpu, bus, true);
 }
 
 #[inline]
-pub fn stz<AM: AddressingMode, B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, bus: &mut B) {
+pub fn stz<AM: AddressingMode, B: Mos6502CompatibleBus>(cpu: &mut Cpu<B>, bus: &mut B) {
     let addr = AM::fetch_address(cpu, bus);
-    bus.write(addr, Byte::ZERO, AccessType::DataWrite);
+    bus.cpu_write(addr, Byte::ZERO, AccessType::DataWrite);
 
     cpu.pc += AM::BYTES;
 }
 
-pub fn trb<AM: AddressingMode, B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, bus: &mut B) {
+pub fn trb<AM: AddressingMode, B: Mos6502CompatibleBus>(cpu: &mut Cpu<B>, bus: &mut B) {
     let addr = AM::fetch_address(cpu, bus);
-    let val = bus.read(addr, AccessType::DataRead);
+    let val = read_for_modify::<Wdc65c02s, _>(cpu, bus, addr);
 
     cpu.p.set_zero(cpu.a & val == 0);
-    bus.write(addr, !cpu.a & val, AccessType::DataWrite);
+    bus.cpu_write(addr, !cpu.a & val, AccessType::DataWrite);
 
     cpu.pc += AM::BYTES;
 }
 
-pub fn tsb<AM: AddressingMode, B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, bus: &mut B) {
+pub fn tsb<AM: AddressingMode, B: Mos6502CompatibleBus>(cpu: &mut Cpu<B>, bus: &mut B) {
     let addr = AM::fetch_address(cpu, bus);
-    let val = bus.read(addr, AccessType::DataRead);
+    let val = read_for_modify::<Wdc65c02s, _>(cpu, bus, addr);
     let result = val | cpu.a;
 
     cpu.p.set_zero((cpu.a & val) == Byte(0));
-    bus.write(addr, result, AccessType::DataWrite);
+    bus.cpu_write(addr, result, AccessType::DataWrite);
 
     cpu.pc += AM::BYTES;
 }
 
-pub fn inc_a<B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, _bus: &mut B) {
+pub fn inc_a<B: Mos6502CompatibleBus>(cpu: &mut Cpu<B>, _bus: &mut B) {
     cpu.a += 1;
 
-    cpu.p.set_signed(cpu.a.is_signed());
-    cpu.p.set_zero(cpu.a == 0);
+    cpu.p.update_nz(cpu.a);
 
-    cpu.pc += 1;
+    cpu.pc += Accumulator::BYTES;
 }
-pub fn dec_a<B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, _bus: &mut B) {
+pub fn dec_a<B: Mos6502CompatibleBus>(cpu: &mut Cpu<B>, _bus: &mut B) {
     cpu.a -= 1;
 
-    cpu.p.set_signed(cpu.a.is_signed());
-    cpu.p.set_zero(cpu.a == 0);
+    cpu.p.update_nz(cpu.a);
 
-    cpu.pc += 1;
+    cpu.pc += Accumulator::BYTES;
 }
 
-pub fn phx<B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, bus: &mut B) {
+pub fn phx<B: Mos6502CompatibleBus>(cpu: &mut Cpu<B>, bus: &mut B) {
     cpu.push(bus, cpu.x);
 
-    cpu.pc += 1;
+    cpu.pc += Implied::BYTES;
 }
 
-pub fn phy<B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, bus: &mut B) {
+pub fn phy<B: Mos6502CompatibleBus>(cpu: &mut Cpu<B>, bus: &mut B) {
     cpu.push(bus, cpu.y);
 
-    cpu.pc += 1;
+    cpu.pc += Implied::BYTES;
 }
 
-pub fn plx<B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, bus: &mut B) {
+pub fn plx<B: Mos6502CompatibleBus>(cpu: &mut Cpu<B>, bus: &mut B) {
     cpu.x = cpu.pop(bus);
-    cpu.p.set_signed(cpu.x.is_signed());
-    cpu.p.set_zero(cpu.x == 0);
+    cpu.p.update_nz(cpu.x);
 
-    cpu.pc += 1;
+    cpu.pc += Implied::BYTES;
 }
-pub fn ply<B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, bus: &mut B) {
+pub fn ply<B: Mos6502CompatibleBus>(cpu: &mut Cpu<B>, bus: &mut B) {
     cpu.y = cpu.pop(bus);
 
-    cpu.p.set_signed(cpu.y.is_signed());
-    cpu.p.set_zero(cpu.y == 0);
+    cpu.p.update_nz(cpu.y);
 
-    cpu.pc += 1;
+    cpu.pc += Implied::BYTES;
 }
 
-pub fn bbr0<B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, bus: &mut B) {
+pub fn bbr0<B: Mos6502CompatibleBus>(cpu: &mut Cpu<B>, bus: &mut B) {
     branch_on_zero_page_bit::<0, false, B>(cpu, bus);
 }
-pub fn bbr1<B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, bus: &mut B) {
+pub fn bbr1<B: Mos6502CompatibleBus>(cpu: &mut Cpu<B>, bus: &mut B) {
     branch_on_zero_page_bit::<1, false, B>(cpu, bus);
 }
-pub fn bbr2<B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, bus: &mut B) {
+pub fn bbr2<B: Mos6502CompatibleBus>(cpu: &mut Cpu<B>, bus: &mut B) {
     branch_on_zero_page_bit::<2, false, B>(cpu, bus);
 }
-pub fn bbr3<B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, bus: &mut B) {
+pub fn bbr3<B: Mos6502CompatibleBus>(cpu: &mut Cpu<B>, bus: &mut B) {
     branch_on_zero_page_bit::<3, false, B>(cpu, bus);
 }
-pub fn bbr4<B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, bus: &mut B) {
+pub fn bbr4<B: Mos6502CompatibleBus>(cpu: &mut Cpu<B>, bus: &mut B) {
     branch_on_zero_page_bit::<4, false, B>(cpu, bus);
 }
-pub fn bbr5<B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, bus: &mut B) {
+pub fn bbr5<B: Mos6502CompatibleBus>(cpu: &mut Cpu<B>, bus: &mut B) {
     branch_on_zero_page_bit::<5, false, B>(cpu, bus);
 }
-pub fn bbr6<B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, bus: &mut B) {
+pub fn bbr6<B: Mos6502CompatibleBus>(cpu: &mut Cpu<B>, bus: &mut B) {
     branch_on_zero_page_bit::<6, false, B>(cpu, bus);
 }
-pub fn bbr7<B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, bus: &mut B) {
+pub fn bbr7<B: Mos6502CompatibleBus>(cpu: &mut Cpu<B>, bus: &mut B) {
     branch_on_zero_page_bit::<7, false, B>(cpu, bus);
 }
 
-pub fn bbs0<B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, bus: &mut B) {
+pub fn bbs0<B: Mos6502CompatibleBus>(cpu: &mut Cpu<B>, bus: &mut B) {
     branch_on_zero_page_bit::<0, true, B>(cpu, bus);
 }
-pub fn bbs1<B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, bus: &mut B) {
+pub fn bbs1<B: Mos6502CompatibleBus>(cpu: &mut Cpu<B>, bus: &mut B) {
     branch_on_zero_page_bit::<1, true, B>(cpu, bus);
 }
-pub fn bbs2<B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, bus: &mut B) {
+pub fn bbs2<B: Mos6502CompatibleBus>(cpu: &mut Cpu<B>, bus: &mut B) {
     branch_on_zero_page_bit::<2, true, B>(cpu, bus);
 }
-pub fn bbs3<B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, bus: &mut B) {
+pub fn bbs3<B: Mos6502CompatibleBus>(cpu: &mut Cpu<B>, bus: &mut B) {
     branch_on_zero_page_bit::<3, true, B>(cpu, bus);
 }
-pub fn bbs4<B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, bus: &mut B) {
+pub fn bbs4<B: Mos6502CompatibleBus>(cpu: &mut Cpu<B>, bus: &mut B) {
     branch_on_zero_page_bit::<4, true, B>(cpu, bus);
 }
-pub fn bbs5<B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, bus: &mut B) {
+pub fn bbs5<B: Mos6502CompatibleBus>(cpu: &mut Cpu<B>, bus: &mut B) {
     branch_on_zero_page_bit::<5, true, B>(cpu, bus);
 }
-pub fn bbs6<B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, bus: &mut B) {
+pub fn bbs6<B: Mos6502CompatibleBus>(cpu: &mut Cpu<B>, bus: &mut B) {
     branch_on_zero_page_bit::<6, true, B>(cpu, bus);
 }
-pub fn bbs7<B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, bus: &mut B) {
+pub fn bbs7<B: Mos6502CompatibleBus>(cpu: &mut Cpu<B>, bus: &mut B) {
     branch_on_zero_page_bit::<7, true, B>(cpu, bus);
 }
 
-pub fn rmb0<B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, bus: &mut B) {
+pub fn rmb0<B: Mos6502CompatibleBus>(cpu: &mut Cpu<B>, bus: &mut B) {
     modify_zero_page_bit::<0, RESET, B>(cpu, bus);
 }
-pub fn rmb1<B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, bus: &mut B) {
+pub fn rmb1<B: Mos6502CompatibleBus>(cpu: &mut Cpu<B>, bus: &mut B) {
     modify_zero_page_bit::<1, RESET, B>(cpu, bus);
 }
-pub fn rmb2<B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, bus: &mut B) {
+pub fn rmb2<B: Mos6502CompatibleBus>(cpu: &mut Cpu<B>, bus: &mut B) {
     modify_zero_page_bit::<2, RESET, B>(cpu, bus);
 }
-pub fn rmb3<B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, bus: &mut B) {
+pub fn rmb3<B: Mos6502CompatibleBus>(cpu: &mut Cpu<B>, bus: &mut B) {
     modify_zero_page_bit::<3, RESET, B>(cpu, bus);
 }
-pub fn rmb4<B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, bus: &mut B) {
+pub fn rmb4<B: Mos6502CompatibleBus>(cpu: &mut Cpu<B>, bus: &mut B) {
     modify_zero_page_bit::<4, RESET, B>(cpu, bus);
 }
-pub fn rmb5<B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, bus: &mut B) {
+pub fn rmb5<B: Mos6502CompatibleBus>(cpu: &mut Cpu<B>, bus: &mut B) {
     modify_zero_page_bit::<5, RESET, B>(cpu, bus);
 }
-pub fn rmb6<B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, bus: &mut B) {
+pub fn rmb6<B: Mos6502CompatibleBus>(cpu: &mut Cpu<B>, bus: &mut B) {
     modify_zero_page_bit::<6, RESET, B>(cpu, bus);
 }
-pub fn rmb7<B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, bus: &mut B) {
+pub fn rmb7<B: Mos6502CompatibleBus>(cpu: &mut Cpu<B>, bus: &mut B) {
     modify_zero_page_bit::<7, RESET, B>(cpu, bus);
 }
-pub fn smb0<B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, bus: &mut B) {
+pub fn smb0<B: Mos6502CompatibleBus>(cpu: &mut Cpu<B>, bus: &mut B) {
     modify_zero_page_bit::<0, SET, B>(cpu, bus);
 }
-pub fn smb1<B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, bus: &mut B) {
+pub fn smb1<B: Mos6502CompatibleBus>(cpu: &mut Cpu<B>, bus: &mut B) {
     modify_zero_page_bit::<1, SET, B>(cpu, bus);
 }
-pub fn smb2<B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, bus: &mut B) {
+pub fn smb2<B: Mos6502CompatibleBus>(cpu: &mut Cpu<B>, bus: &mut B) {
     modify_zero_page_bit::<2, SET, B>(cpu, bus);
 }
-pub fn smb3<B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, bus: &mut B) {
+pub fn smb3<B: Mos6502CompatibleBus>(cpu: &mut Cpu<B>, bus: &mut B) {
     modify_zero_page_bit::<3, SET, B>(cpu, bus);
 }
-pub fn smb4<B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, bus: &mut B) {
+pub fn smb4<B: Mos6502CompatibleBus>(cpu: &mut Cpu<B>, bus: &mut B) {
     modify_zero_page_bit::<4, SET, B>(cpu, bus);
 }
-pub fn smb5<B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, bus: &mut B) {
+pub fn smb5<B: Mos6502CompatibleBus>(cpu: &mut Cpu<B>, bus: &mut B) {
     modify_zero_page_bit::<5, SET, B>(cpu, bus);
 }
-pub fn smb6<B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, bus: &mut B) {
+pub fn smb6<B: Mos6502CompatibleBus>(cpu: &mut Cpu<B>, bus: &mut B) {
     modify_zero_page_bit::<6, SET, B>(cpu, bus);
 }
-pub fn smb7<B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, bus: &mut B) {
+pub fn smb7<B: Mos6502CompatibleBus>(cpu: &mut Cpu<B>, bus: &mut B) {
     modify_zero_page_bit::<7, SET, B>(cpu, bus);
 }
-pub fn stp<B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, _bus: &mut B) {
+pub fn stp<B: Mos6502CompatibleBus>(cpu: &mut Cpu<B>, _bus: &mut B) {
     cpu.run_state = RunState::Halted;
 }
-pub fn wai<B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, _bus: &mut B) {
+pub fn wai<B: Mos6502CompatibleBus>(cpu: &mut Cpu<B>, _bus: &mut B) {
     cpu.run_state = RunState::Waiting;
-    cpu.pc += 1;
+    cpu.pc += Implied::BYTES;
 }
 
-pub fn brk<B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, bus: &mut B) {
+pub fn brk<B: Mos6502CompatibleBus>(cpu: &mut Cpu<B>, bus: &mut B) {
     let return_pc = word!(cpu.pc + 2);
 
     cpu.push(bus, return_pc.hi());
@@ -1001,14 +1508,14 @@ pub fn brk<B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, bus: &mut B) {
     cpu.p.set_decimal_mode(false);
     cpu.p.set_interrupt_disabled(true);
 
-    let lo = bus.read(IRQ_VECTOR_LO, AccessType::InterruptVectorRead);
-    let hi = bus.read(IRQ_VECTOR_HI, AccessType::InterruptVectorRead);
+    let lo = bus.cpu_read(IRQ_VECTOR_LO, AccessType::InterruptVectorRead);
+    let hi = bus.cpu_read(IRQ_VECTOR_HI, AccessType::InterruptVectorRead);
     cpu.pc = word!((lo, hi));
 }
 
-pub fn bit<B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, bus: &mut B) {
+pub fn bit<B: Mos6502CompatibleBus>(cpu: &mut Cpu<B>, bus: &mut B) {
     let addr = Immediate::fetch_address(cpu, bus);
-    let operand = bus.read(addr, AccessType::DataRead);
+    let operand = cpu.read_data(bus, addr);
 
     cpu.p.set_zero((cpu.a & operand) == Byte(0));
 
@@ -1018,24 +1525,21 @@ pub fn bit<B: Mos6502CompatibleBus + 'static>(cpu: &mut Cpu<B>, bus: &mut B) {
 fn branch_on_zero_page_bit<
     const BIT: u8,
     const BRANCH_WHEN_SET: bool,
-    B: Mos6502CompatibleBus + 'static,
+    B: Mos6502CompatibleBus,
 >(
     cpu: &mut Cpu<B>,
     bus: &mut B,
 ) {
     debug_assert!(BIT < 8);
 
-    let zp_addr: Word = bus.read(cpu.pc + 1, AccessType::DataRead).into();
-    let value = bus.read(zp_addr, AccessType::DataRead).as_u8();
-    let rel = i8::from(bus.read(cpu.pc + 2, AccessType::DataRead));
+    let zp_addr = cpu.zero_page(cpu.operand(bus, 1));
+    let value = cpu.read_data(bus, zp_addr);
+    let rel = i8::from(cpu.operand(bus, 2));
 
     let base = cpu.pc + 3u16;
     cpu.pc = base;
 
-    let mask = 1u8 << BIT;
-    let bit_set = (value & mask) != 0;
-
-    if bit_set == BRANCH_WHEN_SET {
+    if value.bit(BIT) == BRANCH_WHEN_SET {
         let target = base + rel;
         cpu.cycles += 1;
         if cpu.crosses_page(base, target) {
@@ -1045,34 +1549,28 @@ fn branch_on_zero_page_bit<
     }
 }
 
-fn modify_zero_page_bit<const BIT: u8, const SET_BIT: bool, B: Mos6502CompatibleBus + 'static>(
+fn modify_zero_page_bit<const BIT: u8, const SET_BIT: bool, B: Mos6502CompatibleBus>(
     cpu: &mut Cpu<B>,
     bus: &mut B,
 ) {
     debug_assert!(BIT < 8);
 
-    let zp_addr: Word = bus.read(cpu.pc + 1, AccessType::DataRead).into();
-    let mut value = bus.read(zp_addr, AccessType::DataRead);
-    let mask = 1u8 << BIT;
-
-    if SET_BIT {
-        value |= mask;
-    } else {
-        value &= !mask;
-    }
+    let zp_addr = cpu.zero_page(cpu.operand(bus, 1));
+    let value = read_for_modify::<Wdc65c02s, _>(cpu, bus, zp_addr);
 
-    bus.write(zp_addr, value, AccessType::DataWrite);
+    bus.cpu_write(zp_addr, value.with_bit(BIT, SET_BIT), AccessType::DataWrite);
     cpu.pc += 2;
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::processor::addressing_mode::{AbsoluteX, Immediate, ZeroPage};
+    use crate::processor::addressing_mode::{AbsoluteX, ZeroPage};
     use crate::processor::flags::Flags;
     use crate::SimpleBus;
-    use crate::{RunState, STACK_SPACE_START};
-    use ull::{byte, word, Bus};
+    use crate::processor::cpu::Interrupt;
+    use crate::{RunState, IRQ_VECTOR_LO, NMI_VECTOR_LO, STACK_SPACE_START};
+    use ull::{byte, word, Address, Bus};
     type TestBus = SimpleBus;
 
     #[test]
@@ -1082,11 +1580,36 @@ mod tests {
         cpu.pc = word!(0x4000);
         bus.write(cpu.pc + 1, byte!(0x04), AccessType::DataWrite);
 
-        bra::<Immediate, _>(&mut cpu, &mut bus);
+        bra::<Relative, _>(&mut cpu, &mut bus);
 
         assert_eq!(cpu.pc, word!(0x4006));
     }
 
+    #[test]
+    fn test_indirect_jumps_take_six_cycles() {
+        let mut bus = TestBus::default();
+        // JMP ($10FF) with the pointer straddling a page, then JMP ($2000,X) with X=2.
+        bus.write_block(word!(0x10FF), &[0x00, 0x30], AccessType::DataWrite);
+        bus.write_block(word!(0x2002), &[0x00, 0x40], AccessType::DataWrite);
+        bus.write_block(word!(0x3000), &[0x7C, 0x00, 0x20], AccessType::DataWrite);
+        let mut cpu: Cpu<TestBus> = Cpu::with_program::<Wdc65c02s>(
+            &mut bus,
+            word!(0x8000),
+            &[0x6C, 0xFF, 0x10],
+            word!(0x8000),
+        );
+        cpu.x = byte!(0x02);
+
+        assert_eq!(cpu.step(&mut bus), 6);
+        assert_eq!(cpu.pc, word!(0x3000));
+        assert_eq!(cpu.step(&mut bus), 6);
+        assert_eq!(cpu.pc, word!(0x4000));
+
+        // The NMOS part keeps the 5-cycle (and page-wrapping) JMP (abs).
+        let table = crate::instruction::mos6502::Mos6502::base_table::<TestBus>();
+        assert_eq!(table[0x6C].cycles, 5);
+    }
+
     #[test]
     fn test_stz_zero_page_clears_memory() {
         let mut bus = TestBus::default();
@@ -1326,6 +1849,31 @@ mod tests {
         assert_eq!(cpu.run_state, RunState::Halted);
     }
 
+    #[test]
+    fn test_stp_resumes_only_after_reset() {
+        let mut bus = TestBus::default();
+        // STP; INX, with the reset vector pointing at the INX.
+        let mut cpu: Cpu<TestBus> =
+            Cpu::with_program::<Wdc65c02s>(&mut bus, word!(0x8000u16), &[0xDB, 0xE8], word!(0x8001u16));
+        cpu.pc = word!(0x8000u16);
+
+        cpu.step(&mut bus);
+        assert_eq!(cpu.run_state, RunState::Halted);
+
+        cpu.request_interrupt(Interrupt::Irq);
+        cpu.request_interrupt(Interrupt::Nmi);
+        assert_eq!(cpu.step(&mut bus), 0);
+        assert_eq!(cpu.run_state, RunState::Halted);
+
+        cpu.request_interrupt(Interrupt::Reset);
+        cpu.step(&mut bus);
+        assert_eq!(cpu.run_state, RunState::Running);
+        assert_eq!(cpu.pc, word!(0x8001u16));
+
+        cpu.step(&mut bus);
+        assert_eq!(cpu.x, byte!(0x01));
+    }
+
     #[test]
     fn test_wai_sets_waiting_and_advances_pc() {
         let mut bus = TestBus::default();
@@ -1337,4 +1885,149 @@ mod tests {
         assert_eq!(cpu.run_state, RunState::Waiting);
         assert_eq!(cpu.pc, start + 1);
     }
+
+    fn decimal_adc_cycles<S: InstructionSet>() -> u8 {
+        let mut bus = TestBus::default();
+        // SED; ADC #$01
+        let mut cpu: Cpu<TestBus> =
+            Cpu::with_program::<S>(&mut bus, word!(0x8000u16), &[0xF8, 0x69, 0x01], word!(0x8000u16));
+
+        cpu.step(&mut bus);
+        cpu.step(&mut bus)
+    }
+
+    #[test]
+    fn test_decimal_adc_takes_an_extra_cycle() {
+        assert_eq!(decimal_adc_cycles::<Wdc65c02s>(), 3);
+        assert_eq!(decimal_adc_cycles::<Mos6502>(), 2);
+    }
+
+    #[test]
+    fn test_decimal_sbc_penalty_stacks_with_page_cross() {
+        let mut bus = TestBus::default();
+        bus.write(word!(0x2100u16), byte!(0x01), AccessType::DataWrite);
+        // SED; SEC; LDX #$FF; SBC $2001,X
+        let program = [0xF8, 0x38, 0xA2, 0xFF, 0xFD, 0x01, 0x20];
+        let mut cpu: Cpu<TestBus> =
+            Cpu::with_program::<Wdc65c02s>(&mut bus, word!(0x8000u16), &program, word!(0x8000u16));
+
+        for _ in 0..3 {
+            cpu.step(&mut bus);
+        }
+        assert_eq!(cpu.step(&mut bus), 6);
+    }
+
+    fn waiting_cpu(disable_irq: bool) -> (Cpu<TestBus>, TestBus) {
+        let mut bus = TestBus::default();
+        bus.write_block(IRQ_VECTOR_LO, &[0x00, 0x90], AccessType::DataWrite);
+        // WAI; INX
+        let mut cpu: Cpu<TestBus> =
+            Cpu::with_program::<Wdc65c02s>(&mut bus, word!(0x8000u16), &[0xCB, 0xE8], word!(0x8000u16));
+        cpu.p.set_interrupt_disabled(disable_irq);
+
+        cpu.step(&mut bus);
+        assert_eq!(cpu.step(&mut bus), 0);
+        assert_eq!(cpu.run_state, RunState::Waiting);
+        (cpu, bus)
+    }
+
+    #[test]
+    fn test_wai_resumes_after_itself_on_masked_irq() {
+        let (mut cpu, mut bus) = waiting_cpu(true);
+
+        cpu.request_interrupt(Interrupt::Irq);
+        cpu.step(&mut bus);
+
+        assert_eq!(cpu.run_state, RunState::Running);
+        assert_eq!(cpu.x, byte!(0x01));
+        assert_eq!(cpu.pc, word!(0x8002u16));
+        assert!(cpu.irq_line());
+    }
+
+    #[test]
+    fn test_wai_vectors_on_unmasked_irq() {
+        let (mut cpu, mut bus) = waiting_cpu(false);
+
+        cpu.request_interrupt(Interrupt::Irq);
+        cpu.step(&mut bus);
+
+        assert_eq!(cpu.run_state, RunState::Running);
+        assert_eq!(cpu.pc, word!(0x9000u16));
+        assert_eq!(cpu.x, byte!(0x00));
+        // The pushed return address is the instruction after WAI.
+        assert_eq!(cpu.peek_stack(&mut bus, 1), byte!(0x01));
+        assert_eq!(cpu.peek_stack(&mut bus, 2), byte!(0x80));
+    }
+
+    #[test]
+    fn test_wai_always_wakes_on_nmi() {
+        let (mut cpu, mut bus) = waiting_cpu(true);
+        bus.write_block(NMI_VECTOR_LO, &[0x00, 0xA0], AccessType::DataWrite);
+
+        cpu.request_interrupt(Interrupt::Nmi);
+        cpu.step(&mut bus);
+
+        assert_eq!(cpu.run_state, RunState::Running);
+        assert_eq!(cpu.pc, word!(0xA000u16));
+    }
+
+    #[test]
+    fn test_single_cycle_nops_consume_one_byte_and_one_cycle() {
+        // Every $x3/$xB opcode except WAI ($CB) and STP ($DB) is a 1-byte, 1-cycle NOP.
+        let opcodes = (0x00..=0xFFu8)
+            .filter(|op| matches!(op & 0x0F, 0x03 | 0x0B))
+            .filter(|op| !matches!(op, 0xCB | 0xDB));
+
+        for opcode in opcodes {
+            let mut bus = TestBus::default();
+            let mut cpu: Cpu<TestBus> = Cpu::with_instruction_set::<Wdc65c02s>();
+            cpu.pc = word!(0x0200u16);
+            cpu.a = byte!(0x11);
+            cpu.x = byte!(0x22);
+            cpu.y = byte!(0x33);
+            let p = cpu.p;
+            let sp = cpu.sp;
+            // Fill the would-be operand bytes so a stray 2/3-byte decode is visible.
+            bus.write_block(word!(0x0200u16), &[opcode, 0xFF, 0xFF], AccessType::DataWrite);
+            let mut before = [0u8; 0x400];
+            bus.read_block(word!(0u16), &mut before, AccessType::DataRead);
+
+            let cycles = cpu.step(&mut bus);
+
+            assert_eq!(cycles, 1, "opcode {opcode:02X}");
+            assert_eq!(cpu.pc, word!(0x0201u16), "opcode {opcode:02X}");
+            assert_eq!(cpu.a, byte!(0x11), "opcode {opcode:02X}");
+            assert_eq!(cpu.x, byte!(0x22), "opcode {opcode:02X}");
+            assert_eq!(cpu.y, byte!(0x33), "opcode {opcode:02X}");
+            assert_eq!(cpu.sp, sp, "opcode {opcode:02X}");
+            assert_eq!(cpu.p.bits(), p.bits(), "opcode {opcode:02X}");
+            assert_eq!(cpu.run_state, RunState::Running, "opcode {opcode:02X}");
+
+            let mut after = [0u8; 0x400];
+            bus.read_block(word!(0u16), &mut after, AccessType::DataRead);
+            assert_eq!(before, after, "opcode {opcode:02X} touched memory");
+        }
+    }
+
+    #[test]
+    fn table_metadata_names_known_opcodes() {
+        let table = Wdc65c02s::base_table::<TestBus>();
+        let expected = [
+            (0x04, "TSB", AddressingModeKind::ZeroPage),
+            (0x1A, "INC", AddressingModeKind::Accumulator),
+            (0x7C, "JMP", AddressingModeKind::AbsoluteIndirectX),
+            (0x80, "BRA", AddressingModeKind::Relative),
+            (0x89, "BIT", AddressingModeKind::Immediate),
+            (0x9C, "STZ", AddressingModeKind::Absolute),
+            (0xB2, "LDA", AddressingModeKind::ZeroPageIndirect),
+            (0xCB, "WAI", AddressingModeKind::Implied),
+            (0xDB, "STP", AddressingModeKind::Implied),
+            (0xF7, "SMB7", AddressingModeKind::ZeroPage),
+            (0xFF, "BBS7", AddressingModeKind::ZeroPageRelative),
+        ];
+        for (opcode, mnemonic, mode) in expected {
+            assert_eq!(table[opcode].mnemonic, mnemonic, "opcode {opcode:02X}");
+            assert_eq!(table[opcode].mode, mode, "opcode {opcode:02X}");
+        }
+    }
 }
@@ -0,0 +1,1221 @@
+//! Opcode semantics for the WDC 65C816, executed by [`Cpu816`].
+//!
+//! Unlike the 6502 tables, the 65C816 isn't expressed as an [`InstructionTable`](crate::InstructionTable):
+//! the same opcode moves one or two bytes depending on the M/X flags, and most instructions
+//! address 24-bit memory, neither of which fits `fn(&mut Cpu<B>, &mut B)`. Instead
+//! [`Cpu816::step`] decodes through a single `match`, charging [`Wdc65c816::CYCLES`] plus the usual
+//! datasheet adjustments (16-bit operands, direct page not page-aligned, index page crossings,
+//! taken branches).
+
+use crate::bus::Mos6502CompatibleBus;
+use crate::processor::cpu::RunState;
+use crate::processor::cpu816::{
+    long_address, Cpu816, EMULATION_COP_VECTOR, EMULATION_IRQ_VECTOR, NATIVE_BRK_VECTOR,
+    NATIVE_COP_VECTOR,
+};
+use crate::processor::flags::Flags;
+use crate::AccessType;
+use ull::{Byte, Word};
+
+/// Marker for the 65C816 instruction set; run it with [`Cpu816`].
+pub struct Wdc65c816;
+
+impl Wdc65c816 {
+    /// Assembler mnemonic for each opcode.
+    pub const MNEMONICS: [&'static str; 256] = [
+        "BRK", "ORA", "COP", "ORA", "TSB", "ORA", "ASL", "ORA", "PHP", "ORA", "ASL", "PHD", "TSB", "ORA", "ASL", "ORA", //
+        "BPL", "ORA", "ORA", "ORA", "TRB", "ORA", "ASL", "ORA", "CLC", "ORA", "INC", "TCS", "TRB", "ORA", "ASL", "ORA", //
+        "JSR", "AND", "JSL", "AND", "BIT", "AND", "ROL", "AND", "PLP", "AND", "ROL", "PLD", "BIT", "AND", "ROL", "AND", //
+        "BMI", "AND", "AND", "AND", "BIT", "AND", "ROL", "AND", "SEC", "AND", "DEC", "TSC", "BIT", "AND", "ROL", "AND", //
+        "RTI", "EOR", "WDM", "EOR", "MVP", "EOR", "LSR", "EOR", "PHA", "EOR", "LSR", "PHK", "JMP", "EOR", "LSR", "EOR", //
+        "BVC", "EOR", "EOR", "EOR", "MVN", "EOR", "LSR", "EOR", "CLI", "EOR", "PHY", "TCD", "JML", "EOR", "LSR", "EOR", //
+        "RTS", "ADC", "PER", "ADC", "STZ", "ADC", "ROR", "ADC", "PLA", "ADC", "ROR", "RTL", "JMP", "ADC", "ROR", "ADC", //
+        "BVS", "ADC", "ADC", "ADC", "STZ", "ADC", "ROR", "ADC", "SEI", "ADC", "PLY", "TDC", "JMP", "ADC", "ROR", "ADC", //
+        "BRA", "STA", "BRL", "STA", "STY", "STA", "STX", "STA", "DEY", "BIT", "TXA", "PHB", "STY", "STA", "STX", "STA", //
+        "BCC", "STA", "STA", "STA", "STY", "STA", "STX", "STA", "TYA", "STA", "TXS", "TXY", "STZ", "STA", "STZ", "STA", //
+        "LDY", "LDA", "LDX", "LDA", "LDY", "LDA", "LDX", "LDA", "TAY", "LDA", "TAX", "PLB", "LDY", "LDA", "LDX", "LDA", //
+        "BCS", "LDA", "LDA", "LDA", "LDY", "LDA", "LDX", "LDA", "CLV", "LDA", "TSX", "TYX", "LDY", "LDA", "LDX", "LDA", //
+        "CPY", "CMP", "REP", "CMP", "CPY", "CMP", "DEC", "CMP", "INY", "CMP", "DEX", "WAI", "CPY", "CMP", "DEC", "CMP", //
+        "BNE", "CMP", "CMP", "CMP", "PEI", "CMP", "DEC", "CMP", "CLD", "CMP", "PHX", "STP", "JML", "CMP", "DEC", "CMP", //
+        "CPX", "SBC", "SEP", "SBC", "CPX", "SBC", "INC", "SBC", "INX", "SBC", "NOP", "XBA", "CPX", "SBC", "INC", "SBC", //
+        "BEQ", "SBC", "SBC", "SBC", "PEA", "SBC", "INC", "SBC", "SED", "SBC", "PLX", "XCE", "JSR", "SBC", "INC", "SBC", //
+    ];
+
+    /// Base cycle counts with 8-bit registers, a page-aligned direct page, no page crossings
+    /// and untaken branches. `BRK`/`COP` list their emulation-mode cost; native mode adds one.
+    pub const CYCLES: [u8; 256] = CYCLES;
+}
+
+const CYCLES: [u8; 256] = [
+    7, 6, 7, 4, 5, 3, 5, 6, 3, 2, 2, 4, 6, 4, 6, 5, //
+    2, 5, 5, 7, 5, 4, 6, 6, 2, 4, 2, 2, 6, 4, 7, 5, //
+    6, 6, 8, 4, 3, 3, 5, 6, 4, 2, 2, 5, 4, 4, 6, 5, //
+    2, 5, 5, 7, 4, 4, 6, 6, 2, 4, 2, 2, 4, 4, 7, 5, //
+    6, 6, 2, 4, 7, 3, 5, 6, 3, 2, 2, 3, 3, 4, 6, 5, //
+    2, 5, 5, 7, 7, 4, 6, 6, 2, 4, 3, 2, 4, 4, 7, 5, //
+    6, 6, 6, 4, 3, 3, 5, 6, 4, 2, 2, 6, 5, 4, 6, 5, //
+    2, 5, 5, 7, 4, 4, 6, 6, 2, 4, 4, 2, 6, 4, 7, 5, //
+    3, 6, 4, 4, 3, 3, 3, 6, 2, 2, 2, 3, 4, 4, 4, 5, //
+    2, 6, 5, 7, 4, 4, 4, 6, 2, 5, 2, 2, 4, 5, 5, 5, //
+    2, 6, 2, 4, 3, 3, 3, 6, 2, 2, 2, 4, 4, 4, 4, 5, //
+    2, 5, 5, 7, 4, 4, 4, 6, 2, 4, 2, 2, 4, 4, 4, 5, //
+    2, 6, 3, 4, 3, 3, 5, 6, 2, 2, 2, 3, 4, 4, 6, 5, //
+    2, 5, 5, 7, 6, 4, 6, 6, 2, 4, 3, 3, 6, 4, 7, 5, //
+    2, 6, 3, 4, 3, 3, 5, 6, 2, 2, 2, 3, 4, 4, 6, 5, //
+    2, 5, 5, 7, 5, 4, 6, 6, 2, 4, 4, 2, 8, 4, 7, 5, //
+];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Mode {
+    Immediate,
+    Direct,
+    DirectX,
+    DirectY,
+    DirectIndirect,
+    DirectIndirectLong,
+    DirectXIndirect,
+    DirectIndirectY,
+    DirectIndirectLongY,
+    Absolute,
+    AbsoluteX,
+    AbsoluteY,
+    Long,
+    LongX,
+    StackRelative,
+    StackRelativeIndirectY,
+}
+
+impl Mode {
+    fn is_direct(self) -> bool {
+        matches!(
+            self,
+            Mode::Direct
+                | Mode::DirectX
+                | Mode::DirectY
+                | Mode::DirectIndirect
+                | Mode::DirectIndirectLong
+                | Mode::DirectXIndirect
+                | Mode::DirectIndirectY
+                | Mode::DirectIndirectLongY
+        )
+    }
+}
+
+/// Effective address of a data operand.
+#[derive(Clone, Copy, Debug)]
+struct Operand {
+    addr: usize,
+    /// Direct page, stack and immediate operands wrap within their bank instead of carrying
+    /// into the next one.
+    wrap_bank: bool,
+}
+
+impl Operand {
+    fn next(self) -> usize {
+        if self.wrap_bank {
+            (self.addr & 0xFF_0000) | ((self.addr + 1) & 0xFFFF)
+        } else {
+            (self.addr + 1) & 0xFF_FFFF
+        }
+    }
+}
+
+const fn mask(wide: bool) -> u16 {
+    if wide { 0xFFFF } else { 0x00FF }
+}
+
+const fn sign(wide: bool) -> u16 {
+    if wide { 0x8000 } else { 0x0080 }
+}
+
+/// ORA/AND/EOR/ADC/STA/LDA/CMP/SBC occupy every `x1`, `x3`, `x5`, `x7`, `x9`, `xD` and `xF`
+/// column plus `(dp)` at `x2` of odd rows; `$89` is `BIT #` instead of `STA #`.
+fn group_one_mode(opcode: u8) -> Option<Mode> {
+    if opcode == 0x89 {
+        return None;
+    }
+    Some(match opcode & 0x1F {
+        0x01 => Mode::DirectXIndirect,
+        0x03 => Mode::StackRelative,
+        0x05 => Mode::Direct,
+        0x07 => Mode::DirectIndirectLong,
+        0x09 => Mode::Immediate,
+        0x0D => Mode::Absolute,
+        0x0F => Mode::Long,
+        0x11 => Mode::DirectIndirectY,
+        0x12 => Mode::DirectIndirect,
+        0x13 => Mode::StackRelativeIndirectY,
+        0x15 => Mode::DirectX,
+        0x17 => Mode::DirectIndirectLongY,
+        0x19 => Mode::AbsoluteY,
+        0x1D => Mode::AbsoluteX,
+        0x1F => Mode::LongX,
+        _ => return None,
+    })
+}
+
+impl<B: Mos6502CompatibleBus> Cpu816<B> {
+    fn accumulator(&self) -> u16 {
+        self.a.0 & mask(self.wide_accumulator())
+    }
+
+    /// Write the accumulator at the current M width, preserving B when 8-bit.
+    fn set_accumulator(&mut self, value: u16) {
+        self.a.0 = if self.wide_accumulator() {
+            value
+        } else {
+            (self.a.0 & 0xFF00) | (value & 0x00FF)
+        };
+    }
+
+    fn index_value(&self, value: u16) -> u16 {
+        value & mask(self.wide_index())
+    }
+
+    fn set_nz(&mut self, value: u16, wide: bool) {
+        let value = value & mask(wide);
+        self.p.set_zero(value == 0);
+        self.p.set_signed(value & sign(wide) != 0);
+    }
+
+    /// Direct page address of `offset + index`. In emulation mode with a page-aligned direct
+    /// page, indexing wraps within the page like 6502 zero page does.
+    fn direct_address(&self, offset: u8, index: u16) -> u16 {
+        if self.emulation && self.d.0 & 0x00FF == 0 {
+            self.d.0 | (u16::from(offset).wrapping_add(index) & 0x00FF)
+        } else {
+            self.d.0.wrapping_add(u16::from(offset)).wrapping_add(index)
+        }
+    }
+
+    fn read_direct_word(&self, bus: &mut B, addr: u16) -> u16 {
+        let lo = bus.cpu_read(addr as usize, AccessType::DataRead).0;
+        let hi_addr = if self.emulation && self.d.0 & 0x00FF == 0 {
+            (addr & 0xFF00) | (addr.wrapping_add(1) & 0x00FF)
+        } else {
+            addr.wrapping_add(1)
+        };
+        let hi = bus.cpu_read(hi_addr as usize, AccessType::DataRead).0;
+        u16::from_le_bytes([lo, hi])
+    }
+
+    fn read_direct_long(&self, bus: &mut B, addr: u16) -> usize {
+        let ptr = self.read_direct_word(bus, addr);
+        let bank = bus.cpu_read(addr.wrapping_add(2) as usize, AccessType::DataRead).0;
+        long_address(bank, ptr)
+    }
+
+    fn data_address(&self, addr: u16) -> usize {
+        long_address(self.dbr.0, addr)
+    }
+
+    /// Index a 24-bit base, charging the read penalty when requested.
+    fn indexed(&mut self, base: usize, index: u16, penalize: bool) -> usize {
+        let addr = (base + index as usize) & 0xFF_FFFF;
+        if penalize && (self.wide_index() || (base ^ addr) & 0xFF00 != 0) {
+            self.cycles += 1;
+        }
+        addr
+    }
+
+    /// Fetch the operand bytes for `mode` and compute the effective address.
+    ///
+    /// `wide` sizes immediate operands; `read` enables the index page-crossing penalty, which
+    /// stores and read-modify-write instructions already include in their base cost.
+    fn operand(&mut self, bus: &mut B, mode: Mode, wide: bool, read: bool) -> Operand {
+        if mode.is_direct() && self.d.0 & 0x00FF != 0 {
+            self.cycles += 1;
+        }
+
+        let (addr, wrap_bank) = match mode {
+            Mode::Immediate => {
+                let addr = long_address(self.pbr.0, self.pc.0);
+                self.pc += if wide { 2 } else { 1 };
+                (addr, true)
+            }
+            Mode::Direct => {
+                let offset = self.fetch_byte(bus);
+                (self.direct_address(offset, 0) as usize, true)
+            }
+            Mode::DirectX => {
+                let offset = self.fetch_byte(bus);
+                (self.direct_address(offset, self.x.0) as usize, true)
+            }
+            Mode::DirectY => {
+                let offset = self.fetch_byte(bus);
+                (self.direct_address(offset, self.y.0) as usize, true)
+            }
+            Mode::DirectIndirect => {
+                let offset = self.fetch_byte(bus);
+                let ptr = self.read_direct_word(bus, self.direct_address(offset, 0));
+                (self.data_address(ptr), false)
+            }
+            Mode::DirectIndirectLong => {
+                let offset = self.fetch_byte(bus);
+                (self.read_direct_long(bus, self.direct_address(offset, 0)), false)
+            }
+            Mode::DirectXIndirect => {
+                let offset = self.fetch_byte(bus);
+                let ptr = self.read_direct_word(bus, self.direct_address(offset, self.x.0));
+                (self.data_address(ptr), false)
+            }
+            Mode::DirectIndirectY => {
+                let offset = self.fetch_byte(bus);
+                let ptr = self.read_direct_word(bus, self.direct_address(offset, 0));
+                let base = self.data_address(ptr);
+                (self.indexed(base, self.y.0, read), false)
+            }
+            Mode::DirectIndirectLongY => {
+                let offset = self.fetch_byte(bus);
+                let base = self.read_direct_long(bus, self.direct_address(offset, 0));
+                ((base + self.y.0 as usize) & 0xFF_FFFF, false)
+            }
+            Mode::Absolute => {
+                let addr = self.fetch_word(bus);
+                (self.data_address(addr), false)
+            }
+            Mode::AbsoluteX => {
+                let base = self.fetch_word(bus);
+                let base = self.data_address(base);
+                (self.indexed(base, self.x.0, read), false)
+            }
+            Mode::AbsoluteY => {
+                let base = self.fetch_word(bus);
+                let base = self.data_address(base);
+                (self.indexed(base, self.y.0, read), false)
+            }
+            Mode::Long => (self.fetch_long(bus), false),
+            Mode::LongX => {
+                let base = self.fetch_long(bus);
+                ((base + self.x.0 as usize) & 0xFF_FFFF, false)
+            }
+            Mode::StackRelative => {
+                let offset = self.fetch_byte(bus);
+                (self.sp.0.wrapping_add(u16::from(offset)) as usize, true)
+            }
+            Mode::StackRelativeIndirectY => {
+                let offset = self.fetch_byte(bus);
+                let ptr_addr = self.sp.0.wrapping_add(u16::from(offset));
+                let ptr = self.read_word(bus, 0, ptr_addr, AccessType::DataRead);
+                let base = self.data_address(ptr);
+                ((base + self.y.0 as usize) & 0xFF_FFFF, false)
+            }
+        };
+
+        Operand { addr, wrap_bank }
+    }
+
+    fn read_operand(&mut self, bus: &mut B, operand: Operand, wide: bool) -> u16 {
+        let lo = bus.cpu_read(operand.addr, AccessType::DataRead).0;
+        let hi = if wide {
+            bus.cpu_read(operand.next(), AccessType::DataRead).0
+        } else {
+            0
+        };
+        u16::from_le_bytes([lo, hi])
+    }
+
+    fn write_operand(&mut self, bus: &mut B, operand: Operand, value: u16, wide: bool) {
+        let [lo, hi] = value.to_le_bytes();
+        bus.cpu_write(operand.addr, Byte(lo), AccessType::DataWrite);
+        if wide {
+            bus.cpu_write(operand.next(), Byte(hi), AccessType::DataWrite);
+        }
+    }
+
+    /// Charge the extra cycle a 16-bit operand costs.
+    fn charge_wide(&mut self, wide: bool) {
+        if wide {
+            self.cycles += 1;
+        }
+    }
+
+    fn compare(&mut self, register: u16, value: u16, wide: bool) {
+        let register = register & mask(wide);
+        self.p.set_carry(register >= value);
+        self.set_nz(register.wrapping_sub(value), wide);
+    }
+
+    fn add_with_carry(&mut self, value: u16) {
+        let wide = self.wide_accumulator();
+        let a = i32::from(self.accumulator());
+        let value = i32::from(value);
+        let carry = i32::from(self.p.contains(Flags::Carry));
+        let top = i32::from(mask(wide));
+
+        let mut result = if self.p.contains(Flags::DecimalMode) {
+            let digits = if wide { 4 } else { 2 };
+            let mut result = 0;
+            let mut digit_carry = carry;
+            for digit in 0..digits {
+                let shift = digit * 4;
+                let mut sum = ((a >> shift) & 0xF) + ((value >> shift) & 0xF) + digit_carry;
+                if digit == digits - 1 {
+                    result += sum << shift;
+                } else {
+                    if sum > 9 {
+                        sum += 6;
+                    }
+                    digit_carry = i32::from(sum > 0xF);
+                    result |= (sum & 0xF) << shift;
+                }
+            }
+            result
+        } else {
+            a + value + carry
+        };
+
+        let sign = i32::from(sign(wide));
+        self.p
+            .set_overflow(!(a ^ value) & (a ^ result) & sign != 0);
+        let decimal_limit = if wide { 0x9FFF } else { 0x9F };
+        if self.p.contains(Flags::DecimalMode) && result > decimal_limit {
+            result += if wide { 0x6000 } else { 0x60 };
+        }
+        self.p.set_carry(result > top);
+        let result = (result & top) as u16;
+        self.set_accumulator(result);
+        self.set_nz(result, wide);
+    }
+
+    fn subtract_with_borrow(&mut self, value: u16) {
+        let wide = self.wide_accumulator();
+        let a = i32::from(self.accumulator());
+        let top = i32::from(mask(wide));
+        let value = !i32::from(value) & top;
+        let carry = i32::from(self.p.contains(Flags::Carry));
+
+        let mut result = if self.p.contains(Flags::DecimalMode) {
+            let digits = if wide { 4 } else { 2 };
+            let mut result = 0;
+            let mut digit_carry = carry;
+            for digit in 0..digits {
+                let shift = digit * 4;
+                let mut sum = ((a >> shift) & 0xF) + ((value >> shift) & 0xF) + digit_carry;
+                if digit == digits - 1 {
+                    result += sum << shift;
+                } else {
+                    if sum <= 0xF {
+                        sum -= 6;
+                    }
+                    digit_carry = i32::from(sum > 0xF);
+                    result += (sum & 0xF) << shift;
+                }
+            }
+            result
+        } else {
+            a + value + carry
+        };
+
+        let sign = i32::from(sign(wide));
+        self.p
+            .set_overflow(!(a ^ value) & (a ^ result) & sign != 0);
+        if self.p.contains(Flags::DecimalMode) && result <= top {
+            result -= if wide { 0x6000 } else { 0x60 };
+        }
+        self.p.set_carry(result > top);
+        let result = (result & top) as u16;
+        self.set_accumulator(result);
+        self.set_nz(result, wide);
+    }
+
+    fn shift_left(&mut self, value: u16, wide: bool, carry_in: bool) -> u16 {
+        self.p.set_carry(value & sign(wide) != 0);
+        let result = ((value << 1) | u16::from(carry_in)) & mask(wide);
+        self.set_nz(result, wide);
+        result
+    }
+
+    fn shift_right(&mut self, value: u16, wide: bool, carry_in: bool) -> u16 {
+        self.p.set_carry(value & 1 != 0);
+        let result = (value >> 1) | if carry_in { sign(wide) } else { 0 };
+        self.set_nz(result, wide);
+        result
+    }
+
+    /// Apply a read-modify-write `operation` to the accumulator or memory at `mode`.
+    fn modify(&mut self, bus: &mut B, mode: Option<Mode>, operation: u8) {
+        let wide = self.wide_accumulator();
+        let carry = self.p.contains(Flags::Carry);
+        let apply = |cpu: &mut Self, value: u16| -> u16 {
+            match operation {
+                0 => cpu.shift_left(value, wide, false),
+                1 => cpu.shift_left(value, wide, carry),
+                2 => cpu.shift_right(value, wide, false),
+                3 => cpu.shift_right(value, wide, carry),
+                4 => {
+                    let result = value.wrapping_sub(1) & mask(wide);
+                    cpu.set_nz(result, wide);
+                    result
+                }
+                5 => {
+                    let result = value.wrapping_add(1) & mask(wide);
+                    cpu.set_nz(result, wide);
+                    result
+                }
+                // TSB / TRB
+                6 | 7 => {
+                    let a = cpu.accumulator();
+                    cpu.p.set_zero(a & value == 0);
+                    if operation == 6 { value | a } else { value & !a }
+                }
+                _ => unreachable!("no read-modify-write operation {operation}"),
+            }
+        };
+
+        match mode {
+            None => {
+                let result = apply(self, self.accumulator());
+                self.set_accumulator(result);
+            }
+            Some(mode) => {
+                let operand = self.operand(bus, mode, wide, false);
+                if wide {
+                    self.cycles += 2;
+                }
+                let value = self.read_operand(bus, operand, wide);
+                let result = apply(self, value);
+                self.write_operand(bus, operand, result, wide);
+            }
+        }
+    }
+
+    fn branch(&mut self, bus: &mut B, taken: bool) {
+        let offset = self.fetch_byte(bus) as i8;
+        if taken {
+            let target = self.pc.0.wrapping_add_signed(i16::from(offset));
+            self.cycles += 1;
+            if self.emulation && (target ^ self.pc.0) & 0xFF00 != 0 {
+                self.cycles += 1;
+            }
+            self.pc = Word(target);
+        }
+    }
+
+    fn push_sized(&mut self, bus: &mut B, value: u16, wide: bool) {
+        if wide {
+            self.push_word(bus, value);
+        } else {
+            self.push_byte(bus, value as u8);
+        }
+    }
+
+    fn pull_sized(&mut self, bus: &mut B, wide: bool) -> u16 {
+        let value = if wide {
+            self.pull_word(bus)
+        } else {
+            u16::from(self.pull_byte(bus))
+        };
+        self.set_nz(value, wide);
+        value
+    }
+
+    fn block_move(&mut self, bus: &mut B, step: i16) {
+        let destination = self.fetch_byte(bus);
+        let source = self.fetch_byte(bus);
+        self.dbr = Byte(destination);
+
+        let value = bus.cpu_read(long_address(source, self.x.0), AccessType::DataRead);
+        bus.cpu_write(long_address(destination, self.y.0), value, AccessType::DataWrite);
+
+        let index_mask = mask(self.wide_index());
+        self.x.0 = self.x.0.wrapping_add_signed(step) & index_mask;
+        self.y.0 = self.y.0.wrapping_add_signed(step) & index_mask;
+        self.a.0 = self.a.0.wrapping_sub(1);
+        // Re-execute until the 16-bit count in C underflows, one byte per step.
+        if self.a.0 != 0xFFFF {
+            self.pc -= 3;
+        }
+    }
+}
+
+/// Execute `opcode`, whose byte has already been fetched (PC points past it).
+pub(crate) fn execute<B: Mos6502CompatibleBus>(cpu: &mut Cpu816<B>, bus: &mut B, opcode: u8) {
+    // BRK and COP are charged by the interrupt sequence, which knows the mode.
+    if !matches!(opcode, 0x00 | 0x02) {
+        cpu.cycles += u64::from(CYCLES[opcode as usize]);
+    }
+    let wide_m = cpu.wide_accumulator();
+    let wide_x = cpu.wide_index();
+
+    if let Some(mode) = group_one_mode(opcode) {
+        cpu.charge_wide(wide_m);
+        let operation = opcode >> 5;
+        if operation == 4 {
+            let operand = cpu.operand(bus, mode, wide_m, false);
+            let value = cpu.accumulator();
+            cpu.write_operand(bus, operand, value, wide_m);
+            return;
+        }
+
+        let operand = cpu.operand(bus, mode, wide_m, true);
+        let value = cpu.read_operand(bus, operand, wide_m);
+        match operation {
+            0 => {
+                let result = cpu.accumulator() | value;
+                cpu.set_accumulator(result);
+                cpu.set_nz(result, wide_m);
+            }
+            1 => {
+                let result = cpu.accumulator() & value;
+                cpu.set_accumulator(result);
+                cpu.set_nz(result, wide_m);
+            }
+            2 => {
+                let result = cpu.accumulator() ^ value;
+                cpu.set_accumulator(result);
+                cpu.set_nz(result, wide_m);
+            }
+            3 => cpu.add_with_carry(value),
+            5 => {
+                cpu.set_accumulator(value);
+                cpu.set_nz(value, wide_m);
+            }
+            6 => cpu.compare(cpu.a.0, value, wide_m),
+            7 => cpu.subtract_with_borrow(value),
+            _ => unreachable!(),
+        }
+        return;
+    }
+
+    match opcode {
+        // ASL, ROL, LSR, ROR on memory and the accumulator.
+        0x06 | 0x0E | 0x16 | 0x1E | 0x26 | 0x2E | 0x36 | 0x3E | 0x46 | 0x4E | 0x56 | 0x5E
+        | 0x66 | 0x6E | 0x76 | 0x7E => {
+            let mode = match opcode & 0x1F {
+                0x06 => Mode::Direct,
+                0x0E => Mode::Absolute,
+                0x16 => Mode::DirectX,
+                _ => Mode::AbsoluteX,
+            };
+            cpu.modify(bus, Some(mode), opcode >> 5);
+        }
+        0x0A | 0x2A | 0x4A | 0x6A => cpu.modify(bus, None, opcode >> 5),
+        0xC6 | 0xCE | 0xD6 | 0xDE | 0xE6 | 0xEE | 0xF6 | 0xFE => {
+            let mode = match opcode & 0x1F {
+                0x06 => Mode::Direct,
+                0x0E => Mode::Absolute,
+                0x16 => Mode::DirectX,
+                _ => Mode::AbsoluteX,
+            };
+            cpu.modify(bus, Some(mode), if opcode < 0xE0 { 4 } else { 5 });
+        }
+        0x3A => cpu.modify(bus, None, 4),
+        0x1A => cpu.modify(bus, None, 5),
+        0x04 => cpu.modify(bus, Some(Mode::Direct), 6),
+        0x0C => cpu.modify(bus, Some(Mode::Absolute), 6),
+        0x14 => cpu.modify(bus, Some(Mode::Direct), 7),
+        0x1C => cpu.modify(bus, Some(Mode::Absolute), 7),
+
+        // BIT
+        0x89 => {
+            cpu.charge_wide(wide_m);
+            let operand = cpu.operand(bus, Mode::Immediate, wide_m, true);
+            let value = cpu.read_operand(bus, operand, wide_m);
+            cpu.p.set_zero(cpu.accumulator() & value == 0);
+        }
+        0x24 | 0x2C | 0x34 | 0x3C => {
+            let mode = match opcode {
+                0x24 => Mode::Direct,
+                0x2C => Mode::Absolute,
+                0x34 => Mode::DirectX,
+                _ => Mode::AbsoluteX,
+            };
+            cpu.charge_wide(wide_m);
+            let operand = cpu.operand(bus, mode, wide_m, true);
+            let value = cpu.read_operand(bus, operand, wide_m);
+            cpu.p.set_zero(cpu.accumulator() & value == 0);
+            cpu.p.set_signed(value & sign(wide_m) != 0);
+            cpu.p.set_overflow(value & (sign(wide_m) >> 1) != 0);
+        }
+
+        // STZ
+        0x64 | 0x74 | 0x9C | 0x9E => {
+            let mode = match opcode {
+                0x64 => Mode::Direct,
+                0x74 => Mode::DirectX,
+                0x9C => Mode::Absolute,
+                _ => Mode::AbsoluteX,
+            };
+            cpu.charge_wide(wide_m);
+            let operand = cpu.operand(bus, mode, wide_m, false);
+            cpu.write_operand(bus, operand, 0, wide_m);
+        }
+
+        // LDX, LDY, CPX, CPY, STX, STY
+        0xA2 | 0xA6 | 0xAE | 0xB6 | 0xBE | 0xA0 | 0xA4 | 0xAC | 0xB4 | 0xBC | 0xE0 | 0xE4
+        | 0xEC | 0xC0 | 0xC4 | 0xCC => {
+            let mode = match opcode & 0x1F {
+                0x00 | 0x02 => Mode::Immediate,
+                0x04 | 0x06 => Mode::Direct,
+                0x0C | 0x0E => Mode::Absolute,
+                0x14 => Mode::DirectX,
+                0x16 => Mode::DirectY,
+                0x1C => Mode::AbsoluteX,
+                _ => Mode::AbsoluteY,
+            };
+            cpu.charge_wide(wide_x);
+            let operand = cpu.operand(bus, mode, wide_x, true);
+            let value = cpu.read_operand(bus, operand, wide_x);
+            match opcode {
+                0xA2 | 0xA6 | 0xAE | 0xB6 | 0xBE => {
+                    cpu.x = Word(value);
+                    cpu.set_nz(value, wide_x);
+                }
+                0xA0 | 0xA4 | 0xAC | 0xB4 | 0xBC => {
+                    cpu.y = Word(value);
+                    cpu.set_nz(value, wide_x);
+                }
+                0xE0 | 0xE4 | 0xEC => cpu.compare(cpu.x.0, value, wide_x),
+                _ => cpu.compare(cpu.y.0, value, wide_x),
+            }
+        }
+        0x86 | 0x8E | 0x96 | 0x84 | 0x8C | 0x94 => {
+            let mode = match opcode {
+                0x86 | 0x84 => Mode::Direct,
+                0x8E | 0x8C => Mode::Absolute,
+                0x96 => Mode::DirectY,
+                _ => Mode::DirectX,
+            };
+            cpu.charge_wide(wide_x);
+            let operand = cpu.operand(bus, mode, wide_x, false);
+            let value = if matches!(opcode, 0x86 | 0x8E | 0x96) {
+                cpu.x.0
+            } else {
+                cpu.y.0
+            };
+            cpu.write_operand(bus, operand, value, wide_x);
+        }
+
+        // Branches: bits 7-6 pick N/V/C/Z, bit 5 the value that takes the branch.
+        0x10 | 0x30 | 0x50 | 0x70 | 0x90 | 0xB0 | 0xD0 | 0xF0 => {
+            let flag = match opcode >> 6 {
+                0 => Flags::Sign,
+                1 => Flags::Overflow,
+                2 => Flags::Carry,
+                _ => Flags::Zero,
+            };
+            let taken = cpu.p.contains(flag) == (opcode & 0x20 != 0);
+            cpu.branch(bus, taken);
+        }
+        0x80 => {
+            // BRA's base count already includes the taken cycle.
+            cpu.cycles -= 1;
+            cpu.branch(bus, true);
+        }
+        0x82 => {
+            let offset = cpu.fetch_word(bus);
+            cpu.pc += offset;
+        }
+
+        // Jumps and calls
+        0x4C => cpu.pc = Word(cpu.fetch_word(bus)),
+        0x5C => {
+            let target = cpu.fetch_long(bus);
+            cpu.pbr = Byte((target >> 16) as u8);
+            cpu.pc = Word(target as u16);
+        }
+        0x6C => {
+            let ptr = cpu.fetch_word(bus);
+            cpu.pc = Word(cpu.read_word(bus, 0, ptr, AccessType::DataRead));
+        }
+        0x7C => {
+            let ptr = cpu.fetch_word(bus).wrapping_add(cpu.x.0);
+            cpu.pc = Word(cpu.read_word(bus, cpu.pbr.0, ptr, AccessType::DataRead));
+        }
+        0xDC => {
+            let ptr = cpu.fetch_word(bus);
+            let addr = cpu.read_word(bus, 0, ptr, AccessType::DataRead);
+            let bank = bus.cpu_read(ptr.wrapping_add(2) as usize, AccessType::DataRead);
+            cpu.pbr = bank;
+            cpu.pc = Word(addr);
+        }
+        0x20 => {
+            let target = cpu.fetch_word(bus);
+            cpu.push_word(bus, cpu.pc.0.wrapping_sub(1));
+            cpu.pc = Word(target);
+        }
+        0xFC => {
+            let ptr = cpu.fetch_word(bus).wrapping_add(cpu.x.0);
+            cpu.push_word(bus, cpu.pc.0.wrapping_sub(1));
+            cpu.pc = Word(cpu.read_word(bus, cpu.pbr.0, ptr, AccessType::DataRead));
+        }
+        0x22 => {
+            let target = cpu.fetch_long(bus);
+            cpu.push_byte(bus, cpu.pbr.0);
+            cpu.push_word(bus, cpu.pc.0.wrapping_sub(1));
+            cpu.pbr = Byte((target >> 16) as u8);
+            cpu.pc = Word(target as u16);
+        }
+        0x60 => cpu.pc = Word(cpu.pull_word(bus).wrapping_add(1)),
+        0x6B => {
+            cpu.pc = Word(cpu.pull_word(bus).wrapping_add(1));
+            cpu.pbr = Byte(cpu.pull_byte(bus));
+        }
+        0x40 => {
+            let flags = Flags::from_bits_retain(cpu.pull_byte(bus));
+            cpu.set_p(flags);
+            cpu.pc = Word(cpu.pull_word(bus));
+            if !cpu.emulation {
+                cpu.pbr = Byte(cpu.pull_byte(bus));
+                cpu.cycles += 1;
+            }
+        }
+
+        // Software interrupts; the signature byte is skipped.
+        0x00 => {
+            cpu.fetch_byte(bus);
+            let vector = if cpu.emulation {
+                EMULATION_IRQ_VECTOR
+            } else {
+                NATIVE_BRK_VECTOR
+            };
+            cpu.enter_interrupt(bus, vector, true);
+        }
+        0x02 => {
+            cpu.fetch_byte(bus);
+            let vector = if cpu.emulation {
+                EMULATION_COP_VECTOR
+            } else {
+                NATIVE_COP_VECTOR
+            };
+            cpu.enter_interrupt(bus, vector, true);
+        }
+
+        // Stack
+        0x08 => cpu.push_byte(bus, cpu.p.bits()),
+        0x28 => {
+            let flags = Flags::from_bits_retain(cpu.pull_byte(bus));
+            cpu.set_p(flags);
+        }
+        0x48 => {
+            cpu.charge_wide(wide_m);
+            cpu.push_sized(bus, cpu.a.0, wide_m);
+        }
+        0x68 => {
+            cpu.charge_wide(wide_m);
+            let value = cpu.pull_sized(bus, wide_m);
+            cpu.set_accumulator(value);
+        }
+        0xDA | 0x5A => {
+            cpu.charge_wide(wide_x);
+            let value = if opcode == 0xDA { cpu.x.0 } else { cpu.y.0 };
+            cpu.push_sized(bus, value, wide_x);
+        }
+        0xFA | 0x7A => {
+            cpu.charge_wide(wide_x);
+            let value = Word(cpu.pull_sized(bus, wide_x));
+            if opcode == 0xFA {
+                cpu.x = value;
+            } else {
+                cpu.y = value;
+            }
+        }
+        0x0B => cpu.push_word(bus, cpu.d.0),
+        0x2B => cpu.d = Word(cpu.pull_sized(bus, true)),
+        0x4B => cpu.push_byte(bus, cpu.pbr.0),
+        0x8B => cpu.push_byte(bus, cpu.dbr.0),
+        0xAB => cpu.dbr = Byte(cpu.pull_sized(bus, false) as u8),
+        0xF4 => {
+            let value = cpu.fetch_word(bus);
+            cpu.push_word(bus, value);
+        }
+        0xD4 => {
+            if cpu.d.0 & 0x00FF != 0 {
+                cpu.cycles += 1;
+            }
+            let offset = cpu.fetch_byte(bus);
+            let value = cpu.read_direct_word(bus, cpu.direct_address(offset, 0));
+            cpu.push_word(bus, value);
+        }
+        0x62 => {
+            let offset = cpu.fetch_word(bus);
+            cpu.push_word(bus, cpu.pc.0.wrapping_add(offset));
+        }
+
+        // Transfers
+        0xAA | 0xA8 => {
+            let value = cpu.index_value(cpu.a.0);
+            if opcode == 0xAA {
+                cpu.x = Word(value);
+            } else {
+                cpu.y = Word(value);
+            }
+            cpu.set_nz(value, wide_x);
+        }
+        0x8A | 0x98 => {
+            let value = if opcode == 0x8A { cpu.x.0 } else { cpu.y.0 };
+            cpu.set_accumulator(value);
+            cpu.set_nz(value, wide_m);
+        }
+        0x9B => {
+            cpu.y = cpu.x;
+            cpu.set_nz(cpu.y.0, wide_x);
+        }
+        0xBB => {
+            cpu.x = cpu.y;
+            cpu.set_nz(cpu.x.0, wide_x);
+        }
+        0xBA => {
+            cpu.x = Word(cpu.index_value(cpu.sp.0));
+            cpu.set_nz(cpu.x.0, wide_x);
+        }
+        0x9A | 0x1B => {
+            let value = if opcode == 0x9A { cpu.x.0 } else { cpu.a.0 };
+            cpu.sp = if cpu.emulation {
+                Word(0x0100 | (value & 0x00FF))
+            } else {
+                Word(value)
+            };
+        }
+        0x3B => {
+            cpu.a = cpu.sp;
+            cpu.set_nz(cpu.a.0, true);
+        }
+        0x5B => {
+            cpu.d = cpu.a;
+            cpu.set_nz(cpu.d.0, true);
+        }
+        0x7B => {
+            cpu.a = cpu.d;
+            cpu.set_nz(cpu.a.0, true);
+        }
+        0xEB => {
+            cpu.a = Word(cpu.a.0.swap_bytes());
+            cpu.set_nz(cpu.a.0, false);
+        }
+
+        // Index increment/decrement
+        0xE8 => {
+            cpu.x = Word(cpu.index_value(cpu.x.0.wrapping_add(1)));
+            cpu.set_nz(cpu.x.0, wide_x);
+        }
+        0xC8 => {
+            cpu.y = Word(cpu.index_value(cpu.y.0.wrapping_add(1)));
+            cpu.set_nz(cpu.y.0, wide_x);
+        }
+        0xCA => {
+            cpu.x = Word(cpu.index_value(cpu.x.0.wrapping_sub(1)));
+            cpu.set_nz(cpu.x.0, wide_x);
+        }
+        0x88 => {
+            cpu.y = Word(cpu.index_value(cpu.y.0.wrapping_sub(1)));
+            cpu.set_nz(cpu.y.0, wide_x);
+        }
+
+        // Status flags and mode switches
+        0x18 => cpu.p.set_carry(false),
+        0x38 => cpu.p.set_carry(true),
+        0x58 => cpu.p.set_interrupt_disabled(false),
+        0x78 => cpu.p.set_interrupt_disabled(true),
+        0xB8 => cpu.p.set_overflow(false),
+        0xD8 => cpu.p.set_decimal_mode(false),
+        0xF8 => cpu.p.set_decimal_mode(true),
+        0xC2 => {
+            let bits = Flags::from_bits_retain(cpu.fetch_byte(bus));
+            cpu.set_p(cpu.p - bits);
+        }
+        0xE2 => {
+            let bits = Flags::from_bits_retain(cpu.fetch_byte(bus));
+            cpu.set_p(cpu.p | bits);
+        }
+        0xFB => {
+            let carry = cpu.p.contains(Flags::Carry);
+            cpu.p.set_carry(cpu.emulation);
+            cpu.set_emulation(carry);
+        }
+
+        // Block moves
+        0x54 => cpu.block_move(bus, 1),
+        0x44 => cpu.block_move(bus, -1),
+
+        0xCB => cpu.run_state = RunState::Waiting,
+        0xDB => cpu.run_state = RunState::Halted,
+        0x42 => {
+            cpu.fetch_byte(bus);
+        }
+        0xEA => {}
+
+        _ => unreachable!("opcode {opcode:02X} is decoded as a group one instruction"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::processor::cpu::Interrupt;
+    use crate::processor::cpu816::{FLAG_M, FLAG_X, NATIVE_IRQ_VECTOR};
+    use alloc::vec;
+    use alloc::vec::Vec;
+    use ull::{Address, Bus};
+
+    /// Flat 16 MiB memory so every bank is backed.
+    struct LongBus {
+        mem: Vec<u8>,
+    }
+
+    impl LongBus {
+        fn load(&mut self, addr: usize, bytes: &[u8]) {
+            self.mem[addr..addr + bytes.len()].copy_from_slice(bytes);
+        }
+
+        fn peek(&self, addr: usize) -> u8 {
+            self.mem[addr]
+        }
+    }
+
+    impl Bus for LongBus {
+        type Access = AccessType;
+        type Data = Byte;
+
+        fn read<A: Address>(&mut self, addr: A, _access: AccessType) -> Byte {
+            Byte(self.mem[addr.as_usize() & 0xFF_FFFF])
+        }
+
+        fn write<A: Address, V: Into<Byte>>(&mut self, addr: A, value: V, _access: AccessType) {
+            self.mem[addr.as_usize() & 0xFF_FFFF] = value.into().0;
+        }
+    }
+
+    impl Mos6502CompatibleBus for LongBus {}
+
+    /// Load `program` at $00:8000 and reset into it.
+    fn boot(program: &[u8]) -> (Cpu816<LongBus>, LongBus) {
+        let mut bus = LongBus {
+            mem: vec![0; 0x100_0000],
+        };
+        bus.load(0x8000, program);
+        bus.load(0xFFFC, &[0x00, 0x80]);
+        let mut cpu = Cpu816::new();
+        cpu.reset(&mut bus);
+        (cpu, bus)
+    }
+
+    fn run(cpu: &mut Cpu816<LongBus>, bus: &mut LongBus, steps: usize) -> u64 {
+        let before = cpu.cycles;
+        for _ in 0..steps {
+            cpu.step(bus);
+        }
+        cpu.cycles - before
+    }
+
+    // CLC; XCE; REP #$30
+    const NATIVE_16: [u8; 4] = [0x18, 0xFB, 0xC2, 0x30];
+
+    fn native(rest: &[u8]) -> (Cpu816<LongBus>, LongBus) {
+        let program = [&NATIVE_16[..], rest].concat();
+        let (mut cpu, mut bus) = boot(&program);
+        run(&mut cpu, &mut bus, 3);
+        assert!(!cpu.emulation);
+        assert!(cpu.wide_accumulator() && cpu.wide_index());
+        (cpu, bus)
+    }
+
+    #[test]
+    fn resets_into_emulation_mode_with_8_bit_registers() {
+        // LDA #$FF; ADC #$01; TSX
+        let (mut cpu, mut bus) = boot(&[0xA9, 0xFF, 0x69, 0x01, 0xBA]);
+
+        assert!(cpu.emulation);
+        assert!(cpu.p.contains(FLAG_M | FLAG_X | Flags::InterruptDisabled));
+        assert_eq!(run(&mut cpu, &mut bus, 2), 4);
+        assert_eq!(cpu.a, Word(0x0000));
+        assert!(cpu.p.contains(Flags::Carry | Flags::Zero));
+        run(&mut cpu, &mut bus, 1);
+        assert_eq!(cpu.x, Word(0x00FD));
+    }
+
+    #[test]
+    fn rep_selects_16_bit_accumulator_and_index() {
+        // LDA #$1234; LDX #$ABCD; TXY; INY
+        let (mut cpu, mut bus) = native(&[0xA9, 0x34, 0x12, 0xA2, 0xCD, 0xAB, 0x9B, 0xC8]);
+
+        assert_eq!(run(&mut cpu, &mut bus, 4), 3 + 3 + 2 + 2);
+        assert_eq!(cpu.a, Word(0x1234));
+        assert_eq!(cpu.x, Word(0xABCD));
+        assert_eq!(cpu.y, Word(0xABCE));
+        assert!(cpu.p.contains(Flags::Sign));
+        assert_eq!(cpu.pc, Word(0x800C));
+    }
+
+    #[test]
+    fn eight_bit_accumulator_preserves_b_and_xba_swaps_it() {
+        // LDA #$1234; SEP #$20; LDA #$56; XBA
+        let (mut cpu, mut bus) = native(&[0xA9, 0x34, 0x12, 0xE2, 0x20, 0xA9, 0x56, 0xEB]);
+
+        run(&mut cpu, &mut bus, 3);
+        assert_eq!(cpu.a, Word(0x1256));
+        run(&mut cpu, &mut bus, 1);
+        assert_eq!(cpu.a, Word(0x5612));
+        // N/Z follow the new low byte.
+        assert!(!cpu.p.contains(Flags::Sign | Flags::Zero));
+    }
+
+    #[test]
+    fn setting_x_clears_index_high_bytes() {
+        // LDX #$1234; SEP #$10
+        let (mut cpu, mut bus) = native(&[0xA2, 0x34, 0x12, 0xE2, 0x10]);
+
+        run(&mut cpu, &mut bus, 2);
+        assert_eq!(cpu.x, Word(0x0034));
+    }
+
+    #[test]
+    fn long_and_bank_relative_addressing() {
+        // LDA $123456; LDX #$0010; STA $7EFFF8,X; PHB-free bank switch via PEA/PLB:
+        // PEA $0303; PLB; STA $FFFF (lands in bank 3)
+        let (mut cpu, mut bus) = native(&[
+            0xAF, 0x56, 0x34, 0x12, 0xA2, 0x10, 0x00, 0x9F, 0xF8, 0xFF, 0x7E, 0xF4, 0x03, 0x03,
+            0xAB, 0x8D, 0xFF, 0xFF,
+        ]);
+        bus.load(0x12_3456, &[0xCD, 0xAB]);
+
+        run(&mut cpu, &mut bus, 6);
+        assert_eq!(cpu.a, Word(0xABCD));
+        // $7E:FFF8 + $10 carries into bank $7F.
+        assert_eq!((bus.peek(0x7F_0008), bus.peek(0x7F_0009)), (0xCD, 0xAB));
+        assert_eq!(cpu.dbr, Byte(0x03));
+        // The 16-bit store's high byte carries into the next bank too.
+        assert_eq!((bus.peek(0x03_FFFF), bus.peek(0x04_0000)), (0xCD, 0xAB));
+    }
+
+    #[test]
+    fn indexed_reads_charge_wide_index_penalty() {
+        // LDA $2000,X with 16-bit X always pays the extra cycle.
+        let (mut cpu, mut bus) = native(&[0xBD, 0x00, 0x20]);
+        assert_eq!(run(&mut cpu, &mut bus, 1), 4 + 1 + 1);
+
+        // With 8-bit registers only a page crossing does.
+        let (mut cpu, mut bus) = boot(&[0xA2, 0x10, 0xBD, 0x00, 0x20, 0xBD, 0xF0, 0x20]);
+        run(&mut cpu, &mut bus, 1);
+        assert_eq!(run(&mut cpu, &mut bus, 1), 4);
+        assert_eq!(run(&mut cpu, &mut bus, 1), 5);
+    }
+
+    #[test]
+    fn direct_page_offset_costs_a_cycle() {
+        // LDA #$1200; TCD; LDA $34
+        let (mut cpu, mut bus) = native(&[0xA9, 0x01, 0x12, 0x5B, 0xA5, 0x34]);
+        bus.load(0x1235, &[0x78, 0x56]);
+
+        run(&mut cpu, &mut bus, 2);
+        assert_eq!(cpu.d, Word(0x1201));
+        assert_eq!(run(&mut cpu, &mut bus, 1), 3 + 1 + 1);
+        assert_eq!(cpu.a, Word(0x5678));
+    }
+
+    #[test]
+    fn mvn_moves_one_byte_per_step() {
+        // LDA #$0003; LDX #$1000; LDY #$2000; MVN $7E,$01
+        let (mut cpu, mut bus) =
+            native(&[0xA9, 0x03, 0x00, 0xA2, 0x00, 0x10, 0xA0, 0x00, 0x20, 0x54, 0x7E, 0x01]);
+        bus.load(0x01_1000, &[1, 2, 3, 4, 5]);
+
+        run(&mut cpu, &mut bus, 3);
+        let cycles = run(&mut cpu, &mut bus, 4);
+
+        assert_eq!(cycles, 4 * 7);
+        assert_eq!(&bus.mem[0x7E_2000..0x7E_2005], &[1, 2, 3, 4, 0]);
+        assert_eq!(cpu.a, Word(0xFFFF));
+        assert_eq!(cpu.x, Word(0x1004));
+        assert_eq!(cpu.y, Word(0x2004));
+        assert_eq!(cpu.dbr, Byte(0x7E));
+        assert_eq!(cpu.pc, Word(0x8010));
+    }
+
+    #[test]
+    fn mvp_walks_backwards() {
+        // LDA #$0001; LDX #$1001; LDY #$2001; MVP $00,$00
+        let (mut cpu, mut bus) =
+            native(&[0xA9, 0x01, 0x00, 0xA2, 0x01, 0x10, 0xA0, 0x01, 0x20, 0x44, 0x00, 0x00]);
+        bus.load(0x1000, &[0xAA, 0xBB]);
+
+        run(&mut cpu, &mut bus, 5);
+        assert_eq!((bus.peek(0x2000), bus.peek(0x2001)), (0xAA, 0xBB));
+        assert_eq!(cpu.x, Word(0x0FFF));
+    }
+
+    #[test]
+    fn jsl_and_rtl_cross_program_banks() {
+        // JSL $05:9000; at $05:9000: LDA #$0042; RTL
+        let (mut cpu, mut bus) = native(&[0x22, 0x00, 0x90, 0x05, 0xEA]);
+        bus.load(0x05_9000, &[0xA9, 0x42, 0x00, 0x6B]);
+
+        assert_eq!(run(&mut cpu, &mut bus, 1), 8);
+        assert_eq!((cpu.pbr, cpu.pc), (Byte(0x05), Word(0x9000)));
+        run(&mut cpu, &mut bus, 2);
+        assert_eq!((cpu.pbr, cpu.pc), (Byte(0x00), Word(0x8008)));
+        assert_eq!(cpu.a, Word(0x0042));
+    }
+
+    #[test]
+    fn native_irq_pushes_program_bank_and_rti_restores_it() {
+        // CLI; JML $02:4000 (which spins on BRA $FE)
+        let (mut cpu, mut bus) = native(&[0x58, 0x5C, 0x00, 0x40, 0x02]);
+        bus.load(0x02_4000, &[0x80, 0xFE]);
+        bus.load(NATIVE_IRQ_VECTOR as usize, &[0x00, 0xA0]);
+        bus.load(0xA000, &[0x40]);
+
+        run(&mut cpu, &mut bus, 2);
+        cpu.request_interrupt(Interrupt::Irq);
+        assert_eq!(cpu.step(&mut bus), 8);
+        assert_eq!((cpu.pbr, cpu.pc), (Byte(0x00), Word(0xA000)));
+        assert!(cpu.p.contains(Flags::InterruptDisabled));
+
+        assert_eq!(cpu.step(&mut bus), 7);
+        assert_eq!((cpu.pbr, cpu.pc), (Byte(0x02), Word(0x4000)));
+        assert!(!cpu.p.contains(Flags::InterruptDisabled));
+    }
+
+    #[test]
+    fn sixteen_bit_decimal_arithmetic() {
+        // SED; CLC; LDA #$1999; ADC #$0001; SEC; SBC #$0001
+        let (mut cpu, mut bus) =
+            native(&[0xF8, 0x18, 0xA9, 0x99, 0x19, 0x69, 0x01, 0x00, 0x38, 0xE9, 0x01, 0x00]);
+
+        run(&mut cpu, &mut bus, 4);
+        assert_eq!(cpu.a, Word(0x2000));
+        assert!(!cpu.p.contains(Flags::Carry));
+        run(&mut cpu, &mut bus, 2);
+        assert_eq!(cpu.a, Word(0x1999));
+        assert!(cpu.p.contains(Flags::Carry));
+    }
+
+    #[test]
+    fn eight_bit_decimal_wraps_with_carry() {
+        // SED; CLC; LDA #$99; ADC #$01
+        let (mut cpu, mut bus) = boot(&[0xF8, 0x18, 0xA9, 0x99, 0x69, 0x01]);
+
+        run(&mut cpu, &mut bus, 4);
+        assert_eq!(cpu.a, Word(0x0000));
+        assert!(cpu.p.contains(Flags::Carry | Flags::Zero));
+    }
+
+    #[test]
+    fn emulation_stack_stays_in_page_one() {
+        // LDX #$00; TXS; PHA; PHA
+        let (mut cpu, mut bus) = boot(&[0xA2, 0x00, 0x9A, 0x48, 0x48]);
+
+        run(&mut cpu, &mut bus, 4);
+        assert_eq!(cpu.sp, Word(0x01FE));
+    }
+
+    #[test]
+    fn xce_back_to_emulation_forces_8_bit_state() {
+        // LDX #$1234; LDA #$0200; TCS; SEC; XCE
+        let (mut cpu, mut bus) = native(&[0xA2, 0x34, 0x12, 0xA9, 0x00, 0x02, 0x1B, 0x38, 0xFB]);
+
+        run(&mut cpu, &mut bus, 5);
+        assert!(cpu.emulation);
+        assert!(!cpu.p.contains(Flags::Carry));
+        assert!(cpu.p.contains(FLAG_M | FLAG_X));
+        assert_eq!(cpu.x, Word(0x0034));
+        assert_eq!(cpu.sp, Word(0x0100));
+    }
+
+    #[test]
+    fn every_opcode_has_a_mnemonic_and_cycle_count() {
+        assert!(Wdc65c816::MNEMONICS.iter().all(|m| m.len() == 3));
+        assert!(Wdc65c816::CYCLES.iter().all(|&c| (2..=8).contains(&c)));
+    }
+}
@@ -60,20 +60,27 @@ extern crate alloc;
 
 pub mod access;
 pub mod bus;
+pub mod error;
 pub mod instruction;
 pub mod processor;
 
 /// Re-export core primitives/bus for convenience so downstream users can depend on `ull65`
 /// only, while internal modules still import them explicitly from `ull`.
-pub use access::{AccessType, Phase, ResetVectorExt};
-pub use bus::{Mos6502CompatibleBus, simple_bus::SimpleBus, testing_bus::TestingBus};
-pub use instruction::{Instruction, InstructionSet, InstructionTable};
+pub use access::{AccessType, CyclePhase, Phase, ResetVectorExt};
+pub use bus::{
+    Mos6502CompatibleBus, banked::BankedBus, simple_bus::SimpleBus, slice::SliceBus, testing_bus::TestingBus, trap_bus::TrapBus, watch_bus::WatchBus,
+};
+pub use error::{Error, Result};
+pub use instruction::{
+    FlowKind, IllegalPolicy, Instruction, InstructionSet, InstructionTable, TableStorage,
+};
 pub use processor::addressing_mode::{self, AddressingMode};
-pub use processor::run::{RunConfig, RunOutcome, RunPredicate, RunSummary};
+pub use processor::run::{DEFAULT_STEP_GUARD, RunConfig, RunOutcome, RunPredicate, RunSummary};
 pub use processor::{
     cpu::{
-        IRQ_VECTOR_HI, IRQ_VECTOR_LO, NMI_VECTOR_HI, NMI_VECTOR_LO, RESET_VECTOR_HI,
-        RESET_VECTOR_LO, STACK_SPACE_START,
-    }, Cpu,
+        INTERRUPT_CYCLES, IRQ_VECTOR_HI, IRQ_VECTOR_LO, NMI_VECTOR_HI, NMI_VECTOR_LO,
+        InstructionContext, InstructionHook, OpcodeHandler,
+        RESET_VECTOR_HI, RESET_VECTOR_LO, STACK_SPACE_START,
+    }, Cpu, CpuState, Registers,
     RunState,
 };
@@ -1,10 +1,19 @@
 //! CPU core, status flags, and addressing modes.
 
 pub mod addressing_mode;
+pub mod asm;
 pub mod cpu;
+#[cfg(feature = "wdc65c816")]
+pub mod cpu816;
+pub mod disasm;
+pub mod exec;
 pub mod flags;
+#[cfg(feature = "history")]
+pub mod history;
+pub mod profile;
 pub mod run;
+pub mod trace;
 
 pub use addressing_mode::AddressingMode;
-pub use cpu::{Cpu, RunState};
+pub use cpu::{Cpu, CpuState, Registers, RunState};
 pub use run::{RunConfig, RunOutcome, RunPredicate, RunSummary};
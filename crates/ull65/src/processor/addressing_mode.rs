@@ -1,4 +1,4 @@
-//! Nine addressing modes of the 6502.
+//! Addressing modes of the 6502 family, including the 65C02 and 65CE02 additions.
 //!
 //! Each mode is a zero-sized type implementing [`AddressingMode`], enabling generic
 //! instruction with compile-time dispatch.
@@ -6,8 +6,37 @@
 use crate::processor::Cpu;
 use crate::bus::Mos6502CompatibleBus;
 use crate::{AccessType};
-use ull::word;
-use ull::Word;
+use ull::{Byte, Word};
+
+/// Read the little-endian pointer stored at `zp` in the zero page ([`Cpu::zero_page`]).
+///
+/// The high byte comes from `zp + 1` wrapped within the page, so a pointer at `$FF` takes its
+/// high byte from `$00` rather than `$0100`, as on every 6502 variant.
+pub fn read_zp_pointer<B: Mos6502CompatibleBus>(cpu: &Cpu<B>, bus: &mut B, zp: Byte) -> Word {
+    let lo = bus.cpu_read(cpu.zero_page(zp), AccessType::DataRead);
+    let hi = bus.cpu_read(cpu.zero_page(zp + 1u8), AccessType::DataRead);
+    Word::from((lo, hi))
+}
+
+/// Resolve an indexed read of `base + index`, with the extra cycle it costs when the index
+/// carries into the high byte.
+///
+/// That cycle is a real bus access. NMOS parts read the address before the carry is fixed up
+/// (`base`'s high byte with the indexed low byte), which can trigger I/O side effects on the
+/// wrong page; the 65C02 reads `cmos_dummy`, the last byte of the instruction, instead.
+fn indexed_read<B: Mos6502CompatibleBus>(
+    bus: &mut B,
+    base: Word,
+    index: Byte,
+    cmos_dummy: Option<Word>,
+) -> (Word, u8) {
+    let (addr, crossed) = base.offset(index.0);
+    if crossed {
+        let dummy = cmos_dummy.unwrap_or(Word::from((addr.lo(), base.hi())));
+        bus.cpu_read(dummy, AccessType::DummyRead);
+    }
+    (addr, u8::from(crossed))
+}
 
 /// Trait for computing effective addresses in different addressing modes.
 ///
@@ -22,12 +51,128 @@ pub trait AddressingMode {
     /// Compute the effective address for this addressing mode.
     ///
     /// Does not advance PC—that's the instruction's responsibility.
+    ///
+    /// Operand bytes are read with [`Cpu::operand`], which returns the ones
+    /// [`Cpu::cycle`] has already fetched instead of reading them again.
     fn fetch_address<B: Mos6502CompatibleBus>(cpu: &Cpu<B>, bus: &mut B) -> Word;
 
+    /// Compute the effective address plus the extra cycles a *read* through this mode costs.
+    ///
+    /// Indexed modes (`abs,X`, `abs,Y`, `(zp),Y`) take one more cycle when adding the index
+    /// carries into the high byte. Read instructions add the penalty to `cpu.cycles`; stores and
+    /// read-modify-write instructions always pay the worst case, which their base cycle counts
+    /// already include, so they keep using [`fetch_address`](Self::fetch_address).
+    fn fetch_address_penalized<B: Mos6502CompatibleBus>(cpu: &Cpu<B>, bus: &mut B) -> (Word, u8) {
+        (Self::fetch_address(cpu, bus), 0)
+    }
+
     /// Total bytes for an instruction using this mode (including opcode).
     const BYTES: u16;
 }
 
+/// Runtime tag naming an addressing mode, carried by each [`Instruction`](crate::Instruction)
+/// so tooling (disassemblers, tracers) can decode and render operands.
+///
+/// Mirrors the zero-sized mode types below, plus the 65C02's zero-page-and-branch form, which
+/// isn't modeled as a separate type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AddressingModeKind {
+    /// No operand (`CLC`, `RTS`).
+    Implied,
+    /// Operates on A (`ASL A`).
+    Accumulator,
+    /// `#$12`
+    Immediate,
+    /// `#$1234`, the 65CE02's `PHW`.
+    ImmediateWord,
+    /// `$1234`
+    Absolute,
+    /// `$1234,X`
+    AbsoluteX,
+    /// `$1234,Y`
+    AbsoluteY,
+    /// `($1234)` on the NMOS 6502 `JMP`, whose pointer's high byte wraps within its page.
+    AbsoluteIndirect,
+    /// `($1234)` with the page-wrap bug fixed: the 65C02 `JMP` and 65CE02 `JSR`.
+    AbsoluteIndirectCorrect,
+    /// `($1234,X)`, 65C02 `JMP`.
+    AbsoluteIndirectX,
+    /// `$12`
+    ZeroPage,
+    /// `$12,X`
+    ZeroPageX,
+    /// `$12,Y`
+    ZeroPageY,
+    /// `($12)`, 65C02 only.
+    ZeroPageIndirect,
+    /// `($12),Z`, the 65CE02's form of `($12)`.
+    ZeroPageIndirectZ,
+    /// `($12,X)`
+    ZeroPageXIndirect,
+    /// `($12),Y`
+    ZeroPageIndirectY,
+    /// `($12,SP),Y`, 65CE02 only.
+    StackIndirectY,
+    /// Signed 8-bit branch offset.
+    Relative,
+    /// Signed 16-bit branch offset (65CE02 long branches).
+    RelativeLong,
+    /// Zero-page operand plus branch offset (65C02 `BBR`/`BBS`).
+    ZeroPageRelative,
+}
+
+impl AddressingModeKind {
+    /// Number of operand bytes following the opcode.
+    #[must_use]
+    pub const fn operand_bytes(self) -> u8 {
+        match self {
+            AddressingModeKind::Implied | AddressingModeKind::Accumulator => 0,
+            AddressingModeKind::Immediate
+            | AddressingModeKind::ZeroPage
+            | AddressingModeKind::ZeroPageX
+            | AddressingModeKind::ZeroPageY
+            | AddressingModeKind::ZeroPageIndirect
+            | AddressingModeKind::ZeroPageIndirectZ
+            | AddressingModeKind::ZeroPageXIndirect
+            | AddressingModeKind::ZeroPageIndirectY
+            | AddressingModeKind::StackIndirectY
+            | AddressingModeKind::Relative => 1,
+            AddressingModeKind::ImmediateWord
+            | AddressingModeKind::Absolute
+            | AddressingModeKind::AbsoluteX
+            | AddressingModeKind::AbsoluteY
+            | AddressingModeKind::AbsoluteIndirect
+            | AddressingModeKind::AbsoluteIndirectCorrect
+            | AddressingModeKind::AbsoluteIndirectX
+            | AddressingModeKind::RelativeLong
+            | AddressingModeKind::ZeroPageRelative => 2,
+        }
+    }
+}
+
+/// No operand (`CLC`, `TAX`, `PHA`).
+///
+/// There's nothing to address; [`fetch_address`](AddressingMode::fetch_address) returns PC so
+/// the mode still fits the trait, and instructions only use it for [`BYTES`](AddressingMode::BYTES).
+pub struct Implied;
+impl AddressingMode for Implied {
+    fn fetch_address<B: Mos6502CompatibleBus>(cpu: &Cpu<B>, _bus: &mut B) -> Word {
+        cpu.pc
+    }
+
+    const BYTES: u16 = 1;
+}
+
+/// Operates on A (`ASL A`, `INC A`). Like [`Implied`], it has no memory operand.
+pub struct Accumulator;
+impl AddressingMode for Accumulator {
+    fn fetch_address<B: Mos6502CompatibleBus>(cpu: &Cpu<B>, _bus: &mut B) -> Word {
+        cpu.pc
+    }
+
+    const BYTES: u16 = 1;
+}
+
 pub struct Immediate;
 impl AddressingMode for Immediate {
     fn fetch_address<B: Mos6502CompatibleBus>(cpu: &Cpu<B>, _bus: &mut B) -> Word {
@@ -37,11 +182,21 @@ impl AddressingMode for Immediate {
     const BYTES: u16 = 2;
 }
 
+/// A 16-bit immediate, little-endian in the two bytes after the opcode.
+pub struct ImmediateWord;
+impl AddressingMode for ImmediateWord {
+    fn fetch_address<B: Mos6502CompatibleBus>(cpu: &Cpu<B>, _bus: &mut B) -> Word {
+        cpu.pc + 1
+    }
+
+    const BYTES: u16 = 3;
+}
+
 pub struct Absolute;
 impl AddressingMode for Absolute {
     fn fetch_address<B: Mos6502CompatibleBus>(cpu: &Cpu<B>, bus: &mut B) -> Word {
-        let lo = bus.read(cpu.pc + 1, AccessType::DataRead);
-        let hi = bus.read(cpu.pc + 2, AccessType::DataRead);
+        let lo = cpu.operand(bus, 1);
+        let hi = cpu.operand(bus, 2);
         (lo, hi).into()
     }
 
@@ -51,24 +206,44 @@ impl AddressingMode for Absolute {
 pub struct AbsoluteX;
 impl AddressingMode for AbsoluteX {
     fn fetch_address<B: Mos6502CompatibleBus>(cpu: &Cpu<B>, bus: &mut B) -> Word {
-        let lo = bus.read(cpu.pc + 1, AccessType::DataRead);
-        let hi = bus.read(cpu.pc + 2, AccessType::DataRead);
+        let lo = cpu.operand(bus, 1);
+        let hi = cpu.operand(bus, 2);
         let base: Word = (lo, hi).into();
         base + cpu.x
     }
 
+    fn fetch_address_penalized<B: Mos6502CompatibleBus>(cpu: &Cpu<B>, bus: &mut B) -> (Word, u8) {
+        let base = Absolute::fetch_address(cpu, bus);
+        indexed_read(bus, base, cpu.x, None)
+    }
+
+    const BYTES: u16 = 3;
+}
+
+/// [`AbsoluteX`] with the 65C02's page-crossing dummy read of the instruction's last byte.
+pub struct AbsoluteXCmos;
+impl AddressingMode for AbsoluteXCmos {
+    fn fetch_address<B: Mos6502CompatibleBus>(cpu: &Cpu<B>, bus: &mut B) -> Word {
+        AbsoluteX::fetch_address(cpu, bus)
+    }
+
+    fn fetch_address_penalized<B: Mos6502CompatibleBus>(cpu: &Cpu<B>, bus: &mut B) -> (Word, u8) {
+        let base = Absolute::fetch_address(cpu, bus);
+        indexed_read(bus, base, cpu.x, Some(cpu.pc + 2))
+    }
+
     const BYTES: u16 = 3;
 }
 
 pub struct AbsoluteIndirectX;
 impl AddressingMode for AbsoluteIndirectX {
     fn fetch_address<B: Mos6502CompatibleBus>(cpu: &Cpu<B>, bus: &mut B) -> Word {
-        let lo = bus.read(cpu.pc + 1, AccessType::DataRead);
-        let hi = bus.read(cpu.pc + 2, AccessType::DataRead);
+        let lo = cpu.operand(bus, 1);
+        let hi = cpu.operand(bus, 2);
         let ptr = Word::from((lo, hi)) + cpu.x;
 
-        let target_lo = bus.read(ptr, AccessType::DataRead);
-        let target_hi = bus.read(ptr + 1, AccessType::DataRead);
+        let target_lo = bus.cpu_read(ptr, AccessType::DataRead);
+        let target_hi = bus.cpu_read(ptr + 1, AccessType::DataRead);
 
         (target_lo, target_hi).into()
     }
@@ -79,26 +254,46 @@ impl AddressingMode for AbsoluteIndirectX {
 pub struct AbsoluteY;
 impl AddressingMode for AbsoluteY {
     fn fetch_address<B: Mos6502CompatibleBus>(cpu: &Cpu<B>, bus: &mut B) -> Word {
-        let lo = bus.read(cpu.pc + 1, AccessType::DataRead);
-        let hi = bus.read(cpu.pc + 2, AccessType::DataRead);
+        let lo = cpu.operand(bus, 1);
+        let hi = cpu.operand(bus, 2);
         let base: Word = (lo, hi).into();
         base + cpu.y
     }
 
+    fn fetch_address_penalized<B: Mos6502CompatibleBus>(cpu: &Cpu<B>, bus: &mut B) -> (Word, u8) {
+        let base = Absolute::fetch_address(cpu, bus);
+        indexed_read(bus, base, cpu.y, None)
+    }
+
+    const BYTES: u16 = 3;
+}
+
+/// [`AbsoluteY`] with the 65C02's page-crossing dummy read of the instruction's last byte.
+pub struct AbsoluteYCmos;
+impl AddressingMode for AbsoluteYCmos {
+    fn fetch_address<B: Mos6502CompatibleBus>(cpu: &Cpu<B>, bus: &mut B) -> Word {
+        AbsoluteY::fetch_address(cpu, bus)
+    }
+
+    fn fetch_address_penalized<B: Mos6502CompatibleBus>(cpu: &Cpu<B>, bus: &mut B) -> (Word, u8) {
+        let base = Absolute::fetch_address(cpu, bus);
+        indexed_read(bus, base, cpu.y, Some(cpu.pc + 2))
+    }
+
     const BYTES: u16 = 3;
 }
 
 pub struct AbsoluteIndirect;
 impl AddressingMode for AbsoluteIndirect {
     fn fetch_address<B: Mos6502CompatibleBus>(cpu: &Cpu<B>, bus: &mut B) -> Word {
-        let lo = bus.read(cpu.pc + 1, AccessType::DataRead);
-        let hi = bus.read(cpu.pc + 2, AccessType::DataRead);
+        let lo = cpu.operand(bus, 1);
+        let hi = cpu.operand(bus, 2);
         let ptr: Word = (lo, hi).into();
 
-        let effective_lo = bus.read(ptr, AccessType::DataRead);
+        let effective_lo = bus.cpu_read(ptr, AccessType::DataRead);
         let ptr_raw: u16 = ptr.into();
         let high_addr = Word::from((ptr_raw & 0xFF00) | ((ptr_raw + 1) & 0x00FF)); // NMOS wraparound bug
-        let effective_hi = bus.read(high_addr, AccessType::DataRead);
+        let effective_hi = bus.cpu_read(high_addr, AccessType::DataRead);
         (effective_lo, effective_hi).into()
     }
 
@@ -110,11 +305,11 @@ pub struct AbsoluteIndirectCorrect;
 impl AddressingMode for AbsoluteIndirectCorrect {
     fn fetch_address<B: Mos6502CompatibleBus>(cpu: &Cpu<B>, bus: &mut B) -> Word {
         let ptr = Word::from((
-            bus.read(cpu.pc + 1, AccessType::DataRead),
-            bus.read(cpu.pc + 2, AccessType::DataRead),
+            cpu.operand(bus, 1),
+            cpu.operand(bus, 2),
         ));
-        let lo = bus.read(ptr, AccessType::DataRead);
-        let hi = bus.read(ptr + 1, AccessType::DataRead);
+        let lo = bus.cpu_read(ptr, AccessType::DataRead);
+        let hi = bus.cpu_read(ptr + 1, AccessType::DataRead);
         (lo, hi).into()
     }
 
@@ -124,7 +319,7 @@ impl AddressingMode for AbsoluteIndirectCorrect {
 pub struct ZeroPage;
 impl AddressingMode for ZeroPage {
     fn fetch_address<B: Mos6502CompatibleBus>(cpu: &Cpu<B>, bus: &mut B) -> Word {
-        bus.read(cpu.pc + 1, AccessType::DataRead).into()
+        cpu.zero_page(cpu.operand(bus, 1))
     }
 
     const BYTES: u16 = 2;
@@ -133,7 +328,7 @@ impl AddressingMode for ZeroPage {
 pub struct ZeroPageX;
 impl AddressingMode for ZeroPageX {
     fn fetch_address<B: Mos6502CompatibleBus>(cpu: &Cpu<B>, bus: &mut B) -> Word {
-        (bus.read(cpu.pc + 1, AccessType::DataRead) + cpu.x).into()
+        cpu.zero_page(cpu.operand(bus, 1) + cpu.x)
     }
 
     const BYTES: u16 = 2;
@@ -142,10 +337,20 @@ impl AddressingMode for ZeroPageX {
 pub struct ZeroPageIndirect;
 impl AddressingMode for ZeroPageIndirect {
     fn fetch_address<B: Mos6502CompatibleBus>(cpu: &Cpu<B>, bus: &mut B) -> Word {
-        let ptr = bus.read(cpu.pc + 1, AccessType::DataRead);
-        let lo = bus.read(ptr, AccessType::DataRead);
-        let hi = bus.read(ptr + 1u8, AccessType::DataRead);
-        (lo, hi).into()
+        let ptr = cpu.operand(bus, 1);
+        read_zp_pointer(cpu, bus, ptr)
+    }
+
+    const BYTES: u16 = 2;
+}
+
+/// `($12),Z`: the zero-page pointer indexed by the 65CE02's Z register.
+///
+/// The 65CE02 took over the 65C02's `($12)` opcodes for this mode; with Z = 0 the two match.
+pub struct ZeroPageIndirectZ;
+impl AddressingMode for ZeroPageIndirectZ {
+    fn fetch_address<B: Mos6502CompatibleBus>(cpu: &Cpu<B>, bus: &mut B) -> Word {
+        ZeroPageIndirect::fetch_address(cpu, bus) + cpu.z
     }
 
     const BYTES: u16 = 2;
@@ -154,7 +359,7 @@ impl AddressingMode for ZeroPageIndirect {
 pub struct ZeroPageY;
 impl AddressingMode for ZeroPageY {
     fn fetch_address<B: Mos6502CompatibleBus>(cpu: &Cpu<B>, bus: &mut B) -> Word {
-        (bus.read(cpu.pc + 1, AccessType::DataRead) + cpu.y).into()
+        cpu.zero_page(cpu.operand(bus, 1) + cpu.y)
     }
 
     const BYTES: u16 = 2;
@@ -163,25 +368,122 @@ impl AddressingMode for ZeroPageY {
 pub struct ZeroPageXIndirect;
 impl AddressingMode for ZeroPageXIndirect {
     fn fetch_address<B: Mos6502CompatibleBus>(cpu: &Cpu<B>, bus: &mut B) -> Word {
-        let addr = bus.read(cpu.pc + 1, AccessType::DataRead) + cpu.x;
-        let lo = bus.read(addr, AccessType::DataRead);
-        let hi = bus.read(addr + 1u8, AccessType::DataRead);
-        (lo, hi).into()
+        let ptr = cpu.operand(bus, 1) + cpu.x;
+        read_zp_pointer(cpu, bus, ptr)
+    }
+
+    const BYTES: u16 = 2;
+}
+
+/// Signed 8-bit offset from the address of the next instruction, used by the branches.
+///
+/// The penalized form reports the extra cycle a taken branch costs when the target lands on
+/// another page; the cycle every taken branch pays is left to the instruction.
+pub struct Relative;
+impl AddressingMode for Relative {
+    fn fetch_address<B: Mos6502CompatibleBus>(cpu: &Cpu<B>, bus: &mut B) -> Word {
+        let offset = i8::from(cpu.operand(bus, 1));
+        cpu.pc + Self::BYTES + offset
+    }
+
+    fn fetch_address_penalized<B: Mos6502CompatibleBus>(cpu: &Cpu<B>, bus: &mut B) -> (Word, u8) {
+        let base = cpu.pc + Self::BYTES;
+        let target = Self::fetch_address(cpu, bus);
+        (target, u8::from(cpu.crosses_page(base, target)))
     }
 
     const BYTES: u16 = 2;
 }
 
+/// Signed 16-bit branch offset of the 65CE02's long branches.
+///
+/// The offset counts from the instruction's last byte rather than from the next instruction, so
+/// the target is `pc + 2 + offset`.
+pub struct RelativeLong;
+impl AddressingMode for RelativeLong {
+    fn fetch_address<B: Mos6502CompatibleBus>(cpu: &Cpu<B>, bus: &mut B) -> Word {
+        let offset = Word::from((cpu.operand(bus, 1), cpu.operand(bus, 2)));
+        Word(cpu.pc.0.wrapping_add(2).wrapping_add(offset.0))
+    }
+
+    const BYTES: u16 = 3;
+}
+
 pub struct ZeroPageIndirectY;
 impl AddressingMode for ZeroPageIndirectY {
     fn fetch_address<B: Mos6502CompatibleBus>(cpu: &Cpu<B>, bus: &mut B) -> Word {
-        let addr = bus.read(cpu.pc + 1, AccessType::DataRead);
-        let lo = bus.read(addr, AccessType::DataRead);
-        let hi = bus.read(addr + 1u8, AccessType::DataRead);
-        let ptr = word!((lo, hi));
+        let zp = cpu.operand(bus, 1);
+        read_zp_pointer(cpu, bus, zp) + cpu.y
+    }
+
+    fn fetch_address_penalized<B: Mos6502CompatibleBus>(cpu: &Cpu<B>, bus: &mut B) -> (Word, u8) {
+        let base = ZeroPageIndirect::fetch_address(cpu, bus);
+        indexed_read(bus, base, cpu.y, None)
+    }
+
+    const BYTES: u16 = 2;
+}
+
+/// `($12,SP),Y`: a pointer on the stack, `$12` bytes above SP, indexed by Y.
+///
+/// The pointer's address is the full 16-bit [`stack_base`](Cpu::stack_base) + SP + offset, so it
+/// can sit on the page above the stack pointer.
+pub struct StackIndirectY;
+impl AddressingMode for StackIndirectY {
+    fn fetch_address<B: Mos6502CompatibleBus>(cpu: &Cpu<B>, bus: &mut B) -> Word {
+        let offset = cpu.operand(bus, 1);
+        let ptr = cpu.stack_base() + cpu.sp + offset;
+        let lo = bus.cpu_read(ptr, AccessType::DataRead);
+        let hi = bus.cpu_read(ptr + 1, AccessType::DataRead);
+        Word::from((lo, hi)) + cpu.y
+    }
+
+    const BYTES: u16 = 2;
+}
+
+/// [`ZeroPageIndirectY`] with the 65C02's page-crossing dummy read of the instruction's last
+/// byte.
+pub struct ZeroPageIndirectYCmos;
+impl AddressingMode for ZeroPageIndirectYCmos {
+    fn fetch_address<B: Mos6502CompatibleBus>(cpu: &Cpu<B>, bus: &mut B) -> Word {
+        ZeroPageIndirectY::fetch_address(cpu, bus)
+    }
 
-        ptr + cpu.y
+    fn fetch_address_penalized<B: Mos6502CompatibleBus>(cpu: &Cpu<B>, bus: &mut B) -> (Word, u8) {
+        let base = ZeroPageIndirect::fetch_address(cpu, bus);
+        indexed_read(bus, base, cpu.y, Some(cpu.pc + 1))
     }
 
     const BYTES: u16 = 2;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SimpleBus;
+    use crate::instruction::mos6502::Mos6502;
+    use ull::Bus;
+
+    #[test]
+    fn zero_page_pointers_wrap_within_page_zero() {
+        let mut bus = SimpleBus::default();
+        bus.write(Word(0x00FF), Byte(0x34), AccessType::DataWrite);
+        bus.write(Word(0x0000), Byte(0x12), AccessType::DataWrite);
+        // The high byte must not come from here.
+        bus.write(Word(0x0100), Byte(0xEE), AccessType::DataWrite);
+
+        // Operand $FF for (zp), (zp,X) with X=0 and (zp),Y with Y=1.
+        let mut cpu: Cpu<SimpleBus> =
+            Cpu::with_program::<Mos6502>(&mut bus, Word(0x8000), &[0x00, 0xFF], Word(0x8000));
+        assert_eq!(read_zp_pointer(&cpu, &mut bus, Byte(0xFF)), Word(0x1234));
+        assert_eq!(ZeroPageIndirect::fetch_address(&cpu, &mut bus), Word(0x1234));
+        assert_eq!(ZeroPageXIndirect::fetch_address(&cpu, &mut bus), Word(0x1234));
+        cpu.y = Byte(0x01);
+        assert_eq!(ZeroPageIndirectY::fetch_address(&cpu, &mut bus), Word(0x1235));
+
+        // (zp,X) wraps the indexed pointer address too: $80 + $7F = $FF.
+        bus.write(Word(0x8001), Byte(0x80), AccessType::DataWrite);
+        cpu.x = Byte(0x7F);
+        assert_eq!(ZeroPageXIndirect::fetch_address(&cpu, &mut bus), Word(0x1234));
+    }
+}
@@ -0,0 +1,317 @@
+//! Single-instruction assembler driven by the instruction table metadata.
+//!
+//! The inverse of [`disasm`](crate::processor::disasm): operands are written the way the
+//! disassembler prints them, and the opcode is looked up with [`InstructionTable::find`].
+
+use crate::bus::Mos6502CompatibleBus;
+use crate::instruction::InstructionTable;
+use crate::processor::addressing_mode::AddressingModeKind;
+use alloc::string::String;
+use core::fmt;
+use ull::Word;
+
+/// Why [`assemble_line`] couldn't encode a line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AsmError {
+    /// The line has no mnemonic.
+    Empty,
+    /// No opcode in the table has this mnemonic.
+    UnknownMnemonic,
+    /// The operand isn't in any recognised addressing-mode syntax, or a value doesn't parse.
+    InvalidOperand,
+    /// The mnemonic exists, but not with the addressing mode the operand implies.
+    UnsupportedMode,
+    /// A branch target is more than -128/+127 bytes from the next instruction.
+    BranchOutOfRange,
+}
+
+impl fmt::Display for AsmError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            AsmError::Empty => "empty line",
+            AsmError::UnknownMnemonic => "unknown mnemonic",
+            AsmError::InvalidOperand => "invalid operand",
+            AsmError::UnsupportedMode => "addressing mode not supported by this instruction",
+            AsmError::BranchOutOfRange => "branch target out of range",
+        })
+    }
+}
+
+impl core::error::Error for AsmError {}
+
+/// Assemble one instruction located at `pc`, returning its bytes and length.
+///
+/// Operands use the syntax [`disassemble`](crate::processor::disasm::disassemble) prints:
+/// `#$12`, `$12`, `$1234,X`, `($12,X)`, `($12),Y`, `($1234)`, `A`, and absolute branch targets
+/// (`BNE $8010`, `BBR0 $12,$8010`), plus the 65CE02's `($12),Z` and `($12,SP),Y`. Numbers are hex with an optional `$` or `0x` prefix, and
+/// mnemonics and registers are case-insensitive. A value written with one or two digits
+/// prefers the zero-page form of an instruction and falls back to the absolute one; three or
+/// four digits always select the absolute form. Only the first `len` bytes of the array are
+/// meaningful.
+///
+/// # Errors
+///
+/// Returns an [`AsmError`] describing the first problem found.
+///
+/// # Examples
+///
+/// ```
+/// use ull::Word;
+/// use ull65::instruction::mos6502::Mos6502;
+/// use ull65::processor::asm::assemble_line;
+/// use ull65::SimpleBus;
+///
+/// let table = Mos6502::base_table::<SimpleBus>();
+///
+/// assert_eq!(assemble_line(&table, "LDA ($12),Y", Word(0x8000)), Ok(([0xB1, 0x12, 0x00], 2)));
+/// assert_eq!(assemble_line(&table, "bne $7FF0", Word(0x8000)), Ok(([0xD0, 0xEE, 0x00], 2)));
+/// ```
+pub fn assemble_line<B: Mos6502CompatibleBus>(
+    table: &InstructionTable<B>,
+    text: &str,
+    pc: Word,
+) -> Result<([u8; 3], usize), AsmError> {
+    let text = text.trim();
+    let (mnemonic, operand) = text.split_once(char::is_whitespace).unwrap_or((text, ""));
+    if mnemonic.is_empty() {
+        return Err(AsmError::Empty);
+    }
+    let mnemonic = mnemonic.to_ascii_uppercase();
+    let operand: String = operand
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .map(|c| c.to_ascii_uppercase())
+        .collect();
+
+    let mut unsupported = false;
+    for (mode, value) in candidates(&operand)? {
+        let Some(opcode) = table.find(&mnemonic, mode) else {
+            continue;
+        };
+        let [lo, hi] = match mode {
+            AddressingModeKind::Relative => [branch_offset(pc + 2u16, value)?, 0],
+            AddressingModeKind::ZeroPageRelative => {
+                let (zp, target) = (value & 0xFF, value >> 16);
+                [zp as u8, branch_offset(pc + 3u16, target)?]
+            }
+            _ => (value as u16).to_le_bytes(),
+        };
+        let len = usize::from(mode.operand_bytes()) + 1;
+        return Ok(([opcode, lo, hi], len));
+    }
+    unsupported |= table.iter().any(|(_, instruction)| instruction.mnemonic == mnemonic);
+
+    Err(if unsupported {
+        AsmError::UnsupportedMode
+    } else {
+        AsmError::UnknownMnemonic
+    })
+}
+
+/// Addressing modes the operand syntax could mean, most specific first, with the operand value.
+///
+/// `ZeroPageRelative` packs the branch target into bits 16-31 above the zero-page address.
+fn candidates(operand: &str) -> Result<impl Iterator<Item = (AddressingModeKind, u32)>, AsmError> {
+    use AddressingModeKind::*;
+
+    let mut modes: [Option<AddressingModeKind>; 3] = [None; 3];
+    let mut value = 0u32;
+
+    let mut set = |list: &[AddressingModeKind]| {
+        for (slot, mode) in modes.iter_mut().zip(list) {
+            *slot = Some(*mode);
+        }
+    };
+
+    if operand.is_empty() {
+        set(&[Implied, Accumulator]);
+    } else if operand == "A" {
+        set(&[Accumulator]);
+    } else if let Some(imm) = operand.strip_prefix('#') {
+        value = parse_value(imm, 2)?.0;
+        set(&[Immediate]);
+    } else if let Some(inner) = operand.strip_prefix('(') {
+        if let Some(offset) = inner.strip_suffix(",SP),Y") {
+            value = parse_value(offset, 2)?.0;
+            set(&[StackIndirectY]);
+        } else if let Some(addr) = inner.strip_suffix("),Z") {
+            value = parse_value(addr, 2)?.0;
+            set(&[ZeroPageIndirectZ]);
+        } else if let Some(addr) = inner.strip_suffix(",X)") {
+            let (addr, short) = parse_value(addr, 4)?;
+            value = addr;
+            set(if short {
+                &[ZeroPageXIndirect, AbsoluteIndirectX]
+            } else {
+                &[AbsoluteIndirectX]
+            });
+        } else if let Some(addr) = inner.strip_suffix("),Y") {
+            value = parse_value(addr, 2)?.0;
+            set(&[ZeroPageIndirectY]);
+        } else if let Some(addr) = inner.strip_suffix(')') {
+            let (addr, short) = parse_value(addr, 4)?;
+            value = addr;
+            set(if short {
+                &[ZeroPageIndirect, AbsoluteIndirect, AbsoluteIndirectCorrect]
+            } else {
+                &[AbsoluteIndirect, AbsoluteIndirectCorrect]
+            });
+        } else {
+            return Err(AsmError::InvalidOperand);
+        }
+    } else if let Some(addr) = operand.strip_suffix(",X") {
+        let (addr, short) = parse_value(addr, 4)?;
+        value = addr;
+        set(if short { &[ZeroPageX, AbsoluteX] } else { &[AbsoluteX] });
+    } else if let Some(addr) = operand.strip_suffix(",Y") {
+        let (addr, short) = parse_value(addr, 4)?;
+        value = addr;
+        set(if short { &[ZeroPageY, AbsoluteY] } else { &[AbsoluteY] });
+    } else if let Some((zp, target)) = operand.split_once(',') {
+        value = parse_value(zp, 2)?.0 | parse_value(target, 4)?.0 << 16;
+        set(&[ZeroPageRelative]);
+    } else {
+        let (addr, short) = parse_value(operand, 4)?;
+        value = addr;
+        set(if short {
+            &[Relative, ZeroPage, Absolute]
+        } else {
+            &[Relative, Absolute]
+        });
+    }
+
+    Ok(modes.into_iter().flatten().map(move |mode| (mode, value)))
+}
+
+/// Parse a hex value of at most `max_digits` digits, reporting whether it was written with two
+/// digits or fewer (and so can select a zero-page form).
+fn parse_value(text: &str, max_digits: usize) -> Result<(u32, bool), AsmError> {
+    let digits = text
+        .strip_prefix('$')
+        .or_else(|| text.strip_prefix("0X"))
+        .unwrap_or(text);
+    if digits.len() > max_digits {
+        return Err(AsmError::InvalidOperand);
+    }
+    let value = Word::from_hex(digits).map_err(|_| AsmError::InvalidOperand)?;
+    Ok((u32::from(value.0), digits.len() <= 2))
+}
+
+/// Offset from `next` (the address after the branch) to `target`, if it fits in an `i8`.
+fn branch_offset(next: Word, target: u32) -> Result<u8, AsmError> {
+    let offset = i32::try_from(target).unwrap_or(i32::MAX) - i32::from(next.0);
+    i8::try_from(offset)
+        .map(|offset| offset as u8)
+        .map_err(|_| AsmError::BranchOutOfRange)
+}
+
+/// Assemble newline-separated NMOS instructions starting at $8000, where the unit tests'
+/// reset vector points. Blank lines are skipped; there are no labels or directives, so branch
+/// and jump targets are absolute addresses.
+///
+/// # Panics
+///
+/// On the first line that doesn't assemble.
+#[cfg(test)]
+pub(crate) fn assemble(source: &str) -> alloc::vec::Vec<u8> {
+    let table = crate::instruction::mos6502::Mos6502::base_table::<crate::SimpleBus>();
+    let mut pc = Word(0x8000);
+    let mut program = alloc::vec::Vec::new();
+
+    for (number, line) in source.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let (bytes, len) = assemble_line(&table, line, pc)
+            .unwrap_or_else(|err| panic!("line {}: `{}`: {err}", number + 1, line.trim()));
+        program.extend_from_slice(&bytes[..len]);
+        pc += len;
+    }
+
+    program
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SimpleBus;
+    use crate::instruction::mos6502::Mos6502;
+    use crate::instruction::wdc65c02s::Wdc65c02s;
+    use crate::processor::disasm::disassemble;
+    use alloc::string::ToString;
+
+    fn round_trip(table: &InstructionTable<SimpleBus>, line: &str, pc: u16) -> String {
+        let (bytes, len) = assemble_line(table, line, Word(pc)).unwrap();
+        let mut bus = SimpleBus::default();
+        bus.load(Word(pc), &bytes[..len]);
+        let decoded = disassemble(table, &mut bus, Word(pc));
+        assert_eq!(usize::from(decoded.len), len, "{line}");
+        decoded.to_string()
+    }
+
+    #[test]
+    fn assemble_joins_lines_into_one_program() {
+        assert_eq!(assemble("LDA #$42\nBRK"), [0xA9, 0x42, 0x00]);
+        // Branch targets account for the bytes before them.
+        assert_eq!(
+            assemble("  ldx #$03\n\n  dex\n  bne $8002\n"),
+            [0xA2, 0x03, 0xCA, 0xD0, 0xFD]
+        );
+    }
+
+    #[test]
+    fn assembled_lines_disassemble_back_to_the_same_text() {
+        let table = Mos6502::base_table::<SimpleBus>();
+        for line in [
+            "BRK",
+            "ASL A",
+            "LDA #$42",
+            "STA $12",
+            "LDX $12,Y",
+            "STA $1234,X",
+            "LDA $1234,Y",
+            "JMP ($FFFC)",
+            "ORA ($20,X)",
+            "LDA ($12),Y",
+            "BNE $8010",
+            "BEQ $7F82",
+            "JSR $FF00",
+            "LDA $0012",
+        ] {
+            assert_eq!(round_trip(&table, line, 0x8000), line);
+        }
+
+        let cmos = Wdc65c02s::base_table::<SimpleBus>();
+        for line in ["LDA ($12)", "JMP ($1234,X)", "BBR3 $12,$8020", "STZ $40,X", "INC A"] {
+            assert_eq!(round_trip(&cmos, line, 0x8000), line);
+        }
+    }
+
+    #[test]
+    fn operand_size_and_syntax_pick_the_mode() {
+        let table = Mos6502::base_table::<SimpleBus>();
+        let assemble = |line| assemble_line(&table, line, Word(0x8000));
+
+        assert_eq!(assemble("  lda   $12 , x "), Ok(([0xB5, 0x12, 0x00], 2)));
+        assert_eq!(assemble("LDA 0x1234"), Ok(([0xAD, 0x34, 0x12], 3)));
+        // No zero-page JMP, so a short address still assembles as absolute.
+        assert_eq!(assemble("JMP $12"), Ok(([0x4C, 0x12, 0x00], 3)));
+        assert_eq!(assemble("ASL"), Ok(([0x0A, 0x00, 0x00], 1)));
+        assert_eq!(assemble("NOP"), Ok(([0xEA, 0x00, 0x00], 1)));
+    }
+
+    #[test]
+    fn reports_what_went_wrong() {
+        let table = Mos6502::base_table::<SimpleBus>();
+        let assemble = |line| assemble_line(&table, line, Word(0x8000));
+
+        assert_eq!(assemble("   "), Err(AsmError::Empty));
+        assert_eq!(assemble("FOO #$12"), Err(AsmError::UnknownMnemonic));
+        assert_eq!(assemble("STA #$12"), Err(AsmError::UnsupportedMode));
+        assert_eq!(assemble("LDA ($1234),Y"), Err(AsmError::InvalidOperand));
+        assert_eq!(assemble("LDA #$123"), Err(AsmError::InvalidOperand));
+        assert_eq!(assemble("LDA $12,Z"), Err(AsmError::InvalidOperand));
+        assert_eq!(assemble("BNE $8100"), Err(AsmError::BranchOutOfRange));
+        assert_eq!(assemble("BNE $8081"), Ok(([0xD0, 0x7F, 0x00], 2)));
+    }
+}
@@ -1,10 +1,18 @@
 //! 6502 CPU implementation with registers and execution loop.
 
 use crate::bus::Mos6502CompatibleBus;
-use crate::instruction::{mos6502::Mos6502, InstructionSet, InstructionTable};
+use crate::instruction::{
+    mos6502::Mos6502, FlowKind, InstructionSet, InstructionTable, TableStorage,
+};
+use crate::processor::addressing_mode::AddressingModeKind;
 use crate::processor::flags::Flags;
+#[cfg(feature = "history")]
+use crate::processor::history::{History, HistoryEntry};
 use crate::processor::run::{RunConfig, RunOutcome, RunSummary};
-use crate::{AccessType, ResetVectorExt};
+use crate::{AccessType, CyclePhase, ResetVectorExt};
+use alloc::boxed::Box;
+use alloc::collections::BTreeMap;
+use alloc::rc::Rc;
 use core::fmt;
 use ull::{byte, word};
 use ull::{Address, Byte, Word};
@@ -17,6 +25,7 @@ pub enum Interrupt {
 }
 
 #[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum RunState {
     Running,
     Waiting,
@@ -34,9 +43,230 @@ pub const NMI_VECTOR_HI: Word = Word(0xFFFB);
 pub const RESET_VECTOR_LO: Word = Word(0xFFFC);
 /// RESET vector high byte address.
 pub const RESET_VECTOR_HI: Word = Word(0xFFFD);
+/// Cycles the 6502 spends on an IRQ, NMI or RESET sequence before the handler's first opcode.
+pub const INTERRUPT_CYCLES: u8 = 7;
 /// Start of stack space (the 6502 stack grows downward from 0x01FF to 0x0100).
 pub const STACK_SPACE_START: Word = Word(0x0100);
 
+/// Boxed closure that replaces the table entry for a single opcode.
+///
+/// See [`Cpu::set_opcode_handler`].
+pub type OpcodeHandler<B> = Box<dyn FnMut(&mut Cpu<B>, &mut B)>;
+
+/// Boxed closure run after every executed instruction.
+///
+/// See [`Cpu::on_instruction`].
+pub type InstructionHook = Box<dyn FnMut(&InstructionContext)>;
+
+/// What an [`InstructionHook`] learns about the instruction that just ran.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InstructionContext {
+    /// Address the opcode was fetched from.
+    pub pc: Word,
+    /// The opcode that ran.
+    pub opcode: Byte,
+    /// Cycles the instruction charged.
+    pub cycles: u8,
+    /// [`Cpu::total_cycles`] once the instruction has completed.
+    pub total_cycles: u64,
+}
+
+struct OpcodeOverride<B: Mos6502CompatibleBus> {
+    cycles: u8,
+    handler: OpcodeHandler<B>,
+}
+
+/// Progress of [`Cpu::cycle`] through the current instruction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum MicroStep {
+    /// Between instructions; the next cycle fetches an opcode or starts an interrupt.
+    Boundary,
+    /// The opcode has been fetched and its handler runs on cycle `execute_on`. `operands` holds
+    /// the operand bytes already read on cycles 2 and 3, for [`Cpu::operand`] to hand back.
+    Decode {
+        opcode: Byte,
+        elapsed: u8,
+        execute_on: u8,
+        operands: [Option<Byte>; 2],
+    },
+    /// The handler has run; `remaining` cycles of the instruction are still to elapse.
+    Finish { remaining: u8 },
+}
+
+/// What happened at the start of a step, before any opcode handler runs.
+enum StepStart {
+    /// Halted, or waiting with nothing to wake it.
+    Idle,
+    /// An interrupt or reset sequence ran and took this many cycles.
+    Serviced(u8),
+    /// An opcode was fetched and is ready to dispatch.
+    Fetched(Byte),
+}
+
+/// Operand bytes [`Cpu::cycle`] reads on their own cycles (2, then 3) ahead of the handler.
+///
+/// Only modes whose operand reads all go through [`Cpu::operand`] are listed; the rest read
+/// their operands along with the data access.
+const fn latched_operands(mode: AddressingModeKind) -> u8 {
+    match mode {
+        AddressingModeKind::Absolute
+        | AddressingModeKind::AbsoluteX
+        | AddressingModeKind::AbsoluteY
+        | AddressingModeKind::AbsoluteIndirect
+        | AddressingModeKind::AbsoluteIndirectCorrect
+        | AddressingModeKind::AbsoluteIndirectX => 2,
+        AddressingModeKind::ZeroPage
+        | AddressingModeKind::ZeroPageX
+        | AddressingModeKind::ZeroPageY
+        | AddressingModeKind::ZeroPageIndirect
+        | AddressingModeKind::ZeroPageIndirectZ
+        | AddressingModeKind::ZeroPageXIndirect
+        | AddressingModeKind::ZeroPageIndirectY
+        | AddressingModeKind::StackIndirectY => 1,
+        _ => 0,
+    }
+}
+
+/// Cycle of an instruction on which its effective-address data access lands, counting the
+/// opcode fetch as cycle 1. Modes whose timing isn't modeled run on their last cycle.
+const fn data_cycle(mode: AddressingModeKind) -> u8 {
+    match mode {
+        AddressingModeKind::Implied
+        | AddressingModeKind::Accumulator
+        | AddressingModeKind::Immediate
+        | AddressingModeKind::Relative
+        | AddressingModeKind::ZeroPageRelative => 2,
+        AddressingModeKind::ImmediateWord
+        | AddressingModeKind::RelativeLong
+        | AddressingModeKind::ZeroPage => 3,
+        AddressingModeKind::ZeroPageX
+        | AddressingModeKind::ZeroPageY
+        | AddressingModeKind::Absolute
+        | AddressingModeKind::AbsoluteX
+        | AddressingModeKind::AbsoluteY => 4,
+        AddressingModeKind::ZeroPageIndirect
+        | AddressingModeKind::ZeroPageIndirectZ
+        | AddressingModeKind::ZeroPageIndirectY => 5,
+        AddressingModeKind::ZeroPageXIndirect | AddressingModeKind::StackIndirectY => 6,
+        AddressingModeKind::AbsoluteIndirect
+        | AddressingModeKind::AbsoluteIndirectCorrect
+        | AddressingModeKind::AbsoluteIndirectX => u8::MAX,
+    }
+}
+
+/// Snapshot of everything [`Cpu`] needs to resume execution, for save-states.
+///
+/// The instruction table and opcode handlers are deliberately left out: the table is rebuilt
+/// from the [`InstructionSet`] when the CPU is constructed, and closures can't be serialized.
+/// Enable the `serde` feature to derive `Serialize`/`Deserialize`.
+///
+/// New fields are added as the CPU grows, so the struct can't be built with a literal outside
+/// this crate. Start from [`Cpu::save_state`] or [`CpuState::default`], a freshly constructed
+/// CPU's state, and set the fields you need.
+///
+/// # Examples
+///
+/// ```
+/// use ull::{Byte, Word};
+/// use ull65::{Cpu, CpuState, SimpleBus};
+///
+/// let mut state = CpuState::default();
+/// state.a = Byte(0x42);
+/// state.pc = Word(0x0200);
+///
+/// let mut cpu: Cpu<SimpleBus> = Cpu::default();
+/// cpu.load_state(state);
+/// assert_eq!((cpu.a, cpu.pc), (Byte(0x42), Word(0x0200)));
+/// ```
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub struct CpuState {
+    pub a: Byte,
+    pub x: Byte,
+    pub y: Byte,
+    pub p: Flags,
+    pub sp: Byte,
+    pub pc: Word,
+    pub cycles: u64,
+    pub run_state: RunState,
+    pub irq_pending: bool,
+    pub nmi_pending: bool,
+    pub reset_pending: bool,
+    pub last_opcode: Byte,
+    /// Level last driven by [`Cpu::set_nmi_line`], needed to detect the next edge.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub nmi_line: bool,
+    /// Level driven by [`Cpu::set_irq_line`].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub irq_line: bool,
+    /// The 65CE02's Z register.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub z: Byte,
+    /// The 65CE02's base page register.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub b: Byte,
+    /// The 65CE02's 16-bit stack mode; see [`Cpu::set_stack_extended`].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub stack_extended: bool,
+    /// See [`Cpu::stack_base`]; its high byte is the 65CE02's SPH register.
+    #[cfg_attr(feature = "serde", serde(default = "default_stack_base"))]
+    pub stack_base: Word,
+}
+
+impl Default for CpuState {
+    fn default() -> Self {
+        Self {
+            a: byte!(0),
+            x: byte!(0),
+            y: byte!(0),
+            p: Flags::InterruptDisabled | Flags::Expansion,
+            sp: byte!(0xFD),
+            pc: word!(0u16),
+            cycles: 0,
+            run_state: RunState::Running,
+            irq_pending: false,
+            nmi_pending: false,
+            reset_pending: false,
+            last_opcode: byte!(0),
+            nmi_line: false,
+            irq_line: false,
+            z: byte!(0),
+            b: byte!(0),
+            stack_extended: false,
+            stack_base: STACK_SPACE_START,
+        }
+    }
+}
+
+/// States saved before `stack_base` was recorded used the stock stack page.
+#[cfg(feature = "serde")]
+fn default_stack_base() -> Word {
+    STACK_SPACE_START
+}
+
+/// The programmer-visible registers, as a value that can be compared or applied in one go.
+///
+/// See [`Cpu::registers`] and [`Cpu::set_registers`]. Equality compares `p` bit for bit.
+#[derive(Debug, Clone, Copy)]
+pub struct Registers {
+    pub a: Byte,
+    pub x: Byte,
+    pub y: Byte,
+    pub p: Flags,
+    pub sp: Byte,
+    pub pc: Word,
+}
+
+impl PartialEq for Registers {
+    fn eq(&self, other: &Self) -> bool {
+        (self.a, self.x, self.y, self.p.bits(), self.sp, self.pc)
+            == (other.a, other.x, other.y, other.p.bits(), other.sp, other.pc)
+    }
+}
+
+impl Eq for Registers {}
+
 /// The 6502 CPU with registers and instruction table.
 ///
 /// Maintains the CPU state over a generic [`Mos6502CompatibleBus`] implementation to allow custom memory/I/O.
@@ -72,47 +302,159 @@ pub struct Cpu<B: Mos6502CompatibleBus> {
     pub x: Byte,
     /// Y index register.
     pub y: Byte,
+    /// Z register, the 65CE02's third index register. Other parts never touch it, so it stays 0.
+    pub z: Byte,
+    /// Base page register, the 65CE02's high byte for every zero-page address, set by `TAB`.
+    /// Other parts never touch it, so their zero page stays at $0000.
+    pub b: Byte,
     /// Processor status flags.
     pub p: Flags,
-    /// Stack pointer (0x00-0xFF, actual stack is at 0x0100 + sp).
+    /// Stack pointer (0x00-0xFF, actual stack is at [`stack_base`](Self::stack_base) + sp).
     pub sp: Byte,
     /// Program counter.
     pub pc: Word,
-    /// Total cycles executed.
+    /// Cycles executed since the last cold [`reset`](Self::reset).
+    ///
+    /// See [`total_cycles`](Self::total_cycles) for a count that survives resets.
     pub cycles: u64,
+    /// Cycles already folded into `lifetime_cycles`; only ever moves forward with them.
+    lifetime_cycles: u64,
+    /// Value of `cycles` when `lifetime_cycles` was last brought up to date.
+    synced_cycles: u64,
+    /// [`total_cycles`](Self::total_cycles) when the current instruction's opcode was fetched.
+    fetch_cycle: u64,
+    instruction_set_name: Option<&'static str>,
+    stack_base: Word,
+    stack_extended: bool,
     last_step_cycles: u8,
+    last_step_bus_cycles: Option<u8>,
     /// Opcode executed by the most recent successful [`step`](Self::step) call.
     pub last_opcode: Byte,
-    /// Instruction dispatch table.
-    pub table: InstructionTable<B>,
+    /// Instruction dispatch table, either owned or shared with other CPUs.
+    pub table: TableStorage<B>,
     pub run_state: RunState,
     irq_pending: bool,
+    irq_line: bool,
     nmi_pending: bool,
+    nmi_line: bool,
     reset_pending: bool,
+    ready: bool,
+    opcode_handlers: BTreeMap<u8, OpcodeOverride<B>>,
+    /// Opcode whose handler is running; cleared if the handler removes itself meanwhile.
+    running_handler: Option<u8>,
+    illegal_trap: Option<OpcodeHandler<B>>,
+    /// Called after each executed instruction (not after interrupt sequences).
+    ///
+    /// Runs from both [`step`](Self::step) and [`cycle`](Self::cycle), once the instruction's
+    /// cycles have been charged, which makes it a cheap place to hang a tracer or profiler.
+    pub on_instruction: Option<InstructionHook>,
+    micro: MicroStep,
+    #[cfg(feature = "coverage")]
+    coverage: Box<[u32; 256]>,
+    #[cfg(feature = "history")]
+    history: History,
 }
 
 impl<B: Mos6502CompatibleBus> fmt::Debug for Cpu<B> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         // Here only because the jump table is too noisy
         f.debug_struct("Cpu")
+            .field("instruction_set", &self.instruction_set_name)
             .field("a", &self.a)
             .field("x", &self.x)
             .field("y", &self.y)
+            .field("z", &self.z)
+            .field("b", &self.b)
             .field("p", &self.p)
             .field("sp", &self.sp)
             .field("pc", &self.pc)
+            .field("stack_base", &self.stack_base)
+            .field("stack_extended", &self.stack_extended)
             .field("cycles", &self.cycles)
+            .field("total_cycles", &self.total_cycles())
             .field("last_step_cycles", &self.last_step_cycles)
+            .field("last_step_bus_cycles", &self.last_step_bus_cycles)
             .field("last_opcode", &self.last_opcode)
             .field("run_state", &self.run_state)
             .field("irq_pending", &self.irq_pending)
+            .field("irq_line", &self.irq_line)
             .field("nmi_pending", &self.nmi_pending)
+            .field("nmi_line", &self.nmi_line)
             .field("reset_pending", &self.reset_pending)
+            .field("ready", &self.ready)
+            .field("opcode_handlers", &self.opcode_handlers.keys())
+            .field("illegal_trap", &self.illegal_trap.is_some())
+            .field("on_instruction", &self.on_instruction.is_some())
+            .field("micro", &self.micro)
             .finish_non_exhaustive()
     }
 }
 
-impl<B: Mos6502CompatibleBus + 'static> Cpu<B> {
+impl<B: Mos6502CompatibleBus> Cpu<B> {
+    /// Fork the CPU, e.g. to explore two execution paths from the same point.
+    ///
+    /// Registers, pending interrupts, the in-flight [`cycle`](Self::cycle) position and the
+    /// instruction table are copied (a shared table stays shared). Boxed closures can't be
+    /// cloned, which is why `Cpu` isn't `Clone`: the fork starts without any
+    /// [`set_opcode_handler`](Self::set_opcode_handler) overrides, illegal-opcode trap or
+    /// [`on_instruction`](Self::on_instruction) hook. Install them again if the fork needs them.
+    #[must_use]
+    pub fn fork_without_hooks(&self) -> Self {
+        Self {
+            a: self.a,
+            x: self.x,
+            y: self.y,
+            z: self.z,
+            b: self.b,
+            p: self.p,
+            sp: self.sp,
+            pc: self.pc,
+            cycles: self.cycles,
+            lifetime_cycles: self.lifetime_cycles,
+            synced_cycles: self.synced_cycles,
+            fetch_cycle: self.fetch_cycle,
+            instruction_set_name: self.instruction_set_name,
+            stack_base: self.stack_base,
+            stack_extended: self.stack_extended,
+            last_step_cycles: self.last_step_cycles,
+            last_step_bus_cycles: self.last_step_bus_cycles,
+            last_opcode: self.last_opcode,
+            table: self.table.clone(),
+            run_state: self.run_state,
+            irq_pending: self.irq_pending,
+            irq_line: self.irq_line,
+            nmi_pending: self.nmi_pending,
+            nmi_line: self.nmi_line,
+            reset_pending: self.reset_pending,
+            ready: self.ready,
+            opcode_handlers: BTreeMap::new(),
+            running_handler: None,
+            illegal_trap: None,
+            on_instruction: None,
+            micro: self.micro,
+            #[cfg(feature = "coverage")]
+            coverage: self.coverage.clone(),
+            #[cfg(feature = "history")]
+            history: self.history.clone(),
+        }
+    }
+}
+
+/// One-line register dump for logs, e.g. `A:42 X:00 Y:00 SP:FD PC:8003 [nv-BdIzc] CYC:14`.
+impl<B: Mos6502CompatibleBus> fmt::Display for Cpu<B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(name) = self.instruction_set_name {
+            write!(f, "{name}: ")?;
+        }
+        write!(
+            f,
+            "A:{:02X} X:{:02X} Y:{:02X} SP:{:02X} PC:{:04X} [{}] CYC:{}",
+            self.a, self.x, self.y, self.sp, self.pc, self.p, self.cycles
+        )
+    }
+}
+
+impl<B: Mos6502CompatibleBus> Cpu<B> {
     /// Create a new CPU with the specified instruction set.
     ///
     /// Initializes all registers to their power-on state:
@@ -131,21 +473,72 @@ impl<B: Mos6502CompatibleBus + 'static> Cpu<B> {
     /// ```
     #[must_use]
     pub fn with_instruction_set<S: InstructionSet>() -> Self {
+        let mut cpu = Self::with_table(S::instruction_table::<B>().into());
+        cpu.instruction_set_name = Some(S::NAME);
+        cpu
+    }
+
+    /// Create a new CPU that dispatches through a table shared with other CPUs.
+    ///
+    /// [`with_instruction_set`](Self::with_instruction_set) builds and boxes a fresh table per CPU;
+    /// this shares one instead, which avoids that allocation and the 256-entry copy when
+    /// creating many CPUs. Registers start in the same power-on state.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::rc::Rc;
+    /// use ull65::instruction::mos6502::Mos6502;
+    /// use ull65::{Cpu, SimpleBus};
+    ///
+    /// let table = Rc::new(Mos6502::base_table());
+    ///
+    /// let cpus: Vec<Cpu<SimpleBus>> =
+    ///     (0..4).map(|_| Cpu::with_shared_table(Rc::clone(&table))).collect();
+    /// assert_eq!(cpus[3].table[0xEA].cycles, 2);
+    /// ```
+    #[must_use]
+    pub fn with_shared_table(table: Rc<InstructionTable<B>>) -> Self {
+        Self::with_table(table.into())
+    }
+
+    fn with_table(table: TableStorage<B>) -> Self {
         Self {
             a: byte!(0),
             x: byte!(0),
             y: byte!(0),
+            z: byte!(0),
+            b: byte!(0),
             p: Flags::InterruptDisabled | Flags::Expansion,
             sp: byte!(0xFD),
             pc: word!(0u16),
             cycles: 0,
+            lifetime_cycles: 0,
+            synced_cycles: 0,
+            fetch_cycle: 0,
+            instruction_set_name: None,
+            stack_base: STACK_SPACE_START,
+            stack_extended: false,
             last_step_cycles: 0,
+            last_step_bus_cycles: None,
             last_opcode: byte!(0),
-            table: S::instruction_table::<B>(),
+            table,
             run_state: RunState::Running,
             irq_pending: false,
+            irq_line: false,
             nmi_pending: false,
+            nmi_line: false,
             reset_pending: false,
+            ready: true,
+            opcode_handlers: BTreeMap::new(),
+            running_handler: None,
+            illegal_trap: None,
+            on_instruction: None,
+            micro: MicroStep::Boundary,
+            #[cfg(feature = "coverage")]
+            coverage: Box::new([0; 256]),
+            #[cfg(feature = "history")]
+            history: History::new(),
         }
     }
 
@@ -196,8 +589,9 @@ impl<B: Mos6502CompatibleBus + 'static> Cpu<B> {
 
     /// Reset the CPU to initial state and load PC from reset vector.
     ///
-    /// Clears all registers (except loads PC from $FFFC/FFFD) and resets cycle count.
-    /// This mimics the hardware RESET line behavior.
+    /// This is the cold path, equivalent to [`power_on_reset`](Self::power_on_reset): SP goes
+    /// back to `$FD` and the cycle counter to zero. Use [`reset_warm`](Self::reset_warm) to
+    /// model pulling the RESET pin on a running system instead.
     ///
     /// # Examples
     ///
@@ -216,18 +610,99 @@ impl<B: Mos6502CompatibleBus + 'static> Cpu<B> {
     /// assert_eq!(cpu.pc, Word(0x8000));
     /// ```
     pub fn reset(&mut self, bus: &mut B) {
+        self.power_on_reset(bus);
+    }
+
+    /// Bring the CPU up from a cold start and load PC from the reset vector.
+    ///
+    /// Real silicon powers up with indeterminate registers; this picks a deterministic state:
+    /// - A, X, Y (and the 65CE02's Z and B) = 0
+    /// - SP = 0xFD, with the 65CE02's 16-bit stack mode off
+    /// - P = Interrupt Disabled (plus the always-set expansion bit)
+    /// - cycle counter, last opcode and pending interrupts cleared ([`total_cycles`](Self::total_cycles)
+    ///   keeps counting)
+    /// - run state = [`RunState::Running`]
+    pub fn power_on_reset(&mut self, bus: &mut B) {
         self.a = byte!(0);
         self.x = byte!(0);
         self.y = byte!(0);
+        self.z = byte!(0);
+        self.b = byte!(0);
         self.sp = byte!(0xFD);
+        self.stack_extended = false;
         self.p = Flags::InterruptDisabled | Flags::Expansion;
+        self.sync_lifetime_cycles();
         self.cycles = 0;
+        self.synced_cycles = 0;
         self.last_step_cycles = 0;
+        self.last_step_bus_cycles = None;
         self.last_opcode = byte!(0);
+        self.irq_pending = false;
+        self.nmi_pending = false;
+        self.reset_pending = false;
         self.run_state = RunState::Running;
-        let lo = bus.read(RESET_VECTOR_LO, AccessType::InterruptVectorRead);
-        let hi = bus.read(RESET_VECTOR_HI, AccessType::InterruptVectorRead);
-        self.pc = word!((lo, hi));
+        self.micro = MicroStep::Boundary;
+        self.load_reset_vector(bus);
+    }
+
+    /// Model the hardware RESET line on a running system.
+    ///
+    /// The 6502 runs its reset sequence as an interrupt with the stack writes suppressed, so
+    /// only a few things change:
+    /// - SP is decremented by 3 (nothing is written to the stack)
+    /// - I is set; every other flag, A, X and Y are preserved
+    /// - pending IRQ/NMI requests are dropped
+    /// - run state = [`RunState::Running`], which also releases `WAI`/`STP`
+    /// - PC is loaded from the reset vector
+    ///
+    /// The cycle counter keeps running but isn't charged here, which suits hosts that account
+    /// for the sequence themselves; [`reset_warm`](Self::reset_warm) charges it.
+    pub fn reset_line(&mut self, bus: &mut B) {
+        self.sp -= 3;
+        self.p.set_interrupt_disabled(true);
+        self.last_step_cycles = 0;
+        self.last_step_bus_cycles = None;
+        self.irq_pending = false;
+        self.nmi_pending = false;
+        self.reset_pending = false;
+        self.run_state = RunState::Running;
+        self.micro = MicroStep::Boundary;
+        self.load_reset_vector(bus);
+    }
+
+    /// Warm reset: run the whole reset sequence a running system sees when RESET is pulled.
+    ///
+    /// Unlike [`reset`](Self::reset), SP is decremented by 3 (wrapping) from wherever it was
+    /// and the cycle counter isn't zeroed, so boot code that inspects SP or a RAM signature
+    /// can tell a warm start from power-on. On top of the register changes made by
+    /// [`reset_line`](Self::reset_line), the sequence's [`INTERRUPT_CYCLES`] are charged, exactly
+    /// as when [`step`](Self::step) services a requested [`Interrupt::Reset`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ull::Word;
+    /// use ull65::instruction::mos6502::Mos6502;
+    /// use ull65::{Cpu, SimpleBus};
+    ///
+    /// let mut bus = SimpleBus::default();
+    /// let mut cpu: Cpu<SimpleBus> =
+    ///     Cpu::with_program::<Mos6502>(&mut bus, Word(0x8000), &[0xEA], Word(0x8000));
+    /// cpu.step(&mut bus);
+    ///
+    /// cpu.reset_warm(&mut bus);
+    /// assert_eq!(cpu.sp.0, 0xFA);
+    /// assert_eq!(cpu.cycles, 2 + 7);
+    /// ```
+    pub fn reset_warm(&mut self, bus: &mut B) {
+        self.reset_line(bus);
+        self.cycles += u64::from(INTERRUPT_CYCLES);
+        self.last_step_cycles = INTERRUPT_CYCLES;
+        self.sync_lifetime_cycles();
+    }
+
+    fn load_reset_vector(&mut self, bus: &mut B) {
+        self.pc = bus.read16(RESET_VECTOR_LO, AccessType::InterruptVectorRead);
     }
 
     /// Execute one instruction.
@@ -236,6 +711,13 @@ impl<B: Mos6502CompatibleBus + 'static> Cpu<B> {
     /// and increments the cycle counter. The instruction function is responsible for
     /// advancing PC.
     ///
+    /// A pending interrupt is serviced instead of fetching an opcode; that step returns
+    /// [`INTERRUPT_CYCLES`].
+    ///
+    /// The opcode fetch, the operand bytes and the effective-address read go through
+    /// [`Bus::read_phased`], tagged with the [`CyclePhase`] of the cycle [`cycle`](Self::cycle)
+    /// would place them on; every other access uses plain [`Bus::read`] and [`Bus::write`].
+    ///
     /// # Examples
     ///
     /// ```
@@ -253,55 +735,368 @@ impl<B: Mos6502CompatibleBus + 'static> Cpu<B> {
     /// assert!(cycles > 0);
     /// ```
     pub fn step(&mut self, bus: &mut B) -> u8 {
-        if self.run_state == RunState::Halted {
-            self.last_step_cycles = 0;
-            return 0;
+        let accesses_before = bus.access_count();
+        self.sync_lifetime_cycles();
+        let cycles = self.step_inner(bus);
+        self.sync_lifetime_cycles();
+        self.last_step_bus_cycles = accesses_before
+            .zip(bus.access_count())
+            .map(|(before, after)| (after - before) as u8);
+        cycles
+    }
+
+    fn step_inner(&mut self, bus: &mut B) -> u8 {
+        match self.begin_step(bus) {
+            StepStart::Idle => 0,
+            StepStart::Serviced(cycles) => cycles,
+            StepStart::Fetched(opcode) => self.dispatch(bus, opcode),
         }
+    }
 
+    /// Service a pending reset or interrupt, or fetch the next opcode.
+    fn begin_step(&mut self, bus: &mut B) -> StepStart {
+        // RESET is the only way out of STP/JAM, so it's honored even while halted.
         if self.reset_pending {
-            self.reset(bus);
-            self.reset_pending = false;
-            return 0;
+            self.reset_warm(bus);
+            return StepStart::Serviced(INTERRUPT_CYCLES);
+        }
+
+        if self.run_state == RunState::Halted {
+            self.last_step_cycles = 0;
+            return StepStart::Idle;
         }
 
         if self.nmi_pending {
             self.enter_interrupt(bus, Interrupt::Nmi);
             self.nmi_pending = false;
-            self.last_step_cycles = 0;
-            return 0;
+            self.last_step_cycles = INTERRUPT_CYCLES;
+            return StepStart::Serviced(INTERRUPT_CYCLES);
         }
 
-        if self.irq_pending && !self.p.contains(Flags::InterruptDisabled) {
+        // WAI wakes on IRQ even when I is set; it just resumes after the WAI instead of
+        // vectoring, and the IRQ stays asserted until the device releases it.
+        let irq_asserted = self.irq_pending || self.irq_line;
+        if self.run_state == RunState::Waiting
+            && irq_asserted
+            && self.p.contains(Flags::InterruptDisabled)
+        {
+            self.run_state = RunState::Running;
+        }
+
+        if irq_asserted && !self.p.contains(Flags::InterruptDisabled) {
             self.enter_interrupt(bus, Interrupt::Irq);
+            // A one-shot request is consumed; a held line stays up until the device releases it.
             self.irq_pending = false;
-            self.last_step_cycles = 0;
-            return 0;
+            self.last_step_cycles = INTERRUPT_CYCLES;
+            return StepStart::Serviced(INTERRUPT_CYCLES);
         }
 
         if self.run_state == RunState::Waiting {
             self.last_step_cycles = 0;
-            return 0;
+            return StepStart::Idle;
         }
 
-        let next_opcode = bus.read(self.pc, AccessType::OpcodeFetch);
+        self.fetch_cycle = self.total_cycles();
+        let next_opcode = bus.cpu_read_phased(self.pc, AccessType::OpcodeFetch, self.cycle_phase(1));
         self.last_opcode = next_opcode;
-        let instruction = &self.table[next_opcode.as_usize()];
-        let execute = instruction.execute;
-        let cycles = instruction.cycles;
+        #[cfg(feature = "coverage")]
+        {
+            self.coverage[next_opcode.as_usize()] += 1;
+        }
+        #[cfg(feature = "history")]
+        self.history.record(HistoryEntry {
+            pc: self.pc,
+            opcode: next_opcode,
+            a: self.a,
+            x: self.x,
+            y: self.y,
+            p: self.p,
+            sp: self.sp,
+        });
+        StepStart::Fetched(next_opcode)
+    }
+
+    /// Whether cycle `n` (1 for the opcode fetch) of the current instruction is a GET (even
+    /// [`total_cycles`](Self::total_cycles)) or a PUT (odd) cycle.
+    fn cycle_phase(&self, n: u8) -> CyclePhase {
+        if (self.fetch_cycle + u64::from(n) - 1).is_multiple_of(2) {
+            CyclePhase::Get
+        } else {
+            CyclePhase::Put
+        }
+    }
+
+    /// Read the effective address of the current instruction, on the cycle its addressing mode
+    /// places that access (see [`cycle`](Self::cycle)).
+    pub(crate) fn read_data(&self, bus: &mut B, addr: Word) -> Byte {
+        let phase = self.cycle_phase(self.execute_cycle(self.last_opcode));
+        bus.cpu_read_phased(addr, AccessType::DataRead, phase)
+    }
+
+    /// Detach the closure installed for `opcode` so it can run with `&mut self`.
+    ///
+    /// Most CPUs never install one, so the map lookup is skipped while it's empty.
+    fn take_opcode_handler(&mut self, opcode: Byte) -> Option<OpcodeOverride<B>> {
+        if self.opcode_handlers.is_empty() {
+            None
+        } else {
+            self.opcode_handlers.remove(&opcode.0)
+        }
+    }
 
+    /// Run the handler for an already fetched opcode and charge its cycles.
+    fn dispatch(&mut self, bus: &mut B, opcode: Byte) -> u8 {
         let before = self.cycles;
-        execute(self, bus);
-        self.cycles += u64::from(cycles);
+        let pc = self.pc;
+
+        if let Some(mut hook) = self.take_opcode_handler(opcode) {
+            let outer = self.running_handler.replace(opcode.0);
+            (hook.handler)(self, bus);
+            self.cycles += u64::from(hook.cycles);
+            // The handler may have removed itself, or installed a replacement; keep that one.
+            if self.running_handler == Some(opcode.0) {
+                self.opcode_handlers.entry(opcode.0).or_insert(hook);
+            }
+            self.running_handler = outer;
+        } else {
+            let instruction = &self.table[opcode.as_usize()];
+            let execute = instruction.execute;
+            let cycles = instruction.cycles;
+            execute(self, bus);
+            self.cycles += u64::from(cycles);
+        }
+
         let consumed = (self.cycles - before) as u8;
         self.last_step_cycles = consumed;
+        let total_cycles = self.total_cycles();
+        if let Some(hook) = self.on_instruction.as_mut() {
+            hook(&InstructionContext {
+                pc,
+                opcode,
+                cycles: consumed,
+                total_cycles,
+            });
+        }
         consumed
     }
 
+    /// Advance the CPU by a single clock cycle and report whether an instruction boundary was
+    /// reached.
+    ///
+    /// This is the entry point for emulators that interleave the CPU with other chips (a PPU or
+    /// APU) cycle by cycle. Each call advances [`cycles`](Self::cycles) by one and calls
+    /// [`Bus::on_tick`] with 1; pending DMA is drained at instruction boundaries, like
+    /// [`tick`](Self::tick) does.
+    ///
+    /// Instructions still execute as a whole, so the bus traffic is placed on cycles as follows:
+    /// - cycle 1 fetches the opcode (or runs an interrupt/reset sequence);
+    /// - for the zero-page, absolute and indirect modes, cycle 2 reads the first operand byte
+    ///   and, for the absolute ones, cycle 3 reads the second;
+    /// - the instruction's handler, and with it every remaining access, runs on the cycle where
+    ///   its addressing mode performs the effective-address access: 2 for implied, immediate and
+    ///   relative, 3 for `zp`, 4 for `zp,X`/`zp,Y`/`abs`/`abs,X`/`abs,Y`, 5 for `(zp)`/`(zp),Y`
+    ///   and 6 for `(zp,X)`; indirect jumps run on their last cycle;
+    /// - the rest of the instruction's cycles, including page-crossing and branch penalties,
+    ///   pass without bus activity.
+    ///
+    /// That puts operand fetches, loads and stores on the same cycle as hardware, which is what
+    /// register polling (`BIT $2002`) and mid-scanline writes depend on. Pointer reads, the
+    /// extra cycles of read-modify-write instructions and stack traffic are not separated out;
+    /// they share a cycle with the handler.
+    ///
+    /// The opcode fetch, the operand bytes and the effective-address read go through
+    /// [`Bus::read_phased`] with [`CyclePhase::Get`] when [`total_cycles`](Self::total_cycles)
+    /// is even at the start of their cycle and [`CyclePhase::Put`] when it's odd, so the phase
+    /// follows the cycle count across resets. Pointer, stack and vector reads use plain
+    /// [`Bus::read`].
+    ///
+    /// Cycles spent halted or waiting still call [`Bus::on_tick`], so devices keep running and
+    /// can raise the interrupt that ends a `WAI`, but they aren't added to
+    /// [`cycles`](Self::cycles), matching [`step`](Self::step) which reports 0 for them.
+    ///
+    /// Finish the current instruction before switching back to [`step`](Self::step) or
+    /// [`tick`](Self::tick); see [`at_instruction_boundary`](Self::at_instruction_boundary).
+    ///
+    /// While [RDY](Self::set_ready) is low, fetch and operand cycles stall: the bus still gets
+    /// its tick but the instruction doesn't advance.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ull::Word;
+    /// use ull65::instruction::mos6502::Mos6502;
+    /// use ull65::{Cpu, SimpleBus};
+    ///
+    /// let mut bus = SimpleBus::default();
+    /// // LDA $1234 (4 cycles)
+    /// let mut cpu: Cpu<SimpleBus> =
+    ///     Cpu::with_program::<Mos6502>(&mut bus, Word(0x8000), &[0xAD, 0x34, 0x12], Word(0x8000));
+    ///
+    /// let mut cycles = 1;
+    /// while !cpu.cycle(&mut bus) {
+    ///     cycles += 1;
+    /// }
+    /// assert_eq!(cycles, 4);
+    /// assert_eq!(cpu.pc, Word(0x8003));
+    /// ```
+    pub fn cycle(&mut self, bus: &mut B) -> bool {
+        self.sync_lifetime_cycles();
+        // RDY only stops read cycles. The cycles left once the handler has run are where stores
+        // and read-modify-write results land, so those carry on.
+        if !self.ready && !matches!(self.micro, MicroStep::Finish { .. }) {
+            bus.on_tick(1);
+            return self.micro == MicroStep::Boundary;
+        }
+
+        let boundary = match self.micro {
+            MicroStep::Boundary => {
+                let before = self.cycles;
+                match self.begin_step(bus) {
+                    StepStart::Idle => {
+                        bus.on_tick(1);
+                        return true;
+                    }
+                    StepStart::Serviced(cycles) => {
+                        self.cycles = before + 1;
+                        self.finish_cycles(cycles.saturating_sub(1))
+                    }
+                    StepStart::Fetched(opcode) => {
+                        self.cycles = before + 1;
+                        self.decode_cycle(bus, opcode, 1)
+                    }
+                }
+            }
+            MicroStep::Decode {
+                opcode, elapsed, ..
+            } => {
+                self.cycles += 1;
+                self.decode_cycle(bus, opcode, elapsed + 1)
+            }
+            MicroStep::Finish { remaining } => {
+                self.cycles += 1;
+                self.finish_cycles(remaining - 1)
+            }
+        };
+
+        self.sync_lifetime_cycles();
+        bus.on_tick(1);
+        if boundary {
+            while let Some(dma_cycles) = bus.poll_dma_cycle() {
+                bus.on_tick(dma_cycles);
+            }
+        }
+
+        boundary
+    }
+
+    /// `true` when no instruction is partway through [`cycle`](Self::cycle), so
+    /// [`step`](Self::step) and [`tick`](Self::tick) can safely take over.
+    #[must_use]
+    pub fn at_instruction_boundary(&self) -> bool {
+        self.micro == MicroStep::Boundary
+    }
+
+    /// Enter cycle `elapsed` of a fetched instruction, running its handler once it's due.
+    ///
+    /// Until then, cycles 2 and 3 read the operand bytes the addressing mode needs.
+    fn decode_cycle(&mut self, bus: &mut B, opcode: Byte, elapsed: u8) -> bool {
+        let (execute_on, mut operands) = match self.micro {
+            MicroStep::Decode {
+                execute_on,
+                operands,
+                ..
+            } => (execute_on, operands),
+            _ => (self.execute_cycle(opcode), [None; 2]),
+        };
+
+        if elapsed < execute_on {
+            let index = elapsed - 1;
+            if (1..=2).contains(&index) && index <= latched_operands(self.decoded(opcode).0) {
+                let phase = self.cycle_phase(elapsed);
+                let byte = bus.cpu_read_phased(self.pc + u16::from(index), AccessType::DataRead, phase);
+                operands[usize::from(index) - 1] = Some(byte);
+            }
+            self.micro = MicroStep::Decode {
+                opcode,
+                elapsed,
+                execute_on,
+                operands,
+            };
+            return false;
+        }
+
+        // Charge the handler from the start of the instruction, as `step` would, then put the
+        // counter back to the cycles that have actually elapsed.
+        let start = self.cycles - u64::from(elapsed);
+        self.cycles = start;
+        let consumed = self.dispatch(bus, opcode);
+        self.cycles = start + u64::from(elapsed);
+        self.finish_cycles(consumed.saturating_sub(elapsed))
+    }
+
+    fn finish_cycles(&mut self, remaining: u8) -> bool {
+        if remaining == 0 {
+            self.micro = MicroStep::Boundary;
+            true
+        } else {
+            self.micro = MicroStep::Finish { remaining };
+            false
+        }
+    }
+
+    /// Addressing mode and base cycle count `opcode` runs with; handlers count as implied.
+    fn decoded(&self, opcode: Byte) -> (AddressingModeKind, u8) {
+        let hook = if self.opcode_handlers.is_empty() {
+            None
+        } else {
+            self.opcode_handlers.get(&opcode.0)
+        };
+        match hook {
+            Some(hook) => (AddressingModeKind::Implied, hook.cycles),
+            None => {
+                let instruction = &self.table[opcode.as_usize()];
+                (instruction.mode, instruction.cycles)
+            }
+        }
+    }
+
+    /// Cycle on which [`cycle`](Self::cycle) runs the handler for `opcode`.
+    fn execute_cycle(&self, opcode: Byte) -> u8 {
+        let (mode, cycles) = self.decoded(opcode);
+        data_cycle(mode).min(cycles).max(1)
+    }
+
+    /// Operand byte `n` (1 or 2) of the instruction at PC.
+    ///
+    /// [`cycle`](Self::cycle) reads operand bytes on their own cycles, before the handler runs;
+    /// this hands those back rather than reading them twice, and reads the bus otherwise.
+    /// Addressing modes fetch their operands through it.
+    pub fn operand(&self, bus: &mut B, n: u16) -> Byte {
+        let latched = match self.micro {
+            MicroStep::Decode { operands, .. } => {
+                operands.get(usize::from(n).wrapping_sub(1)).copied().flatten()
+            }
+            _ => None,
+        };
+        latched.unwrap_or_else(|| {
+            let phase = self.cycle_phase(n as u8 + 1);
+            bus.cpu_read_phased(self.pc + n, AccessType::DataRead, phase)
+        })
+    }
+
     /// Execute one instruction and synchronize the bus.
     ///
     /// This method wraps [`step`](Self::step) and automatically advances the attached bus via
     /// [`Bus::on_tick`] while also draining any pending DMA work reported by [`Bus::poll_dma_cycle`].
+    ///
+    /// While [RDY](Self::set_ready) is low no instruction starts: each call ticks the bus for a
+    /// single stalled cycle and returns 1.
     pub fn tick(&mut self, bus: &mut B) -> u8 {
+        if !self.ready {
+            bus.on_tick(1);
+            return 1;
+        }
+
         let cycles = self.step(bus);
         if cycles > 0 {
             bus.on_tick(cycles);
@@ -315,14 +1110,26 @@ impl<B: Mos6502CompatibleBus + 'static> Cpu<B> {
     }
 
     /// Drive the CPU until a configured stop condition occurs and return a summary.
+    ///
+    /// Even with no explicit stop condition the run is bounded: see
+    /// [`RunConfig::step_guard`] and [`RunConfig::stop_on_self_loop`]. While
+    /// [RDY](Self::set_ready) is low nothing can release it mid-run, so the run stops with
+    /// [`RunOutcome::NotReady`] before the next instruction instead of counting stalled cycles.
     pub fn run_until(&mut self, bus: &mut B, config: RunConfig<'_, B>) -> RunSummary {
         let RunConfig {
             instruction_limit,
+            cycle_limit,
             stop_on_brk,
             mut predicate,
+            breakpoints,
+            added_breakpoints,
+            step_guard,
+            stop_on_self_loop,
         } = config;
 
         let mut summary = RunSummary::default();
+        // Writes made before the run (loading a program, poking memory) shouldn't stop it.
+        let _ = bus.take_watch_hit();
 
         loop {
             if let Some(limit) = instruction_limit
@@ -332,15 +1139,60 @@ impl<B: Mos6502CompatibleBus + 'static> Cpu<B> {
                 break;
             }
 
+            if let Some(limit) = cycle_limit
+                && summary.cycles >= limit
+            {
+                summary.mark(RunOutcome::HitCycleLimit);
+                break;
+            }
+
+            if summary.instructions_executed > 0
+                && (breakpoints.contains(&self.pc) || added_breakpoints.contains(self.pc))
+            {
+                summary.mark(RunOutcome::HitBreakpoint(self.pc));
+                break;
+            }
+
+            if summary.instructions_executed >= step_guard {
+                summary.mark(RunOutcome::Stalled);
+                break;
+            }
+
+            if !self.ready {
+                summary.mark(RunOutcome::NotReady);
+                break;
+            }
+
+            let pc_before = self.pc;
             let cycles = self.tick(bus);
             if cycles == 0 {
-                summary.mark(RunOutcome::Stalled);
+                summary.mark(match self.run_state {
+                    RunState::Halted => RunOutcome::Halted,
+                    RunState::Waiting => RunOutcome::Waiting,
+                    RunState::Running => RunOutcome::Stalled,
+                });
                 break;
             }
 
             summary.instructions_executed += 1;
             summary.cycles += u64::from(cycles);
 
+            if let Some(addr) = bus.take_watch_hit() {
+                summary.mark(RunOutcome::HitWatchpoint(addr));
+                break;
+            }
+
+            // STP leaves PC on itself, which would otherwise look like a jump-to-self.
+            if self.run_state == RunState::Halted {
+                summary.mark(RunOutcome::Halted);
+                break;
+            }
+
+            if stop_on_self_loop && self.pc == pc_before {
+                summary.mark(RunOutcome::Stalled);
+                break;
+            }
+
             if stop_on_brk && self.last_opcode == byte!(0x00) {
                 summary.mark(RunOutcome::HitBrk);
                 break;
@@ -354,30 +1206,388 @@ impl<B: Mos6502CompatibleBus + 'static> Cpu<B> {
             }
         }
 
+        summary.last_pc = self.pc;
+        summary.last_opcode = self.last_opcode;
         summary
     }
 
-    /// Push a byte onto the stack.
+    /// Debugger "step over": run a subroutine call and everything it calls, or
+    /// [`step`](Self::step) once for any other instruction.
     ///
-    /// The stack grows downward from 0x01FF. Stack pointer is decremented after the write.
-    pub fn push(&mut self, bus: &mut B, val: Byte) {
-        let addr = STACK_SPACE_START + self.sp;
-        bus.write(addr, val, AccessType::StackWrite);
-        self.sp -= 1;
-    }
-
-    /// Pop a byte from the stack.
+    /// Calls are the instructions whose [`flow`](crate::Instruction::flow) is
+    /// [`FlowKind::Call`]: every `JSR` form and the 65CE02's `BSR`. A call runs until PC is back
+    /// at the instruction after it *and* SP is back where it was, so a recursive routine that
+    /// passes through the return address at a deeper stack level doesn't stop the run early. The run keeps the default safety limits
+    /// ([`RunConfig::step_guard`], jump-to-self detection), and a return is reported as
+    /// [`RunOutcome::HitPredicate`]; a single step reports [`RunOutcome::HitInstructionLimit`].
     ///
-    /// Stack pointer is incremented before the read.
-    pub fn pop(&mut self, bus: &mut B) -> Byte {
-        self.sp += 1;
-        let addr = STACK_SPACE_START + self.sp;
-        bus.read(addr, AccessType::StackRead)
-    }
-
-    /// Check if two addresses are on different pages.
+    /// # Examples
     ///
-    /// Some instruction take an extra cycle when crossing page boundaries (when the
+    /// ```
+    /// use ull::Word;
+    /// use ull65::instruction::mos6502::Mos6502;
+    /// use ull65::{Cpu, SimpleBus};
+    ///
+    /// // JSR $8004; BRK; sub: INX; INX; RTS
+    /// let mut bus = SimpleBus::default();
+    /// let program = [0x20, 0x04, 0x80, 0x00, 0xE8, 0xE8, 0x60];
+    /// let mut cpu: Cpu<SimpleBus> =
+    ///     Cpu::with_program::<Mos6502>(&mut bus, Word(0x8000), &program, Word(0x8000));
+    ///
+    /// let summary = cpu.step_over(&mut bus);
+    /// assert_eq!(summary.instructions_executed, 4);
+    /// assert_eq!((cpu.pc, cpu.x.0), (Word(0x8003), 2));
+    /// ```
+    pub fn step_over(&mut self, bus: &mut B) -> RunSummary {
+        let opcode = bus.cpu_read(self.pc, AccessType::Peek);
+        let instruction = &self.table[opcode.as_usize()];
+        if instruction.flow() != FlowKind::Call {
+            return self.run_until(bus, RunConfig::builder().instruction_limit(1));
+        }
+
+        let return_to = self.pc + (1 + u16::from(instruction.mode.operand_bytes()));
+        let sp = self.sp;
+        let mut returned = |cpu: &Cpu<B>| cpu.pc == return_to && cpu.sp == sp;
+        self.run_until(bus, RunConfig::builder().cpu_predicate(&mut returned))
+    }
+
+    /// Debugger "step out": run until the current subroutine or interrupt handler returns.
+    ///
+    /// The stack position is captured on entry and the run stops after the first return
+    /// ([`FlowKind::Return`]: `RTS`, `RTI` or the 65CE02's `RTN #`) that leaves it higher, so
+    /// returns from calls nested deeper inside the routine don't count. Like
+    /// [`step_over`](Self::step_over) the default safety limits apply and a return is reported as
+    /// [`RunOutcome::HitPredicate`]. Calling this from the top level runs until something pops
+    /// past the current stack frame, or a limit is hit.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ull::Word;
+    /// use ull65::instruction::mos6502::Mos6502;
+    /// use ull65::{Cpu, SimpleBus};
+    ///
+    /// // JSR $8004; BRK; sub: INX; INX; RTS
+    /// let mut bus = SimpleBus::default();
+    /// let program = [0x20, 0x04, 0x80, 0x00, 0xE8, 0xE8, 0x60];
+    /// let mut cpu: Cpu<SimpleBus> =
+    ///     Cpu::with_program::<Mos6502>(&mut bus, Word(0x8000), &program, Word(0x8000));
+    /// cpu.step(&mut bus); // into the subroutine
+    ///
+    /// cpu.step_out(&mut bus);
+    /// assert_eq!((cpu.pc, cpu.x.0), (Word(0x8003), 2));
+    /// ```
+    pub fn step_out(&mut self, bus: &mut B) -> RunSummary {
+        let entry = (self.stack_base, self.sp);
+        let mut returned = |cpu: &Cpu<B>| {
+            cpu.table[cpu.last_opcode.as_usize()].flow() == FlowKind::Return
+                && cpu.stack_rise(entry) > 0
+        };
+        self.run_until(bus, RunConfig::builder().cpu_predicate(&mut returned))
+    }
+
+    /// How many bytes the stack has been pulled by since it was at `base` + `sp`, negative once
+    /// it has grown instead.
+    ///
+    /// The distance wraps the way the stack does: within the page for the 8-bit stack, so a
+    /// return from a frame that straddles SP = $00 still counts as a pull, and across the whole
+    /// address space for the 65CE02's 16-bit stack.
+    fn stack_rise(&self, (base, sp): (Word, Byte)) -> i16 {
+        if self.stack_extended {
+            (self.stack_base + self.sp).0.wrapping_sub((base + sp).0) as i16
+        } else {
+            i16::from(self.sp.0.wrapping_sub(sp.0) as i8)
+        }
+    }
+
+    /// Resolve the memory address the instruction at PC will access, without executing it.
+    ///
+    /// Dispatches on the opcode's [`AddressingModeKind`] from the instruction table and follows
+    /// indexing and pointers the way the CPU would (zero-page wraparound, the NMOS `JMP ($xxFF)`
+    /// bug). For `JMP`/`JSR` that's the jump target and for `BBR`/`BBS` the tested zero-page
+    /// byte. Returns `None` for implied, accumulator, immediate and relative forms, and for
+    /// opcodes routed to a [`set_opcode_handler`](Self::set_opcode_handler) override. Every
+    /// access is an [`AccessType::Peek`], so watch-aware buses can ignore them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ull::{Byte, Word};
+    /// use ull65::instruction::mos6502::Mos6502;
+    /// use ull65::{Cpu, SimpleBus};
+    ///
+    /// // STA $12F0,X
+    /// let mut bus = SimpleBus::default();
+    /// let mut cpu: Cpu<SimpleBus> =
+    ///     Cpu::with_program::<Mos6502>(&mut bus, Word(0x8000), &[0x9D, 0xF0, 0x12], Word(0x8000));
+    /// cpu.x = Byte(0x20);
+    ///
+    /// assert_eq!(cpu.effective_address(&mut bus), Some(Word(0x1310)));
+    /// assert_eq!(cpu.pc, Word(0x8000));
+    /// ```
+    pub fn effective_address(&self, bus: &mut B) -> Option<Word> {
+        fn peek<B: Mos6502CompatibleBus>(bus: &mut B, addr: Word) -> Byte {
+            bus.cpu_read(addr, AccessType::Peek)
+        }
+        fn peek_word<B: Mos6502CompatibleBus>(bus: &mut B, lo: Word, hi: Word) -> Word {
+            Word::from((peek(bus, lo), peek(bus, hi)))
+        }
+        let zero_page_pointer = |bus: &mut B, ptr: Byte| {
+            peek_word(bus, self.zero_page(ptr), self.zero_page(ptr + 1u8))
+        };
+
+        let opcode = peek(bus, self.pc);
+        if !self.opcode_handlers.is_empty() && self.opcode_handlers.contains_key(&opcode.0) {
+            return None;
+        }
+        let instruction = &self.table[opcode.as_usize()];
+        let operand = self.pc + 1u16;
+
+        let address = match instruction.mode {
+            AddressingModeKind::Implied
+            | AddressingModeKind::Accumulator
+            | AddressingModeKind::Immediate
+            | AddressingModeKind::ImmediateWord
+            | AddressingModeKind::Relative
+            | AddressingModeKind::RelativeLong => return None,
+            AddressingModeKind::ZeroPage | AddressingModeKind::ZeroPageRelative => {
+                self.zero_page(peek(bus, operand))
+            }
+            AddressingModeKind::ZeroPageX => self.zero_page(peek(bus, operand) + self.x),
+            AddressingModeKind::ZeroPageY => self.zero_page(peek(bus, operand) + self.y),
+            AddressingModeKind::Absolute => peek_word(bus, operand, operand + 1u16),
+            AddressingModeKind::AbsoluteX => peek_word(bus, operand, operand + 1u16) + self.x,
+            AddressingModeKind::AbsoluteY => peek_word(bus, operand, operand + 1u16) + self.y,
+            AddressingModeKind::AbsoluteIndirect => {
+                let ptr = peek_word(bus, operand, operand + 1u16);
+                peek_word(bus, ptr, Word::from((ptr.lo() + 1u8, ptr.hi())))
+            }
+            AddressingModeKind::AbsoluteIndirectCorrect => {
+                let ptr = peek_word(bus, operand, operand + 1u16);
+                peek_word(bus, ptr, ptr + 1u16)
+            }
+            AddressingModeKind::AbsoluteIndirectX => {
+                let ptr = peek_word(bus, operand, operand + 1u16) + self.x;
+                peek_word(bus, ptr, ptr + 1u16)
+            }
+            AddressingModeKind::ZeroPageIndirect => {
+                let ptr = peek(bus, operand);
+                zero_page_pointer(bus, ptr)
+            }
+            AddressingModeKind::ZeroPageIndirectZ => {
+                let ptr = peek(bus, operand);
+                zero_page_pointer(bus, ptr) + self.z
+            }
+            AddressingModeKind::ZeroPageXIndirect => {
+                let ptr = peek(bus, operand) + self.x;
+                zero_page_pointer(bus, ptr)
+            }
+            AddressingModeKind::ZeroPageIndirectY => {
+                let ptr = peek(bus, operand);
+                zero_page_pointer(bus, ptr) + self.y
+            }
+            AddressingModeKind::StackIndirectY => {
+                let ptr = self.stack_base + self.sp + peek(bus, operand);
+                peek_word(bus, ptr, ptr + 1u16) + self.y
+            }
+        };
+
+        Some(address)
+    }
+
+    /// Length in bytes of the instruction at PC, from its table entry's addressing mode.
+    ///
+    /// Only the opcode is read (as an [`AccessType::Peek`]), so `pc + length` is where a
+    /// debugger puts a temporary breakpoint for "run to next instruction". Opcodes routed to a
+    /// [`set_opcode_handler`](Self::set_opcode_handler) override still report their table
+    /// entry's length, since the handler decides how far PC moves.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ull::Word;
+    /// use ull65::instruction::mos6502::Mos6502;
+    /// use ull65::{Cpu, SimpleBus};
+    ///
+    /// // LDA #$01; STA $0200
+    /// let mut bus = SimpleBus::default();
+    /// let program = [0xA9, 0x01, 0x8D, 0x00, 0x02];
+    /// let mut cpu: Cpu<SimpleBus> =
+    ///     Cpu::with_program::<Mos6502>(&mut bus, Word(0x8000), &program, Word(0x8000));
+    ///
+    /// assert_eq!(cpu.instruction_length(&mut bus), 2);
+    /// cpu.step(&mut bus);
+    /// assert_eq!(cpu.instruction_length(&mut bus), 3);
+    /// ```
+    #[must_use]
+    pub fn instruction_length(&self, bus: &mut B) -> u16 {
+        let opcode = bus.cpu_read(self.pc, AccessType::Peek);
+        u16::from(self.table[opcode.as_usize()].mode.operand_bytes()) + 1
+    }
+
+    /// [`InstructionSet::NAME`] of the set this CPU was built from, for log and trace headers.
+    ///
+    /// `None` for CPUs built with [`with_shared_table`](Self::with_shared_table), since a bare
+    /// table doesn't say which set produced it. [`Display`](fmt::Display) prefixes the registers
+    /// with the name when there is one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ull65::instruction::wdc65c02s::Wdc65c02s;
+    /// use ull65::{Cpu, SimpleBus};
+    ///
+    /// let cpu: Cpu<SimpleBus> = Cpu::with_instruction_set::<Wdc65c02s>();
+    /// assert_eq!(cpu.instruction_set_name(), Some("WDC 65C02S"));
+    /// assert!(cpu.to_string().starts_with("WDC 65C02S: A:00"));
+    /// ```
+    #[must_use]
+    pub fn instruction_set_name(&self) -> Option<&'static str> {
+        self.instruction_set_name
+    }
+
+    /// Address of the stack page; SP indexes into the 256 bytes starting here.
+    ///
+    /// Defaults to [`STACK_SPACE_START`] (page one), as on every stock 6502.
+    #[must_use]
+    pub fn stack_base(&self) -> Word {
+        self.stack_base
+    }
+
+    /// Relocate the stack for targets that don't keep it in page one.
+    ///
+    /// Every stack access, including interrupt entry, `JSR`/`RTS` and the debugger helpers, goes
+    /// through `base + SP` afterwards. Changing it with data on the stack strands that data.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ull::{Byte, Word};
+    /// use ull65::{AccessType, Cpu, SimpleBus};
+    /// use ull::Bus;
+    ///
+    /// let mut bus = SimpleBus::default();
+    /// let mut cpu: Cpu<SimpleBus> = Cpu::default();
+    /// cpu.set_stack_base(Word(0x0300));
+    /// cpu.sp = Byte(0xFF);
+    /// cpu.push(&mut bus, Byte(0x42));
+    ///
+    /// assert_eq!(bus.read(Word(0x03FF), AccessType::DataRead), Byte(0x42));
+    /// ```
+    pub fn set_stack_base(&mut self, base: Word) {
+        self.stack_base = base;
+    }
+
+    /// Whether the 65CE02's 16-bit stack mode is on.
+    #[must_use]
+    pub fn stack_extended(&self) -> bool {
+        self.stack_extended
+    }
+
+    /// Switch the 65CE02's 16-bit stack mode, which `CLE` turns on and `SEE` off.
+    ///
+    /// In 16-bit mode the high byte of [`stack_base`](Self::stack_base) acts as the stack
+    /// pointer's high byte (SPH): a push that takes SP below $00 moves the base down a page and a
+    /// pull past $FF moves it up, instead of wrapping within the page. The 65CE02 reports the
+    /// mode in P's bit 5 (`E`, set for the 8-bit stack), so that bit follows it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ull::{Byte, Word};
+    /// use ull65::{Cpu, SimpleBus};
+    ///
+    /// let mut bus = SimpleBus::default();
+    /// let mut cpu: Cpu<SimpleBus> = Cpu::default();
+    /// cpu.set_stack_extended(true);
+    /// cpu.sp = Byte(0x00);
+    /// cpu.push(&mut bus, Byte(0x42));
+    ///
+    /// assert_eq!((cpu.stack_base(), cpu.sp), (Word(0x0000), Byte(0xFF)));
+    /// assert_eq!(cpu.pop(&mut bus), Byte(0x42));
+    /// assert_eq!(cpu.stack_base(), Word(0x0100));
+    /// ```
+    pub fn set_stack_extended(&mut self, extended: bool) {
+        self.stack_extended = extended;
+        self.p.set(Flags::Expansion, !extended);
+    }
+
+    /// Push a byte onto the stack.
+    ///
+    /// The stack grows downward from [`stack_base`](Self::stack_base) + 0xFF. Stack pointer is
+    /// decremented after the write.
+    pub fn push(&mut self, bus: &mut B, val: Byte) {
+        let addr = self.stack_base + self.sp;
+        bus.cpu_write(addr, val, AccessType::StackWrite);
+        if self.stack_extended && self.sp == 0 {
+            self.stack_base = Word(self.stack_base.0.wrapping_sub(0x100));
+        }
+        self.sp -= 1;
+    }
+
+    /// Pop a byte from the stack.
+    ///
+    /// Stack pointer is incremented before the read.
+    pub fn pop(&mut self, bus: &mut B) -> Byte {
+        self.sp += 1;
+        if self.stack_extended && self.sp == 0 {
+            self.stack_base = Word(self.stack_base.0.wrapping_add(0x100));
+        }
+        let addr = self.stack_base + self.sp;
+        bus.cpu_read(addr, AccessType::StackRead)
+    }
+
+    /// Read a stack byte without popping it.
+    ///
+    /// `depth` 0 is the most recently pushed byte (at `$0100 + SP + 1`), 1 the one below it,
+    /// and so on; the offset wraps within the stack page like the hardware stack does. SP is left
+    /// untouched, which makes this suitable for debugger stack views.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ull::Byte;
+    /// use ull65::{Cpu, SimpleBus};
+    ///
+    /// let mut bus = SimpleBus::default();
+    /// let mut cpu: Cpu<SimpleBus> = Cpu::default();
+    /// cpu.sp = Byte(0xFF);
+    /// cpu.push(&mut bus, Byte(0x12));
+    /// cpu.push(&mut bus, Byte(0x34));
+    ///
+    /// assert_eq!(cpu.peek_stack(&mut bus, 0), Byte(0x34));
+    /// assert_eq!(cpu.peek_stack(&mut bus, 1), Byte(0x12));
+    /// assert_eq!(cpu.stack_depth(), 2);
+    /// ```
+    pub fn peek_stack(&self, bus: &mut B, depth: u8) -> Byte {
+        if self.stack_extended {
+            let top = self.stack_base + self.sp;
+            return bus.cpu_read(top + (1 + u16::from(depth)), AccessType::StackRead);
+        }
+        let offset = self.sp + 1 + usize::from(depth);
+        bus.cpu_read(self.stack_base + offset, AccessType::StackRead)
+    }
+
+    /// Number of bytes currently on the stack, assuming it started empty at SP = $FF.
+    ///
+    /// Programs conventionally initialize SP with `LDX #$FF; TXS`. Note that
+    /// [`power_on_reset`](Self::power_on_reset) leaves SP at $FD, which reads as a depth of 2.
+    #[must_use]
+    pub fn stack_depth(&self) -> u8 {
+        0xFF - self.sp.0
+    }
+
+    /// Address of byte `offset` of the zero page, which the 65CE02 moves with
+    /// [`b`](Self::b).
+    #[inline]
+    #[must_use]
+    pub fn zero_page(&self, offset: Byte) -> Word {
+        Word::from((offset, self.b))
+    }
+
+    /// Check if two addresses are on different pages.
+    ///
+    /// Some instruction take an extra cycle when crossing page boundaries (when the
     /// high byte changes). This is used internally for cycle-accurate timing.
     #[inline]
     #[must_use]
@@ -385,6 +1595,10 @@ impl<B: Mos6502CompatibleBus + 'static> Cpu<B> {
         from.hi() != to.hi()
     }
 
+    /// Latch an interrupt to be serviced at the next instruction boundary.
+    ///
+    /// For NMI this acts as a single falling edge regardless of the level driven through
+    /// [`set_nmi_line`](Self::set_nmi_line).
     pub fn request_interrupt(&mut self, interrupt: Interrupt) {
         match interrupt {
             Interrupt::Reset => self.reset_pending = true,
@@ -393,6 +1607,113 @@ impl<B: Mos6502CompatibleBus + 'static> Cpu<B> {
         }
     }
 
+    /// Drive the level-sensitive IRQ input; `true` means asserted (the pin pulled low).
+    ///
+    /// While the line is held, every instruction boundary with I clear services an IRQ, so a
+    /// handler that re-enables interrupts without acknowledging its device is interrupted
+    /// again. Servicing never lowers the line; the device does that by calling this with
+    /// `false`.
+    pub fn set_irq_line(&mut self, asserted: bool) {
+        self.irq_line = asserted;
+    }
+
+    /// Whether an IRQ is being asserted, through [`set_irq_line`](Self::set_irq_line) or a
+    /// not-yet-serviced [`request_interrupt`](Self::request_interrupt), and will be serviced
+    /// once the I flag allows it.
+    #[must_use]
+    pub fn irq_line(&self) -> bool {
+        self.irq_pending || self.irq_line
+    }
+
+    /// Drive the NMI input; `true` means asserted (the pin pulled low).
+    ///
+    /// NMI is edge-triggered: only the transition from released to asserted latches an NMI,
+    /// so holding the line asserted fires once, and the device has to release and re-assert it
+    /// to fire again. The edge is latched immediately, so one that arrives mid-instruction is
+    /// serviced at the next boundary.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ull65::instruction::mos6502::Mos6502;
+    /// use ull65::{Cpu, SimpleBus};
+    ///
+    /// let mut cpu: Cpu<SimpleBus> = Cpu::with_instruction_set::<Mos6502>();
+    /// cpu.set_nmi_line(true);
+    /// assert!(cpu.nmi_pending());
+    ///
+    /// cpu.clear_pending_interrupts();
+    /// cpu.set_nmi_line(true); // still held: no new edge
+    /// assert!(!cpu.nmi_pending());
+    /// ```
+    pub fn set_nmi_line(&mut self, asserted: bool) {
+        if asserted && !self.nmi_line {
+            self.nmi_pending = true;
+        }
+        self.nmi_line = asserted;
+    }
+
+    /// Drive the RDY input; `false` pulls it low and stalls the CPU.
+    ///
+    /// Devices use RDY for DMA and wait states. Only [`cycle`](Self::cycle) and
+    /// [`tick`](Self::tick) honour it; both keep ticking the bus, so the device that pulled RDY
+    /// low keeps running and can release it. As on the real chip, write cycles aren't held up:
+    /// an instruction whose handler has already run finishes its remaining cycles. Stalled cycles
+    /// aren't added to [`cycles`](Self::cycles). [`step`](Self::step) ignores the line, and
+    /// [`run_until`](Self::run_until) stops with [`RunOutcome::NotReady`] while it's low.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ull::Word;
+    /// use ull65::instruction::mos6502::Mos6502;
+    /// use ull65::{Cpu, SimpleBus};
+    ///
+    /// let mut bus = SimpleBus::default();
+    /// let mut cpu: Cpu<SimpleBus> =
+    ///     Cpu::with_program::<Mos6502>(&mut bus, Word(0x8000), &[0xEA], Word(0x8000));
+    ///
+    /// cpu.set_ready(false);
+    /// assert_eq!(cpu.tick(&mut bus), 1);
+    /// assert_eq!(cpu.pc, Word(0x8000));
+    ///
+    /// cpu.set_ready(true);
+    /// assert_eq!(cpu.tick(&mut bus), 2);
+    /// assert_eq!(cpu.pc, Word(0x8001));
+    /// ```
+    pub fn set_ready(&mut self, ready: bool) {
+        self.ready = ready;
+    }
+
+    /// Level last driven by [`set_ready`](Self::set_ready); `true` unless RDY is held low.
+    #[must_use]
+    pub fn ready(&self) -> bool {
+        self.ready
+    }
+
+    /// Whether an NMI has been requested but not yet serviced.
+    #[must_use]
+    pub fn nmi_pending(&self) -> bool {
+        self.nmi_pending
+    }
+
+    /// Consume a pending NMI, reporting whether there was one. Used by the NMOS `BRK` handler,
+    /// which loses its vector to an NMI that arrives while the break sequence is under way.
+    pub(crate) fn take_pending_nmi(&mut self) -> bool {
+        core::mem::take(&mut self.nmi_pending)
+    }
+
+    /// Drop every outstanding [`request_interrupt`](Self::request_interrupt), including a
+    /// pending reset. Lines driven with [`set_irq_line`](Self::set_irq_line) stay as they are.
+    ///
+    /// Useful for reconciling CPU state with device state, e.g. after a peripheral acknowledges
+    /// its interrupt before the CPU got around to servicing it.
+    pub fn clear_pending_interrupts(&mut self) {
+        self.irq_pending = false;
+        self.nmi_pending = false;
+        self.reset_pending = false;
+    }
+
     fn enter_interrupt(&mut self, bus: &mut B, interrupt: Interrupt) {
         self.run_state = RunState::Running;
 
@@ -404,101 +1725,1749 @@ impl<B: Mos6502CompatibleBus + 'static> Cpu<B> {
 
         self.push(bus, self.pc.hi());
         self.push(bus, self.pc.lo());
-        let mut flags = self.p;
+        // Hardware interrupts push B clear (that's how handlers tell them apart from BRK) and the
+        // unused bit set. D is pushed as-is so RTI restores the interrupted code's mode.
+        let mut flags = self.p | Flags::Expansion;
         flags.remove(Flags::Break);
-        flags.remove(Flags::DecimalMode);
         self.push(bus, flags.into());
 
         self.p.set_decimal_mode(false);
         self.p.set_interrupt_disabled(true);
 
-        let lo = bus.read(vector_lo, AccessType::InterruptVectorRead);
-        let hi = bus.read(vector_hi, AccessType::InterruptVectorRead);
-        self.pc = word!((lo, hi));
+        let lo = bus.cpu_read(vector_lo, AccessType::InterruptVectorRead);
+        let hi = bus.cpu_read(vector_hi, AccessType::InterruptVectorRead);
+        self.pc = word!((lo, hi));
+        self.cycles += u64::from(INTERRUPT_CYCLES);
+    }
+
+    /// Cycles executed since the CPU was created, including those before any resets.
+    ///
+    /// [`cycles`](Self::cycles) is the per-session counter that a cold reset zeroes; this one
+    /// only moves forward, which suits long-running emulation that resets the guest
+    /// periodically. Lowering `cycles`, whether directly or by restoring an older snapshot with
+    /// [`load_state`](Self::load_state), doesn't take anything off it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ull::Word;
+    /// use ull65::instruction::mos6502::Mos6502;
+    /// use ull65::{Cpu, SimpleBus};
+    ///
+    /// let mut bus = SimpleBus::default();
+    /// let mut cpu: Cpu<SimpleBus> =
+    ///     Cpu::with_program::<Mos6502>(&mut bus, Word(0x8000), &[0xEA], Word(0x8000));
+    /// cpu.step(&mut bus);
+    /// cpu.reset(&mut bus);
+    ///
+    /// assert_eq!(cpu.cycles, 0);
+    /// assert_eq!(cpu.total_cycles(), 2);
+    /// ```
+    #[must_use]
+    pub fn total_cycles(&self) -> u64 {
+        self.lifetime_cycles + self.cycles.saturating_sub(self.synced_cycles)
+    }
+
+    /// Fold the cycles charged since the last call into the lifetime count. If `cycles` was
+    /// lowered in between, counting simply resumes from its new value.
+    fn sync_lifetime_cycles(&mut self) {
+        self.lifetime_cycles = self.total_cycles();
+        self.synced_cycles = self.cycles;
+    }
+
+    /// Cycles consumed by the most recent [`step`](Self::step) call.
+    #[must_use]
+    pub fn last_step_cycles(&self) -> u8 {
+        self.last_step_cycles
+    }
+
+    /// How many times each opcode has been fetched and executed, indexed by opcode.
+    ///
+    /// Interrupt entries and idle (`WAI`/halted) steps aren't counted. Slots that stay zero
+    /// after running a ROM are opcodes it never exercised. The counts survive resets; clear
+    /// them with [`reset_coverage`](Self::reset_coverage).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ull::Word;
+    /// use ull65::instruction::mos6502::Mos6502;
+    /// use ull65::{Cpu, SimpleBus};
+    ///
+    /// let mut bus = SimpleBus::default();
+    /// let mut cpu: Cpu<SimpleBus> =
+    ///     Cpu::with_program::<Mos6502>(&mut bus, Word(0x8000), &[0xEA, 0xEA, 0xE8], Word(0x8000));
+    /// cpu.step(&mut bus);
+    /// cpu.step(&mut bus);
+    /// cpu.step(&mut bus);
+    ///
+    /// assert_eq!(cpu.coverage()[0xEA], 2);
+    /// assert_eq!(cpu.coverage()[0xE8], 1);
+    /// ```
+    #[cfg(feature = "coverage")]
+    #[must_use]
+    pub fn coverage(&self) -> &[u32; 256] {
+        &self.coverage
+    }
+
+    /// Zero every [`coverage`](Self::coverage) counter.
+    #[cfg(feature = "coverage")]
+    pub fn reset_coverage(&mut self) {
+        self.coverage.fill(0);
+    }
+
+    /// The last [`HISTORY_CAPACITY`](crate::processor::history::HISTORY_CAPACITY) instructions
+    /// fetched, oldest first.
+    ///
+    /// Kept in a fixed array inside the CPU, so it works without an allocator and costs nothing
+    /// until something goes wrong. Like [`coverage`](Self::coverage), interrupt entries and idle
+    /// steps aren't recorded and the history survives resets; drop it with
+    /// [`clear_history`](Self::clear_history).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ull::{Byte, Word};
+    /// use ull65::instruction::mos6502::Mos6502;
+    /// use ull65::{Cpu, SimpleBus};
+    ///
+    /// // LDA #$07; TAX
+    /// let mut bus = SimpleBus::default();
+    /// let mut cpu: Cpu<SimpleBus> =
+    ///     Cpu::with_program::<Mos6502>(&mut bus, Word(0x8000), &[0xA9, 0x07, 0xAA], Word(0x8000));
+    /// cpu.step(&mut bus);
+    /// cpu.step(&mut bus);
+    ///
+    /// let history = cpu.history();
+    /// assert_eq!(history.len(), 2);
+    /// assert_eq!(history[1].pc, Word(0x8002));
+    /// assert_eq!(history[1].opcode, Byte(0xAA));
+    /// assert_eq!(history[1].a, Byte(0x07));
+    /// ```
+    #[cfg(feature = "history")]
+    #[must_use]
+    pub fn history(&self) -> &[HistoryEntry] {
+        self.history.as_slice()
+    }
+
+    /// Forget every entry in [`history`](Self::history).
+    #[cfg(feature = "history")]
+    pub fn clear_history(&mut self) {
+        self.history.clear();
+    }
+
+    /// Bus accesses performed by the most recent [`step`](Self::step) call.
+    ///
+    /// Derived from [`Bus::access_count`], so this is `None` unless the bus keeps a count (as
+    /// [`TestingBus`](crate::TestingBus) does). For a correctly timed instruction it equals
+    /// [`last_step_cycles`](Self::last_step_cycles); instructions whose internal/dummy cycles
+    /// aren't modeled as bus accesses (e.g. implied-mode `NOP`, which charges 2 cycles but only
+    /// fetches its opcode) report fewer, which is exactly the mismatch this is meant to expose.
+    #[must_use]
+    pub fn last_step_bus_cycles(&self) -> Option<u8> {
+        self.last_step_bus_cycles
+    }
+
+    /// Copy out A, X, Y, P, SP and PC.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ull65::instruction::mos6502::Mos6502;
+    /// use ull65::{Cpu, Registers, SimpleBus};
+    ///
+    /// let mut cpu: Cpu<SimpleBus> = Cpu::with_instruction_set::<Mos6502>();
+    /// let before = cpu.registers();
+    /// cpu.set_registers(Registers { a: ull::Byte(0x42), ..before });
+    ///
+    /// assert_ne!(cpu.registers(), before);
+    /// assert_eq!(cpu.a.0, 0x42);
+    /// ```
+    #[must_use]
+    pub fn registers(&self) -> Registers {
+        Registers {
+            a: self.a,
+            x: self.x,
+            y: self.y,
+            p: self.p,
+            sp: self.sp,
+            pc: self.pc,
+        }
+    }
+
+    /// Overwrite A, X, Y, P, SP and PC; everything else (cycles, interrupts, run state) is
+    /// left alone.
+    pub fn set_registers(&mut self, regs: Registers) {
+        self.a = regs.a;
+        self.x = regs.x;
+        self.y = regs.y;
+        self.p = regs.p;
+        self.sp = regs.sp;
+        self.pc = regs.pc;
+    }
+
+    /// Capture the register file, cycle counter and pending interrupts as a [`CpuState`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ull::Word;
+    /// use ull65::instruction::mos6502::Mos6502;
+    /// use ull65::{Cpu, SimpleBus};
+    ///
+    /// let mut bus = SimpleBus::default();
+    /// let mut cpu: Cpu<SimpleBus> =
+    ///     Cpu::with_program::<Mos6502>(&mut bus, Word(0x8000), &[0xE8, 0xE8], Word(0x8000));
+    /// let saved = cpu.save_state();
+    ///
+    /// cpu.step(&mut bus);
+    /// cpu.load_state(saved);
+    /// assert_eq!(cpu.pc, Word(0x8000));
+    /// assert_eq!(cpu.x.0, 0);
+    /// ```
+    #[must_use]
+    pub fn save_state(&self) -> CpuState {
+        CpuState {
+            a: self.a,
+            x: self.x,
+            y: self.y,
+            p: self.p,
+            sp: self.sp,
+            pc: self.pc,
+            cycles: self.cycles,
+            run_state: self.run_state,
+            irq_pending: self.irq_pending,
+            nmi_pending: self.nmi_pending,
+            reset_pending: self.reset_pending,
+            last_opcode: self.last_opcode,
+            nmi_line: self.nmi_line,
+            irq_line: self.irq_line,
+            z: self.z,
+            b: self.b,
+            stack_extended: self.stack_extended,
+            stack_base: self.stack_base,
+        }
+    }
+
+    /// Restore a snapshot taken by [`save_state`](Self::save_state).
+    ///
+    /// The instruction table and any opcode handlers stay as they are, so restore into a CPU
+    /// built from the same [`InstructionSet`] the state was saved from.
+    pub fn load_state(&mut self, state: CpuState) {
+        self.a = state.a;
+        self.x = state.x;
+        self.y = state.y;
+        self.p = state.p;
+        self.sp = state.sp;
+        self.pc = state.pc;
+        self.sync_lifetime_cycles();
+        self.cycles = state.cycles;
+        self.synced_cycles = state.cycles;
+        self.run_state = state.run_state;
+        self.irq_pending = state.irq_pending;
+        self.nmi_pending = state.nmi_pending;
+        self.reset_pending = state.reset_pending;
+        self.last_opcode = state.last_opcode;
+        self.nmi_line = state.nmi_line;
+        self.irq_line = state.irq_line;
+        self.z = state.z;
+        self.b = state.b;
+        self.stack_extended = state.stack_extended;
+        self.stack_base = state.stack_base;
+        self.micro = MicroStep::Boundary;
+    }
+
+    /// Route `opcode` to a closure instead of its [`InstructionTable`] entry.
+    ///
+    /// Unlike [`InstructionTable::with`], the handler can capture state, which makes it suitable
+    /// for host "syscall" traps such as a putchar hook. Overrides are checked before the table on
+    /// every [`step`](Self::step). The handler is responsible for advancing PC, and `cycles` is
+    /// charged after it returns, just like a table entry. Returns any handler previously
+    /// installed for the opcode.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ull::{Bus, Word};
+    /// use ull65::{AccessType, Cpu, SimpleBus};
+    /// use ull65::instruction::mos6502::Mos6502;
+    ///
+    /// let mut bus = SimpleBus::default();
+    /// let mut cpu: Cpu<SimpleBus> =
+    ///     Cpu::with_program::<Mos6502>(&mut bus, Word(0x8000), &[0x02], Word(0x8000));
+    ///
+    /// // Treat the $02 JAM opcode as a 1-byte trap that loads A with a host value.
+    /// let mut calls = 0u8;
+    /// cpu.set_opcode_handler(0x02, 2, move |cpu, _bus| {
+    ///     calls += 1;
+    ///     cpu.a.0 = calls;
+    ///     cpu.pc += 1;
+    /// });
+    ///
+    /// assert_eq!(cpu.step(&mut bus), 2);
+    /// assert_eq!(cpu.a.0, 1);
+    /// ```
+    pub fn set_opcode_handler<F>(
+        &mut self,
+        opcode: u8,
+        cycles: u8,
+        handler: F,
+    ) -> Option<OpcodeHandler<B>>
+    where
+        F: FnMut(&mut Cpu<B>, &mut B) + 'static,
+    {
+        self.opcode_handlers
+            .insert(
+                opcode,
+                OpcodeOverride {
+                    cycles,
+                    handler: Box::new(handler),
+                },
+            )
+            .map(|previous| previous.handler)
+    }
+
+    /// Remove the closure installed for `opcode`, restoring dispatch through the table.
+    ///
+    /// A handler may remove itself; it's dropped once it returns, so this returns `None` then.
+    pub fn remove_opcode_handler(&mut self, opcode: u8) -> Option<OpcodeHandler<B>> {
+        if self.running_handler == Some(opcode) {
+            self.running_handler = None;
+        }
+        self.opcode_handlers
+            .remove(&opcode)
+            .map(|previous| previous.handler)
+    }
+
+    /// Install the handler run for undocumented opcodes when the instruction set's
+    /// [`ON_ILLEGAL`](crate::InstructionSet::ON_ILLEGAL) is [`IllegalPolicy::Trap`].
+    ///
+    /// The offending opcode is in [`last_opcode`](Self::last_opcode) and PC still points at it;
+    /// as with [`set_opcode_handler`](Self::set_opcode_handler), the handler is responsible for
+    /// moving PC on (or halting). The table entry's cycles are charged either way. Returns the
+    /// previously installed handler.
+    ///
+    /// [`IllegalPolicy::Trap`]: crate::IllegalPolicy::Trap
+    pub fn set_illegal_trap<F>(&mut self, handler: F) -> Option<OpcodeHandler<B>>
+    where
+        F: FnMut(&mut Cpu<B>, &mut B) + 'static,
+    {
+        self.illegal_trap.replace(Box::new(handler))
+    }
+
+    /// Remove the illegal-opcode trap, so trapping instruction sets halt instead.
+    pub fn remove_illegal_trap(&mut self) -> Option<OpcodeHandler<B>> {
+        self.illegal_trap.take()
+    }
+
+    /// Run the illegal-opcode trap, returning `false` if none is installed.
+    pub(crate) fn run_illegal_trap(&mut self, bus: &mut B) -> bool {
+        let Some(mut trap) = self.illegal_trap.take() else {
+            return false;
+        };
+        trap(self, bus);
+        // The trap may have installed a replacement for itself; keep that one.
+        self.illegal_trap.get_or_insert(trap);
+        true
+    }
+}
+
+impl<B: Mos6502CompatibleBus> Default for Cpu<B> {
+    fn default() -> Self {
+        Self::with_instruction_set::<Mos6502>()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate alloc;
+
+    use ull::Bus;
+    use super::*;
+    use crate::instruction::csg65ce02::Csg65ce02;
+    use crate::instruction::mos6502::Mos6502;
+    use crate::instruction::wdc65c02s::Wdc65c02s;
+    use crate::processor::asm::assemble;
+    use crate::processor::run::RunPredicate;
+    use crate::AccessType;
+    use crate::Phase;
+    use crate::TestingBus;  
+
+    type TestBus = TestingBus;
+
+    fn prepare_cpu(bus: &mut TestBus) -> Cpu<TestBus> {
+        bus.write(RESET_VECTOR_LO, byte!(0x00), AccessType::DataWrite);
+        bus.write(RESET_VECTOR_HI, byte!(0x80), AccessType::DataWrite);
+        let mut cpu: Cpu<TestBus> = Cpu::with_instruction_set::<Mos6502>();
+        cpu.reset(bus);
+        cpu
+    }
+
+    #[test]
+    fn with_reset_vector_sets_pc_and_vector_bytes() {
+        let mut bus = TestBus::default();
+        let reset = Word(0x9000);
+
+        let cpu: Cpu<TestBus> = Cpu::with_reset_vector::<Mos6502>(&mut bus, reset);
+
+        assert_eq!(cpu.pc, reset);
+        assert_eq!(bus.read(RESET_VECTOR_LO, AccessType::DataRead), reset.lo());
+        assert_eq!(bus.read(RESET_VECTOR_HI, AccessType::DataRead), reset.hi());
+    }
+
+    #[test]
+    fn with_shared_table_borrows_instead_of_copying() {
+        let table = Rc::new(Mos6502::base_table());
+
+        let mut bus = TestBus::default();
+        bus.write_block(Word(0x8000), &[0xE8], AccessType::DataWrite);
+        let mut first: Cpu<TestBus> = Cpu::with_shared_table(Rc::clone(&table));
+        let second: Cpu<TestBus> = Cpu::with_shared_table(table);
+
+        assert!(core::ptr::eq(&*first.table, &*second.table));
+        assert!(matches!(first.table, TableStorage::Shared(_)));
+
+        first.pc = Word(0x8000);
+        assert_eq!(first.step(&mut bus), 2);
+        assert_eq!(first.x, byte!(1));
+    }
+
+    #[test]
+    fn with_program_loads_bytes_and_sets_pc() {
+        let mut bus = TestBus::default();
+        let program = [0xEA, 0x00];
+        let start = Word(0x8000);
+
+        let cpu: Cpu<TestBus> = Cpu::with_program::<Mos6502>(&mut bus, start, &program, start);
+
+        assert_eq!(cpu.pc, start);
+        assert_eq!(bus.read(start, AccessType::OpcodeFetch), Byte(program[0]));
+        assert_eq!(
+            bus.read(start + 1, AccessType::OpcodeFetch),
+            Byte(program[1])
+        );
+    }
+
+    #[test]
+    fn power_on_reset_clears_registers_and_counters() {
+        let mut bus = TestBus::default();
+        let mut cpu = prepare_cpu(&mut bus);
+        cpu.a = byte!(0x11);
+        cpu.x = byte!(0x22);
+        cpu.y = byte!(0x33);
+        cpu.sp = byte!(0x80);
+        cpu.p = Flags::Carry | Flags::DecimalMode | Flags::Expansion;
+        cpu.cycles = 1234;
+        cpu.pc = Word(0x1234);
+        cpu.run_state = RunState::Halted;
+        cpu.request_interrupt(Interrupt::Nmi);
+
+        cpu.power_on_reset(&mut bus);
+
+        assert_eq!(cpu.a, byte!(0));
+        assert_eq!(cpu.x, byte!(0));
+        assert_eq!(cpu.y, byte!(0));
+        assert_eq!(cpu.sp, byte!(0xFD));
+        assert_eq!(cpu.p.bits(), (Flags::InterruptDisabled | Flags::Expansion).bits());
+        assert_eq!(cpu.cycles, 0);
+        assert_eq!(cpu.pc, Word(0x8000));
+        assert_eq!(cpu.run_state, RunState::Running);
+
+        // The dropped NMI must not fire on the next step.
+        bus.write(Word(0x8000), byte!(0xEA), AccessType::DataWrite);
+        assert_eq!(cpu.step(&mut bus), 2);
+        assert_eq!(cpu.pc, Word(0x8001));
+    }
+
+    #[test]
+    fn reset_line_preserves_registers_and_drops_sp_by_three() {
+        let mut bus = TestBus::default();
+        let mut cpu = prepare_cpu(&mut bus);
+        cpu.a = byte!(0x11);
+        cpu.x = byte!(0x22);
+        cpu.y = byte!(0x33);
+        cpu.sp = byte!(0x01);
+        cpu.p = Flags::Carry | Flags::DecimalMode | Flags::Expansion;
+        cpu.cycles = 1234;
+        cpu.pc = Word(0x1234);
+        cpu.run_state = RunState::Waiting;
+        let stack_before = bus.read(Word(0x01FF), AccessType::DataRead);
+
+        cpu.reset_line(&mut bus);
+
+        assert_eq!(cpu.a, byte!(0x11));
+        assert_eq!(cpu.x, byte!(0x22));
+        assert_eq!(cpu.y, byte!(0x33));
+        assert_eq!(cpu.sp, byte!(0xFE));
+        assert_eq!(
+            cpu.p.bits(),
+            (Flags::Carry | Flags::DecimalMode | Flags::InterruptDisabled | Flags::Expansion).bits()
+        );
+        assert_eq!(cpu.cycles, 1234);
+        assert_eq!(cpu.pc, Word(0x8000));
+        assert_eq!(cpu.run_state, RunState::Running);
+        assert_eq!(bus.read(Word(0x01FF), AccessType::DataRead), stack_before);
+    }
+
+    #[test]
+    fn reset_zeroes_cycles_but_keeps_total_cycles() {
+        let mut bus = TestBus::default();
+        bus.write_block(Word(0x8000), &[0xEA, 0xEA, 0xEA], AccessType::DataWrite);
+        let mut cpu = prepare_cpu(&mut bus);
+
+        cpu.step(&mut bus);
+        cpu.step(&mut bus);
+        cpu.reset(&mut bus);
+        assert_eq!(cpu.cycles, 0);
+        assert_eq!(cpu.total_cycles(), 4);
+
+        cpu.step(&mut bus);
+        cpu.reset_warm(&mut bus);
+        assert_eq!(cpu.cycles, 2 + u64::from(INTERRUPT_CYCLES));
+        assert_eq!(cpu.total_cycles(), 13);
+    }
+
+    #[test]
+    fn total_cycles_never_goes_back_when_cycles_is_lowered() {
+        let mut bus = TestBus::default();
+        bus.write_block(Word(0x8000), &[0xEA, 0xEA, 0xEA], AccessType::DataWrite);
+        let mut cpu = prepare_cpu(&mut bus);
+
+        cpu.step(&mut bus);
+        let saved = cpu.save_state();
+        cpu.step(&mut bus);
+        cpu.step(&mut bus);
+        assert_eq!(cpu.total_cycles(), 6);
+
+        cpu.load_state(saved);
+        assert_eq!(cpu.cycles, 2);
+        assert_eq!(cpu.total_cycles(), 6);
+        cpu.step(&mut bus);
+        assert_eq!(cpu.cycles, 4);
+        assert_eq!(cpu.total_cycles(), 8);
+
+        cpu.cycles = 0;
+        assert_eq!(cpu.total_cycles(), 8);
+        cpu.step(&mut bus);
+        assert_eq!(cpu.total_cycles(), 10);
+    }
+
+    #[test]
+    fn reset_is_cold_while_reset_warm_keeps_sp_and_cycles() {
+        let mut bus = TestBus::default();
+        let mut cold = prepare_cpu(&mut bus);
+        cold.sp = byte!(0x02);
+        cold.cycles = 500;
+        let mut warm = prepare_cpu(&mut bus);
+        warm.sp = byte!(0x02);
+        warm.cycles = 500;
+
+        cold.reset(&mut bus);
+        warm.reset_warm(&mut bus);
+
+        assert_eq!(cold.sp, byte!(0xFD));
+        assert_eq!(cold.cycles, 0);
+        assert_eq!(warm.sp, byte!(0xFF));
+        assert_eq!(warm.cycles, 500 + u64::from(INTERRUPT_CYCLES));
+        assert_eq!(cold.pc, warm.pc);
+    }
+
+    #[test]
+    fn requested_reset_uses_reset_line() {
+        let mut bus = TestBus::default();
+        let mut cpu = prepare_cpu(&mut bus);
+        cpu.a = byte!(0x42);
+        cpu.pc = Word(0x4000);
+
+        cpu.request_interrupt(Interrupt::Reset);
+        let cycles_before = cpu.cycles;
+        assert_eq!(cpu.step(&mut bus), INTERRUPT_CYCLES);
+        assert_eq!(cpu.cycles, cycles_before + 7);
+
+        assert_eq!(cpu.a, byte!(0x42));
+        assert_eq!(cpu.sp, byte!(0xFA));
+        assert_eq!(cpu.pc, Word(0x8000));
+    }
+
+    #[test]
+    fn pending_interrupts_can_be_inspected_and_cleared() {
+        let mut bus = TestBus::default();
+        bus.write_block(Word(0x8000), &[0xEA], AccessType::DataWrite);
+        let mut cpu = prepare_cpu(&mut bus);
+        assert!(!cpu.irq_line());
+        assert!(!cpu.nmi_pending());
+
+        cpu.request_interrupt(Interrupt::Irq);
+        cpu.request_interrupt(Interrupt::Nmi);
+        cpu.request_interrupt(Interrupt::Reset);
+        assert!(cpu.irq_line());
+        assert!(cpu.nmi_pending());
+
+        cpu.clear_pending_interrupts();
+        assert!(!cpu.irq_line());
+        assert!(!cpu.nmi_pending());
+
+        // Nothing is serviced: the next step executes the NOP at the reset address.
+        assert_eq!(cpu.step(&mut bus), 2);
+        assert_eq!(cpu.pc, Word(0x8001));
+    }
+
+    #[test]
+    fn masked_irq_stays_pending() {
+        let mut bus = TestBus::default();
+        bus.write_block(Word(0x8000), &[0xEA], AccessType::DataWrite);
+        let mut cpu = prepare_cpu(&mut bus);
+
+        cpu.request_interrupt(Interrupt::Irq);
+        cpu.step(&mut bus);
+
+        assert!(cpu.p.contains(Flags::InterruptDisabled));
+        assert!(cpu.irq_line());
+    }
+
+    fn interrupt_entry(interrupt: Interrupt, vector: Word, flags: Flags) -> (Cpu<TestBus>, TestBus) {
+        let mut bus = TestBus::default();
+        bus.write(vector, byte!(0x00), AccessType::DataWrite);
+        bus.write(vector + 1, byte!(0x90), AccessType::DataWrite);
+        let mut cpu = prepare_cpu(&mut bus);
+        cpu.pc = Word(0x1234);
+        cpu.p = flags;
+
+        cpu.request_interrupt(interrupt);
+        cpu.step(&mut bus);
+
+        assert_eq!(cpu.pc, Word(0x9000));
+        assert_eq!(cpu.sp, byte!(0xFA));
+        assert_eq!(bus.read(Word(0x01FD), AccessType::DataRead), byte!(0x12));
+        assert_eq!(bus.read(Word(0x01FC), AccessType::DataRead), byte!(0x34));
+        (cpu, bus)
+    }
+
+    #[test]
+    fn display_is_a_one_line_register_dump() {
+        let mut bus = TestBus::default();
+        let mut cpu = prepare_cpu(&mut bus);
+        cpu.a = byte!(0x42);
+        cpu.pc = Word(0x8003);
+        cpu.p = Flags::Break | Flags::InterruptDisabled | Flags::Expansion;
+        cpu.cycles = 14;
+
+        assert_eq!(
+            alloc::format!("{cpu}"),
+            "MOS 6502: A:42 X:00 Y:00 SP:FD PC:8003 [nv-BdIzc] CYC:14"
+        );
+    }
+
+    #[test]
+    fn registers_round_trip_through_getter_and_setter() {
+        let mut bus = TestBus::default();
+        let mut cpu = prepare_cpu(&mut bus);
+        let regs = Registers {
+            a: byte!(0x12),
+            x: byte!(0x34),
+            y: byte!(0x56),
+            p: Flags::Carry | Flags::Sign | Flags::Expansion,
+            sp: byte!(0x80),
+            pc: Word(0xC000),
+        };
+        let cycles = cpu.cycles;
+
+        cpu.set_registers(regs);
+
+        assert_eq!(cpu.registers(), regs);
+        assert_eq!(cpu.pc, Word(0xC000));
+        assert_eq!(cpu.p.bits(), regs.p.bits());
+        assert_eq!(cpu.cycles, cycles);
+        assert_ne!(cpu.registers(), Registers { p: Flags::Expansion, ..regs });
+    }
+
+    #[test]
+    fn held_nmi_line_fires_once_and_toggling_fires_again() {
+        let mut bus = TestBus::default();
+        bus.fill_block(Word(0x8000), 0x10, byte!(0xEA), AccessType::DataWrite);
+        bus.fill_block(Word(0x9000), 0x10, byte!(0xEA), AccessType::DataWrite);
+        bus.set_nmi_vector(Word(0x9000));
+        let mut cpu = prepare_cpu(&mut bus);
+
+        cpu.set_nmi_line(true);
+        assert_eq!(cpu.step(&mut bus), INTERRUPT_CYCLES);
+        assert_eq!(cpu.pc, Word(0x9000));
+
+        for _ in 0..4 {
+            cpu.set_nmi_line(true);
+            assert_eq!(cpu.step(&mut bus), 2);
+        }
+        assert_eq!(cpu.pc, Word(0x9004));
+        assert_eq!(cpu.sp, byte!(0xFA));
+
+        cpu.set_nmi_line(false);
+        cpu.step(&mut bus);
+        assert!(!cpu.nmi_pending());
+        cpu.set_nmi_line(true);
+        assert_eq!(cpu.step(&mut bus), INTERRUPT_CYCLES);
+        assert_eq!(cpu.pc, Word(0x9000));
+        assert_eq!(cpu.sp, byte!(0xF7));
+    }
+
+    #[test]
+    fn held_irq_line_fires_again_after_cli() {
+        let mut bus = TestBus::default();
+        bus.fill_block(Word(0x8000), 0x10, byte!(0xEA), AccessType::DataWrite);
+        // Handler: CLI; NOP
+        bus.write_block(Word(0x9000), &[0x58, 0xEA], AccessType::DataWrite);
+        bus.set_irq_vector(Word(0x9000));
+        let mut cpu = prepare_cpu(&mut bus);
+        cpu.p.set_interrupt_disabled(false);
+
+        cpu.set_irq_line(true);
+        assert_eq!(cpu.step(&mut bus), INTERRUPT_CYCLES);
+        assert_eq!(cpu.pc, Word(0x9000));
+        assert!(cpu.irq_line());
+
+        // CLI with the line still held lets the same IRQ straight back in.
+        assert_eq!(cpu.step(&mut bus), 2);
+        assert_eq!(cpu.step(&mut bus), INTERRUPT_CYCLES);
+        assert_eq!(cpu.pc, Word(0x9000));
+        assert_eq!(cpu.sp, byte!(0xF7));
+
+        cpu.set_irq_line(false);
+        cpu.step(&mut bus);
+        assert_eq!(cpu.step(&mut bus), 2);
+        assert_eq!(cpu.pc, Word(0x9002));
+        assert!(!cpu.irq_line());
+    }
+
+    #[test]
+    fn irq_entry_pushes_status_with_break_clear_and_unused_set() {
+        let flags = Flags::Carry | Flags::Sign | Flags::Break;
+        let (cpu, mut bus) = interrupt_entry(Interrupt::Irq, IRQ_VECTOR_LO, flags);
+
+        // N, C and the unused bit; B is never set in a hardware-pushed status byte.
+        assert_eq!(bus.read(Word(0x01FB), AccessType::DataRead), byte!(0b1010_0001));
+        assert!(cpu.p.contains(Flags::InterruptDisabled));
+        assert!(!cpu.p.contains(Flags::DecimalMode));
+        assert!(cpu.p.contains(Flags::Carry | Flags::Sign));
+    }
+
+    #[test]
+    fn holding_rdy_low_stalls_reads_but_not_the_bus() {
+        let mut bus = TestBus::default();
+        // LDA $1234 (4 cycles)
+        bus.write_block(Word(0x8000), &[0xAD, 0x34, 0x12], AccessType::DataWrite);
+        bus.write(Word(0x1234), byte!(0x42), AccessType::DataWrite);
+        let mut cpu = prepare_cpu(&mut bus);
+        let cycles = cpu.cycles;
+
+        assert!(!cpu.cycle(&mut bus));
+        cpu.set_ready(false);
+        for _ in 0..5 {
+            assert!(!cpu.cycle(&mut bus));
+        }
+        assert_eq!(bus.ticks, 6);
+        assert_eq!(cpu.cycles, cycles + 1);
+        assert_eq!(cpu.a, byte!(0));
+
+        cpu.set_ready(true);
+        assert!(!cpu.cycle(&mut bus));
+        assert!(!cpu.cycle(&mut bus));
+        assert!(cpu.cycle(&mut bus));
+        assert_eq!(cpu.a, byte!(0x42));
+        assert_eq!(cpu.cycles, cycles + 4);
+        assert_eq!(bus.ticks, 9);
+    }
+
+    #[test]
+    fn rdy_does_not_hold_up_write_cycles() {
+        let mut bus = TestBus::default();
+        // INC $1234 (6 cycles): the data read lands on cycle 4, the writes follow.
+        bus.write_block(Word(0x8000), &[0xEE, 0x34, 0x12], AccessType::DataWrite);
+        let mut cpu = prepare_cpu(&mut bus);
+
+        for _ in 0..4 {
+            assert!(!cpu.cycle(&mut bus));
+        }
+        cpu.set_ready(false);
+        assert!(!cpu.cycle(&mut bus));
+        assert!(cpu.cycle(&mut bus));
+        assert_eq!(bus.read(Word(0x1234), AccessType::DataRead), byte!(0x01));
+
+        // The next fetch is a read, so it waits.
+        assert!(cpu.cycle(&mut bus));
+        assert_eq!(cpu.pc, Word(0x8003));
+        assert!(cpu.at_instruction_boundary());
+        assert_eq!(bus.ticks, 7);
+    }
+
+    #[test]
+    fn tick_with_rdy_low_only_advances_the_bus() {
+        let mut bus = TestBus::default();
+        bus.write_block(Word(0x8000), &[0xEA], AccessType::DataWrite);
+        let mut cpu = prepare_cpu(&mut bus);
+        cpu.set_ready(false);
+
+        assert_eq!(cpu.tick(&mut bus), 1);
+        assert_eq!(cpu.tick(&mut bus), 1);
+        assert_eq!(cpu.pc, Word(0x8000));
+        assert_eq!(bus.tick_log, [1, 1]);
+    }
+
+    #[test]
+    fn taking_an_interrupt_charges_seven_cycles() {
+        for interrupt in [Interrupt::Irq, Interrupt::Nmi] {
+            let mut bus = TestBus::default();
+            let mut cpu = prepare_cpu(&mut bus);
+            cpu.p = Flags::Expansion;
+            cpu.cycles = 100;
+
+            cpu.request_interrupt(interrupt);
+            assert_eq!(cpu.tick(&mut bus), 7);
+
+            assert_eq!(cpu.cycles, 107);
+            assert_eq!(cpu.last_step_cycles(), 7);
+            assert_eq!(bus.tick_log, [7]);
+        }
+    }
+
+    fn nmi_during_brk<S: InstructionSet>() -> (Cpu<TestBus>, TestBus) {
+        let mut bus = TestBus::default();
+        bus.set_irq_vector(Word(0x9000));
+        bus.set_nmi_vector(Word(0xA000));
+        let mut cpu: Cpu<TestBus> =
+            Cpu::with_program::<S>(&mut bus, Word(0x8000), &[0x00, 0xEA], Word(0x8000));
+        cpu.p = Flags::Expansion;
+
+        // Fetch the BRK opcode, then raise NMI while the break sequence is still running.
+        assert!(!cpu.cycle(&mut bus));
+        cpu.request_interrupt(Interrupt::Nmi);
+        while !cpu.cycle(&mut bus) {}
+        (cpu, bus)
+    }
+
+    #[test]
+    fn nmi_hijacks_brk_on_nmos() {
+        let (cpu, mut bus) = nmi_during_brk::<Mos6502>();
+
+        assert_eq!(cpu.pc, Word(0xA000));
+        assert!(!cpu.nmi_pending());
+        // Return address still skips the BRK padding byte, and B is set in the pushed status.
+        assert_eq!(bus.read(Word(0x01FD), AccessType::DataRead), byte!(0x80));
+        assert_eq!(bus.read(Word(0x01FC), AccessType::DataRead), byte!(0x02));
+        assert_eq!(bus.read(Word(0x01FB), AccessType::DataRead), byte!(0b0011_0000));
+        assert!(cpu.p.contains(Flags::InterruptDisabled));
+    }
+
+    #[test]
+    fn nmi_does_not_hijack_brk_on_cmos() {
+        let (mut cpu, mut bus) = nmi_during_brk::<Wdc65c02s>();
+
+        assert_eq!(cpu.pc, Word(0x9000));
+        assert!(cpu.nmi_pending());
+        assert_eq!(bus.read(Word(0x01FB), AccessType::DataRead), byte!(0b0011_0000));
+
+        // The NMI is taken afterwards, at the next boundary.
+        cpu.step(&mut bus);
+        assert_eq!(cpu.pc, Word(0xA000));
+    }
+
+    #[test]
+    fn nmi_entry_pushes_decimal_flag_then_clears_it() {
+        let flags = Flags::DecimalMode | Flags::Overflow | Flags::Expansion;
+        let (cpu, mut bus) = interrupt_entry(Interrupt::Nmi, NMI_VECTOR_LO, flags);
+
+        assert_eq!(bus.read(Word(0x01FB), AccessType::DataRead), byte!(0b0110_1000));
+        assert!(cpu.p.contains(Flags::InterruptDisabled));
+        assert!(!cpu.p.contains(Flags::DecimalMode));
+        assert!(cpu.p.contains(Flags::Overflow));
+    }
+
+    #[test]
+    fn rti_after_irq_restores_interrupted_status() {
+        let flags = Flags::DecimalMode | Flags::Zero | Flags::Expansion;
+        let (mut cpu, mut bus) = interrupt_entry(Interrupt::Irq, IRQ_VECTOR_LO, flags);
+        bus.write(Word(0x9000), byte!(0x40), AccessType::DataWrite); // RTI
+
+        cpu.step(&mut bus);
+
+        assert_eq!(cpu.pc, Word(0x1234));
+        assert_eq!(cpu.p.bits(), flags.bits());
+    }
+
+    #[test]
+    fn peek_stack_reads_without_moving_sp() {
+        let mut bus = TestBus::default();
+        // JSR $9000 from $8000 pushes the return address $8002 (hi then lo).
+        bus.write_block(Word(0x8000), &[0x20, 0x00, 0x90], AccessType::DataWrite);
+        let mut cpu = prepare_cpu(&mut bus);
+        cpu.sp = byte!(0xFF);
+
+        cpu.step(&mut bus);
+
+        assert_eq!(cpu.stack_depth(), 2);
+        assert_eq!(cpu.peek_stack(&mut bus, 0), byte!(0x02));
+        assert_eq!(cpu.peek_stack(&mut bus, 1), byte!(0x80));
+        assert_eq!(cpu.sp, byte!(0xFD));
+    }
+
+    #[test]
+    fn peek_stack_wraps_within_page_one() {
+        let mut bus = TestBus::default();
+        let mut cpu = prepare_cpu(&mut bus);
+        bus.write(Word(0x0100), byte!(0xAB), AccessType::DataWrite);
+        cpu.sp = byte!(0xFE);
+
+        assert_eq!(cpu.peek_stack(&mut bus, 1), byte!(0xAB));
+        assert_eq!(cpu.stack_depth(), 1);
+    }
+
+    fn effective_address_of(
+        program: &[u8],
+        setup: impl FnOnce(&mut Cpu<TestBus>, &mut TestBus),
+    ) -> Option<Word> {
+        let mut bus = TestBus::default();
+        bus.write_block(Word(0x8000), program, AccessType::DataWrite);
+        let mut cpu = prepare_cpu(&mut bus);
+        setup(&mut cpu, &mut bus);
+        let address = cpu.effective_address(&mut bus);
+        assert_eq!(cpu.pc, Word(0x8000));
+        address
+    }
+
+    #[test]
+    fn effective_address_resolves_absolute_and_indexed_forms() {
+        // STA $1234
+        assert_eq!(effective_address_of(&[0x8D, 0x34, 0x12], |_, _| {}), Some(Word(0x1234)));
+        // LDA $12F0,X crosses into the next page.
+        let indexed = effective_address_of(&[0xBD, 0xF0, 0x12], |cpu, _| cpu.x = byte!(0x20));
+        assert_eq!(indexed, Some(Word(0x1310)));
+        // LDA $FFF0,Y wraps around the address space.
+        let wrapped = effective_address_of(&[0xB9, 0xF0, 0xFF], |cpu, _| cpu.y = byte!(0x20));
+        assert_eq!(wrapped, Some(Word(0x0010)));
+        // STX $F0,Y stays in the zero page.
+        let zero_page = effective_address_of(&[0x96, 0xF0], |cpu, _| cpu.y = byte!(0x20));
+        assert_eq!(zero_page, Some(Word(0x0010)));
+    }
+
+    #[test]
+    fn effective_address_follows_pointers() {
+        // STA ($FF,X) with X = 1 reads the pointer from $00/$01.
+        let pre_indexed = effective_address_of(&[0x81, 0xFF], |cpu, bus| {
+            cpu.x = byte!(0x01);
+            bus.write_block(Word(0x0000), &[0x00, 0x30], AccessType::DataWrite);
+        });
+        assert_eq!(pre_indexed, Some(Word(0x3000)));
+
+        // LDA ($FF),Y takes the pointer's high byte from $00, not $100.
+        let post_indexed = effective_address_of(&[0xB1, 0xFF], |cpu, bus| {
+            cpu.y = byte!(0x05);
+            bus.write(Word(0x00FF), byte!(0x00), AccessType::DataWrite);
+            bus.write(Word(0x0000), byte!(0x40), AccessType::DataWrite);
+        });
+        assert_eq!(post_indexed, Some(Word(0x4005)));
+
+        // JMP ($10FF) on NMOS reads the high byte from $1000.
+        let indirect = effective_address_of(&[0x6C, 0xFF, 0x10], |_, bus| {
+            bus.write(Word(0x10FF), byte!(0x34), AccessType::DataWrite);
+            bus.write(Word(0x1000), byte!(0x12), AccessType::DataWrite);
+            bus.write(Word(0x1100), byte!(0x56), AccessType::DataWrite);
+        });
+        assert_eq!(indirect, Some(Word(0x1234)));
+    }
+
+    #[test]
+    fn effective_address_is_none_without_a_memory_operand() {
+        // INX, ASL A, LDA #$10, BNE *+2
+        for program in [&[0xE8][..], &[0x0A], &[0xA9, 0x10], &[0xD0, 0x00]] {
+            assert_eq!(effective_address_of(program, |_, _| {}), None);
+        }
+    }
+
+    #[test]
+    fn effective_address_uses_the_fixed_cmos_indirect_jump() {
+        let mut bus = TestBus::default();
+        bus.write_block(Word(0x8000), &[0x6C, 0xFF, 0x10], AccessType::DataWrite);
+        bus.write(Word(0x10FF), byte!(0x34), AccessType::DataWrite);
+        bus.write(Word(0x1000), byte!(0x12), AccessType::DataWrite);
+        bus.write(Word(0x1100), byte!(0x56), AccessType::DataWrite);
+        let cpu: Cpu<TestBus> = Cpu::with_reset_vector::<Wdc65c02s>(&mut bus, Word(0x8000));
+
+        assert_eq!(cpu.effective_address(&mut bus), Some(Word(0x5634)));
+    }
+
+    #[test]
+    fn effective_address_uses_the_fixed_65ce02_indirect_jsr() {
+        // JSR ($10FF) takes 5 cycles like the NMOS JMP, but reads the high byte from $1100.
+        let mut bus = TestBus::default();
+        bus.write_block(Word(0x8000), &[0x22, 0xFF, 0x10], AccessType::DataWrite);
+        bus.write(Word(0x10FF), byte!(0x34), AccessType::DataWrite);
+        bus.write(Word(0x1000), byte!(0x12), AccessType::DataWrite);
+        bus.write(Word(0x1100), byte!(0x56), AccessType::DataWrite);
+        let mut cpu: Cpu<TestBus> = Cpu::with_reset_vector::<Csg65ce02>(&mut bus, Word(0x8000));
+        assert_eq!(cpu.table[0x22].cycles, 5);
+
+        assert_eq!(cpu.effective_address(&mut bus), Some(Word(0x5634)));
+        cpu.step(&mut bus);
+        assert_eq!(cpu.pc, Word(0x5634));
+    }
+
+    #[test]
+    fn instruction_length_covers_one_two_and_three_byte_forms() {
+        let mut bus = TestBus::default();
+        let program = assemble(
+            "INX
+             ASL A
+             LDA #$10
+             BNE $8008
+             STA $1234,X
+             JMP ($0200)",
+        );
+        bus.write_block(Word(0x8000), &program, AccessType::DataWrite);
+        let mut cpu = prepare_cpu(&mut bus);
+
+        let mut lengths = alloc::vec::Vec::new();
+        for pc in [0x8000, 0x8001, 0x8002, 0x8004, 0x8006, 0x8009] {
+            cpu.pc = Word(pc);
+            lengths.push(cpu.instruction_length(&mut bus));
+        }
+        assert_eq!(lengths, [1, 1, 2, 2, 3, 3]);
+        assert!(bus.take_log().is_empty());
+    }
+
+    #[test]
+    fn relocated_stack_base_moves_pushes_and_pulls() {
+        let mut bus = TestBus::default();
+        // JSR $9000; at $9000: PHA, PLA, RTS.
+        bus.write_block(Word(0x8000), &[0x20, 0x00, 0x90], AccessType::DataWrite);
+        bus.write_block(Word(0x9000), &[0x48, 0x68, 0x60], AccessType::DataWrite);
+        let mut cpu = prepare_cpu(&mut bus);
+        cpu.set_stack_base(Word(0x0400));
+        cpu.sp = byte!(0xFF);
+        cpu.a = byte!(0x5A);
+
+        cpu.step(&mut bus);
+        cpu.step(&mut bus);
+
+        assert_eq!(bus.read(Word(0x04FF), AccessType::DataRead), byte!(0x80));
+        assert_eq!(bus.read(Word(0x04FE), AccessType::DataRead), byte!(0x02));
+        assert_eq!(bus.read(Word(0x04FD), AccessType::DataRead), byte!(0x5A));
+        assert_eq!(bus.read(Word(0x01FD), AccessType::DataRead), byte!(0x00));
+        assert_eq!(cpu.peek_stack(&mut bus, 0), byte!(0x5A));
+
+        cpu.a = byte!(0);
+        cpu.step(&mut bus);
+        cpu.step(&mut bus);
+
+        assert_eq!(cpu.a, byte!(0x5A));
+        assert_eq!(cpu.pc, Word(0x8003));
+        assert_eq!(cpu.sp, byte!(0xFF));
+    }
+
+    #[test]
+    fn tick_advances_bus_and_drains_dma() {
+        let mut bus = TestBus::default();
+        bus.write_block(Word(0x8000), &[0xEA, 0xEA], AccessType::DataWrite);
+        bus.queue_dma(3);
+        bus.queue_dma(2);
+
+        let mut cpu = prepare_cpu(&mut bus);
+        let cycles = cpu.tick(&mut bus);
+
+        assert_eq!(cycles, 2);
+        assert_eq!(cpu.last_opcode, byte!(0xEA));
+        assert_eq!(bus.ticks, 2 + 3 + 2);
+        assert_eq!(bus.dma_ticks, 5);
+    }
+
+    #[test]
+    fn tick_drains_dma_bursts_in_fifo_order() {
+        let mut bus = TestBus::default();
+        bus.write_block(Word(0x8000), &[0xEA, 0xEA], AccessType::DataWrite);
+        bus.queue_dma(5);
+        bus.queue_dma(1);
+        bus.queue_dma(3);
+
+        let mut cpu = prepare_cpu(&mut bus);
+        cpu.tick(&mut bus);
+
+        // One on_tick for the instruction, then one per DMA burst in the order queued.
+        assert_eq!(bus.tick_log, [2, 5, 1, 3]);
+        assert_eq!(bus.dma_ticks, 9);
+
+        // The queue is empty afterwards, so the next tick only reports the instruction.
+        bus.tick_log.clear();
+        cpu.tick(&mut bus);
+        assert_eq!(bus.tick_log, [2]);
+        assert_eq!(bus.dma_ticks, 9);
+    }
+
+    #[test]
+    fn last_step_bus_cycles_counts_accesses() {
+        let mut bus = TestBus::default();
+        let program = assemble("LDA $1234\nSTA $10\nNOP");
+        bus.write_block(Word(0x8000), &program, AccessType::DataWrite);
+        let mut cpu = prepare_cpu(&mut bus);
+
+        assert_eq!(cpu.step(&mut bus), 4);
+        assert_eq!(cpu.last_step_bus_cycles(), Some(4));
+
+        assert_eq!(cpu.step(&mut bus), 3);
+        assert_eq!(cpu.last_step_bus_cycles(), Some(3));
+
+        // The NOP's second cycle is an internal operation that isn't modeled as an access yet.
+        assert_eq!(cpu.step(&mut bus), 2);
+        assert_eq!(cpu.last_step_bus_cycles(), Some(1));
+    }
+
+    #[test]
+    fn last_step_bus_cycles_is_none_without_bus_support() {
+        let mut bus = crate::SimpleBus::default();
+        bus.write(Word(0x0000), byte!(0xEA), AccessType::DataWrite);
+        let mut cpu: Cpu<crate::SimpleBus> = Cpu::default();
+
+        cpu.step(&mut bus);
+
+        assert_eq!(cpu.last_step_bus_cycles(), None);
+    }
+
+    #[test]
+    fn run_until_stops_on_brk() {
+        let mut bus = TestBus::default();
+        bus.write_block(Word(0x8000), &[0xA9, 0x01, 0x00], AccessType::DataWrite);
+        let mut cpu = prepare_cpu(&mut bus);
+
+        let summary = cpu.run_until(
+            &mut bus,
+            RunConfig {
+                stop_on_brk: true,
+                ..RunConfig::default()
+            },
+        );
+
+        assert!(summary.hit_brk());
+        assert_eq!(summary.instructions_executed, 2);
+        assert!(!summary.hit_instruction_limit());
+        assert!(!summary.hit_predicate());
+    }
+
+    #[test]
+    fn run_until_stops_on_predicate() {
+        let mut bus = TestBus::default();
+        bus.write_block(Word(0x8000), &[0xE8, 0xE8, 0x00], AccessType::DataWrite);
+        let mut cpu = prepare_cpu(&mut bus);
+
+        let mut stop_when_x_is_two = |cpu: &Cpu<TestBus>, _bus: &mut TestBus| cpu.x == byte!(0x02);
+
+        let summary = cpu.run_until(
+            &mut bus,
+            RunConfig {
+                predicate: Some(RunPredicate::new(&mut stop_when_x_is_two)),
+                ..RunConfig::default()
+            },
+        );
+
+        assert!(summary.hit_predicate());
+        assert_eq!(summary.instructions_executed, 2);
+        assert!(!summary.hit_brk());
+    }
+
+    #[test]
+    fn run_until_stops_on_cpu_only_predicate() {
+        let mut bus = TestBus::default();
+        bus.write_block(Word(0x8000), &[0xE8, 0xE8, 0xE8, 0x00], AccessType::DataWrite);
+        let mut cpu = prepare_cpu(&mut bus);
+
+        let mut stop_when_x_is_three = |cpu: &Cpu<TestBus>| cpu.x == byte!(0x03);
+
+        let summary = cpu.run_until(
+            &mut bus,
+            RunConfig {
+                predicate: Some(RunPredicate::cpu_only(&mut stop_when_x_is_three)),
+                ..RunConfig::default()
+            },
+        );
+
+        assert!(summary.hit_predicate());
+        assert_eq!(summary.instructions_executed, 3);
+        assert_eq!(bus.read(cpu.pc, AccessType::DataRead), byte!(0x00));
+    }
+
+    #[test]
+    fn run_until_passes_instruction_cycles_to_predicate() {
+        // The LDA crosses a page and takes 5 cycles.
+        let program = assemble("INX\nLDA $12FF,X\nINX\nBRK");
+        let mut bus = TestBus::default();
+        bus.write_block(Word(0x8000), &program, AccessType::DataWrite);
+        let mut cpu = prepare_cpu(&mut bus);
+
+        let mut seen = alloc::vec::Vec::new();
+        let mut longer_than_four = |cpu: &Cpu<TestBus>, cycles: u8| {
+            seen.push(cycles);
+            assert_eq!(cycles, cpu.last_step_cycles());
+            cycles > 4
+        };
+        let summary = cpu.run_until(
+            &mut bus,
+            RunConfig {
+                predicate: Some(RunPredicate::with_cycles(&mut longer_than_four)),
+                ..RunConfig::default()
+            },
+        );
+
+        assert!(summary.hit_predicate());
+        assert_eq!(cpu.pc, Word(0x8004));
+        assert_eq!(seen, [2, 5]);
+    }
+
+    #[test]
+    fn step_over_runs_nested_subroutines() {
+        // $8000 JSR sub1; $8003 LDA #$01; $8005 BRK
+        // sub1 ($8010): JSR sub2; INX; RTS
+        // sub2 ($8020): INY; RTS
+        let mut bus = TestBus::default();
+        bus.write_block(Word(0x8000), &[0x20, 0x10, 0x80, 0xA9, 0x01, 0x00], AccessType::DataWrite);
+        bus.write_block(Word(0x8010), &[0x20, 0x20, 0x80, 0xE8, 0x60], AccessType::DataWrite);
+        bus.write_block(Word(0x8020), &[0xC8, 0x60], AccessType::DataWrite);
+        let mut cpu = prepare_cpu(&mut bus);
+        let sp = cpu.sp;
+
+        let summary = cpu.step_over(&mut bus);
+        assert!(summary.hit_predicate());
+        assert_eq!(summary.instructions_executed, 6);
+        assert_eq!((cpu.pc, cpu.sp), (Word(0x8003), sp));
+        assert_eq!((cpu.x, cpu.y), (Byte(1), Byte(1)));
+
+        let summary = cpu.step_over(&mut bus);
+        assert!(summary.hit_instruction_limit());
+        assert_eq!(summary.instructions_executed, 1);
+        assert_eq!((cpu.pc, cpu.a), (Word(0x8005), Byte(1)));
+    }
+
+    #[test]
+    fn step_over_tracks_stack_depth_through_recursion() {
+        // The return address is also the subroutine's entry point, so stopping on PC alone would
+        // end the run right after the first JSR.
+        let program = assemble(
+            "JSR $8003
+             DEX
+             BEQ $800A
+             JSR $8003
+             NOP
+             RTS",
+        );
+        let mut bus = TestBus::default();
+        bus.write_block(Word(0x8000), &program, AccessType::DataWrite);
+        let mut cpu = prepare_cpu(&mut bus);
+        cpu.x = Byte(3);
+        let sp = cpu.sp;
+
+        let summary = cpu.step_over(&mut bus);
+
+        assert!(summary.hit_predicate());
+        assert_eq!((cpu.pc, cpu.sp, cpu.x), (Word(0x8003), sp, Byte(0)));
+        // JSR, three rounds of DEX/BEQ with a JSR after the first two, then RTS/NOP/RTS/NOP/RTS.
+        assert_eq!(summary.instructions_executed, 1 + 8 + 5);
+    }
+
+    #[test]
+    fn step_over_runs_65ce02_calls() {
+        // $8000 BSR $8010; $8003 JSR ($8020,X); $8006 BRK
+        // $8010: INY; RTS     $8020: .word $8030     $8030: INY; RTS
+        let mut bus = TestBus::default();
+        let program = [0x63, 0x0E, 0x00, 0x23, 0x20, 0x80, 0x00];
+        bus.write_block(Word(0x8000), &program, AccessType::DataWrite);
+        bus.write_block(Word(0x8010), &[0xC8, 0x60], AccessType::DataWrite);
+        bus.write_block(Word(0x8020), &[0x30, 0x80], AccessType::DataWrite);
+        bus.write_block(Word(0x8030), &[0xC8, 0x60], AccessType::DataWrite);
+        let mut cpu: Cpu<TestBus> = Cpu::with_reset_vector::<Csg65ce02>(&mut bus, Word(0x8000));
+        let sp = cpu.sp;
+
+        let summary = cpu.step_over(&mut bus);
+        assert!(summary.hit_predicate());
+        assert_eq!((cpu.pc, cpu.sp, cpu.y), (Word(0x8003), sp, Byte(1)));
+
+        let summary = cpu.step_over(&mut bus);
+        assert!(summary.hit_predicate());
+        assert_eq!((cpu.pc, cpu.sp, cpu.y), (Word(0x8006), sp, Byte(2)));
+    }
+
+    #[test]
+    fn step_out_returns_from_a_two_deep_call_chain() {
+        // $8000 JSR sub1; $8003 BRK
+        // sub1 ($8010): JSR sub2; INX; RTS
+        // sub2 ($8020): JSR sub3; INY; RTS
+        // sub3 ($8030): RTS
+        let mut bus = TestBus::default();
+        bus.write_block(Word(0x8000), &[0x20, 0x10, 0x80, 0x00], AccessType::DataWrite);
+        bus.write_block(Word(0x8010), &[0x20, 0x20, 0x80, 0xE8, 0x60], AccessType::DataWrite);
+        bus.write_block(Word(0x8020), &[0x20, 0x30, 0x80, 0xC8, 0x60], AccessType::DataWrite);
+        bus.write_block(Word(0x8030), &[0x60], AccessType::DataWrite);
+        let mut cpu = prepare_cpu(&mut bus);
+        let top = cpu.sp;
+        cpu.step(&mut bus);
+        cpu.step(&mut bus);
+        assert_eq!(cpu.pc, Word(0x8020));
+
+        // sub3's RTS returns inside sub2's frame, so only sub2's own RTS ends the run.
+        let summary = cpu.step_out(&mut bus);
+        assert!(summary.hit_predicate());
+        assert_eq!(summary.instructions_executed, 4);
+        assert_eq!((cpu.pc, cpu.y, cpu.x), (Word(0x8013), Byte(1), Byte(0)));
+
+        let summary = cpu.step_out(&mut bus);
+        assert!(summary.hit_predicate());
+        assert_eq!((cpu.pc, cpu.sp, cpu.x), (Word(0x8003), top, Byte(1)));
+    }
+
+    #[test]
+    fn step_out_sees_returns_across_the_stack_wrap() {
+        // $8000 JSR sub; $8003 BRK; sub ($8010): INX; RTS
+        let mut bus = TestBus::default();
+        bus.write_block(Word(0x8000), &[0x20, 0x10, 0x80, 0x00], AccessType::DataWrite);
+        bus.write_block(Word(0x8010), &[0xE8, 0x60], AccessType::DataWrite);
+
+        // The 8-bit stack wraps from $0100 to $01FF, so the return lowers SP from $FF to $01.
+        let mut cpu = prepare_cpu(&mut bus);
+        cpu.sp = byte!(0x01);
+        cpu.step(&mut bus);
+        assert_eq!(cpu.sp, byte!(0xFF));
+        let summary = cpu.step_out(&mut bus);
+        assert!(summary.hit_predicate());
+        assert_eq!((cpu.pc, cpu.sp, cpu.x), (Word(0x8003), byte!(0x01), byte!(1)));
+
+        // The 65CE02's 16-bit stack moves down into $00FF and back up to $0101.
+        let mut cpu: Cpu<TestBus> = Cpu::with_reset_vector::<Csg65ce02>(&mut bus, Word(0x8000));
+        cpu.set_stack_extended(true);
+        cpu.sp = byte!(0x01);
+        cpu.step(&mut bus);
+        assert_eq!((cpu.stack_base(), cpu.sp), (Word(0x0000), byte!(0xFF)));
+        let summary = cpu.step_out(&mut bus);
+        assert!(summary.hit_predicate());
+        assert_eq!((cpu.pc, cpu.stack_base(), cpu.sp), (Word(0x8003), Word(0x0100), byte!(0x01)));
+    }
+
+    #[test]
+    fn step_out_treats_rtn_as_a_return() {
+        // $8000 LDA #$AA; PHA; JSR sub; $8006 BRK; sub ($8010): INX; RTN #$01 drops the argument
+        let mut bus = TestBus::default();
+        let program = [0xA9, 0xAA, 0x48, 0x20, 0x10, 0x80, 0x00];
+        bus.write_block(Word(0x8000), &program, AccessType::DataWrite);
+        bus.write_block(Word(0x8010), &[0xE8, 0x62, 0x01], AccessType::DataWrite);
+        let mut cpu: Cpu<TestBus> = Cpu::with_reset_vector::<Csg65ce02>(&mut bus, Word(0x8000));
+        let sp = cpu.sp;
+        for _ in 0..3 {
+            cpu.step(&mut bus);
+        }
+        assert_eq!(cpu.pc, Word(0x8010));
+
+        let summary = cpu.step_out(&mut bus);
+        assert!(summary.hit_predicate());
+        assert_eq!(summary.instructions_executed, 2);
+        assert_eq!((cpu.pc, cpu.sp, cpu.x), (Word(0x8006), sp, byte!(1)));
+    }
+
+    #[test]
+    fn opcode_handler_traps_before_table_dispatch() {
+        use alloc::rc::Rc;
+        use alloc::vec::Vec;
+        use core::cell::RefCell;
+
+        // LDA #'H'; JAM (putchar trap); LDA #'i'; JAM; BRK
+        let mut bus = TestBus::default();
+        bus.write_block(
+            Word(0x8000),
+            &[0xA9, b'H', 0x02, 0xA9, b'i', 0x02, 0x00],
+            AccessType::DataWrite,
+        );
+        let mut cpu = prepare_cpu(&mut bus);
+
+        let output = Rc::new(RefCell::new(Vec::new()));
+        let sink = Rc::clone(&output);
+        assert!(cpu
+            .set_opcode_handler(0x02, 6, move |cpu, _bus| {
+                sink.borrow_mut().push(cpu.a.0);
+                cpu.pc += 1;
+            })
+            .is_none());
+
+        let summary = cpu.run_until(
+            &mut bus,
+            RunConfig {
+                stop_on_brk: true,
+                ..RunConfig::default()
+            },
+        );
+
+        assert!(summary.hit_brk());
+        assert_eq!(output.borrow().as_slice(), b"Hi");
+        assert_eq!(summary.cycles, 2 + 6 + 2 + 6 + 7);
+
+        assert!(cpu.remove_opcode_handler(0x02).is_some());
+        cpu.pc = Word(0x8002);
+        cpu.step(&mut bus);
+        assert_eq!(cpu.run_state, RunState::Halted);
+    }
+
+    #[test]
+    fn opcode_handler_can_remove_or_replace_itself() {
+        // JAM; JAM; JAM
+        let mut bus = TestBus::default();
+        bus.write_block(Word(0x8000), &[0x02, 0x02, 0x02], AccessType::DataWrite);
+        let mut cpu = prepare_cpu(&mut bus);
+
+        // A one-shot trap: it runs once and then hands $02 back to the table.
+        cpu.set_opcode_handler(0x02, 2, |cpu, _bus| {
+            cpu.x += 1;
+            cpu.pc += 1;
+            assert!(cpu.remove_opcode_handler(0x02).is_none());
+        });
+        cpu.step(&mut bus);
+        assert_eq!(cpu.x, Byte(1));
+        assert!(cpu.remove_opcode_handler(0x02).is_none());
+        cpu.step(&mut bus);
+        assert_eq!(cpu.run_state, RunState::Halted);
+
+        // Removing itself and installing a successor keeps the successor.
+        let mut cpu = prepare_cpu(&mut bus);
+        cpu.set_opcode_handler(0x02, 2, |cpu, _bus| {
+            cpu.remove_opcode_handler(0x02);
+            cpu.set_opcode_handler(0x02, 2, |cpu, _bus| {
+                cpu.y += 1;
+                cpu.pc += 1;
+            });
+            cpu.pc += 1;
+        });
+        cpu.step(&mut bus);
+        cpu.step(&mut bus);
+        cpu.step(&mut bus);
+        assert_eq!((cpu.pc, cpu.y), (Word(0x8003), Byte(2)));
+    }
+
+    #[test]
+    fn instruction_hook_sees_every_executed_instruction() {
+        use alloc::rc::Rc;
+        use alloc::vec::Vec;
+        use core::cell::RefCell;
+
+        // LDA #$01; STA $10; NOP; BRK
+        let mut bus = TestBus::default();
+        bus.write_block(
+            Word(0x8000),
+            &[0xA9, 0x01, 0x85, 0x10, 0xEA, 0x00],
+            AccessType::DataWrite,
+        );
+        let mut cpu = prepare_cpu(&mut bus);
+
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        let sink = Rc::clone(&seen);
+        cpu.on_instruction = Some(Box::new(move |ctx: &InstructionContext| {
+            sink.borrow_mut().push(*ctx);
+        }));
+
+        let start = cpu.cycles;
+        let summary = cpu.run_until(
+            &mut bus,
+            RunConfig {
+                stop_on_brk: true,
+                ..RunConfig::default()
+            },
+        );
+
+        let seen = seen.borrow();
+        assert_eq!(seen.len() as u64, summary.instructions_executed);
+        let fetched: Vec<(u16, u8, u8)> = seen
+            .iter()
+            .map(|ctx| (ctx.pc.0, ctx.opcode.0, ctx.cycles))
+            .collect();
+        assert_eq!(
+            fetched,
+            [
+                (0x8000, 0xA9, 2),
+                (0x8002, 0x85, 3),
+                (0x8004, 0xEA, 2),
+                (0x8005, 0x00, 7)
+            ]
+        );
+        assert_eq!(seen[0].total_cycles, start + 2);
+        assert_eq!(seen[3].total_cycles, cpu.cycles);
+    }
+
+    // LDX #$00; loop: INX; STX $10; JMP loop
+    const COUNTER_PROGRAM: [u8; 8] = [0xA2, 0x00, 0xE8, 0x86, 0x10, 0x4C, 0x02, 0x80];
+
+    fn trace(cpu: &mut Cpu<TestBus>, bus: &mut TestBus, steps: usize) -> alloc::vec::Vec<(u16, u8, u64)> {
+        (0..steps)
+            .map(|_| {
+                cpu.step(bus);
+                (cpu.pc.0, cpu.x.0, cpu.cycles)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn load_state_replays_identically() {
+        let mut bus = TestBus::default();
+        let mut cpu: Cpu<TestBus> =
+            Cpu::with_program::<Mos6502>(&mut bus, Word(0x8000), &COUNTER_PROGRAM, Word(0x8000));
+        for _ in 0..5 {
+            cpu.step(&mut bus);
+        }
+        cpu.p.set_interrupt_disabled(true);
+        cpu.request_interrupt(Interrupt::Irq);
+
+        let saved = cpu.save_state();
+        let first = trace(&mut cpu, &mut bus, 12);
+        cpu.load_state(saved);
+        assert_eq!(cpu.last_opcode, saved.last_opcode);
+        assert!(cpu.irq_line());
+        let second = trace(&mut cpu, &mut bus, 12);
+
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn load_state_restores_the_stack_page() {
+        let mut bus = TestBus::default();
+        let mut cpu: Cpu<TestBus> = Cpu::with_instruction_set::<Mos6502>();
+        cpu.set_stack_extended(true);
+        cpu.set_stack_base(Word(0x1200));
+        cpu.sp = Byte(0x00);
+        cpu.push(&mut bus, Byte(0x42));
+
+        let mut restored: Cpu<TestBus> = Cpu::with_instruction_set::<Mos6502>();
+        restored.load_state(cpu.save_state());
+
+        assert_eq!(restored.stack_base(), Word(0x1100));
+        assert!(restored.stack_extended());
+        assert_eq!(restored.pop(&mut bus), Byte(0x42));
+        assert_eq!(restored.stack_base(), Word(0x1200));
+    }
+
+    #[cfg(feature = "coverage")]
+    #[test]
+    fn coverage_counts_executed_opcodes() {
+        let mut bus = TestBus::default();
+        let mut cpu: Cpu<TestBus> =
+            Cpu::with_program::<Mos6502>(&mut bus, Word(0x8000), &COUNTER_PROGRAM, Word(0x8000));
+        // LDX, then three INX/STX/JMP loops.
+        trace(&mut cpu, &mut bus, 10);
+        // An interrupt entry isn't an opcode fetch.
+        cpu.request_interrupt(Interrupt::Nmi);
+        cpu.step(&mut bus);
+
+        let coverage = cpu.coverage();
+        assert_eq!(coverage[0xA2], 1);
+        assert_eq!(coverage[0xE8], 3);
+        assert_eq!(coverage[0x86], 3);
+        assert_eq!(coverage[0x4C], 3);
+        assert_eq!(coverage.iter().sum::<u32>(), 10);
+
+        cpu.reset_coverage();
+        assert!(cpu.coverage().iter().all(|&count| count == 0));
+    }
+
+    #[cfg(feature = "history")]
+    #[test]
+    fn history_keeps_only_the_most_recent_instructions() {
+        use crate::processor::history::HISTORY_CAPACITY;
+
+        let mut bus = TestBus::default();
+        let mut cpu: Cpu<TestBus> =
+            Cpu::with_program::<Mos6502>(&mut bus, Word(0x8000), &COUNTER_PROGRAM, Word(0x8000));
+        // LDX, then INX/STX/JMP loops: well past the capacity.
+        let steps = HISTORY_CAPACITY + 10;
+        trace(&mut cpu, &mut bus, steps);
+
+        let history = cpu.history();
+        assert_eq!(history.len(), HISTORY_CAPACITY);
+        // The LDX and the first three loops have been pushed out; 42 = 1 + 3 * 13 + 2.
+        assert_eq!(history[0].opcode, byte!(0xE8));
+        assert_eq!(history[0].x, byte!(3));
+        let newest = history[HISTORY_CAPACITY - 1];
+        assert_eq!(newest.opcode, byte!(0x86));
+        assert_eq!(newest.x, byte!(14));
+        for pair in history.windows(2) {
+            assert_ne!(pair[0].pc, pair[1].pc);
+        }
+
+        cpu.clear_history();
+        assert!(cpu.history().is_empty());
     }
 
-    /// Cycles consumed by the most recent [`step`](Self::step) call.
-    #[must_use]
-    pub fn last_step_cycles(&self) -> u8 {
-        self.last_step_cycles
-    }
-}
+    #[cfg(feature = "serde")]
+    #[test]
+    fn cpu_state_round_trips_through_serde() {
+        let mut bus = TestBus::default();
+        let mut cpu: Cpu<TestBus> =
+            Cpu::with_program::<Mos6502>(&mut bus, Word(0x8000), &COUNTER_PROGRAM, Word(0x8000));
+        for _ in 0..7 {
+            cpu.step(&mut bus);
+        }
+        cpu.request_interrupt(Interrupt::Nmi);
 
-impl<B: Mos6502CompatibleBus + 'static> Default for Cpu<B> {
-    fn default() -> Self {
-        Self::with_instruction_set::<Mos6502>()
+        let json = serde_json::to_string(&cpu.save_state()).unwrap();
+        let mut restored: Cpu<TestBus> = Cpu::with_instruction_set::<Mos6502>();
+        restored.load_state(serde_json::from_str(&json).unwrap());
+
+        assert_eq!(restored.p.bits(), cpu.p.bits());
+        assert!(restored.nmi_pending());
+        assert_eq!(
+            trace(&mut restored, &mut bus, 12),
+            trace(&mut cpu, &mut bus, 12)
+        );
     }
-}
 
-#[cfg(test)]
-mod tests {
-    extern crate alloc;
+    #[cfg(feature = "serde")]
+    #[test]
+    fn cpu_state_without_a_stack_base_uses_page_one() {
+        let mut cpu: Cpu<TestBus> = Cpu::with_instruction_set::<Mos6502>();
+        cpu.set_stack_base(Word(0x0300));
+        let mut json: serde_json::Value = serde_json::to_value(cpu.save_state()).unwrap();
+        json.as_object_mut().unwrap().remove("stack_base");
 
-    use ull::Bus;
-    use super::*;
-    use crate::instruction::mos6502::Mos6502;
-    use crate::processor::run::RunPredicate;
-    use crate::AccessType;
-    use crate::TestingBus;  
+        cpu.load_state(serde_json::from_value(json).unwrap());
+        assert_eq!(cpu.stack_base(), STACK_SPACE_START);
+    }
 
-    type TestBus = TestingBus;
+    #[test]
+    fn run_until_stops_while_rdy_is_low() {
+        let mut bus = TestBus::default();
+        // INX; INX; INX
+        bus.write_block(Word(0x8000), &[0xE8, 0xE8, 0xE8], AccessType::DataWrite);
+        let mut cpu = prepare_cpu(&mut bus);
+        cpu.set_ready(false);
 
-    fn prepare_cpu(bus: &mut TestBus) -> Cpu<TestBus> {
-        bus.write(RESET_VECTOR_LO, byte!(0x00), AccessType::DataWrite);
-        bus.write(RESET_VECTOR_HI, byte!(0x80), AccessType::DataWrite);
-        let mut cpu: Cpu<TestBus> = Cpu::with_instruction_set::<Mos6502>();
-        cpu.reset(bus);
-        cpu
+        let limit = |n| RunConfig {
+            instruction_limit: Some(n),
+            ..RunConfig::default()
+        };
+        let summary = cpu.run_until(&mut bus, limit(3));
+        assert!(summary.not_ready());
+        assert_eq!(summary.instructions_executed, 0);
+        assert_eq!(summary.cycles, 0);
+        assert_eq!(cpu.x, Byte(0));
+
+        cpu.set_ready(true);
+        let summary = cpu.run_until(&mut bus, limit(3));
+        assert!(summary.hit_instruction_limit());
+        assert_eq!(cpu.x, Byte(3));
     }
 
     #[test]
-    fn with_reset_vector_sets_pc_and_vector_bytes() {
+    fn run_until_enforces_instruction_limit() {
         let mut bus = TestBus::default();
-        let reset = Word(0x9000);
+        bus.write_block(
+            Word(0x8000),
+            &[0xA9, 0x01, 0xE8, 0x00],
+            AccessType::DataWrite,
+        );
+        let mut cpu = prepare_cpu(&mut bus);
 
-        let cpu: Cpu<TestBus> = Cpu::with_reset_vector::<Mos6502>(&mut bus, reset);
+        let summary = cpu.run_until(
+            &mut bus,
+            RunConfig {
+                instruction_limit: Some(1),
+                stop_on_brk: true,
+                ..RunConfig::default()
+            },
+        );
 
-        assert_eq!(cpu.pc, reset);
-        assert_eq!(bus.read(RESET_VECTOR_LO, AccessType::DataRead), reset.lo());
-        assert_eq!(bus.read(RESET_VECTOR_HI, AccessType::DataRead), reset.hi());
+        assert!(summary.hit_instruction_limit());
+        assert_eq!(summary.instructions_executed, 1);
+        assert!(!summary.hit_brk());
     }
 
     #[test]
-    fn with_program_loads_bytes_and_sets_pc() {
+    fn run_until_stops_at_first_boundary_past_cycle_limit() {
+        // LDX #imm (2) + INX (2) + STX zp (3) lands exactly on 7 cycles.
         let mut bus = TestBus::default();
-        let program = [0xEA, 0x00];
-        let start = Word(0x8000);
+        let mut cpu: Cpu<TestBus> =
+            Cpu::with_program::<Mos6502>(&mut bus, Word(0x8000), &COUNTER_PROGRAM, Word(0x8000));
+        let exact = cpu.run_until(
+            &mut bus,
+            RunConfig {
+                cycle_limit: Some(7),
+                ..RunConfig::default()
+            },
+        );
+        assert!(exact.hit_cycle_limit());
+        assert_eq!(exact.cycles, 7);
+        assert_eq!(exact.instructions_executed, 3);
+
+        // One more cycle can't split the 3-cycle JMP, so the run overshoots to 10.
+        let mut cpu: Cpu<TestBus> =
+            Cpu::with_program::<Mos6502>(&mut bus, Word(0x8000), &COUNTER_PROGRAM, Word(0x8000));
+        let overshoot = cpu.run_until(
+            &mut bus,
+            RunConfig {
+                cycle_limit: Some(8),
+                ..RunConfig::default()
+            },
+        );
+        assert!(overshoot.hit_cycle_limit());
+        assert_eq!(overshoot.cycles, 10);
+        assert_eq!(overshoot.instructions_executed, 4);
+        assert_eq!(cpu.pc, Word(0x8002));
+    }
 
-        let cpu: Cpu<TestBus> = Cpu::with_program::<Mos6502>(&mut bus, start, &program, start);
+    #[test]
+    fn run_until_stops_before_breakpoint_instruction() {
+        let mut bus = TestBus::default();
+        let mut sled = [0xEA; 16];
+        sled[15] = 0x00;
+        let mut cpu: Cpu<TestBus> =
+            Cpu::with_program::<Mos6502>(&mut bus, Word(0x8000), &sled, Word(0x8000));
+        // Fake an LDA #$01 mid-sled so it's visible whether the breakpoint executed.
+        bus.write_block(Word(0x8008), &[0xA9, 0x01], AccessType::DataWrite);
+
+        let breakpoints = [Word(0x8008), Word(0x800C)];
+        let summary = cpu.run_until(
+            &mut bus,
+            RunConfig {
+                breakpoints: &breakpoints,
+                stop_on_brk: true,
+                ..RunConfig::default()
+            },
+        );
+        assert_eq!(summary.outcome, RunOutcome::HitBreakpoint(Word(0x8008)));
+        assert_eq!(summary.instructions_executed, 8);
+        assert_eq!(cpu.pc, Word(0x8008));
+        assert_eq!(cpu.a, Byte(0x00));
 
-        assert_eq!(cpu.pc, start);
-        assert_eq!(bus.read(start, AccessType::OpcodeFetch), Byte(program[0]));
-        assert_eq!(
-            bus.read(start + 1, AccessType::OpcodeFetch),
-            Byte(program[1])
+        // Resuming steps off the current breakpoint and stops at the next one.
+        let summary = cpu.run_until(
+            &mut bus,
+            RunConfig::builder().breakpoints(&breakpoints).stop_on_brk(),
         );
+        assert_eq!(summary.outcome, RunOutcome::HitBreakpoint(Word(0x800C)));
+        assert_eq!(summary.instructions_executed, 3);
+        assert_eq!(cpu.a, Byte(0x01));
     }
 
     #[test]
-    fn tick_advances_bus_and_drains_dma() {
+    fn run_until_checks_added_and_borrowed_breakpoints() {
         let mut bus = TestBus::default();
-        bus.write_block(Word(0x8000), &[0xEA, 0xEA], AccessType::DataWrite);
-        bus.queue_dma(3);
-        bus.queue_dma(2);
+        let mut cpu: Cpu<TestBus> =
+            Cpu::with_program::<Mos6502>(&mut bus, Word(0x8000), &[0xEA; 16], Word(0x8000));
+        let borrowed = [Word(0x8006)];
 
-        let mut cpu = prepare_cpu(&mut bus);
-        let cycles = cpu.tick(&mut bus);
+        let summary = cpu.run_until(
+            &mut bus,
+            RunConfig::builder().breakpoints(&borrowed).breakpoint(Word(0x8003)),
+        );
+        assert_eq!(summary.outcome, RunOutcome::HitBreakpoint(Word(0x8003)));
 
-        assert_eq!(cycles, 2);
-        assert_eq!(cpu.last_opcode, byte!(0xEA));
-        assert_eq!(bus.ticks, 2 + 3 + 2);
-        assert_eq!(bus.dma_ticks, 5);
+        let summary = cpu.run_until(
+            &mut bus,
+            RunConfig::builder().breakpoint(Word(0x8003)).breakpoints(&borrowed),
+        );
+        assert_eq!(summary.outcome, RunOutcome::HitBreakpoint(Word(0x8006)));
     }
 
     #[test]
-    fn run_until_stops_on_brk() {
+    fn run_summary_records_where_the_run_stopped() {
         let mut bus = TestBus::default();
-        bus.write_block(Word(0x8000), &[0xA9, 0x01, 0x00], AccessType::DataWrite);
+        // LDA #$01; NOP; BRK
+        bus.write_block(Word(0x8000), &[0xA9, 0x01, 0xEA, 0x00], AccessType::DataWrite);
+        bus.write_block(IRQ_VECTOR_LO, &[0x00, 0x90], AccessType::DataWrite);
         let mut cpu = prepare_cpu(&mut bus);
 
         let summary = cpu.run_until(
@@ -510,54 +3479,162 @@ mod tests {
         );
 
         assert!(summary.hit_brk());
-        assert_eq!(summary.instructions_executed, 2);
-        assert!(!summary.hit_instruction_limit());
-        assert!(!summary.hit_predicate());
+        assert_eq!(summary.last_pc, cpu.pc);
+        assert_eq!(summary.last_pc, Word(0x9000));
+        assert_eq!(summary.last_opcode, cpu.last_opcode);
+        assert_eq!(summary.last_opcode, byte!(0x00));
     }
 
     #[test]
-    fn run_until_stops_on_predicate() {
-        let mut bus = TestBus::default();
-        bus.write_block(Word(0x8000), &[0xE8, 0xE8, 0x00], AccessType::DataWrite);
-        let mut cpu = prepare_cpu(&mut bus);
+    fn run_until_stops_after_a_write_to_a_watched_address() {
+        use crate::bus::watch_bus::WatchBus;
+
+        let mut bus = WatchBus::new(TestBus::default());
+        bus.watch(Word(0x0042));
+        // LDA #$07; STA $41; STA $42; NOP; BRK
+        let program = [0xA9, 0x07, 0x85, 0x41, 0x85, 0x42, 0xEA, 0x00];
+        let mut cpu: Cpu<WatchBus<TestBus>> =
+            Cpu::with_program::<Mos6502>(&mut bus, Word(0x8000), &program, Word(0x8000));
+        // Pokes before the run don't count.
+        bus.write(Word(0x0042), byte!(0x01), AccessType::DataWrite);
 
-        let mut stop_when_x_is_two = |cpu: &Cpu<TestBus>, _bus: &mut TestBus| cpu.x == byte!(0x02);
+        let summary = cpu.run_until(
+            &mut bus,
+            RunConfig {
+                stop_on_brk: true,
+                ..RunConfig::default()
+            },
+        );
+
+        assert_eq!(summary.outcome, RunOutcome::HitWatchpoint(Word(0x0042)));
+        assert!(summary.hit_watchpoint());
+        assert_eq!(summary.instructions_executed, 3);
+        assert_eq!(cpu.pc, Word(0x8006));
+        assert_eq!(bus.read(Word(0x0042), AccessType::DataRead), byte!(0x07));
 
+        // Resuming carries on until the next watched write (here, none before BRK).
         let summary = cpu.run_until(
             &mut bus,
             RunConfig {
-                predicate: Some(RunPredicate::new(&mut stop_when_x_is_two)),
+                stop_on_brk: true,
                 ..RunConfig::default()
             },
         );
+        assert!(summary.hit_brk());
+    }
 
-        assert!(summary.hit_predicate());
+    #[test]
+    fn run_until_stalls_on_jump_to_self() {
+        let mut bus = TestBus::default();
+        // NOP; JMP $8001
+        bus.write_block(Word(0x8000), &[0xEA, 0x4C, 0x01, 0x80], AccessType::DataWrite);
+        let mut cpu = prepare_cpu(&mut bus);
+
+        let summary = cpu.run_until(&mut bus, RunConfig::builder().stop_on_self_loop());
+
+        assert!(summary.stalled());
         assert_eq!(summary.instructions_executed, 2);
-        assert!(!summary.hit_brk());
+        assert_eq!(cpu.pc, Word(0x8001));
     }
 
     #[test]
-    fn run_until_enforces_instruction_limit() {
+    fn run_until_reports_halted_on_jam_and_stp() {
+        use crate::instruction::wdc65c02s::Wdc65c02s;
+
         let mut bus = TestBus::default();
-        bus.write_block(
+        // NOP; JAM
+        bus.write_block(Word(0x8000), &[0xEA, 0x02], AccessType::DataWrite);
+        let mut cpu = prepare_cpu(&mut bus);
+
+        let summary = cpu.run_until(&mut bus, RunConfig::default());
+
+        assert!(summary.halted());
+        assert!(!summary.stalled());
+        // JAM charges no cycles, so only the NOP counts.
+        assert_eq!(summary.instructions_executed, 1);
+
+        // Running again doesn't execute anything, but still says why.
+        let summary = cpu.run_until(&mut bus, RunConfig::default());
+        assert!(summary.halted());
+        assert_eq!(summary.instructions_executed, 0);
+
+        // NOP; STP
+        let mut cpu: Cpu<TestBus> =
+            Cpu::with_program::<Wdc65c02s>(&mut bus, Word(0x8000), &[0xEA, 0xDB], Word(0x8000));
+        let summary = cpu.run_until(&mut bus, RunConfig::default());
+        assert!(summary.halted());
+        assert_eq!(summary.instructions_executed, 2);
+    }
+
+    #[test]
+    fn run_until_reports_waiting_on_wai() {
+        use crate::instruction::wdc65c02s::Wdc65c02s;
+
+        let mut bus = TestBus::default();
+        bus.write_block(IRQ_VECTOR_LO, &[0x00, 0x90], AccessType::DataWrite);
+        // CLI; WAI; NOP
+        let mut cpu: Cpu<TestBus> = Cpu::with_program::<Wdc65c02s>(
+            &mut bus,
+            Word(0x8000),
+            &[0x58, 0xCB, 0xEA],
             Word(0x8000),
-            &[0xA9, 0x01, 0xE8, 0x00],
-            AccessType::DataWrite,
         );
-        let mut cpu = prepare_cpu(&mut bus);
 
+        let summary = cpu.run_until(&mut bus, RunConfig::default());
+
+        assert!(summary.waiting());
+        assert_eq!(summary.instructions_executed, 2);
+        assert_eq!(cpu.pc, Word(0x8002));
+
+        cpu.request_interrupt(Interrupt::Irq);
         let summary = cpu.run_until(
             &mut bus,
             RunConfig {
                 instruction_limit: Some(1),
-                stop_on_brk: true,
+                ..RunConfig::default()
+            },
+        );
+        assert!(summary.hit_instruction_limit());
+        assert_eq!(cpu.pc, Word(0x9000));
+    }
+
+    #[test]
+    fn run_until_step_guard_bounds_runaway_loops() {
+        let mut bus = TestBus::default();
+        // loop: INX; JMP loop
+        bus.write_block(Word(0x8000), &[0xE8, 0x4C, 0x00, 0x80], AccessType::DataWrite);
+        let mut cpu = prepare_cpu(&mut bus);
+
+        let summary = cpu.run_until(
+            &mut bus,
+            RunConfig {
+                step_guard: 10,
+                ..RunConfig::default()
+            },
+        );
+
+        assert!(summary.stalled());
+        assert!(!summary.hit_instruction_limit());
+        assert_eq!(summary.instructions_executed, 10);
+        assert_eq!(cpu.x, byte!(5));
+    }
+
+    #[test]
+    fn run_until_runs_through_self_loops_by_default() {
+        let mut bus = TestBus::default();
+        bus.write_block(Word(0x8000), &[0x4C, 0x00, 0x80], AccessType::DataWrite);
+        let mut cpu = prepare_cpu(&mut bus);
+
+        let summary = cpu.run_until(
+            &mut bus,
+            RunConfig {
+                instruction_limit: Some(3),
                 ..RunConfig::default()
             },
         );
 
         assert!(summary.hit_instruction_limit());
-        assert_eq!(summary.instructions_executed, 1);
-        assert!(!summary.hit_brk());
+        assert_eq!(summary.instructions_executed, 3);
     }
 
     #[test]
@@ -627,4 +3704,209 @@ mod tests {
         assert_eq!(cpu.a, Byte(EXPECTED_HIGH));
         assert_eq!(cpu.last_opcode, Byte::ZERO);
     }
+
+    const MIXED_PROGRAM: [u8; 31] = [
+        0xA2, 0x01, // LDX #$01
+        0xA9, 0x42, // LDA #$42
+        0x85, 0x10, // STA $10
+        0xA5, 0x10, // LDA $10
+        0xBD, 0xFF, 0x20, // LDA $20FF,X (crosses a page)
+        0xAD, 0x00, 0x30, // LDA $3000
+        0xA0, 0x00, // LDY #$00
+        0x91, 0x10, // STA ($10),Y
+        0xEE, 0x00, 0x30, // INC $3000
+        0x20, 0x1C, 0x80, // JSR $801C
+        0xD0, 0x00, // BNE +0 (taken)
+        0xEA, // NOP
+        0x00, // BRK, never reached
+        0xA9, 0x01, // $801C: LDA #$01
+        0x60, // RTS
+    ];
+
+    fn mixed_program_cpu(bus: &mut TestBus) -> Cpu<TestBus> {
+        Cpu::with_program::<Mos6502>(bus, Word(0x8000), &MIXED_PROGRAM, Word(0x8000))
+    }
+
+    const MIXED_PROGRAM_CYCLES: [u8; 14] = [2, 2, 3, 3, 5, 4, 2, 6, 6, 6, 2, 6, 3, 2];
+
+    #[test]
+    fn cycle_takes_as_many_calls_as_step_takes_cycles() {
+        let mut step_bus = TestBus::default();
+        let mut step_cpu = mixed_program_cpu(&mut step_bus);
+        let stepped: alloc::vec::Vec<u8> = (0..MIXED_PROGRAM_CYCLES.len())
+            .map(|_| step_cpu.step(&mut step_bus))
+            .collect();
+
+        let mut bus = TestBus::default();
+        let mut cpu = mixed_program_cpu(&mut bus);
+        let mut cycled = alloc::vec::Vec::new();
+        for _ in 0..MIXED_PROGRAM_CYCLES.len() {
+            let mut calls = 1;
+            while !cpu.cycle(&mut bus) {
+                calls += 1;
+            }
+            assert_eq!(cpu.last_step_cycles(), calls);
+            cycled.push(calls);
+        }
+
+        assert_eq!(stepped, MIXED_PROGRAM_CYCLES);
+        assert_eq!(cycled, MIXED_PROGRAM_CYCLES);
+        assert_eq!(cpu.cycles, step_cpu.cycles);
+        assert_eq!((cpu.pc, cpu.a, cpu.sp), (step_cpu.pc, step_cpu.a, step_cpu.sp));
+        assert_eq!(bus.read(Word(0x3000), AccessType::DataRead), Byte(0x01));
+        assert_eq!(bus.tick_log.len() as u64, cpu.cycles);
+    }
+
+    #[test]
+    fn cycle_counter_advances_one_per_call() {
+        let mut bus = TestBus::default();
+        let mut cpu = mixed_program_cpu(&mut bus);
+
+        for expected in 1..=20 {
+            cpu.cycle(&mut bus);
+            assert_eq!(cpu.cycles, expected);
+        }
+    }
+
+    fn accesses_per_cycle(program: &[u8]) -> alloc::vec::Vec<u64> {
+        let mut bus = TestBus::default();
+        let mut cpu: Cpu<TestBus> =
+            Cpu::with_program::<Mos6502>(&mut bus, Word(0x8000), program, Word(0x8000));
+        bus.accesses = 0;
+
+        let mut log = alloc::vec::Vec::new();
+        loop {
+            let boundary = cpu.cycle(&mut bus);
+            log.push(bus.accesses);
+            if boundary {
+                return log;
+            }
+        }
+    }
+
+    #[test]
+    fn cycle_places_data_access_on_addressing_mode_cycle() {
+        // LDA $1234: opcode, operand low and high on cycles 1-3, the data read on cycle 4.
+        assert_eq!(accesses_per_cycle(&[0xAD, 0x34, 0x12]), [1, 2, 3, 4]);
+        // LDA $12: data read on cycle 3.
+        assert_eq!(accesses_per_cycle(&[0xA5, 0x12]), [1, 2, 3]);
+        // STA ($12),Y: the operand on cycle 2, the pointer and write land together on cycle 5
+        // and the sixth cycle is idle.
+        assert_eq!(accesses_per_cycle(&[0x91, 0x12]), [1, 2, 2, 2, 5, 5]);
+        // NOP: the handler runs on cycle 2.
+        assert_eq!(accesses_per_cycle(&[0xEA]), [1, 1]);
+    }
+
+    #[test]
+    fn cycle_fetches_operand_bytes_on_their_own_cycles() {
+        let mut bus = TestBus::default();
+        // LDA $1234,X; INC $12
+        let program = [0xBD, 0x34, 0x12, 0xE6, 0x12];
+        let mut cpu: Cpu<TestBus> =
+            Cpu::with_program::<Mos6502>(&mut bus, Word(0x8000), &program, Word(0x8000));
+        cpu.x = Byte(1);
+        bus.enable_logging();
+
+        let mut accesses = alloc::vec::Vec::new();
+        for cycle in 1..=9u8 {
+            cpu.cycle(&mut bus);
+            accesses.extend(bus.take_log().into_iter().map(|(addr, access, _, phase)| {
+                (cycle, addr.0, access, phase)
+            }));
+        }
+
+        assert_eq!(
+            accesses,
+            [
+                (1, 0x8000, AccessType::OpcodeFetch, Phase::Read),
+                (2, 0x8001, AccessType::DataRead, Phase::Read),
+                (3, 0x8002, AccessType::DataRead, Phase::Read),
+                (4, 0x1235, AccessType::DataRead, Phase::Read),
+                (5, 0x8003, AccessType::OpcodeFetch, Phase::Read),
+                (6, 0x8004, AccessType::DataRead, Phase::Read),
+                // The read, NMOS dummy write and result all land with the handler.
+                (7, 0x0012, AccessType::DataRead, Phase::Read),
+                (7, 0x0012, AccessType::DummyWrite, Phase::Write),
+                (7, 0x0012, AccessType::DataWrite, Phase::Write),
+            ]
+        );
+        assert_eq!(cpu.a, Byte(0));
+        assert!(cpu.at_instruction_boundary());
+    }
+
+    #[test]
+    fn cycle_spreads_interrupt_entry_over_seven_cycles() {
+        let mut bus = TestBus::default();
+        let mut cpu = prepare_cpu(&mut bus);
+        bus.write(IRQ_VECTOR_LO, Byte(0x00), AccessType::DataWrite);
+        bus.write(IRQ_VECTOR_HI, Byte(0x90), AccessType::DataWrite);
+        cpu.p = Flags::Expansion;
+        cpu.request_interrupt(Interrupt::Irq);
+
+        let boundaries: alloc::vec::Vec<bool> = (0..7).map(|_| cpu.cycle(&mut bus)).collect();
+
+        assert_eq!(boundaries, [false, false, false, false, false, false, true]);
+        assert_eq!(cpu.pc, Word(0x9000));
+        assert_eq!(cpu.cycles, 7);
+        assert_eq!(bus.tick_log, [1; 7]);
+    }
+
+    #[test]
+    fn cycle_ticks_the_bus_while_halted_without_counting() {
+        let mut bus = TestBus::default();
+        let mut cpu = prepare_cpu(&mut bus);
+        cpu.run_state = RunState::Halted;
+
+        assert!(cpu.cycle(&mut bus));
+        assert!(cpu.cycle(&mut bus));
+
+        assert_eq!(cpu.cycles, 0);
+        assert_eq!(bus.tick_log, [1, 1]);
+    }
+
+    #[test]
+    fn cycle_reports_boundaries_for_mixing_with_step() {
+        let mut bus = TestBus::default();
+        let mut cpu = mixed_program_cpu(&mut bus);
+        assert!(cpu.at_instruction_boundary());
+
+        cpu.cycle(&mut bus);
+        assert!(!cpu.at_instruction_boundary());
+        cpu.cycle(&mut bus);
+        assert!(cpu.at_instruction_boundary());
+
+        assert_eq!(cpu.step(&mut bus), 2);
+        assert_eq!(cpu.a, Byte(0x42));
+    }
+
+    #[test]
+    fn forked_cpu_runs_independently_of_its_parent() {
+        // LDA $10; CLC; ADC #$01; STA $11; LDX $11
+        let program = [0xA5, 0x10, 0x18, 0x69, 0x01, 0x85, 0x11, 0xA6, 0x11];
+        let mut bus = TestBus::default();
+        let mut fork_bus = TestBus::default();
+        bus.write_block(Word(0x8000), &program, AccessType::DataWrite);
+        fork_bus.write_block(Word(0x8000), &program, AccessType::DataWrite);
+        let mut cpu = prepare_cpu(&mut bus);
+        cpu.set_opcode_handler(0x02, 2, |cpu, _bus| cpu.y = Byte(0xFF));
+        cpu.on_instruction = Some(Box::new(|_| {}));
+
+        let mut fork = cpu.fork_without_hooks();
+        assert_eq!(fork.pc, cpu.pc);
+        assert!(fork.on_instruction.is_none());
+        assert!(fork.remove_opcode_handler(0x02).is_none());
+        bus.write(Word(0x10), Byte(0x10), AccessType::DataWrite);
+        fork_bus.write(Word(0x10), Byte(0x7F), AccessType::DataWrite);
+
+        for _ in 0..5 {
+            cpu.step(&mut bus);
+            fork.step(&mut fork_bus);
+        }
+
+        assert_eq!(cpu.x, Byte(0x11));
+        assert!(!cpu.p.contains(Flags::Overflow));
+        assert_eq!(fork.x, Byte(0x80));
+        assert!(fork.p.contains(Flags::Overflow));
+        assert_eq!(cpu.cycles, fork.cycles);
+    }
 }
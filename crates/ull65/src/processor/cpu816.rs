@@ -0,0 +1,387 @@
+//! WDC 65C816 CPU with 16-bit registers, banked addressing and emulation mode.
+//!
+//! The 65C816 doesn't fit [`Cpu`](crate::Cpu): its accumulator and index registers change width
+//! at runtime, it adds the direct page, data bank and program bank registers, and every data
+//! access is 24 bits wide. [`Cpu816`] is therefore a separate core that reuses the crate's bus,
+//! flags and run-state types. Opcode semantics live in
+//! [`instruction::wdc65c816`](crate::instruction::wdc65c816).
+//!
+//! Addresses are passed to the bus as `usize` values in `0x00_0000..=0xFF_FFFF`, so the bus has to
+//! decode the bank byte. [`SimpleBus`](crate::SimpleBus) ignores it and mirrors its 64 KB into
+//! every bank, which is enough for code that stays in bank 0.
+
+use crate::bus::Mos6502CompatibleBus;
+use crate::instruction::wdc65c816;
+use crate::processor::cpu::{Interrupt, RunState, INTERRUPT_CYCLES};
+use crate::processor::flags::Flags;
+use crate::AccessType;
+use core::fmt;
+use core::marker::PhantomData;
+use ull::{Byte, Word};
+
+/// Accumulator/memory width select (1 = 8-bit). Shares bit 5 with the 6502's unused bit.
+pub const FLAG_M: Flags = Flags::Expansion;
+/// Index register width select (1 = 8-bit). Shares bit 4 with the 6502's break bit.
+pub const FLAG_X: Flags = Flags::Break;
+
+/// Native-mode COP vector.
+pub const NATIVE_COP_VECTOR: u16 = 0xFFE4;
+/// Native-mode BRK vector.
+pub const NATIVE_BRK_VECTOR: u16 = 0xFFE6;
+/// Native-mode NMI vector.
+pub const NATIVE_NMI_VECTOR: u16 = 0xFFEA;
+/// Native-mode IRQ vector.
+pub const NATIVE_IRQ_VECTOR: u16 = 0xFFEE;
+/// Emulation-mode COP vector.
+pub const EMULATION_COP_VECTOR: u16 = 0xFFF4;
+/// Emulation-mode NMI vector.
+pub const EMULATION_NMI_VECTOR: u16 = 0xFFFA;
+/// RESET vector (always taken in emulation mode).
+pub const EMULATION_RESET_VECTOR: u16 = 0xFFFC;
+/// Emulation-mode IRQ/BRK vector.
+pub const EMULATION_IRQ_VECTOR: u16 = 0xFFFE;
+
+/// Combine a bank byte and a 16-bit offset into the `usize` address handed to the bus.
+#[inline]
+#[must_use]
+pub const fn long_address(bank: u8, addr: u16) -> usize {
+    ((bank as usize) << 16) | addr as usize
+}
+
+/// The WDC 65C816.
+///
+/// Powers up (and resets) in emulation mode, where it behaves like a 65C02: 8-bit registers,
+/// stack in page one, 6502 vectors. `CLC; XCE` switches to native mode, after which `REP`/`SEP`
+/// select 8- or 16-bit accumulator (`M`) and index (`X`) registers.
+///
+/// # Examples
+///
+/// ```
+/// use ull::{Bus, Word};
+/// use ull65::processor::cpu816::Cpu816;
+/// use ull65::{AccessType, ResetVectorExt, SimpleBus};
+///
+/// let mut bus = SimpleBus::default();
+/// // CLC; XCE; REP #$20; LDA #$1234
+/// bus.write_block(
+///     Word(0x8000),
+///     &[0x18, 0xFB, 0xC2, 0x20, 0xA9, 0x34, 0x12],
+///     AccessType::DataWrite,
+/// );
+/// bus.set_reset_vector(Word(0x8000));
+///
+/// let mut cpu: Cpu816<SimpleBus> = Cpu816::new();
+/// cpu.reset(&mut bus);
+/// for _ in 0..4 {
+///     cpu.step(&mut bus);
+/// }
+/// assert!(!cpu.emulation);
+/// assert_eq!(cpu.a, Word(0x1234));
+/// ```
+pub struct Cpu816<B: Mos6502CompatibleBus> {
+    /// Accumulator. With `M` set only the low byte takes part in operations; the high byte (`B`)
+    /// is preserved and reachable via `XBA`.
+    pub a: Word,
+    /// X index register. The high byte is forced to zero while `X` is set.
+    pub x: Word,
+    /// Y index register. The high byte is forced to zero while `X` is set.
+    pub y: Word,
+    /// Stack pointer, always in bank 0. Confined to page one in emulation mode.
+    pub sp: Word,
+    /// Direct page register, the base of `dp` addressing.
+    pub d: Word,
+    /// Data bank register, the bank for absolute and indirect data accesses.
+    pub dbr: Byte,
+    /// Program bank register, the bank code is fetched from.
+    pub pbr: Byte,
+    /// Program counter within [`pbr`](Self::pbr).
+    pub pc: Word,
+    /// Processor status. In native mode bit 5 is [`FLAG_M`] and bit 4 is [`FLAG_X`].
+    pub p: Flags,
+    /// Emulation flag (`E`), exchanged with carry by `XCE`.
+    pub emulation: bool,
+    /// Total cycles executed.
+    pub cycles: u64,
+    /// Opcode executed by the most recent [`step`](Self::step) call.
+    pub last_opcode: Byte,
+    pub run_state: RunState,
+    irq_pending: bool,
+    nmi_pending: bool,
+    reset_pending: bool,
+    _bus: PhantomData<fn(&mut B)>,
+}
+
+impl<B: Mos6502CompatibleBus> fmt::Debug for Cpu816<B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Cpu816")
+            .field("a", &self.a)
+            .field("x", &self.x)
+            .field("y", &self.y)
+            .field("sp", &self.sp)
+            .field("d", &self.d)
+            .field("dbr", &self.dbr)
+            .field("pbr", &self.pbr)
+            .field("pc", &self.pc)
+            .field("p", &self.p)
+            .field("emulation", &self.emulation)
+            .field("cycles", &self.cycles)
+            .field("last_opcode", &self.last_opcode)
+            .field("run_state", &self.run_state)
+            .field("irq_pending", &self.irq_pending)
+            .field("nmi_pending", &self.nmi_pending)
+            .field("reset_pending", &self.reset_pending)
+            .finish()
+    }
+}
+
+impl<B: Mos6502CompatibleBus> Default for Cpu816<B> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<B: Mos6502CompatibleBus> Cpu816<B> {
+    /// Create a CPU in its power-on state: emulation mode, 8-bit registers, SP = $01FD.
+    ///
+    /// Call [`reset`](Self::reset) to load PC from the reset vector.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            a: Word(0),
+            x: Word(0),
+            y: Word(0),
+            sp: Word(0x01FD),
+            d: Word(0),
+            dbr: Byte(0),
+            pbr: Byte(0),
+            pc: Word(0),
+            p: Flags::InterruptDisabled | FLAG_M | FLAG_X,
+            emulation: true,
+            cycles: 0,
+            last_opcode: Byte(0),
+            run_state: RunState::Running,
+            irq_pending: false,
+            nmi_pending: false,
+            reset_pending: false,
+            _bus: PhantomData,
+        }
+    }
+
+    /// Run the hardware reset sequence.
+    ///
+    /// Forces emulation mode, 8-bit registers, D = 0, DBR = PBR = 0, SP high byte = $01, sets I,
+    /// clears D, drops pending interrupts and loads PC from `$00FFFC`. A, the low byte of SP and
+    /// the low bytes of X/Y are preserved, like on the chip; the cycle counter keeps running.
+    pub fn reset(&mut self, bus: &mut B) {
+        self.emulation = true;
+        self.p.insert(Flags::InterruptDisabled | FLAG_M | FLAG_X);
+        self.p.set_decimal_mode(false);
+        self.x.0 &= 0x00FF;
+        self.y.0 &= 0x00FF;
+        self.sp = Word(0x0100 | (self.sp.0 & 0x00FF));
+        self.d = Word(0);
+        self.dbr = Byte(0);
+        self.pbr = Byte(0);
+        self.irq_pending = false;
+        self.nmi_pending = false;
+        self.reset_pending = false;
+        self.run_state = RunState::Running;
+        self.pc = Word(self.read_word(bus, 0, EMULATION_RESET_VECTOR, AccessType::InterruptVectorRead));
+    }
+
+    /// Execute one instruction, or service a pending interrupt, and return the cycles consumed.
+    ///
+    /// Returns 0 while halted by `STP` or waiting in `WAI` with nothing to wake it.
+    pub fn step(&mut self, bus: &mut B) -> u8 {
+        let before = self.cycles;
+
+        if self.reset_pending {
+            self.reset(bus);
+            self.cycles += u64::from(INTERRUPT_CYCLES);
+        } else if self.run_state == RunState::Halted {
+            return 0;
+        } else if self.nmi_pending {
+            self.nmi_pending = false;
+            self.run_state = RunState::Running;
+            let vector = if self.emulation {
+                EMULATION_NMI_VECTOR
+            } else {
+                NATIVE_NMI_VECTOR
+            };
+            self.enter_interrupt(bus, vector, false);
+        } else if self.irq_pending && !self.p.contains(Flags::InterruptDisabled) {
+            self.irq_pending = false;
+            self.run_state = RunState::Running;
+            let vector = if self.emulation {
+                EMULATION_IRQ_VECTOR
+            } else {
+                NATIVE_IRQ_VECTOR
+            };
+            self.enter_interrupt(bus, vector, false);
+        } else {
+            // WAI resumes without vectoring when the IRQ is masked.
+            if self.run_state == RunState::Waiting && self.irq_pending {
+                self.run_state = RunState::Running;
+            }
+            if self.run_state == RunState::Waiting {
+                return 0;
+            }
+
+            let opcode = self.fetch_opcode(bus);
+            self.last_opcode = Byte(opcode);
+            wdc65c816::execute(self, bus, opcode);
+        }
+
+        (self.cycles - before) as u8
+    }
+
+    /// Execute one instruction and advance the bus clock by the cycles it took.
+    pub fn tick(&mut self, bus: &mut B) -> u8 {
+        let cycles = self.step(bus);
+        if cycles > 0 {
+            bus.on_tick(cycles);
+        }
+
+        while let Some(dma_cycles) = bus.poll_dma_cycle() {
+            bus.on_tick(dma_cycles);
+        }
+
+        cycles
+    }
+
+    pub fn request_interrupt(&mut self, interrupt: Interrupt) {
+        match interrupt {
+            Interrupt::Reset => self.reset_pending = true,
+            Interrupt::Nmi => self.nmi_pending = true,
+            Interrupt::Irq => self.irq_pending = true,
+        }
+    }
+
+    /// Whether an IRQ is being asserted and will be serviced once the I flag allows it.
+    #[must_use]
+    pub fn irq_line(&self) -> bool {
+        self.irq_pending
+    }
+
+    /// Whether an NMI has been requested but not yet serviced.
+    #[must_use]
+    pub fn nmi_pending(&self) -> bool {
+        self.nmi_pending
+    }
+
+    /// `true` when the accumulator and memory operations are 16 bits wide (`M` clear).
+    #[must_use]
+    pub fn wide_accumulator(&self) -> bool {
+        !self.p.contains(FLAG_M)
+    }
+
+    /// `true` when X and Y are 16 bits wide (`X` clear).
+    #[must_use]
+    pub fn wide_index(&self) -> bool {
+        !self.p.contains(FLAG_X)
+    }
+
+    /// Replace P, enforcing the mode invariants: M and X stay set in emulation mode, and
+    /// setting X zeroes the high bytes of the index registers.
+    pub fn set_p(&mut self, value: Flags) {
+        self.p = value;
+        if self.emulation {
+            self.p.insert(FLAG_M | FLAG_X);
+        }
+        if self.p.contains(FLAG_X) {
+            self.x.0 &= 0x00FF;
+            self.y.0 &= 0x00FF;
+        }
+    }
+
+    /// Switch between emulation (`true`) and native mode, as `XCE` does.
+    pub fn set_emulation(&mut self, emulation: bool) {
+        self.emulation = emulation;
+        if emulation {
+            self.sp = Word(0x0100 | (self.sp.0 & 0x00FF));
+            self.set_p(self.p);
+        }
+    }
+
+    pub(crate) fn enter_interrupt(&mut self, bus: &mut B, vector: u16, software: bool) {
+        if self.emulation {
+            self.push_word(bus, self.pc.0);
+            let mut flags = self.p | FLAG_M;
+            flags.set(FLAG_X, software);
+            self.push_byte(bus, flags.bits());
+            self.cycles += 7;
+        } else {
+            self.push_byte(bus, self.pbr.0);
+            self.push_word(bus, self.pc.0);
+            self.push_byte(bus, self.p.bits());
+            self.cycles += 8;
+        }
+        self.p.set_interrupt_disabled(true);
+        self.p.set_decimal_mode(false);
+        self.pbr = Byte(0);
+        self.pc = Word(self.read_word(bus, 0, vector, AccessType::InterruptVectorRead));
+    }
+
+    pub(crate) fn fetch_opcode(&mut self, bus: &mut B) -> u8 {
+        let byte = bus.cpu_read(long_address(self.pbr.0, self.pc.0), AccessType::OpcodeFetch);
+        self.pc += 1;
+        byte.0
+    }
+
+    /// Fetch the next program byte (an operand) and advance PC within the program bank.
+    pub(crate) fn fetch_byte(&mut self, bus: &mut B) -> u8 {
+        let byte = bus.cpu_read(long_address(self.pbr.0, self.pc.0), AccessType::DataRead);
+        self.pc += 1;
+        byte.0
+    }
+
+    pub(crate) fn fetch_word(&mut self, bus: &mut B) -> u16 {
+        let lo = self.fetch_byte(bus);
+        let hi = self.fetch_byte(bus);
+        u16::from_le_bytes([lo, hi])
+    }
+
+    pub(crate) fn fetch_long(&mut self, bus: &mut B) -> usize {
+        let addr = self.fetch_word(bus);
+        let bank = self.fetch_byte(bus);
+        long_address(bank, addr)
+    }
+
+    /// Read two bytes at `bank:addr`, wrapping within the bank.
+    pub(crate) fn read_word(&mut self, bus: &mut B, bank: u8, addr: u16, access: AccessType) -> u16 {
+        let lo = bus.cpu_read(long_address(bank, addr), access).0;
+        let hi = bus.cpu_read(long_address(bank, addr.wrapping_add(1)), access).0;
+        u16::from_le_bytes([lo, hi])
+    }
+
+    pub(crate) fn push_byte(&mut self, bus: &mut B, value: u8) {
+        bus.cpu_write(self.sp.0 as usize, Byte(value), AccessType::StackWrite);
+        self.sp = self.stack_step(-1);
+    }
+
+    pub(crate) fn pull_byte(&mut self, bus: &mut B) -> u8 {
+        self.sp = self.stack_step(1);
+        bus.cpu_read(self.sp.0 as usize, AccessType::StackRead).0
+    }
+
+    pub(crate) fn push_word(&mut self, bus: &mut B, value: u16) {
+        let [lo, hi] = value.to_le_bytes();
+        self.push_byte(bus, hi);
+        self.push_byte(bus, lo);
+    }
+
+    pub(crate) fn pull_word(&mut self, bus: &mut B) -> u16 {
+        let lo = self.pull_byte(bus);
+        let hi = self.pull_byte(bus);
+        u16::from_le_bytes([lo, hi])
+    }
+
+    fn stack_step(&self, delta: i16) -> Word {
+        let next = self.sp.0.wrapping_add_signed(delta);
+        if self.emulation {
+            Word(0x0100 | (next & 0x00FF))
+        } else {
+            Word(next)
+        }
+    }
+}
@@ -0,0 +1,406 @@
+//! Single-instruction disassembler driven by the instruction table metadata.
+
+use crate::AccessType;
+use crate::bus::Mos6502CompatibleBus;
+use crate::instruction::InstructionTable;
+use crate::processor::addressing_mode::AddressingModeKind;
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use core::fmt;
+use ull::Word;
+
+/// One decoded instruction, as returned by [`disassemble`].
+///
+/// The [`Display`](fmt::Display) impl renders standard assembler syntax (`LDA ($12),Y`,
+/// `STA $1234,X`, `BNE $8010`), with branch targets resolved to absolute addresses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Disassembled {
+    /// Address of the opcode byte.
+    pub pc: Word,
+    pub opcode: u8,
+    pub mnemonic: &'static str,
+    pub mode: AddressingModeKind,
+    /// Operand bytes in memory order; only the first `len - 1` are meaningful.
+    pub operands: [u8; 2],
+    /// Total instruction length in bytes, including the opcode.
+    pub len: u8,
+}
+
+impl Disassembled {
+    /// Address of the instruction that follows this one.
+    #[must_use]
+    pub fn next_pc(&self) -> Word {
+        self.pc + u16::from(self.len)
+    }
+
+    fn operand_word(&self) -> u16 {
+        u16::from_le_bytes(self.operands)
+    }
+
+    fn branch_target(&self, offset: u8) -> Word {
+        self.next_pc() + (offset as i8 as u16)
+    }
+}
+
+/// Decode the instruction at `pc` using the mnemonic and addressing mode recorded in `table`.
+///
+/// Memory is read with [`AccessType::Peek`], so buses that track side effects can ignore
+/// disassembler traffic. CPU state is not touched.
+///
+/// # Examples
+///
+/// ```
+/// use ull::Word;
+/// use ull65::instruction::mos6502::Mos6502;
+/// use ull65::processor::disasm::disassemble;
+/// use ull65::SimpleBus;
+///
+/// let mut bus = SimpleBus::default();
+/// bus.load(Word(0x8000), &[0xB1, 0x12]);
+/// let table = Mos6502::base_table::<SimpleBus>();
+///
+/// let line = disassemble(&table, &mut bus, Word(0x8000));
+/// assert_eq!(line.len, 2);
+/// assert_eq!(line.to_string(), "LDA ($12),Y");
+/// ```
+pub fn disassemble<B: Mos6502CompatibleBus>(
+    table: &InstructionTable<B>,
+    bus: &mut B,
+    pc: Word,
+) -> Disassembled {
+    let opcode = bus.cpu_read(pc, AccessType::Peek).0;
+    let instruction = &table[opcode as usize];
+    let operand_bytes = instruction.mode.operand_bytes();
+
+    let mut operands = [0; 2];
+    for (i, operand) in operands.iter_mut().take(operand_bytes as usize).enumerate() {
+        *operand = bus.cpu_read(pc + (i as u16 + 1), AccessType::Peek).0;
+    }
+
+    Disassembled {
+        pc,
+        opcode,
+        mnemonic: instruction.mnemonic,
+        mode: instruction.mode,
+        operands,
+        len: operand_bytes + 1,
+    }
+}
+
+impl fmt::Display for Disassembled {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.render(f, None)
+    }
+}
+
+impl Disassembled {
+    /// Render with operand addresses replaced by their names in `symbols`.
+    ///
+    /// Absolute, zero-page and branch-target operands that have a symbol print the name;
+    /// anything else (including immediates) prints as hex, exactly like the plain
+    /// [`Display`](fmt::Display) impl.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ull::Word;
+    /// use ull65::instruction::mos6502::Mos6502;
+    /// use ull65::processor::disasm::{disassemble, SymbolTable};
+    /// use ull65::SimpleBus;
+    ///
+    /// let mut bus = SimpleBus::default();
+    /// bus.load(Word(0x8000), &[0x20, 0x00, 0xFF]);
+    /// let table = Mos6502::base_table::<SimpleBus>();
+    /// let mut symbols = SymbolTable::new();
+    /// symbols.insert(Word(0xFF00), "WOZMON");
+    ///
+    /// let line = disassemble(&table, &mut bus, Word(0x8000));
+    /// assert_eq!(line.with_symbols(&symbols).to_string(), "JSR WOZMON");
+    /// ```
+    #[must_use]
+    pub fn with_symbols<'a>(&'a self, symbols: &'a SymbolTable) -> WithSymbols<'a> {
+        WithSymbols {
+            line: self,
+            symbols,
+        }
+    }
+
+    fn render(&self, f: &mut fmt::Formatter<'_>, symbols: Option<&SymbolTable>) -> fmt::Result {
+        let zp = self.operands[0];
+        let abs = self.operand_word();
+        let name = |addr: u16| symbols.and_then(|symbols| symbols.get(Word(addr)));
+        let zp_operand = Operand {
+            name: name(u16::from(zp)),
+            addr: u16::from(zp),
+            digits: 2,
+        };
+        let abs_operand = Operand {
+            name: name(abs),
+            addr: abs,
+            digits: 4,
+        };
+        match self.mode {
+            AddressingModeKind::Implied => write!(f, "{}", self.mnemonic),
+            AddressingModeKind::Accumulator => write!(f, "{} A", self.mnemonic),
+            AddressingModeKind::Immediate => write!(f, "{} #${zp:02X}", self.mnemonic),
+            AddressingModeKind::ImmediateWord => write!(f, "{} #${abs:04X}", self.mnemonic),
+            AddressingModeKind::Absolute => write!(f, "{} {abs_operand}", self.mnemonic),
+            AddressingModeKind::AbsoluteX => write!(f, "{} {abs_operand},X", self.mnemonic),
+            AddressingModeKind::AbsoluteY => write!(f, "{} {abs_operand},Y", self.mnemonic),
+            AddressingModeKind::AbsoluteIndirect | AddressingModeKind::AbsoluteIndirectCorrect => {
+                write!(f, "{} ({abs_operand})", self.mnemonic)
+            }
+            AddressingModeKind::AbsoluteIndirectX => {
+                write!(f, "{} ({abs_operand},X)", self.mnemonic)
+            }
+            AddressingModeKind::ZeroPage => write!(f, "{} {zp_operand}", self.mnemonic),
+            AddressingModeKind::ZeroPageX => write!(f, "{} {zp_operand},X", self.mnemonic),
+            AddressingModeKind::ZeroPageY => write!(f, "{} {zp_operand},Y", self.mnemonic),
+            AddressingModeKind::ZeroPageIndirect => write!(f, "{} ({zp_operand})", self.mnemonic),
+            AddressingModeKind::ZeroPageIndirectZ => {
+                write!(f, "{} ({zp_operand}),Z", self.mnemonic)
+            }
+            AddressingModeKind::ZeroPageXIndirect => {
+                write!(f, "{} ({zp_operand},X)", self.mnemonic)
+            }
+            AddressingModeKind::ZeroPageIndirectY => {
+                write!(f, "{} ({zp_operand}),Y", self.mnemonic)
+            }
+            AddressingModeKind::StackIndirectY => write!(f, "{} (${zp:02X},SP),Y", self.mnemonic),
+            AddressingModeKind::Relative => {
+                let target = self.branch_target(zp).0;
+                let target = Operand {
+                    name: name(target),
+                    addr: target,
+                    digits: 4,
+                };
+                write!(f, "{} {target}", self.mnemonic)
+            }
+            AddressingModeKind::RelativeLong => {
+                // Long branches count from their last byte, one before the next instruction.
+                let target = (self.next_pc() + abs).0.wrapping_sub(1);
+                let target = Operand {
+                    name: name(target),
+                    addr: target,
+                    digits: 4,
+                };
+                write!(f, "{} {target}", self.mnemonic)
+            }
+            AddressingModeKind::ZeroPageRelative => {
+                let target = self.branch_target(self.operands[1]).0;
+                let target = Operand {
+                    name: name(target),
+                    addr: target,
+                    digits: 4,
+                };
+                write!(f, "{} {zp_operand},{target}", self.mnemonic)
+            }
+        }
+    }
+}
+
+/// An address operand: its symbol if it has one, otherwise `$` and `digits` hex digits.
+struct Operand<'a> {
+    name: Option<&'a str>,
+    addr: u16,
+    digits: usize,
+}
+
+impl fmt::Display for Operand<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.name {
+            Some(name) => f.write_str(name),
+            None => write!(f, "${:0width$X}", self.addr, width = self.digits),
+        }
+    }
+}
+
+/// Labels for addresses, consulted by [`Disassembled::with_symbols`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SymbolTable {
+    names: BTreeMap<u16, String>,
+}
+
+impl SymbolTable {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Name `addr`, returning the name it had before, if any.
+    pub fn insert(&mut self, addr: Word, name: impl Into<String>) -> Option<String> {
+        self.names.insert(addr.0, name.into())
+    }
+
+    /// Forget the name for `addr`.
+    pub fn remove(&mut self, addr: Word) -> Option<String> {
+        self.names.remove(&addr.0)
+    }
+
+    #[must_use]
+    pub fn get(&self, addr: Word) -> Option<&str> {
+        self.names.get(&addr.0).map(String::as_str)
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+}
+
+/// A [`Disassembled`] line rendered against a [`SymbolTable`]; see
+/// [`Disassembled::with_symbols`].
+#[derive(Debug, Clone, Copy)]
+pub struct WithSymbols<'a> {
+    line: &'a Disassembled,
+    symbols: &'a SymbolTable,
+}
+
+impl fmt::Display for WithSymbols<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.line.render(f, Some(self.symbols))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SimpleBus;
+    use crate::instruction::mos6502::Mos6502;
+    use crate::instruction::wdc65c02s::Wdc65c02s;
+    use alloc::string::{String, ToString};
+    use alloc::vec::Vec;
+
+    fn listing(table: &InstructionTable<SimpleBus>, program: &[u8]) -> Vec<String> {
+        let mut bus = SimpleBus::default();
+        bus.load(Word(0x8000), program);
+
+        let mut pc = Word(0x8000);
+        let mut lines = Vec::new();
+        while pc.0 < 0x8000 + program.len() as u16 {
+            let line = disassemble(table, &mut bus, pc);
+            lines.push(line.to_string());
+            pc = line.next_pc();
+        }
+        lines
+    }
+
+    #[test]
+    fn renders_every_nmos_operand_syntax() {
+        let program = [
+            0xEA, // NOP
+            0x0A, // ASL A
+            0xA9, 0x42, // LDA #$42
+            0xA5, 0x12, // LDA $12
+            0xB5, 0x12, // LDA $12,X
+            0xB6, 0x12, // LDX $12,Y
+            0xAD, 0x34, 0x12, // LDA $1234
+            0x9D, 0x34, 0x12, // STA $1234,X
+            0xB9, 0x34, 0x12, // LDA $1234,Y
+            0x6C, 0xFC, 0xFF, // JMP ($FFFC)
+            0xA1, 0x12, // LDA ($12,X)
+            0xB1, 0x12, // LDA ($12),Y
+            0xD0, 0xFE, // BNE to itself
+        ];
+        let table = Mos6502::base_table::<SimpleBus>();
+
+        assert_eq!(
+            listing(&table, &program),
+            [
+                "NOP",
+                "ASL A",
+                "LDA #$42",
+                "LDA $12",
+                "LDA $12,X",
+                "LDX $12,Y",
+                "LDA $1234",
+                "STA $1234,X",
+                "LDA $1234,Y",
+                "JMP ($FFFC)",
+                "LDA ($12,X)",
+                "LDA ($12),Y",
+                "BNE $801A",
+            ]
+        );
+    }
+
+    #[test]
+    fn renders_65c02_only_modes() {
+        let program = [
+            0xB2, 0x12, // LDA ($12)
+            0x7C, 0x00, 0x90, // JMP ($9000,X)
+            0x0F, 0x12, 0x03, // BBR0 $12, +3
+            0x80, 0x80, // BRA -128
+        ];
+        let table = Wdc65c02s::base_table::<SimpleBus>();
+
+        assert_eq!(
+            listing(&table, &program),
+            ["LDA ($12)", "JMP ($9000,X)", "BBR0 $12,$800B", "BRA $7F8A"]
+        );
+    }
+
+    #[test]
+    fn reports_raw_fields_and_length() {
+        let mut bus = SimpleBus::default();
+        bus.load(Word(0x0200), &[0x8D, 0x00, 0xD0]);
+        let table = Mos6502::base_table::<SimpleBus>();
+
+        let line = disassemble(&table, &mut bus, Word(0x0200));
+        assert_eq!(
+            line,
+            Disassembled {
+                pc: Word(0x0200),
+                opcode: 0x8D,
+                mnemonic: "STA",
+                mode: AddressingModeKind::Absolute,
+                operands: [0x00, 0xD0],
+                len: 3,
+            }
+        );
+        assert_eq!(line.next_pc(), Word(0x0203));
+    }
+
+    #[test]
+    fn symbols_rename_known_addresses_and_fall_back_to_hex() {
+        let program = [
+            0x20, 0x00, 0xFF, // JSR $FF00
+            0x4C, 0x03, 0x90, // JMP $9003
+            0xA5, 0x24, // LDA $24
+            0xA9, 0x24, // LDA #$24
+            0xD0, 0xF4, // BNE $8000
+        ];
+        let table = Mos6502::base_table::<SimpleBus>();
+        let mut bus = SimpleBus::default();
+        bus.load(Word(0x8000), &program);
+        let mut symbols = SymbolTable::new();
+        symbols.insert(Word(0xFF00), "WOZMON");
+        symbols.insert(Word(0x0024), "XAML");
+        symbols.insert(Word(0x8000), "START");
+
+        let mut pc = Word(0x8000);
+        let mut lines = Vec::new();
+        for _ in 0..5 {
+            let line = disassemble(&table, &mut bus, pc);
+            lines.push(line.with_symbols(&symbols).to_string());
+            pc = line.next_pc();
+        }
+
+        assert_eq!(
+            lines,
+            [
+                "JSR WOZMON",
+                "JMP $9003",
+                "LDA XAML",
+                "LDA #$24",
+                "BNE START"
+            ]
+        );
+        assert_eq!(symbols.len(), 3);
+    }
+}
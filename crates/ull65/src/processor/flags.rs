@@ -4,6 +4,7 @@
 //! Carry, Zero, Interrupt Disable, Decimal Mode, Break, Expansion, Overflow, and Sign.
 
 use bitflags::bitflags;
+use core::fmt;
 use core::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign};
 use ull::{Address, Byte};
 
@@ -29,6 +30,7 @@ bitflags! {
     /// - Z (Zero): Set when result is zero
     /// - C (Carry): Set on carry/borrow
     #[derive(Default, Copy, Clone, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct Flags: u8 {
         const Carry = 0b0000_0001;              // C
         const Zero = 0b0000_0010;               // Z
@@ -52,6 +54,13 @@ impl Flags {
         self.set(Flags::Sign, signed);
     }
 
+    /// Set Zero and Sign from a result byte, the pair almost every load and ALU operation updates.
+    #[inline]
+    pub fn update_nz(&mut self, value: Byte) {
+        self.set_zero(value == 0);
+        self.set_signed(value.is_signed());
+    }
+
     #[inline]
     pub fn set_carry(&mut self, carry: bool) {
         self.set(Flags::Carry, carry);
@@ -79,6 +88,40 @@ impl Flags {
     }
 }
 
+/// Renders the register the way 6502 monitors do, `NV-BDIZC` from bit 7 down: uppercase for
+/// a set flag, lowercase for a clear one, and `-` for the unused bit 5.
+///
+/// ```
+/// use ull65::processor::flags::Flags;
+///
+/// let p = Flags::InterruptDisabled | Flags::Break | Flags::Expansion;
+/// assert_eq!(p.to_string(), "nv-BdIzc");
+/// ```
+impl fmt::Display for Flags {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        const LETTERS: [(Flags, char); 8] = [
+            (Flags::Sign, 'N'),
+            (Flags::Overflow, 'V'),
+            (Flags::Expansion, '-'),
+            (Flags::Break, 'B'),
+            (Flags::DecimalMode, 'D'),
+            (Flags::InterruptDisabled, 'I'),
+            (Flags::Zero, 'Z'),
+            (Flags::Carry, 'C'),
+        ];
+
+        for (flag, letter) in LETTERS {
+            let shown = if self.contains(flag) {
+                letter
+            } else {
+                letter.to_ascii_lowercase()
+            };
+            fmt::Write::write_char(f, shown)?;
+        }
+        Ok(())
+    }
+}
+
 impl From<Byte> for Flags {
     fn from(byte: Byte) -> Self {
         Flags::from_bits_truncate(byte.as_u8())
@@ -144,3 +187,36 @@ impl BitXorAssign<Flags> for Byte {
         self.0 ^= rhs.bits();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::ToString;
+
+    #[test]
+    fn update_nz_sets_zero_and_sign_from_the_value() {
+        let mut p = Flags::Zero | Flags::Sign | Flags::Carry;
+        p.update_nz(Byte(0x7F));
+        assert_eq!(p.bits(), Flags::Carry.bits());
+
+        p.update_nz(Byte(0x00));
+        assert_eq!(p.bits(), (Flags::Zero | Flags::Carry).bits());
+
+        p.update_nz(Byte(0x80));
+        assert_eq!(p.bits(), (Flags::Sign | Flags::Carry).bits());
+    }
+
+    #[test]
+    fn display_uses_nv_bdizc_notation() {
+        assert_eq!(Flags::empty().to_string(), "nv-bdizc");
+        assert_eq!(Flags::all().to_string(), "NV-BDIZC");
+        assert_eq!(
+            (Flags::Sign | Flags::Zero | Flags::Carry | Flags::Expansion).to_string(),
+            "Nv-bdiZC"
+        );
+        assert_eq!(
+            (Flags::Overflow | Flags::DecimalMode | Flags::InterruptDisabled).to_string(),
+            "nV-bDIzc"
+        );
+    }
+}
@@ -0,0 +1,61 @@
+//! Fixed-size record of the most recently executed instructions, for post-mortem debugging.
+
+use crate::processor::flags::Flags;
+use ull::{Byte, Word};
+
+/// Number of instructions retained by [`Cpu::history`](crate::Cpu::history).
+pub const HISTORY_CAPACITY: usize = 32;
+
+/// CPU state at the moment an instruction was fetched.
+///
+/// `pc` is the address of the opcode and the registers hold the values the instruction started
+/// with, so an entry reads like a line of a trace log.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct HistoryEntry {
+    pub pc: Word,
+    pub opcode: Byte,
+    pub a: Byte,
+    pub x: Byte,
+    pub y: Byte,
+    pub p: Flags,
+    pub sp: Byte,
+}
+
+/// Ring of the last [`HISTORY_CAPACITY`] entries.
+///
+/// Every entry is written twice, `HISTORY_CAPACITY` slots apart, so the live window is always one
+/// contiguous slice without shuffling entries around or allocating.
+#[derive(Clone)]
+pub(crate) struct History {
+    entries: [HistoryEntry; 2 * HISTORY_CAPACITY],
+    next: usize,
+    len: usize,
+}
+
+impl History {
+    pub(crate) fn new() -> Self {
+        Self {
+            entries: [HistoryEntry::default(); 2 * HISTORY_CAPACITY],
+            next: 0,
+            len: 0,
+        }
+    }
+
+    pub(crate) fn record(&mut self, entry: HistoryEntry) {
+        self.entries[self.next] = entry;
+        self.entries[self.next + HISTORY_CAPACITY] = entry;
+        self.next = (self.next + 1) % HISTORY_CAPACITY;
+        self.len = (self.len + 1).min(HISTORY_CAPACITY);
+    }
+
+    /// Retained entries, oldest first.
+    pub(crate) fn as_slice(&self) -> &[HistoryEntry] {
+        let end = self.next + HISTORY_CAPACITY;
+        &self.entries[end - self.len..end]
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.next = 0;
+        self.len = 0;
+    }
+}
@@ -0,0 +1,131 @@
+//! Per-opcode execution counts and cycle totals.
+
+use crate::bus::Mos6502CompatibleBus;
+use crate::processor::cpu::{Cpu, InstructionContext};
+use alloc::boxed::Box;
+use alloc::rc::Rc;
+use core::cell::RefCell;
+
+/// Execution count and total cycles for each of the 256 opcodes, indexed by opcode.
+pub type Histogram = [(u32, u64); 256];
+
+/// Opcode frequency profiler fed by [`Cpu::on_instruction`].
+///
+/// A `Profiler` is a cheap handle: [`attach`](Self::attach) installs a hook that records into
+/// the same counters the handle reads from, so the CPU can be driven with `run_until`, `step` or
+/// `cycle` as usual and the histogram inspected afterwards. Interrupt sequences aren't
+/// instructions and aren't counted; their cycles show up under the handler's opcodes instead.
+///
+/// # Examples
+///
+/// ```
+/// use ull::Word;
+/// use ull65::instruction::mos6502::Mos6502;
+/// use ull65::processor::profile::Profiler;
+/// use ull65::processor::run::RunConfig;
+/// use ull65::{Cpu, SimpleBus};
+///
+/// // LDX #$03; loop: DEX; BNE loop; BRK
+/// let mut bus = SimpleBus::default();
+/// let program = [0xA2, 0x03, 0xCA, 0xD0, 0xFD, 0x00];
+/// let mut cpu: Cpu<SimpleBus> =
+///     Cpu::with_program::<Mos6502>(&mut bus, Word(0x8000), &program, Word(0x8000));
+///
+/// let profiler = Profiler::new();
+/// profiler.attach(&mut cpu);
+/// cpu.run_until(&mut bus, RunConfig { stop_on_brk: true, ..RunConfig::default() });
+///
+/// let mut hottest: Vec<_> = profiler.histogram().into_iter().enumerate().collect();
+/// hottest.sort_by_key(|&(_, (_, cycles))| core::cmp::Reverse(cycles));
+/// assert_eq!(hottest[0], (0xD0, (3, 8))); // BNE: taken twice, then falls through
+/// ```
+#[derive(Debug, Clone)]
+pub struct Profiler {
+    counts: Rc<RefCell<Box<Histogram>>>,
+}
+
+impl Profiler {
+    /// Create a profiler with every counter at zero.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            counts: Rc::new(RefCell::new(Box::new([(0, 0); 256]))),
+        }
+    }
+
+    /// Start recording every instruction `cpu` executes.
+    ///
+    /// This replaces whatever was in [`Cpu::on_instruction`].
+    pub fn attach<B: Mos6502CompatibleBus>(&self, cpu: &mut Cpu<B>) {
+        let profiler = self.clone();
+        cpu.on_instruction = Some(Box::new(move |ctx| profiler.record(ctx)));
+    }
+
+    /// Count one execution of `ctx.opcode` and add its cycles.
+    pub fn record(&self, ctx: &InstructionContext) {
+        let (count, cycles) = &mut self.counts.borrow_mut()[usize::from(ctx.opcode.0)];
+        *count = count.saturating_add(1);
+        *cycles += u64::from(ctx.cycles);
+    }
+
+    /// Snapshot of `(executions, cycles)` per opcode.
+    #[must_use]
+    pub fn histogram(&self) -> Histogram {
+        **self.counts.borrow()
+    }
+
+    /// Zero every counter.
+    pub fn reset(&self) {
+        self.counts.borrow_mut().fill((0, 0));
+    }
+}
+
+impl Default for Profiler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::instruction::mos6502::Mos6502;
+    use crate::processor::run::RunConfig;
+    use crate::TestingBus;
+    use ull::Word;
+
+    #[test]
+    fn histogram_counts_loop_iterations_and_cycles() {
+        // LDY #$04; outer: LDX #$0A; inner: DEX; BNE inner; DEY; BNE outer; BRK
+        let program = [0xA0, 0x04, 0xA2, 0x0A, 0xCA, 0xD0, 0xFD, 0x88, 0xD0, 0xF8, 0x00];
+        let mut bus = TestingBus::default();
+        let mut cpu: Cpu<TestingBus> =
+            Cpu::with_program::<Mos6502>(&mut bus, Word(0x8000), &program, Word(0x8000));
+        let profiler = Profiler::new();
+        profiler.attach(&mut cpu);
+
+        let summary = cpu.run_until(
+            &mut bus,
+            RunConfig {
+                stop_on_brk: true,
+                ..RunConfig::default()
+            },
+        );
+        let histogram = profiler.histogram();
+
+        assert_eq!(histogram[0xA0], (1, 2));
+        assert_eq!(histogram[0xA2], (4, 8));
+        assert_eq!(histogram[0xCA], (40, 80));
+        assert_eq!(histogram[0x88], (4, 8));
+        // 36 taken inner branches and 3 taken outer ones cost 3 cycles, the 5 fall-throughs 2.
+        assert_eq!(histogram[0xD0], (44, 39 * 3 + 5 * 2));
+        assert_eq!(histogram[0x00], (1, 7));
+        assert_eq!(
+            histogram.iter().map(|&(_, cycles)| cycles).sum::<u64>(),
+            summary.cycles
+        );
+
+        profiler.reset();
+        assert!(profiler.histogram().iter().all(|&entry| entry == (0, 0)));
+    }
+}
@@ -1,5 +1,11 @@
 use crate::bus::Mos6502CompatibleBus;
 use crate::Cpu;
+use alloc::vec::Vec;
+use ull::{Byte, Word};
+
+/// Default [`RunConfig::step_guard`]: enough for the bundled functional test ROMs several times
+/// over, while still returning within seconds if a program never reaches its stop condition.
+pub const DEFAULT_STEP_GUARD: u64 = 100_000_000;
 
 /// Reason why [`Cpu::run_until`](crate::processor::cpu::Cpu::run_until) stopped.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -13,8 +19,23 @@ pub enum RunOutcome {
     HitPredicate,
     /// [`RunConfig::instruction_limit`] was reached.
     HitInstructionLimit,
-    /// CPU failed to make forward progress (halted, waiting, etc.).
+    /// [`RunConfig::cycle_limit`] was reached or overshot.
+    HitCycleLimit,
+    /// PC reached one of [`RunConfig::breakpoints`]; the instruction there hasn't executed yet.
+    HitBreakpoint(Word),
+    /// The instruction that just ran wrote to an address watched by a
+    /// [`WatchBus`](crate::WatchBus).
+    HitWatchpoint(Word),
+    /// The CPU executed a `JAM`/`STP` and is [`Halted`](crate::RunState::Halted) until reset.
+    Halted,
+    /// The CPU executed a `WAI` and is [`Waiting`](crate::RunState::Waiting) for an interrupt.
+    Waiting,
+    /// CPU failed to make forward progress while still running (trapped in a jump-to-self, or
+    /// [`RunConfig::step_guard`] was exhausted).
     Stalled,
+    /// RDY is held low (see [`Cpu::set_ready`](crate::Cpu::set_ready)), so no instruction can
+    /// start until the host releases it.
+    NotReady,
 }
 
 /// Summary produced by [`Cpu::run_until`].
@@ -26,6 +47,10 @@ pub struct RunSummary {
     pub cycles: u64,
     /// Outcome describing why execution stopped.
     pub outcome: RunOutcome,
+    /// PC when the run stopped, i.e. the next instruction to execute.
+    pub last_pc: Word,
+    /// Opcode of the last instruction the CPU executed (see [`Cpu::last_opcode`]).
+    pub last_opcode: Byte,
 }
 
 impl RunSummary {
@@ -52,26 +77,136 @@ impl RunSummary {
         self.outcome == RunOutcome::HitInstructionLimit
     }
 
-    /// Returns `true` if the CPU stalled (e.g., waiting, halted).
+    /// Returns `true` if execution reached the configured cycle limit.
+    #[must_use]
+    pub fn hit_cycle_limit(&self) -> bool {
+        self.outcome == RunOutcome::HitCycleLimit
+    }
+
+    /// Returns `true` if execution stopped on a breakpoint.
+    #[must_use]
+    pub fn hit_breakpoint(&self) -> bool {
+        matches!(self.outcome, RunOutcome::HitBreakpoint(_))
+    }
+
+    /// Returns `true` if execution stopped on a write to a watched address.
+    #[must_use]
+    pub fn hit_watchpoint(&self) -> bool {
+        matches!(self.outcome, RunOutcome::HitWatchpoint(_))
+    }
+
+    /// Returns `true` if the run ended because the CPU halted on `JAM`/`STP`.
+    #[must_use]
+    pub fn halted(&self) -> bool {
+        self.outcome == RunOutcome::Halted
+    }
+
+    /// Returns `true` if the run ended with the CPU waiting for an interrupt after `WAI`.
+    #[must_use]
+    pub fn waiting(&self) -> bool {
+        self.outcome == RunOutcome::Waiting
+    }
+
+    /// Returns `true` if the CPU stalled (jump-to-self or step guard exhausted).
     #[must_use]
     pub fn stalled(&self) -> bool {
         self.outcome == RunOutcome::Stalled
     }
+
+    /// Returns `true` if the run stopped because RDY was held low.
+    #[must_use]
+    pub fn not_ready(&self) -> bool {
+        self.outcome == RunOutcome::NotReady
+    }
 }
 
 /// Wrapper around a predicate callback used by [`RunConfig`].
+///
+/// Use [`RunPredicate::new`] when the stop condition needs to inspect the bus,
+/// [`RunPredicate::cpu_only`] for register-only conditions so the bus isn't mutably borrowed, or
+/// [`RunPredicate::with_cycles`] when it depends on how long the last instruction took.
+///
+/// The callback runs after each instruction (or interrupt sequence) has completed and its cycles
+/// have been charged, so [`Cpu::last_step_cycles`] and [`Cpu::cycles`] already include it.
 pub struct RunPredicate<'a, B: Mos6502CompatibleBus> {
-    callback: &'a mut dyn FnMut(&Cpu<B>, &mut B) -> bool,
+    callback: PredicateCallback<'a, B>,
+}
+
+enum PredicateCallback<'a, B: Mos6502CompatibleBus> {
+    WithBus(&'a mut dyn FnMut(&Cpu<B>, &mut B) -> bool),
+    CpuOnly(&'a mut dyn FnMut(&Cpu<B>) -> bool),
+    WithCycles(&'a mut dyn FnMut(&Cpu<B>, u8) -> bool),
 }
 
 impl<'a, B: Mos6502CompatibleBus> RunPredicate<'a, B> {
     /// Create a new predicate wrapper.
     pub fn new(callback: &'a mut dyn FnMut(&Cpu<B>, &mut B) -> bool) -> Self {
-        Self { callback }
+        Self {
+            callback: PredicateCallback::WithBus(callback),
+        }
+    }
+
+    /// Create a predicate that only inspects CPU state (e.g. "stop when A == $42").
+    pub fn cpu_only(callback: &'a mut dyn FnMut(&Cpu<B>) -> bool) -> Self {
+        Self {
+            callback: PredicateCallback::CpuOnly(callback),
+        }
+    }
+
+    /// Create a predicate that also receives the cycles the instruction just consumed.
+    ///
+    /// The count is [`Cpu::last_step_cycles`]: it includes page-crossing and branch penalties
+    /// but not DMA cycles the bus charges afterwards.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ull::Word;
+    /// use ull65::instruction::mos6502::Mos6502;
+    /// use ull65::processor::run::{RunConfig, RunPredicate};
+    /// use ull65::{Cpu, SimpleBus};
+    ///
+    /// // NOP; LDA $1234; NOP
+    /// let mut bus = SimpleBus::default();
+    /// let mut cpu: Cpu<SimpleBus> = Cpu::with_program::<Mos6502>(
+    ///     &mut bus, Word(0x8000), &[0xEA, 0xAD, 0x34, 0x12, 0xEA], Word(0x8000),
+    /// );
+    ///
+    /// let mut slow = |_cpu: &Cpu<SimpleBus>, cycles: u8| cycles > 2;
+    /// let summary = cpu.run_until(
+    ///     &mut bus,
+    ///     RunConfig { predicate: Some(RunPredicate::with_cycles(&mut slow)), ..RunConfig::default() },
+    /// );
+    /// assert!(summary.hit_predicate());
+    /// assert_eq!(cpu.pc, Word(0x8004));
+    /// ```
+    pub fn with_cycles(callback: &'a mut dyn FnMut(&Cpu<B>, u8) -> bool) -> Self {
+        Self {
+            callback: PredicateCallback::WithCycles(callback),
+        }
     }
 
     pub fn should_stop(&mut self, cpu: &Cpu<B>, bus: &mut B) -> bool {
-        (self.callback)(cpu, bus)
+        match &mut self.callback {
+            PredicateCallback::WithBus(callback) => callback(cpu, bus),
+            PredicateCallback::CpuOnly(callback) => callback(cpu),
+            PredicateCallback::WithCycles(callback) => callback(cpu, cpu.last_step_cycles()),
+        }
+    }
+}
+
+/// Breakpoints collected by [`RunConfig::breakpoint`].
+///
+/// Opaque so [`RunConfig::breakpoints`] can stay a plain borrowed slice while the builder still
+/// owns the addresses it's given.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AddedBreakpoints(Vec<Word>);
+
+impl AddedBreakpoints {
+    /// Whether `addr` is one of the collected breakpoints.
+    #[must_use]
+    pub fn contains(&self, addr: Word) -> bool {
+        self.0.contains(&addr)
     }
 }
 
@@ -79,18 +214,150 @@ impl<'a, B: Mos6502CompatibleBus> RunPredicate<'a, B> {
 pub struct RunConfig<'a, B: Mos6502CompatibleBus> {
     /// Maximum number of instructions to execute before stopping.
     pub instruction_limit: Option<u64>,
+    /// Stop once at least this many cycles have run.
+    ///
+    /// Instructions aren't interrupted mid-way, so the run ends at the first instruction boundary
+    /// at or beyond the limit and [`RunSummary::cycles`] may exceed it by up to one instruction.
+    /// Carry the overshoot into the next run if you're budgeting fixed slices (e.g. a scanline).
+    pub cycle_limit: Option<u64>,
     /// Stop automatically when a BRK (opcode 0x00) executes.
     pub stop_on_brk: bool,
     /// Optional predicate invoked after each instruction; returning `true` stops the run.
+    ///
+    /// It runs once the instruction's cycles are charged and after the watchpoint, halt, self-loop
+    /// and BRK checks, so it never sees an instruction that already ended the run.
     pub predicate: Option<RunPredicate<'a, B>>,
+    /// Addresses that stop the run with [`RunOutcome::HitBreakpoint`] before the instruction at
+    /// PC executes, so registers and memory still show the pre-execution state.
+    ///
+    /// The check is skipped for the very first instruction of a run, so calling `run_until` again
+    /// after hitting a breakpoint continues past it instead of stopping immediately.
+    pub breakpoints: &'a [Word],
+    /// Addresses added one at a time with [`breakpoint`](Self::breakpoint), checked alongside
+    /// [`breakpoints`](Self::breakpoints). Leave it at its default in struct literals.
+    pub added_breakpoints: AddedBreakpoints,
+    /// Safety net against runaway programs: after this many instructions the run stops with
+    /// [`RunOutcome::Stalled`]. Defaults to [`DEFAULT_STEP_GUARD`]; use `u64::MAX` to opt out.
+    ///
+    /// Unlike [`instruction_limit`](Self::instruction_limit) this is not an expected stop
+    /// condition, which is why it reports a stall rather than a limit hit.
+    pub step_guard: u64,
+    /// Stop with [`RunOutcome::Stalled`] when an instruction leaves PC where it was (`JMP *`,
+    /// `BNE *` taken, ...). Nothing inside `run_until` can break such a loop, but test ROMs and
+    /// idle loops waiting for an interrupt use it on purpose, so this is opt-in.
+    pub stop_on_self_loop: bool,
 }
 
 impl<B: Mos6502CompatibleBus> Default for RunConfig<'_, B> {
     fn default() -> Self {
         Self {
             instruction_limit: None,
+            cycle_limit: None,
             stop_on_brk: false,
             predicate: None,
+            breakpoints: &[],
+            added_breakpoints: AddedBreakpoints::default(),
+            step_guard: DEFAULT_STEP_GUARD,
+            stop_on_self_loop: false,
         }
     }
 }
+
+/// Chainable setters, as an alternative to the struct literal with `..RunConfig::default()`.
+///
+/// # Examples
+///
+/// ```
+/// use ull::Word;
+/// use ull65::instruction::mos6502::Mos6502;
+/// use ull65::{Cpu, RunConfig, SimpleBus};
+///
+/// // LDX #$00; loop: INX; BNE loop; BRK
+/// let mut bus = SimpleBus::default();
+/// let program = [0xA2, 0x00, 0xE8, 0xD0, 0xFD, 0x00];
+/// let mut cpu: Cpu<SimpleBus> =
+///     Cpu::with_program::<Mos6502>(&mut bus, Word(0x8000), &program, Word(0x8000));
+///
+/// // Instead of
+/// // RunConfig { stop_on_brk: true, instruction_limit: Some(1_000), predicate: Some(...), .. }
+/// let mut x_is_ten = |cpu: &Cpu<SimpleBus>, _bus: &mut SimpleBus| cpu.x.0 == 10;
+/// let config = RunConfig::builder()
+///     .stop_on_brk()
+///     .instruction_limit(1_000)
+///     .breakpoint(Word(0x8005))
+///     .predicate(&mut x_is_ten);
+///
+/// let summary = cpu.run_until(&mut bus, config);
+/// assert!(summary.hit_predicate());
+/// assert_eq!(cpu.x.0, 10);
+/// ```
+impl<'a, B: Mos6502CompatibleBus> RunConfig<'a, B> {
+    /// Start from [`RunConfig::default`].
+    #[must_use]
+    pub fn builder() -> Self {
+        Self::default()
+    }
+
+    /// Set [`instruction_limit`](Self::instruction_limit).
+    #[must_use]
+    pub fn instruction_limit(mut self, limit: u64) -> Self {
+        self.instruction_limit = Some(limit);
+        self
+    }
+
+    /// Set [`cycle_limit`](Self::cycle_limit).
+    #[must_use]
+    pub fn cycle_limit(mut self, limit: u64) -> Self {
+        self.cycle_limit = Some(limit);
+        self
+    }
+
+    /// Enable [`stop_on_brk`](Self::stop_on_brk).
+    #[must_use]
+    pub fn stop_on_brk(mut self) -> Self {
+        self.stop_on_brk = true;
+        self
+    }
+
+    /// Add one breakpoint, owned by the config, on top of [`breakpoints`](Self::breakpoints).
+    #[must_use]
+    pub fn breakpoint(mut self, addr: Word) -> Self {
+        self.added_breakpoints.0.push(addr);
+        self
+    }
+
+    /// Set [`breakpoints`](Self::breakpoints) to a borrowed list.
+    #[must_use]
+    pub fn breakpoints(mut self, addrs: &'a [Word]) -> Self {
+        self.breakpoints = addrs;
+        self
+    }
+
+    /// Stop when `callback` returns `true`; see [`RunPredicate::new`].
+    #[must_use]
+    pub fn predicate(mut self, callback: &'a mut dyn FnMut(&Cpu<B>, &mut B) -> bool) -> Self {
+        self.predicate = Some(RunPredicate::new(callback));
+        self
+    }
+
+    /// Stop when `callback` returns `true`; see [`RunPredicate::cpu_only`].
+    #[must_use]
+    pub fn cpu_predicate(mut self, callback: &'a mut dyn FnMut(&Cpu<B>) -> bool) -> Self {
+        self.predicate = Some(RunPredicate::cpu_only(callback));
+        self
+    }
+
+    /// Set [`step_guard`](Self::step_guard).
+    #[must_use]
+    pub fn step_guard(mut self, steps: u64) -> Self {
+        self.step_guard = steps;
+        self
+    }
+
+    /// Enable [`stop_on_self_loop`](Self::stop_on_self_loop).
+    #[must_use]
+    pub fn stop_on_self_loop(mut self) -> Self {
+        self.stop_on_self_loop = true;
+        self
+    }
+}
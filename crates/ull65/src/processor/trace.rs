@@ -0,0 +1,289 @@
+//! Execution trace lines in the format of the canonical nestest log.
+
+use crate::AccessType;
+use crate::bus::Mos6502CompatibleBus;
+use crate::processor::addressing_mode::AddressingModeKind;
+use crate::processor::cpu::Cpu;
+use crate::processor::disasm::{Disassembled, disassemble};
+use alloc::string::String;
+use core::fmt::{self, Write};
+use ull::Word;
+
+/// Width of the disassembly column, measured from the character after the `*` marker.
+const DISASSEMBLY_WIDTH: usize = 32;
+
+/// Render the instruction at `cpu.pc` and the current registers as a nestest log line.
+///
+/// See [`write_trace_line`] for the format; this is the allocating convenience wrapper.
+///
+/// # Examples
+///
+/// ```
+/// use ull::Word;
+/// use ull65::instruction::mos6502::Mos6502;
+/// use ull65::processor::trace::trace_line;
+/// use ull65::{Cpu, SimpleBus};
+///
+/// let mut bus = SimpleBus::default();
+/// let mut cpu: Cpu<SimpleBus> =
+///     Cpu::with_program::<Mos6502>(&mut bus, Word(0xC000), &[0x4C, 0xF5, 0xC5], Word(0xC000));
+/// cpu.cycles = 7;
+///
+/// assert_eq!(
+///     trace_line(&cpu, &mut bus),
+///     "C000  4C F5 C5  JMP $C5F5                       A:00 X:00 Y:00 P:24 SP:FD CYC:7"
+/// );
+/// ```
+pub fn trace_line<B: Mos6502CompatibleBus>(cpu: &Cpu<B>, bus: &mut B) -> String {
+    let mut line = String::with_capacity(96);
+    // Writing into a String can't fail.
+    let _ = write_trace_line(&mut line, cpu, bus);
+    line
+}
+
+/// Write the instruction at `cpu.pc` and the current registers as a nestest log line.
+///
+/// Call it before executing the instruction. The layout matches `nestest.log` column for column,
+/// minus the `PPU:` field, which belongs to the PPU rather than the CPU:
+///
+/// ```text
+/// C000  4C F5 C5  JMP $C5F5                       A:00 X:00 Y:00 P:24 SP:FD CYC:7
+/// D959  B1 89     LDA ($89),Y = 0300 @ 0300 = 89  A:00 X:65 Y:00 P:27 SP:FB CYC:8760
+/// EE0E  03 45    *SLO ($45,X) @ 47 = 0647 = A5    A:B3 X:02 Y:BF P:E4 SP:FB CYC:19955
+/// ```
+///
+/// Memory operands are annotated with the effective address and the value there, and opcodes
+/// outside the documented NMOS set get a `*` prefix. Mnemonics come from the instruction table,
+/// except `ISC`, which nestest spells `ISB`. Indirect `JMP` targets are resolved with the NMOS
+/// page-wrap bug. Annotation reads use [`AccessType::Peek`] and the CPU isn't touched.
+///
+/// No newline is written, and any [`fmt::Write`] sink works, so `no_std` callers can format into
+/// a fixed buffer.
+///
+/// # Errors
+///
+/// Propagates errors from `out`.
+pub fn write_trace_line<B, W>(out: &mut W, cpu: &Cpu<B>, bus: &mut B) -> fmt::Result
+where
+    B: Mos6502CompatibleBus,
+    W: Write + ?Sized,
+{
+    let line = disassemble(&cpu.table, bus, cpu.pc);
+
+    write!(out, "{:04X}  {:02X}", line.pc.0, line.opcode)?;
+    for operand in &line.operands[..usize::from(line.len - 1)] {
+        write!(out, " {operand:02X}")?;
+    }
+    for _ in line.len..3 {
+        out.write_str("   ")?;
+    }
+    let marker = if is_documented(line.opcode) { ' ' } else { '*' };
+    write!(out, " {marker}")?;
+
+    let mut column = Counted { out, written: 0 };
+    write_disassembly(&mut column, cpu, bus, &line)?;
+    for _ in column.written..DISASSEMBLY_WIDTH {
+        column.out.write_char(' ')?;
+    }
+
+    write!(
+        out,
+        "A:{:02X} X:{:02X} Y:{:02X} P:{:02X} SP:{:02X} CYC:{}",
+        cpu.a.0,
+        cpu.x.0,
+        cpu.y.0,
+        cpu.p.bits(),
+        cpu.sp.0,
+        cpu.cycles
+    )
+}
+
+fn write_disassembly<B, W>(
+    out: &mut W,
+    cpu: &Cpu<B>,
+    bus: &mut B,
+    line: &Disassembled,
+) -> fmt::Result
+where
+    B: Mos6502CompatibleBus,
+    W: Write + ?Sized,
+{
+    let mnemonic = match line.mnemonic {
+        "ISC" => "ISB",
+        other => other,
+    };
+    let zp = line.operands[0];
+    let abs = u16::from_le_bytes(line.operands);
+    let zero_page = |offset: u8| u16::from_le_bytes([offset, cpu.b.0]);
+
+    match line.mode {
+        AddressingModeKind::ZeroPage => {
+            let value = peek(bus, zero_page(zp));
+            write!(out, "{mnemonic} ${zp:02X} = {value:02X}")
+        }
+        AddressingModeKind::ZeroPageX | AddressingModeKind::ZeroPageY => {
+            let (register, index) = if line.mode == AddressingModeKind::ZeroPageX {
+                ('X', cpu.x.0)
+            } else {
+                ('Y', cpu.y.0)
+            };
+            let addr = zp.wrapping_add(index);
+            let value = peek(bus, zero_page(addr));
+            write!(
+                out,
+                "{mnemonic} ${zp:02X},{register} @ {addr:02X} = {value:02X}"
+            )
+        }
+        AddressingModeKind::Absolute if matches!(mnemonic, "JMP" | "JSR") => {
+            write!(out, "{mnemonic} ${abs:04X}")
+        }
+        AddressingModeKind::Absolute => {
+            let value = peek(bus, abs);
+            write!(out, "{mnemonic} ${abs:04X} = {value:02X}")
+        }
+        AddressingModeKind::AbsoluteX | AddressingModeKind::AbsoluteY => {
+            let (register, index) = if line.mode == AddressingModeKind::AbsoluteX {
+                ('X', cpu.x.0)
+            } else {
+                ('Y', cpu.y.0)
+            };
+            let addr = abs.wrapping_add(u16::from(index));
+            let value = peek(bus, addr);
+            write!(
+                out,
+                "{mnemonic} ${abs:04X},{register} @ {addr:04X} = {value:02X}"
+            )
+        }
+        AddressingModeKind::AbsoluteIndirect | AddressingModeKind::AbsoluteIndirectCorrect => {
+            let hi = if line.mode == AddressingModeKind::AbsoluteIndirect {
+                (abs & 0xFF00) | (abs.wrapping_add(1) & 0x00FF)
+            } else {
+                abs.wrapping_add(1)
+            };
+            let target = peek_word(bus, abs, hi);
+            write!(out, "{mnemonic} (${abs:04X}) = {target:04X}")
+        }
+        AddressingModeKind::ZeroPageXIndirect => {
+            let ptr = zp.wrapping_add(cpu.x.0);
+            let addr = peek_word(bus, zero_page(ptr), zero_page(ptr.wrapping_add(1)));
+            let value = peek(bus, addr);
+            write!(
+                out,
+                "{mnemonic} (${zp:02X},X) @ {ptr:02X} = {addr:04X} = {value:02X}"
+            )
+        }
+        AddressingModeKind::ZeroPageIndirectY => {
+            let base = peek_word(bus, zero_page(zp), zero_page(zp.wrapping_add(1)));
+            let addr = base.wrapping_add(u16::from(cpu.y.0));
+            let value = peek(bus, addr);
+            write!(
+                out,
+                "{mnemonic} (${zp:02X}),Y = {base:04X} @ {addr:04X} = {value:02X}"
+            )
+        }
+        // Immediate, implied, branches and the 65C02-only forms carry no annotation.
+        _ => write!(out, "{}", Disassembled { mnemonic, ..*line }),
+    }
+}
+
+fn peek<B: Mos6502CompatibleBus>(bus: &mut B, addr: u16) -> u8 {
+    bus.cpu_read(Word(addr), AccessType::Peek).0
+}
+
+fn peek_word<B: Mos6502CompatibleBus>(bus: &mut B, lo: u16, hi: u16) -> u16 {
+    u16::from_le_bytes([peek(bus, lo), peek(bus, hi)])
+}
+
+/// Whether `opcode` is one of the 151 opcodes documented for the NMOS 6502.
+#[rustfmt::skip]
+pub const fn is_documented(opcode: u8) -> bool {
+    matches!(
+        opcode,
+        0x00 | 0x01 | 0x05 | 0x06 | 0x08 | 0x09 | 0x0A | 0x0D | 0x0E
+            | 0x10 | 0x11 | 0x15 | 0x16 | 0x18 | 0x19 | 0x1D | 0x1E
+            | 0x20 | 0x21 | 0x24 | 0x25 | 0x26 | 0x28 | 0x29 | 0x2A | 0x2C | 0x2D | 0x2E
+            | 0x30 | 0x31 | 0x35 | 0x36 | 0x38 | 0x39 | 0x3D | 0x3E
+            | 0x40 | 0x41 | 0x45 | 0x46 | 0x48 | 0x49 | 0x4A | 0x4C | 0x4D | 0x4E
+            | 0x50 | 0x51 | 0x55 | 0x56 | 0x58 | 0x59 | 0x5D | 0x5E
+            | 0x60 | 0x61 | 0x65 | 0x66 | 0x68 | 0x69 | 0x6A | 0x6C | 0x6D | 0x6E
+            | 0x70 | 0x71 | 0x75 | 0x76 | 0x78 | 0x79 | 0x7D | 0x7E
+            | 0x81 | 0x84 | 0x85 | 0x86 | 0x88 | 0x8A | 0x8C | 0x8D | 0x8E
+            | 0x90 | 0x91 | 0x94 | 0x95 | 0x96 | 0x98 | 0x99 | 0x9A | 0x9D
+            | 0xA0 | 0xA1 | 0xA2 | 0xA4 | 0xA5 | 0xA6 | 0xA8 | 0xA9 | 0xAA | 0xAC | 0xAD | 0xAE
+            | 0xB0 | 0xB1 | 0xB4 | 0xB5 | 0xB6 | 0xB8 | 0xB9 | 0xBA | 0xBC | 0xBD | 0xBE
+            | 0xC0 | 0xC1 | 0xC4 | 0xC5 | 0xC6 | 0xC8 | 0xC9 | 0xCA | 0xCC | 0xCD | 0xCE
+            | 0xD0 | 0xD1 | 0xD5 | 0xD6 | 0xD8 | 0xD9 | 0xDD | 0xDE
+            | 0xE0 | 0xE1 | 0xE4 | 0xE5 | 0xE6 | 0xE8 | 0xE9 | 0xEA | 0xEC | 0xED | 0xEE
+            | 0xF0 | 0xF1 | 0xF5 | 0xF6 | 0xF8 | 0xF9 | 0xFD | 0xFE
+    )
+}
+
+/// Forwards to `out` while counting the characters written, so the column can be padded.
+struct Counted<'a, W: ?Sized> {
+    out: &'a mut W,
+    written: usize,
+}
+
+impl<W: Write + ?Sized> Write for Counted<'_, W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.written += s.chars().count();
+        self.out.write_str(s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::SimpleBus;
+    use crate::instruction::mos6502::Mos6502;
+    use ull::{Bus, Byte};
+
+    fn traced(program: &[u8], setup: impl FnOnce(&mut Cpu<SimpleBus>, &mut SimpleBus)) -> String {
+        let mut bus = SimpleBus::default();
+        let mut cpu: Cpu<SimpleBus> =
+            Cpu::with_program::<Mos6502>(&mut bus, Word(0xE000), program, Word(0xE000));
+        setup(&mut cpu, &mut bus);
+        trace_line(&cpu, &mut bus)
+    }
+
+    #[test]
+    fn marks_undocumented_opcodes_and_uses_nestest_names() {
+        let line = traced(&[0xE3, 0x45], |cpu, bus| {
+            cpu.x = Byte(0x02);
+            bus.write(Word(0x0047), Byte(0x47), AccessType::DataWrite);
+            bus.write(Word(0x0048), Byte(0x06), AccessType::DataWrite);
+            bus.write(Word(0x0647), Byte(0xEB), AccessType::DataWrite);
+        });
+        assert_eq!(
+            line,
+            "E000  E3 45    *ISB ($45,X) @ 47 = 0647 = EB    A:00 X:02 Y:00 P:24 SP:FD CYC:0"
+        );
+    }
+
+    #[test]
+    fn annotates_indexed_and_indirect_operands() {
+        let line = traced(&[0xB1, 0x89], |cpu, bus| {
+            cpu.y = Byte(0x10);
+            bus.write(Word(0x0089), Byte(0xF8), AccessType::DataWrite);
+            bus.write(Word(0x008A), Byte(0x02), AccessType::DataWrite);
+            bus.write(Word(0x0308), Byte(0x42), AccessType::DataWrite);
+        });
+        assert_eq!(
+            line,
+            "E000  B1 89     LDA ($89),Y = 02F8 @ 0308 = 42  A:00 X:00 Y:10 P:24 SP:FD CYC:0"
+        );
+
+        // The NMOS indirect JMP reads the high byte from the start of the same page.
+        let line = traced(&[0x6C, 0xFF, 0x02], |_, bus| {
+            bus.write(Word(0x02FF), Byte(0x34), AccessType::DataWrite);
+            bus.write(Word(0x0200), Byte(0x12), AccessType::DataWrite);
+        });
+        assert!(line.starts_with("E000  6C FF 02  JMP ($02FF) = 1234   "));
+    }
+
+    #[test]
+    fn documented_set_has_151_opcodes() {
+        assert_eq!((0..=255u8).filter(|&op| is_documented(op)).count(), 151);
+    }
+}
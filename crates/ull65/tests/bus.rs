@@ -1,8 +1,19 @@
+use std::cell::RefCell;
 use std::collections::VecDeque;
+use std::rc::Rc;
 use ull::{Address, Bus, Byte, DmaRequest, DmaResult, Word};
 use ull65::instruction::mos6502::Mos6502;
+use ull65::instruction::wdc65c02s::Wdc65c02s;
+use ull65::InstructionSet;
 use ull65::processor::cpu::Cpu;
-use ull65::{AccessType, ResetVectorExt};
+use ull65::bus::banked::IoHandler;
+use ull65::bus::Mos6502CompatibleBus;
+use ull65::bus::simple_bus::RomWrite;
+use ull65::processor::disasm::disassemble;
+use ull65::{
+    AccessType, BankedBus, CyclePhase, Error, Phase, ResetVectorExt, RunConfig, SimpleBus,
+    SliceBus, TestingBus, TrapBus,
+};
 
 struct TestBus {
     mem: [u8; 0x10000],
@@ -63,6 +74,8 @@ impl Bus for TestBus {
     }
 }
 
+impl Mos6502CompatibleBus for TestBus {}
+
 #[test]
 fn dma_cycles_are_accounted() {
     let mut bus = TestBus::default();
@@ -94,6 +107,61 @@ fn dma_cycles_are_accounted() {
     assert_eq!(bus.dma_ticks, 4);
 }
 
+#[test]
+fn dma_copy_moves_a_region_through_read_and_write() {
+    let mut bus = TestingBus::default();
+    let source: Vec<u8> = (0..=0xFF).collect();
+    bus.write_block(Word(0x0300), &source, AccessType::DataWrite);
+    bus.enable_logging();
+
+    let cycles = bus.dma_copy(
+        Word(0x0300),
+        Word(0x2000),
+        0x100,
+        AccessType::DmaRead,
+        AccessType::DmaWrite,
+    );
+
+    assert_eq!(cycles, 0x200);
+    let mut copied = [0u8; 0x100];
+    bus.read_block(Word(0x2000), &mut copied, AccessType::DataRead);
+    assert_eq!(copied.as_slice(), source.as_slice());
+    let log = bus.take_log();
+    assert_eq!(log.len(), 0x200 + 0x100);
+    assert_eq!(
+        log[..2],
+        [
+            (Word(0x0300), AccessType::DmaRead, Byte(0x00), Phase::Read),
+            (Word(0x2000), AccessType::DmaWrite, Byte(0x00), Phase::Write),
+        ]
+    );
+}
+
+#[test]
+fn read_cstr_stops_at_the_terminator_or_max() {
+    let mut bus = SimpleBus::default();
+    bus.load(Word(0x0300), b"HELLO\0WORLD");
+    // "HI" with the high bit set, as the Apple I stores text.
+    bus.load(Word(0x0400), &[0xC8, 0xC9, 0x80]);
+
+    assert_eq!(
+        bus.read_cstr(Word(0x0300), 32, false, AccessType::DataRead),
+        ("HELLO".to_string(), 6)
+    );
+    assert_eq!(
+        bus.read_cstr(Word(0x0306), 5, false, AccessType::DataRead),
+        ("WORLD".to_string(), 5)
+    );
+    assert_eq!(
+        bus.read_cstr(Word(0x0400), 8, true, AccessType::DataRead),
+        ("HI".to_string(), 3)
+    );
+    assert_eq!(
+        bus.read_cstr(Word(0x0400), 8, false, AccessType::DataRead),
+        ("\u{C8}\u{C9}\u{80}".to_string(), 4)
+    );
+}
+
 #[test]
 fn read_block_fetches_consecutive_bytes() {
     let mut bus = TestBus::default();
@@ -111,3 +179,697 @@ fn write_block_stores_consecutive_bytes() {
     assert_eq!(bus.read(Word(0x9001), AccessType::DataRead).0, 0xBB);
     assert_eq!(bus.read(Word(0x9002), AccessType::DataRead).0, 0xCC);
 }
+
+#[test]
+fn fill_block_writes_constant_value() {
+    let mut bus = TestBus::default();
+    bus.fill_block(Word(0x0300), 0x100, Byte(0xEA), AccessType::DataWrite);
+    assert_eq!(bus.read(Word(0x02FF), AccessType::DataRead).0, 0x00);
+    assert_eq!(bus.read(Word(0x0300), AccessType::DataRead).0, 0xEA);
+    assert_eq!(bus.read(Word(0x03FF), AccessType::DataRead).0, 0xEA);
+    assert_eq!(bus.read(Word(0x0400), AccessType::DataRead).0, 0x00);
+}
+
+#[test]
+fn simple_bus_fill_block_wraps_at_top_of_memory() {
+    let mut bus = SimpleBus::default();
+    bus.fill_block(Word(0xFFFE), 4, Byte(0x55), AccessType::DataWrite);
+    let mut buf = [0u8; 4];
+    bus.read_block(Word(0xFFFE), &mut buf, AccessType::DataRead);
+    assert_eq!(buf, [0x55; 4]);
+    assert_eq!(bus.read(Word(0x0002), AccessType::DataRead).0, 0x00);
+}
+
+#[test]
+fn simple_bus_read16_and_write16_are_little_endian() {
+    let mut bus = SimpleBus::default();
+    bus.write16(Word(0x1234), Word(0xBEEF), AccessType::DataWrite);
+    assert_eq!(bus.read(Word(0x1234), AccessType::DataRead).0, 0xEF);
+    assert_eq!(bus.read(Word(0x1235), AccessType::DataRead).0, 0xBE);
+    assert_eq!(bus.read16(Word(0x1234), AccessType::DataRead), Word(0xBEEF));
+
+    bus.write16(Word(0x2000), 0x1234u16, AccessType::DataWrite);
+    assert_eq!(bus.read16(Word(0x2000), AccessType::DataRead), Word(0x1234));
+}
+
+#[test]
+fn simple_bus_mirrors_its_64k_into_every_bank() {
+    let mut bus = SimpleBus::default();
+    bus.write(0x12_3456usize, Byte(0xAB), AccessType::DataWrite);
+    assert_eq!(bus.read(Word(0x3456), AccessType::DataRead), Byte(0xAB));
+    assert_eq!(bus.read(0xFF_3456usize, AccessType::DataRead), Byte(0xAB));
+}
+
+#[cfg(feature = "wdc65c816")]
+#[test]
+fn cpu816_on_simple_bus_reaches_past_bank_zero() {
+    use ull65::processor::cpu816::Cpu816;
+
+    let mut bus = SimpleBus::default();
+    // LDA $123456; CLC; XCE; LDX #$0002 (16-bit after REP); LDA $FFFF,X
+    bus.load(
+        Word(0x8000),
+        &[
+            0xAF, 0x56, 0x34, 0x12, 0x18, 0xFB, 0xC2, 0x10, 0xA2, 0x02, 0x00, 0xBD, 0xFF, 0xFF,
+        ],
+    );
+    bus.load(Word(0x3456), &[0x42]);
+    bus.load(Word(0x0001), &[0x99]);
+    bus.set_reset_vector(Word(0x8000));
+
+    let mut cpu: Cpu816<SimpleBus> = Cpu816::new();
+    cpu.reset(&mut bus);
+    cpu.step(&mut bus);
+    assert_eq!(cpu.a.lo(), Byte(0x42));
+
+    for _ in 0..5 {
+        cpu.step(&mut bus);
+    }
+    // $00:FFFF + 2 carries into bank 1, which mirrors $0001.
+    assert_eq!(cpu.a.lo(), Byte(0x99));
+}
+
+#[test]
+fn simple_bus_read16_wraps_with_the_address_type() {
+    let mut bus = SimpleBus::default();
+    bus.write(Word(0xFFFF), Byte(0x34), AccessType::DataWrite);
+    bus.write(Word(0x0000), Byte(0x12), AccessType::DataWrite);
+    bus.write(Word(0x0100), Byte(0x56), AccessType::DataWrite);
+    assert_eq!(bus.read16(Word(0xFFFF), AccessType::DataRead), Word(0x1234));
+
+    // A zero-page pointer at $FF takes its high byte from $00, not $0100.
+    bus.write(Word(0x00FF), Byte(0x78), AccessType::DataWrite);
+    assert_eq!(bus.read16(Byte(0xFF), AccessType::DataRead), Word(0x1278));
+
+    bus.write16(Byte(0xFF), Word(0xABCD), AccessType::DataWrite);
+    assert_eq!(bus.read(Word(0x00FF), AccessType::DataRead).0, 0xCD);
+    assert_eq!(bus.read(Word(0x0000), AccessType::DataRead).0, 0xAB);
+    assert_eq!(bus.read(Word(0x0100), AccessType::DataRead).0, 0x56);
+}
+
+#[test]
+fn trap_bus_routes_magic_addresses_to_host() {
+    const GETCHAR: Word = Word(0xF004);
+    const PUTCHAR: Word = Word(0xF001);
+
+    let mut bus = TrapBus::new(SimpleBus::default());
+    let mut input = b"ok".iter().copied();
+    bus.on_read_trap(GETCHAR, move |_inner| Byte(input.next().unwrap_or(0)));
+    let output = Rc::new(RefCell::new(Vec::new()));
+    let sink = Rc::clone(&output);
+    bus.on_write_trap(PUTCHAR, move |_inner, byte| sink.borrow_mut().push(byte.0));
+
+    // loop: LDA $F004; BEQ done; STA $F001; JMP loop; done: BRK
+    let program = [
+        0xAD, 0x04, 0xF0, 0xF0, 0x06, 0x8D, 0x01, 0xF0, 0x4C, 0x00, 0x80, 0x00,
+    ];
+    let mut cpu: Cpu<TrapBus<SimpleBus>> =
+        Cpu::with_program::<Mos6502>(&mut bus, Word(0x8000), &program, Word(0x8000));
+    let summary = cpu.run_until(
+        &mut bus,
+        RunConfig {
+            stop_on_brk: true,
+            instruction_limit: Some(32),
+            ..RunConfig::default()
+        },
+    );
+
+    assert!(summary.hit_brk());
+    assert_eq!(output.borrow().as_slice(), b"ok");
+    assert_eq!(bus.inner_mut().read(PUTCHAR, AccessType::DataRead).0, 0x00);
+    assert_eq!(bus.read(PUTCHAR, AccessType::OpcodeFetch).0, 0x00);
+
+    assert!(bus.remove_write_trap(PUTCHAR).is_some());
+    bus.write(PUTCHAR, Byte(0x41), AccessType::DataWrite);
+    assert_eq!(bus.inner_mut().read(PUTCHAR, AccessType::DataRead).0, 0x41);
+}
+
+#[test]
+fn simple_bus_try_load_rejects_images_that_wrap() {
+    let mut bus = SimpleBus::default();
+
+    assert_eq!(bus.try_load(Word(0xFFFE), &[0x01, 0x02]), Ok(()));
+    assert_eq!(bus.read(Word(0xFFFF), AccessType::DataRead).0, 0x02);
+
+    let err = bus.try_load(Word(0xFFFF), &[0xAA, 0xBB]).unwrap_err();
+    assert_eq!(
+        err,
+        Error::OutOfBounds {
+            start: 0xFFFF,
+            len: 2,
+            limit: 0x10000
+        }
+    );
+    assert_eq!(err.to_string(), "2 bytes at $FFFF exceed the $10000-byte address space");
+    assert_eq!(bus.read(Word(0xFFFF), AccessType::DataRead).0, 0x02);
+    assert_eq!(bus.read(Word(0x0000), AccessType::DataRead).0, 0x00);
+}
+
+#[test]
+fn banked_bus_switches_rom_banks_and_mirrors_ram() {
+    let mut bus = BankedBus::new();
+    let ram = bus.add_bank(vec![0; 0x800]);
+    bus.map_bank(0x0000..=0x1FFF, ram, 0, false);
+    let bank0 = bus.map_rom(0x8000..=0xBFFF, vec![0x00; 0x4000]);
+    let bank1 = bus.add_bank(vec![0x11; 0x4000]);
+
+    bus.write(Word(0x0801), Byte(0x42), AccessType::DataWrite);
+    assert_eq!(bus.read(Word(0x0001), AccessType::DataRead).0, 0x42);
+    assert_eq!(bus.read(Word(0x1801), AccessType::DataRead).0, 0x42);
+
+    assert_eq!(bus.read(Word(0x8000), AccessType::DataRead).0, 0x00);
+    bus.map_bank(0x8000..=0xBFFF, bank1, 0, true);
+    assert_eq!(bus.read(Word(0x8000), AccessType::DataRead).0, 0x11);
+    bus.map_bank(0x8000..=0xBFFF, bank0, 0, true);
+    assert_eq!(bus.read(Word(0x8000), AccessType::DataRead).0, 0x00);
+
+    assert_eq!(bus.read(Word(0x4000), AccessType::DataRead), BankedBus::UNMAPPED);
+}
+
+#[test]
+fn banked_bus_ignores_writes_to_rom() {
+    let mut bus = BankedBus::new();
+    let rom = bus.map_rom(0xF000..=0xFFFF, vec![0xEA; 0x1000]);
+
+    bus.write(Word(0xF123), Byte(0x00), AccessType::DataWrite);
+
+    assert_eq!(bus.read(Word(0xF123), AccessType::DataRead).0, 0xEA);
+    assert_eq!(bus.bank(rom)[0x123], 0xEA);
+}
+
+#[test]
+fn banked_bus_routes_io_ranges_to_handlers() {
+    struct Latch(Rc<RefCell<Vec<(u16, u8)>>>);
+
+    impl IoHandler for Latch {
+        fn read(&mut self, offset: u16, _access: AccessType) -> Byte {
+            Byte(0xD0 | offset as u8)
+        }
+
+        fn write(&mut self, offset: u16, value: Byte, _access: AccessType) {
+            self.0.borrow_mut().push((offset, value.0));
+        }
+    }
+
+    let writes = Rc::new(RefCell::new(Vec::new()));
+    let mut bus = BankedBus::new();
+    bus.map_ram(0x0000..=0xFFFF);
+    bus.map_io(0xD010..=0xD013, Latch(writes.clone()));
+
+    assert_eq!(bus.read(Word(0xD012), AccessType::DataRead).0, 0xD2);
+    bus.write(Word(0xD013), Byte(0x7F), AccessType::DataWrite);
+    bus.write(Word(0xD014), Byte(0x55), AccessType::DataWrite);
+
+    assert_eq!(writes.borrow().as_slice(), &[(3, 0x7F)]);
+    assert_eq!(bus.read(Word(0xD014), AccessType::DataRead).0, 0x55);
+}
+
+#[test]
+fn banked_bus_io_handlers_see_the_access_type() {
+    /// A status register that clears when the CPU reads it.
+    struct Status(u8);
+
+    impl IoHandler for Status {
+        fn read(&mut self, _offset: u16, access: AccessType) -> Byte {
+            let value = Byte(self.0);
+            if access != AccessType::Peek {
+                self.0 = 0;
+            }
+            value
+        }
+
+        fn write(&mut self, _offset: u16, value: Byte, _access: AccessType) {
+            self.0 = value.0;
+        }
+    }
+
+    let mut bus = BankedBus::new();
+    bus.map_io(0xD000..=0xD000, Status(0x80));
+
+    assert_eq!(bus.read(Word(0xD000), AccessType::Peek).0, 0x80);
+    assert_eq!(bus.read(Word(0xD000), AccessType::Peek).0, 0x80);
+    assert_eq!(bus.read(Word(0xD000), AccessType::DataRead).0, 0x80);
+    assert_eq!(bus.read(Word(0xD000), AccessType::DataRead).0, 0x00);
+}
+
+#[test]
+fn banked_bus_drops_io_handlers_hidden_by_later_mappings() {
+    struct Device(Rc<()>);
+
+    impl IoHandler for Device {
+        fn read(&mut self, _offset: u16, _access: AccessType) -> Byte {
+            Byte(Rc::strong_count(&self.0) as u8)
+        }
+
+        fn write(&mut self, _offset: u16, _value: Byte, _access: AccessType) {}
+    }
+
+    let alive = Rc::new(());
+    let mut bus = BankedBus::new();
+    bus.map_io(0xD000..=0xD0FF, Device(alive.clone()));
+    bus.map_io(0xD000..=0xD0FF, Device(alive.clone()));
+    assert_eq!(Rc::strong_count(&alive), 2);
+
+    // Partly covered: the second device is still reachable at $D080-$D0FF.
+    bus.map_ram(0xD000..=0xD07F);
+    assert_eq!(Rc::strong_count(&alive), 2);
+    bus.map_ram(0xD080..=0xD0FF);
+    assert_eq!(Rc::strong_count(&alive), 1);
+
+    // The freed slot is reused.
+    bus.map_io(0xD100..=0xD1FF, Device(alive.clone()));
+    assert_eq!(format!("{bus:?}").matches("io: 1").count(), 1);
+}
+
+#[test]
+fn io_hooks_map_a_counter_register_in_front_of_memory() {
+    /// RAM with a counter at $D000: reading it returns the next count, writing it reloads the
+    /// counter. Only CPU data accesses count, so debugger peeks don't disturb it.
+    struct CounterBus {
+        ram: SimpleBus,
+        counter: u8,
+    }
+
+    impl Bus for CounterBus {
+        type Access = AccessType;
+        type Data = Byte;
+
+        fn read<A>(&mut self, addr: A, access: AccessType) -> Byte
+        where
+            A: Address,
+        {
+            self.ram.read(addr, access)
+        }
+
+        fn write<A, V>(&mut self, addr: A, value: V, access: AccessType)
+        where
+            A: Address,
+            V: Into<Self::Data>,
+        {
+            self.ram.write(addr, value, access);
+        }
+    }
+
+    impl Mos6502CompatibleBus for CounterBus {
+        fn on_read_io(&mut self, addr: Word, access: AccessType) -> Option<Byte> {
+            if addr != Word(0xD000) || access != AccessType::DataRead {
+                return None;
+            }
+            self.counter = self.counter.wrapping_add(1);
+            Some(Byte(self.counter))
+        }
+
+        fn on_write_io(&mut self, addr: Word, value: Byte, _access: AccessType) -> bool {
+            if addr != Word(0xD000) {
+                return false;
+            }
+            self.counter = value.0;
+            true
+        }
+    }
+
+    let mut bus = CounterBus {
+        ram: SimpleBus::default(),
+        counter: 0,
+    };
+    // LDA #$10; STA $D000; LDX $D000; LDY $D000; STY $D001; BRK
+    let program = [
+        0xA9, 0x10, 0x8D, 0x00, 0xD0, 0xAE, 0x00, 0xD0, 0xAC, 0x00, 0xD0, 0x8C, 0x01, 0xD0, 0x00,
+    ];
+    let mut cpu: Cpu<CounterBus> =
+        Cpu::with_program::<Mos6502>(&mut bus, Word(0x8000), &program, Word(0x8000));
+    cpu.run_until(&mut bus, RunConfig::builder().stop_on_brk());
+
+    assert_eq!((cpu.x.0, cpu.y.0), (0x11, 0x12));
+    assert_eq!(bus.counter, 0x12);
+    assert_eq!(bus.cpu_read(Word(0xD000), AccessType::Peek).0, 0x00);
+    assert_eq!(bus.cpu_read(Word(0xD001), AccessType::DataRead).0, 0x12);
+}
+
+#[test]
+fn simple_bus_drops_writes_to_readonly_ranges() {
+    let mut bus = SimpleBus::default();
+    bus.load(Word(0xF000), &[0xA9, 0x42]);
+    bus.mark_readonly(0xF000..=0xFFFF);
+
+    bus.write(Word(0xF000), Byte(0x00), AccessType::DataWrite);
+    bus.write_block(Word(0xEFFF), &[0x11, 0x22], AccessType::DataWrite);
+    bus.fill_block(Word(0xFFFF), 2, Byte(0x33), AccessType::DataWrite);
+
+    assert_eq!(bus.read(Word(0xF000), AccessType::DataRead).0, 0xA9);
+    assert_eq!(bus.read(Word(0xF001), AccessType::DataRead).0, 0x42);
+    assert_eq!(bus.read(Word(0xFFFF), AccessType::DataRead).0, 0x00);
+    // Neighbouring RAM is still writable.
+    assert_eq!(bus.read(Word(0xEFFF), AccessType::DataRead).0, 0x11);
+    assert_eq!(bus.read(Word(0x0000), AccessType::DataRead).0, 0x33);
+    assert_eq!(bus.ignored_writes(), 3);
+}
+
+#[test]
+fn simple_bus_readonly_ranges_need_not_be_page_aligned() {
+    let mut bus = SimpleBus::default();
+    bus.mark_readonly(0x80F0..=0x810F);
+
+    bus.fill_block(Word(0x80EF), 0x22, Byte(0x5A), AccessType::DataWrite);
+
+    assert_eq!(bus.read(Word(0x80EF), AccessType::DataRead).0, 0x5A);
+    assert_eq!(bus.read(Word(0x80F0), AccessType::DataRead).0, 0x00);
+    assert_eq!(bus.read(Word(0x810F), AccessType::DataRead).0, 0x00);
+    assert_eq!(bus.read(Word(0x8110), AccessType::DataRead).0, 0x5A);
+    assert_eq!(bus.ignored_writes(), 0x20);
+}
+
+#[test]
+fn simple_bus_records_trapped_rom_writes() {
+    let mut bus = SimpleBus::default();
+    bus.mark_readonly(0xF000..=0xFFFF);
+    bus.mark_unmapped(0x4000..=0x7FFF);
+
+    // Untrapped writes are only counted.
+    bus.write(Word(0xF000), Byte(0x01), AccessType::DataWrite);
+    assert_eq!(bus.take_rom_write(), None);
+
+    bus.set_trap_rom_writes(true);
+    bus.write(Word(0x4000), Byte(0x02), AccessType::DataWrite);
+    bus.write(Word(0x0200), Byte(0x03), AccessType::DataWrite);
+    assert_eq!(bus.take_rom_write(), None);
+
+    bus.write_block(Word(0xFFFE), &[0x04, 0x05], AccessType::DataWrite);
+    assert_eq!(
+        bus.take_rom_write(),
+        Some(RomWrite {
+            addr: Word(0xFFFE),
+            value: Byte(0x04),
+        })
+    );
+    assert_eq!(bus.take_rom_write(), None);
+    assert_eq!(bus.read(Word(0xFFFE), AccessType::DataRead).0, 0x00);
+    assert_eq!(bus.ignored_writes(), 4);
+
+    bus.set_trap_rom_writes(false);
+    bus.write(Word(0xF000), Byte(0x06), AccessType::DataWrite);
+    assert_eq!(bus.take_rom_write(), None);
+}
+
+#[test]
+fn simple_bus_records_the_result_of_an_nmos_rmw_on_rom() {
+    let mut bus = SimpleBus::default();
+    bus.load(Word(0xF000), &[0x41]);
+    // INC $F000 writes $41 back (the dummy write), then $42.
+    let mut cpu: Cpu<SimpleBus> =
+        Cpu::with_program::<Mos6502>(&mut bus, Word(0x8000), &[0xEE, 0x00, 0xF0], Word(0x8000));
+    bus.mark_readonly(0xF000..=0xFFFF);
+    bus.set_trap_rom_writes(true);
+
+    cpu.step(&mut bus);
+
+    assert_eq!(
+        bus.take_rom_write(),
+        Some(RomWrite {
+            addr: Word(0xF000),
+            value: Byte(0x42),
+        })
+    );
+    // Both the dummy write and the real one were dropped.
+    assert_eq!(bus.ignored_writes(), 2);
+    assert_eq!(bus.read(Word(0xF000), AccessType::DataRead).0, 0x41);
+}
+
+#[test]
+fn simple_bus_open_bus_returns_the_last_value_on_the_bus() {
+    let mut bus = SimpleBus::default();
+    bus.mark_unmapped(0x4000..=0x7FFF);
+    bus.load(Word(0x0200), &[0x5A]);
+
+    bus.write(Word(0x4000), Byte(0x99), AccessType::DataWrite);
+    assert_eq!(bus.read(Word(0x4000), AccessType::DataRead).0, 0x00);
+    assert_eq!(bus.ignored_writes(), 1);
+
+    bus.set_open_bus(true);
+    bus.read(Word(0x0200), AccessType::DataRead);
+    assert_eq!(bus.read(Word(0x4000), AccessType::DataRead).0, 0x5A);
+    bus.write(Word(0x0300), Byte(0xC3), AccessType::DataWrite);
+    assert_eq!(bus.read(Word(0x7FFF), AccessType::DataRead).0, 0xC3);
+    // Mapped memory is unaffected.
+    assert_eq!(bus.read(Word(0x0300), AccessType::DataRead).0, 0xC3);
+    assert_eq!(bus.read(Word(0x8000), AccessType::DataRead).0, 0x00);
+}
+
+#[test]
+fn simple_bus_peeks_leave_the_open_bus_value_alone() {
+    let mut bus = SimpleBus::default();
+    bus.mark_unmapped(0x4000..=0x7FFF);
+    bus.set_open_bus(true);
+    bus.load(Word(0x0200), &[0x5A, 0xA5]);
+
+    bus.read(Word(0x0200), AccessType::DataRead);
+    let mut peeked = [0; 2];
+    bus.read_block(Word(0x0200), &mut peeked, AccessType::Peek);
+    assert_eq!(peeked, [0x5A, 0xA5]);
+    assert_eq!(bus.read(Word(0x0201), AccessType::Peek).0, 0xA5);
+    assert_eq!(bus.read(Word(0x4000), AccessType::DataRead).0, 0x5A);
+
+    // A disassembler on a live bus sees memory without disturbing the next CPU read.
+    let table = Mos6502::base_table::<SimpleBus>();
+    disassemble(&table, &mut bus, Word(0x0201));
+    assert_eq!(bus.read(Word(0x4000), AccessType::DataRead).0, 0x5A);
+}
+
+/// Addresses and access types of every bus access one instruction makes with X = Y = 1.
+fn indexed_read_accesses<S: InstructionSet>(program: &[u8]) -> Vec<(u16, AccessType)> {
+    let mut bus = TestingBus::default();
+    // ($10),Y pointer to $12FF
+    bus.write_block(Word(0x0010), &[0xFF, 0x12], AccessType::DataWrite);
+    let mut cpu: Cpu<TestingBus> =
+        Cpu::with_program::<S>(&mut bus, Word(0x8000), program, Word(0x8000));
+    cpu.x = Byte(1);
+    cpu.y = Byte(1);
+    bus.enable_logging();
+    cpu.step(&mut bus);
+    bus.take_log()
+        .into_iter()
+        .map(|(addr, access, ..)| (addr.0, access))
+        .collect()
+}
+
+#[test]
+fn nmos_indexed_reads_touch_the_unfixed_address_on_page_cross() {
+    use AccessType::{DataRead, DummyRead, OpcodeFetch};
+
+    // LDA $12FF,X
+    assert_eq!(
+        indexed_read_accesses::<Mos6502>(&[0xBD, 0xFF, 0x12]),
+        [
+            (0x8000, OpcodeFetch),
+            (0x8001, DataRead),
+            (0x8002, DataRead),
+            (0x1200, DummyRead),
+            (0x1300, DataRead)
+        ]
+    );
+    // LDA ($10),Y
+    assert_eq!(
+        indexed_read_accesses::<Mos6502>(&[0xB1, 0x10]),
+        [
+            (0x8000, OpcodeFetch),
+            (0x8001, DataRead),
+            (0x0010, DataRead),
+            (0x0011, DataRead),
+            (0x1200, DummyRead),
+            (0x1300, DataRead)
+        ]
+    );
+    // LDA $1200,X stays on its page, so there's no extra cycle.
+    assert_eq!(
+        indexed_read_accesses::<Mos6502>(&[0xBD, 0x00, 0x12]),
+        [
+            (0x8000, OpcodeFetch),
+            (0x8001, DataRead),
+            (0x8002, DataRead),
+            (0x1201, DataRead)
+        ]
+    );
+}
+
+#[test]
+fn cmos_indexed_reads_reread_the_last_operand_byte_on_page_cross() {
+    use AccessType::{DataRead, DummyRead, OpcodeFetch};
+
+    // LDA $12FF,X
+    assert_eq!(
+        indexed_read_accesses::<Wdc65c02s>(&[0xBD, 0xFF, 0x12]),
+        [
+            (0x8000, OpcodeFetch),
+            (0x8001, DataRead),
+            (0x8002, DataRead),
+            (0x8002, DummyRead),
+            (0x1300, DataRead)
+        ]
+    );
+    // ADC $12FF,Y
+    assert_eq!(
+        indexed_read_accesses::<Wdc65c02s>(&[0x79, 0xFF, 0x12]),
+        [
+            (0x8000, OpcodeFetch),
+            (0x8001, DataRead),
+            (0x8002, DataRead),
+            (0x8002, DummyRead),
+            (0x1300, DataRead)
+        ]
+    );
+    // LDA ($10),Y
+    assert_eq!(
+        indexed_read_accesses::<Wdc65c02s>(&[0xB1, 0x10]),
+        [
+            (0x8000, OpcodeFetch),
+            (0x8001, DataRead),
+            (0x0010, DataRead),
+            (0x0011, DataRead),
+            (0x8001, DummyRead),
+            (0x1300, DataRead)
+        ]
+    );
+    assert_eq!(
+        indexed_read_accesses::<Wdc65c02s>(&[0xBD, 0x00, 0x12]),
+        [
+            (0x8000, OpcodeFetch),
+            (0x8001, DataRead),
+            (0x8002, DataRead),
+            (0x1201, DataRead)
+        ]
+    );
+}
+
+#[test]
+fn testing_bus_logs_the_accesses_of_jsr() {
+    let mut bus = TestingBus::default();
+    let mut cpu: Cpu<TestingBus> =
+        Cpu::with_program::<Mos6502>(&mut bus, Word(0x8000), &[0x20, 0x00, 0x20], Word(0x8000));
+    bus.enable_logging();
+
+    cpu.step(&mut bus);
+
+    assert_eq!(
+        bus.take_log(),
+        vec![
+            (Word(0x8000), AccessType::OpcodeFetch, Byte(0x20), Phase::Read),
+            (Word(0x8001), AccessType::DataRead, Byte(0x00), Phase::Read),
+            (Word(0x8002), AccessType::DataRead, Byte(0x20), Phase::Read),
+            (Word(0x01FD), AccessType::StackWrite, Byte(0x80), Phase::Write),
+            (Word(0x01FC), AccessType::StackWrite, Byte(0x02), Phase::Write),
+        ]
+    );
+    assert_eq!(cpu.pc, Word(0x2000));
+    assert!(bus.take_log().is_empty());
+
+    bus.read(Word(0x2000), AccessType::DataRead);
+    bus.clear_log();
+    assert!(bus.take_log().is_empty());
+}
+
+#[test]
+fn slice_bus_runs_a_program_from_a_64k_slice() {
+    let mut mem = vec![0u8; 0x10000];
+    // LDX #$05; loop: TXA; STA $0200,X; DEX; BNE loop; BRK
+    let program = [0xA2, 0x05, 0x8A, 0x9D, 0x00, 0x02, 0xCA, 0xD0, 0xF9, 0x00];
+    mem[0x8000..0x8000 + program.len()].copy_from_slice(&program);
+    mem[0xFFFC..0xFFFE].copy_from_slice(&[0x00, 0x80]);
+
+    {
+        let mut bus = SliceBus(&mut mem);
+        let mut cpu: Cpu<SliceBus> = Cpu::with_instruction_set::<Mos6502>();
+        cpu.reset(&mut bus);
+        cpu.run_until(&mut bus, RunConfig::builder().stop_on_brk());
+    }
+
+    assert_eq!(&mem[0x0201..=0x0205], &[1, 2, 3, 4, 5]);
+    assert_eq!(mem[0x0200], 0);
+}
+
+#[test]
+fn slice_bus_mirrors_addresses_past_the_end_of_the_slice() {
+    let mut mem = [0u8; 0x4000];
+    let mut bus = SliceBus(&mut mem);
+
+    bus.write(Word(0xC123), Byte(0x5A), AccessType::DataWrite);
+
+    assert_eq!(bus.read(Word(0x0123), AccessType::DataRead), Byte(0x5A));
+    assert_eq!(bus.read(Word(0x4123), AccessType::DataRead), Byte(0x5A));
+}
+
+#[test]
+fn vector_setters_write_all_three_hardware_vectors() {
+    let mut bus = SimpleBus::default();
+    bus.set_nmi_vector(Word(0x1234));
+    bus.set_reset_vector(Word(0x8000));
+    bus.set_irq_vector(Word(0xABCD));
+
+    let vector = |bus: &mut SimpleBus, addr| bus.read16(Word(addr), AccessType::DataRead);
+    assert_eq!(vector(&mut bus, 0xFFFA), Word(0x1234));
+    assert_eq!(vector(&mut bus, 0xFFFC), Word(0x8000));
+    assert_eq!(vector(&mut bus, 0xFFFE), Word(0xABCD));
+    assert_eq!(bus.read(Word(0xFFFA), AccessType::DataRead), Byte(0x34));
+}
+
+#[test]
+fn cycle_and_step_pass_the_clock_phase_of_fetches_operands_and_loads() {
+    /// Flat RAM that records the address and phase of every phased read.
+    struct PhaseBus {
+        mem: Box<[u8; 0x10000]>,
+        phases: Vec<(u16, CyclePhase)>,
+    }
+
+    impl Bus for PhaseBus {
+        type Access = AccessType;
+        type Data = Byte;
+
+        fn read<A: Address>(&mut self, addr: A, _access: AccessType) -> Byte {
+            Byte(self.mem[addr.as_usize()])
+        }
+
+        fn read_phased<A: Address>(&mut self, addr: A, access: AccessType, phase: CyclePhase) -> Byte {
+            self.phases.push((addr.as_u16(), phase));
+            self.read(addr, access)
+        }
+
+        fn write<A: Address, V: Into<Byte>>(&mut self, addr: A, value: V, _access: AccessType) {
+            self.mem[addr.as_usize()] = value.into().0;
+        }
+    }
+
+    impl Mos6502CompatibleBus for PhaseBus {}
+
+    let mut bus = PhaseBus {
+        mem: Box::new([0; 0x10000]),
+        phases: Vec::new(),
+    };
+    // NOP; LDA $10; NOP; NOP
+    let program = [0xEA, 0xA5, 0x10, 0xEA, 0xEA];
+    let mut cpu: Cpu<PhaseBus> =
+        Cpu::with_program::<Mos6502>(&mut bus, Word(0x8000), &program, Word(0x8000));
+    assert_eq!(cpu.total_cycles(), 0);
+
+    // The fetches land on cycles 0, 2, 5 and 7; LDA reads its operand on 3 and $10 on 4.
+    while cpu.pc != Word(0x8005) {
+        cpu.cycle(&mut bus);
+    }
+
+    let expected = [
+        (0x8000, CyclePhase::Get),
+        (0x8001, CyclePhase::Get),
+        (0x8002, CyclePhase::Put),
+        (0x0010, CyclePhase::Get),
+        (0x8003, CyclePhase::Put),
+        (0x8004, CyclePhase::Put),
+    ];
+    assert_eq!(bus.phases, expected);
+
+    // Whole instructions tag the same accesses with the same phases.
+    bus.phases.clear();
+    let mut cpu: Cpu<PhaseBus> =
+        Cpu::with_program::<Mos6502>(&mut bus, Word(0x8000), &program, Word(0x8000));
+    while cpu.pc != Word(0x8005) {
+        cpu.step(&mut bus);
+    }
+    assert_eq!(bus.phases, expected);
+}
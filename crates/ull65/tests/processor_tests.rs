@@ -0,0 +1,211 @@
+//! Single-step cases in the TomHarte/ProcessorTests JSON format, run through `run_single`.
+//!
+//! The embedded samples always run. To run the full NMOS suite, point `PROCESSOR_TESTS_6502` at
+//! the `6502/v1` directory of a ProcessorTests checkout (one `xx.json` file per opcode); only the
+//! 151 documented opcodes are checked. Without the variable that test does nothing, so CI doesn't
+//! need the data; with it, finding no files is a failure.
+//!
+//! Besides registers and memory, each case's cycle count is checked against the `cycles` list
+//! and its writes are compared, in order, with the ones `run_single` recorded. The reads aren't:
+//! `run_single` logs the accesses the emulator models, and several dummy reads the suite lists per
+//! cycle (an indexed zero-page operand, a stack op's second cycle) have no modeled access.
+
+use serde_json::Value;
+use ull::{Byte, Word};
+use ull65::instruction::mos6502::Mos6502;
+use ull65::processor::exec::run_single;
+use ull65::processor::flags::Flags;
+use ull65::processor::trace::is_documented;
+use ull65::CpuState;
+
+/// `ADC #$50` with A = $50 (sets N and V), `ASL $10` (read-modify-write on zero page), `LDA $F0,X`
+/// (wraps within zero page) and `INC $12F0,X` (read-modify-write across a page).
+const SAMPLE: &str = r#"[
+    {
+        "name": "69 50 00",
+        "initial": { "pc": 4660, "s": 240, "a": 80, "x": 0, "y": 0, "p": 36,
+                     "ram": [[4660, 105], [4661, 80]] },
+        "final":   { "pc": 4662, "s": 240, "a": 160, "x": 0, "y": 0, "p": 228,
+                     "ram": [[4660, 105], [4661, 80]] },
+        "cycles": [[4660, 105, "read"], [4661, 80, "read"]]
+    },
+    {
+        "name": "06 10 ea",
+        "initial": { "pc": 512, "s": 253, "a": 0, "x": 0, "y": 0, "p": 32,
+                     "ram": [[512, 6], [513, 16], [16, 129]] },
+        "final":   { "pc": 514, "s": 253, "a": 0, "x": 0, "y": 0, "p": 33,
+                     "ram": [[512, 6], [513, 16], [16, 2]] },
+        "cycles": [[512, 6, "read"], [513, 16, "read"], [16, 129, "read"],
+                   [16, 129, "write"], [16, 2, "write"]]
+    },
+    {
+        "name": "b5 f0 ea",
+        "initial": { "pc": 768, "s": 253, "a": 0, "x": 32, "y": 0, "p": 36,
+                     "ram": [[768, 181], [769, 240], [770, 234], [240, 0], [16, 153]] },
+        "final":   { "pc": 770, "s": 253, "a": 153, "x": 32, "y": 0, "p": 164,
+                     "ram": [[768, 181], [769, 240], [770, 234], [240, 0], [16, 153]] },
+        "cycles": [[768, 181, "read"], [769, 240, "read"], [240, 0, "read"], [16, 153, "read"]]
+    },
+    {
+        "name": "fe f0 12",
+        "initial": { "pc": 1024, "s": 253, "a": 0, "x": 32, "y": 0, "p": 38,
+                     "ram": [[1024, 254], [1025, 240], [1026, 18], [4624, 0], [4880, 127]] },
+        "final":   { "pc": 1027, "s": 253, "a": 0, "x": 32, "y": 0, "p": 164,
+                     "ram": [[1024, 254], [1025, 240], [1026, 18], [4624, 0], [4880, 128]] },
+        "cycles": [[1024, 254, "read"], [1025, 240, "read"], [1026, 18, "read"],
+                   [4624, 0, "read"], [4880, 127, "read"], [4880, 127, "write"],
+                   [4880, 128, "write"]]
+    }
+]"#;
+
+/// B and bit 5 aren't stored in the status register; the suites fill them in arbitrarily.
+const P_MASK: u8 = !0x30;
+
+fn field(state: &Value, name: &str) -> u64 {
+    state[name]
+        .as_u64()
+        .unwrap_or_else(|| panic!("missing `{name}`"))
+}
+
+fn load(state: &Value, mem: &mut [u8; 0x10000]) -> CpuState {
+    for entry in state["ram"].as_array().expect("missing `ram`") {
+        mem[entry[0].as_u64().unwrap() as usize] = entry[1].as_u64().unwrap() as u8;
+    }
+
+    let mut cpu = CpuState::default();
+    cpu.a = Byte(field(state, "a") as u8);
+    cpu.x = Byte(field(state, "x") as u8);
+    cpu.y = Byte(field(state, "y") as u8);
+    cpu.p = Flags::from(Byte(field(state, "p") as u8));
+    cpu.sp = Byte(field(state, "s") as u8);
+    cpu.pc = Word(field(state, "pc") as u16);
+    cpu
+}
+
+/// Run one case, returning a description of the first mismatch.
+fn run_case(case: &Value) -> Result<(), String> {
+    let name = case["name"].as_str().unwrap_or("?");
+    let mut mem = Box::new([0u8; 0x10000]);
+    let initial = load(&case["initial"], &mut mem);
+
+    let (after, accesses) = run_single::<Mos6502>(initial, &mut mem);
+
+    let expected = &case["final"];
+    let registers = [
+        ("pc", u64::from(after.pc.0), field(expected, "pc")),
+        ("s", u64::from(after.sp.0), field(expected, "s")),
+        ("a", u64::from(after.a.0), field(expected, "a")),
+        ("x", u64::from(after.x.0), field(expected, "x")),
+        ("y", u64::from(after.y.0), field(expected, "y")),
+        (
+            "p",
+            u64::from(Byte::from(after.p).0 & P_MASK),
+            field(expected, "p") & u64::from(P_MASK),
+        ),
+    ];
+    for (register, actual, wanted) in registers {
+        if actual != wanted {
+            return Err(format!("{name}: {register} = {actual}, expected {wanted}"));
+        }
+    }
+
+    for entry in expected["ram"].as_array().expect("missing `ram`") {
+        let addr = entry[0].as_u64().unwrap() as usize;
+        let wanted = entry[1].as_u64().unwrap() as u8;
+        if mem[addr] != wanted {
+            return Err(format!(
+                "{name}: ${addr:04X} = {}, expected {wanted}",
+                mem[addr]
+            ));
+        }
+    }
+
+    let cycles = case["cycles"].as_array().map_or(0, Vec::len) as u64;
+    if after.cycles != cycles {
+        return Err(format!(
+            "{name}: took {} cycles, expected {cycles}",
+            after.cycles
+        ));
+    }
+
+    let wanted: Vec<(u16, u8)> = case["cycles"]
+        .as_array()
+        .expect("missing `cycles`")
+        .iter()
+        .filter(|entry| entry[2] == "write")
+        .map(|entry| {
+            (
+                entry[0].as_u64().unwrap() as u16,
+                entry[1].as_u64().unwrap() as u8,
+            )
+        })
+        .collect();
+    let actual: Vec<(u16, u8)> = accesses
+        .iter()
+        .filter(|access| access.access.is_write())
+        .map(|access| (access.addr.0, access.value.0))
+        .collect();
+    if actual != wanted {
+        return Err(format!("{name}: wrote {actual:?}, expected {wanted:?}"));
+    }
+
+    Ok(())
+}
+
+fn run_cases(json: &str) -> Vec<String> {
+    let cases: Value = serde_json::from_str(json).expect("malformed ProcessorTests JSON");
+    cases
+        .as_array()
+        .expect("expected an array of cases")
+        .iter()
+        .filter_map(|case| run_case(case).err())
+        .collect()
+}
+
+#[test]
+fn embedded_sample_cases_pass() {
+    let failures = run_cases(SAMPLE);
+    assert!(failures.is_empty(), "{failures:#?}");
+}
+
+#[test]
+fn mismatches_are_reported() {
+    let wrong = SAMPLE.replace(r#""a": 160"#, r#""a": 161"#);
+    assert_eq!(run_cases(&wrong), ["69 50 00: a = 160, expected 161"]);
+}
+
+#[test]
+fn write_mismatches_are_reported() {
+    let wrong = SAMPLE.replace(r#"[16, 129, "write"]"#, r#"[16, 128, "write"]"#);
+    assert_eq!(
+        run_cases(&wrong),
+        ["06 10 ea: wrote [(16, 129), (16, 2)], expected [(16, 128), (16, 2)]"]
+    );
+}
+
+#[test]
+fn processor_tests_suite_mos6502() {
+    let Some(dir) = std::env::var_os("PROCESSOR_TESTS_6502") else {
+        return;
+    };
+    let dir = std::path::PathBuf::from(dir);
+
+    let mut failures = Vec::new();
+    let mut files = 0;
+    for opcode in (0..=255u8).filter(|&op| is_documented(op)) {
+        let path = dir.join(format!("{opcode:02x}.json"));
+        let Ok(json) = std::fs::read_to_string(&path) else {
+            continue;
+        };
+        files += 1;
+        failures.extend(run_cases(&json));
+    }
+
+    assert!(files > 0, "no test files found in {}", dir.display());
+    assert!(
+        failures.is_empty(),
+        "{} failing cases, first: {:#?}",
+        failures.len(),
+        &failures[..failures.len().min(10)]
+    );
+}
@@ -0,0 +1,209 @@
+//! Single-step cases in the TomHarte/ProcessorTests 65816 JSON format, run through `Cpu816`.
+//!
+//! The embedded samples always run. To run the full suite, point `PROCESSOR_TESTS_65816` at the
+//! `65816/v1` directory of a ProcessorTests checkout (`xx.e.json` and `xx.n.json` per opcode, for
+//! emulation and native mode). `Cpu816` doesn't model the chip's per-cycle bus activity, so a
+//! case's `cycles` list is only checked for its length.
+#![cfg(feature = "wdc65c816")]
+
+use serde_json::Value;
+use ull::{Address, Bus, Byte, Word};
+use ull65::processor::cpu816::Cpu816;
+use ull65::processor::flags::Flags;
+use ull65::{AccessType, Mos6502CompatibleBus};
+
+/// `LDA $123456` with a 16-bit accumulator, then `LDA $FFFF,X` with 16-bit X = 2, which reads
+/// $01:0001 across the bank boundary.
+const SAMPLE: &str = r#"[
+    {
+        "name": "af n 1",
+        "initial": { "pc": 32768, "s": 511, "p": 16, "a": 0, "x": 0, "y": 0,
+                     "dbr": 0, "d": 0, "pbr": 0, "e": 0,
+                     "ram": [[32768, 175], [32769, 86], [32770, 52], [32771, 18],
+                             [1193046, 120], [1193047, 86]] },
+        "final":   { "pc": 32772, "s": 511, "p": 16, "a": 22136, "x": 0, "y": 0,
+                     "dbr": 0, "d": 0, "pbr": 0, "e": 0,
+                     "ram": [[32768, 175], [32769, 86], [32770, 52], [32771, 18],
+                             [1193046, 120], [1193047, 86]] },
+        "cycles": [[32768, 175, "dp-mx-r-"], [32769, 86, "-p-mx-r-"],
+                   [32770, 52, "-p-mx-r-"], [32771, 18, "-p-mx-r-"],
+                   [1193046, 120, "d--mx-r-"], [1193047, 86, "d--mx-r-"]]
+    },
+    {
+        "name": "bd n 1",
+        "initial": { "pc": 32768, "s": 511, "p": 32, "a": 4608, "x": 2, "y": 0,
+                     "dbr": 0, "d": 0, "pbr": 0, "e": 0,
+                     "ram": [[32768, 189], [32769, 255], [32770, 255], [65537, 153]] },
+        "final":   { "pc": 32771, "s": 511, "p": 160, "a": 4761, "x": 2, "y": 0,
+                     "dbr": 0, "d": 0, "pbr": 0, "e": 0,
+                     "ram": [[32768, 189], [32769, 255], [32770, 255], [65537, 153]] },
+        "cycles": [[32768, 189, "dp-mx-r-"], [32769, 255, "-p-mx-r-"],
+                   [32770, 255, "-p-mx-r-"], [65536, null, "---mx-r-"],
+                   [65537, 153, "d--mx-r-"]]
+    }
+]"#;
+
+/// Flat 16 MB memory so every bank is backed.
+struct LongBus {
+    mem: Vec<u8>,
+}
+
+impl Bus for LongBus {
+    type Access = AccessType;
+    type Data = Byte;
+
+    fn read<A: Address>(&mut self, addr: A, _access: AccessType) -> Byte {
+        Byte(self.mem[addr.as_usize() & 0xFF_FFFF])
+    }
+
+    fn write<A: Address, V: Into<Byte>>(&mut self, addr: A, value: V, _access: AccessType) {
+        self.mem[addr.as_usize() & 0xFF_FFFF] = value.into().0;
+    }
+}
+
+impl Mos6502CompatibleBus for LongBus {}
+
+fn field(state: &Value, name: &str) -> u64 {
+    state[name]
+        .as_u64()
+        .unwrap_or_else(|| panic!("missing `{name}`"))
+}
+
+fn ram(state: &Value) -> impl Iterator<Item = (usize, u8)> + '_ {
+    state["ram"]
+        .as_array()
+        .expect("missing `ram`")
+        .iter()
+        .map(|entry| {
+            (
+                entry[0].as_u64().unwrap() as usize,
+                entry[1].as_u64().unwrap() as u8,
+            )
+        })
+}
+
+/// Run one case, returning a description of the first mismatch.
+fn run_case(case: &Value, bus: &mut LongBus) -> Result<(), String> {
+    let name = case["name"].as_str().unwrap_or("?");
+    let initial = &case["initial"];
+    for (addr, value) in ram(initial) {
+        bus.mem[addr] = value;
+    }
+
+    let mut cpu: Cpu816<LongBus> = Cpu816::new();
+    cpu.emulation = field(initial, "e") != 0;
+    cpu.p = Flags::from(Byte(field(initial, "p") as u8));
+    cpu.a = Word(field(initial, "a") as u16);
+    cpu.x = Word(field(initial, "x") as u16);
+    cpu.y = Word(field(initial, "y") as u16);
+    cpu.sp = Word(field(initial, "s") as u16);
+    cpu.d = Word(field(initial, "d") as u16);
+    cpu.dbr = Byte(field(initial, "dbr") as u8);
+    cpu.pbr = Byte(field(initial, "pbr") as u8);
+    cpu.pc = Word(field(initial, "pc") as u16);
+
+    cpu.step(bus);
+
+    let expected = &case["final"];
+    let registers = [
+        ("pc", u64::from(cpu.pc.0), field(expected, "pc")),
+        ("s", u64::from(cpu.sp.0), field(expected, "s")),
+        ("p", u64::from(cpu.p.bits()), field(expected, "p")),
+        ("a", u64::from(cpu.a.0), field(expected, "a")),
+        ("x", u64::from(cpu.x.0), field(expected, "x")),
+        ("y", u64::from(cpu.y.0), field(expected, "y")),
+        ("d", u64::from(cpu.d.0), field(expected, "d")),
+        ("dbr", u64::from(cpu.dbr.0), field(expected, "dbr")),
+        ("pbr", u64::from(cpu.pbr.0), field(expected, "pbr")),
+        ("e", u64::from(cpu.emulation), field(expected, "e")),
+    ];
+    for (register, actual, wanted) in registers {
+        if actual != wanted {
+            return Err(format!("{name}: {register} = {actual}, expected {wanted}"));
+        }
+    }
+
+    for (addr, wanted) in ram(expected) {
+        if bus.mem[addr] != wanted {
+            return Err(format!(
+                "{name}: ${addr:06X} = {}, expected {wanted}",
+                bus.mem[addr]
+            ));
+        }
+    }
+
+    let cycles = case["cycles"].as_array().map_or(0, Vec::len) as u64;
+    if cpu.cycles != cycles {
+        return Err(format!(
+            "{name}: took {} cycles, expected {cycles}",
+            cpu.cycles
+        ));
+    }
+
+    // Leave memory clean for the next case rather than reallocating 16 MB.
+    for (addr, _) in ram(initial).chain(ram(expected)) {
+        bus.mem[addr] = 0;
+    }
+    Ok(())
+}
+
+fn run_cases(json: &str, bus: &mut LongBus) -> Vec<String> {
+    let cases: Value = serde_json::from_str(json).expect("malformed ProcessorTests JSON");
+    cases
+        .as_array()
+        .expect("expected an array of cases")
+        .iter()
+        .filter_map(|case| run_case(case, bus).err())
+        .collect()
+}
+
+fn long_bus() -> LongBus {
+    LongBus {
+        mem: vec![0; 0x100_0000],
+    }
+}
+
+#[test]
+fn embedded_sample_cases_pass() {
+    let failures = run_cases(SAMPLE, &mut long_bus());
+    assert!(failures.is_empty(), "{failures:#?}");
+}
+
+#[test]
+fn mismatches_are_reported() {
+    let wrong = SAMPLE.replace(r#""a": 22136"#, r#""a": 22137"#);
+    assert_eq!(
+        run_cases(&wrong, &mut long_bus()),
+        ["af n 1: a = 22136, expected 22137"]
+    );
+}
+
+#[test]
+fn processor_tests_suite_wdc65c816() {
+    let Some(dir) = std::env::var_os("PROCESSOR_TESTS_65816") else {
+        return;
+    };
+    let dir = std::path::PathBuf::from(dir);
+
+    let mut bus = long_bus();
+    let mut failures = Vec::new();
+    let mut files = 0;
+    for opcode in 0..=255u8 {
+        for mode in ["e", "n"] {
+            let path = dir.join(format!("{opcode:02x}.{mode}.json"));
+            let Ok(json) = std::fs::read_to_string(&path) else {
+                continue;
+            };
+            files += 1;
+            failures.extend(run_cases(&json, &mut bus));
+        }
+    }
+
+    assert!(files > 0, "no test files found in {}", dir.display());
+    assert!(
+        failures.is_empty(),
+        "{} failing cases, first: {:#?}",
+        failures.len(),
+        &failures[..failures.len().min(10)]
+    );
+}
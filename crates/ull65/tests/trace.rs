@@ -0,0 +1,80 @@
+use ull::{Address, Bus, Byte, Word};
+use ull65::bus::Mos6502CompatibleBus;
+use ull65::instruction::mos6502::Mos6502;
+use ull65::instruction::{InstructionSet, InstructionTable};
+use ull65::processor::trace::trace_line;
+use ull65::{AccessType, Cpu};
+
+const ROM: &[u8] = include_bytes!("../../../thirdparty/nestest/nestest.nes");
+const LOG: &str = include_str!("../../../thirdparty/nestest/nestest.log");
+
+/// 2 KB of mirrored RAM and the nestest PRG bank mirrored into $8000-$FFFF; that's all the
+/// CPU-only part of the ROM touches.
+struct NestestBus {
+    ram: [u8; 0x800],
+    prg: &'static [u8],
+}
+
+impl Bus for NestestBus {
+    type Access = AccessType;
+    type Data = Byte;
+
+    fn read<A: Address>(&mut self, addr: A, _access: AccessType) -> Byte {
+        match addr.as_usize() {
+            addr @ 0x0000..=0x1FFF => Byte(self.ram[addr & 0x07FF]),
+            addr @ 0x8000..=0xFFFF => Byte(self.prg[addr & 0x3FFF]),
+            _ => Byte(0xFF),
+        }
+    }
+
+    fn write<A: Address, V: Into<Byte>>(&mut self, addr: A, value: V, _access: AccessType) {
+        if let addr @ 0x0000..=0x1FFF = addr.as_usize() {
+            self.ram[addr & 0x07FF] = value.into().0;
+        }
+    }
+}
+
+impl Mos6502CompatibleBus for NestestBus {}
+
+struct Ricoh2a03;
+
+impl InstructionSet for Ricoh2a03 {
+    fn instruction_table<B: Mos6502CompatibleBus>() -> InstructionTable<B> {
+        Mos6502::base_table_for::<Self, B>()
+    }
+    const NAME: &'static str = "Ricoh 2A03";
+    const SUPPORTS_DECIMAL_MODE: bool = false;
+}
+
+/// Drop the `PPU:xxx,yyy ` field, which the CPU can't know about.
+fn without_ppu(line: &str) -> String {
+    let start = line.find("PPU:").expect("nestest lines carry a PPU field");
+    let end = line.find("CYC:").expect("nestest lines carry a CYC field");
+    format!("{}{}", &line[..start], &line[end..])
+}
+
+#[test]
+fn trace_matches_nestest_log() {
+    let mut bus = NestestBus {
+        ram: [0; 0x800],
+        prg: &ROM[16..16 + 0x4000],
+    };
+    let mut cpu: Cpu<NestestBus> = Cpu::with_instruction_set::<Ricoh2a03>();
+    cpu.reset(&mut bus);
+    // The automated mode starts at $C000 after the 7-cycle reset sequence.
+    cpu.pc = Word(0xC000);
+    cpu.cycles = 7;
+
+    // The documented-opcode half of the log; the undocumented half exercises timing quirks of
+    // the illegal NOPs that are checked separately.
+    let documented = LOG.lines().take_while(|line| line.as_bytes()[15] != b'*');
+
+    let mut checked = 0;
+    for (number, expected) in documented.enumerate() {
+        let line = trace_line(&cpu, &mut bus);
+        assert_eq!(line, without_ppu(expected), "line {}", number + 1);
+        cpu.step(&mut bus);
+        checked += 1;
+    }
+    assert_eq!(checked, 5003);
+}